@@ -0,0 +1,75 @@
+use tiny_skia::Pixmap;
+
+/// An image decoded to pixels, drawn with
+/// [`OffscreenContext::draw_image`](super::OffscreenContext::draw_image).
+///
+/// Pixels are RGBA with 8 bits per channel, premultiplied by their alpha,
+/// row after row from the top left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Bitmap {
+    /// Returns a bitmap of `data`, `None` if `data` is not as long as the
+    /// pixels of the size.
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> Option<Self> {
+        if data.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Decodes a PNG image, `None` if `data` is not a valid PNG.
+    pub fn from_png(data: &[u8]) -> Option<Self> {
+        let pixmap = Pixmap::decode_png(data).ok()?;
+        Self::new(pixmap.width(), pixmap.height(), pixmap.data().to_vec())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the premultiplied RGBA pixels.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the pixels with the colors divided by their alpha, as HTML
+    /// canvases expect them.
+    pub fn unpremultiplied_data(&self) -> Vec<u8> {
+        let mut data = self.data.clone();
+        for pixel in data.chunks_mut(4) {
+            let alpha = pixel[3] as u32;
+            if alpha > 0 && alpha < 255 {
+                for channel in pixel[..3].iter_mut() {
+                    *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+                }
+            }
+        }
+        data
+    }
+
+    /// Encodes the image as an RGBA PNG.
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut png = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+            encoder.set_color(png::ColorType::RGBA);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.unpremultiplied_data())?;
+        }
+        Ok(png)
+    }
+}
@@ -5,9 +5,9 @@
 #![cfg(not(target_arch = "wasm32"))]
 
 use crate::{
-    BaseLine, CanvasContext, Color, Direction, Gradient, GradientType, LineCap, LineJoin,
-    LinearGradient, PatternExtend, Point, RadialGradient, Rect, RgbaColor, Size, TextAlign,
-    TextMetrics, TextStyle, TextWeight,
+    BaseLine, Bitmap, CanvasContext, Color, Direction, Gradient, GradientType, LayerComposite,
+    LineCap, LineJoin, LinearGradient, OffscreenContext, PatternExtend, Point, RadialGradient,
+    Rect, RgbaColor, Size, TextAlign, TextMetrics, TextStyle, TextWeight,
 };
 use cairo::{self, FontFace, FontSlant, FontWeight, ImageSurface, Surface, SurfacePattern};
 use std::{any::Any, cell::RefCell};
@@ -622,3 +622,127 @@ impl<'a> CanvasContext for Canvas<'a> {
         self.ctx.translate(x, y);
     }
 }
+
+impl<'a> Canvas<'a> {
+    /// Returns an image of `pattern` in device space, with the luminance of
+    /// its colors over black as alpha, and the position of the image.
+    fn luminance_mask(
+        &self,
+        pattern: &cairo::Pattern,
+        inverted: bool,
+    ) -> Option<(ImageSurface, f64, f64)> {
+        self.ctx.save();
+        self.ctx.identity_matrix();
+        let (x1, y1, x2, y2) = self.ctx.clip_extents();
+        self.ctx.restore();
+
+        let width = (x2 - x1).ceil() as i32;
+        let height = (y2 - y1).ceil() as i32;
+        let mut image = ImageSurface::create(cairo::Format::ARgb32, width, height).ok()?;
+        {
+            let ctx = cairo::Context::new(&image);
+            ctx.translate(-x1, -y1);
+            ctx.transform(self.ctx.get_matrix());
+            ctx.set_source(pattern);
+            ctx.paint();
+        }
+        image.flush();
+
+        let stride = image.get_stride() as usize;
+        {
+            let mut data = image.get_data().ok()?;
+            for row in data.chunks_mut(stride) {
+                // premultiplied native endian ARGB, so BGRA in memory on little
+                // endian machines
+                for pixel in row[..width as usize * 4].chunks_mut(4) {
+                    let (b, g, r) = (pixel[0] as f64, pixel[1] as f64, pixel[2] as f64);
+                    let mut luma = (0.2126 * r + 0.7152 * g + 0.0722 * b).round().min(255.0) as u8;
+                    if inverted {
+                        luma = 255 - luma;
+                    }
+                    pixel.copy_from_slice(&[luma; 4]);
+                }
+            }
+        }
+        image.mark_dirty();
+        Some((image, x1, y1))
+    }
+
+    /// Returns a surface holding the pixels of `image`.
+    fn image_surface(image: &Bitmap) -> Option<ImageSurface> {
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        let mut surface = ImageSurface::create(cairo::Format::ARgb32, width, height).ok()?;
+        let stride = surface.get_stride() as usize;
+        {
+            let mut data = surface.get_data().ok()?;
+            let rows = image.data().chunks(image.width() as usize * 4);
+            for (row, pixels) in data.chunks_mut(stride).zip(rows) {
+                for (pixel, rgba) in row.chunks_mut(4).zip(pixels.chunks(4)) {
+                    // both are premultiplied, cairo wants native endian ARGB
+                    let argb = u32::from_be_bytes([rgba[3], rgba[0], rgba[1], rgba[2]]);
+                    pixel.copy_from_slice(&argb.to_ne_bytes());
+                }
+            }
+        }
+        surface.mark_dirty();
+        Some(surface)
+    }
+}
+
+impl<'a> OffscreenContext for Canvas<'a> {
+    fn push_layer(&self) {
+        self.ctx.push_group();
+    }
+
+    fn pop_layer(&self, composite: LayerComposite, alpha: f64) {
+        let pattern = self.ctx.pop_group();
+        self.ctx.save();
+        let operator = match composite {
+            LayerComposite::SourceOver => cairo::Operator::Over,
+            // luminance masks are turned into alpha masks first
+            LayerComposite::DestinationIn | LayerComposite::LumaIn | LayerComposite::LumaOut => {
+                cairo::Operator::DestIn
+            }
+            LayerComposite::DestinationOut => cairo::Operator::DestOut,
+            LayerComposite::Xor => cairo::Operator::Xor,
+        };
+        let inverted = composite == LayerComposite::LumaOut;
+        let mask = match composite {
+            LayerComposite::LumaIn | LayerComposite::LumaOut => {
+                self.luminance_mask(&pattern, inverted)
+            }
+            _ => None,
+        };
+        match mask {
+            Some((image, x, y)) => {
+                self.ctx.identity_matrix();
+                self.ctx.set_source_surface(&image, x, y);
+            }
+            None => self.ctx.set_source(&pattern),
+        }
+        self.ctx.set_operator(operator);
+        self.ctx.paint_with_alpha(alpha);
+        self.ctx.restore();
+    }
+
+    fn paint(&self) {
+        let state = self.state.borrow();
+        if self.handle_paint(&state.fill) {
+            self.ctx.paint();
+        }
+    }
+
+    fn clip(&self) {
+        // the path is kept, as it is by the fill and the stroke
+        self.ctx.clip_preserve();
+    }
+
+    fn draw_image(&self, image: &Bitmap, x: f64, y: f64, alpha: f64) {
+        if let Some(surface) = Self::image_surface(image) {
+            self.ctx.save();
+            self.ctx.set_source_surface(&surface, x, y);
+            self.ctx.paint_with_alpha(alpha);
+            self.ctx.restore();
+        }
+    }
+}
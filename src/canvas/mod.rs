@@ -12,9 +12,18 @@ mod cairoimpl;
 #[cfg(not(target_arch = "wasm32"))]
 pub use cairoimpl::*;
 
+mod bitmap;
+pub use bitmap::*;
+
+mod offscreen;
+pub use offscreen::*;
+
 mod rough;
 pub use rough::*;
 
+mod skiaimpl;
+pub use skiaimpl::*;
+
 pub trait AdvancedShapesExt {
     // /**
     //  * Create a rectangle
@@ -0,0 +1,44 @@
+use super::Bitmap;
+use primitives::CanvasContext;
+
+/// How an offscreen layer is composited onto the layer below it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayerComposite {
+    /// Draws the layer over the layer below.
+    SourceOver,
+    /// Keeps the layer below where the layer is opaque.
+    DestinationIn,
+    /// Keeps the layer below where the layer is transparent.
+    DestinationOut,
+    /// Keeps the layer and the layer below where only one of them is opaque.
+    Xor,
+    /// Keeps the layer below where the layer is bright, the luminance of
+    /// the layer is used as its alpha.
+    LumaIn,
+    /// Keeps the layer below where the layer is dark.
+    LumaOut,
+}
+
+/// A canvas which can draw onto offscreen layers, to composite them with
+/// more than the source-over operation, and draw bitmaps.
+///
+/// Layers are as large as the canvas and keep its current transform.
+pub trait OffscreenContext: CanvasContext {
+    /// Starts to draw onto a new transparent layer until `pop_layer`.
+    fn push_layer(&self);
+
+    /// Composites the current layer onto the layer below it, the alpha of
+    /// the layer is multiplied by `alpha`.
+    fn pop_layer(&self, composite: LayerComposite, alpha: f64);
+
+    /// Fills the whole layer with the fill paint, whatever the transform.
+    fn paint(&self);
+
+    /// Intersects the clip with the current path, until the canvas is
+    /// restored.
+    fn clip(&self);
+
+    /// Draws `image` with its top left corner at `x`, `y` in the current
+    /// transform, one unit per pixel, its alpha multiplied by `alpha`.
+    fn draw_image(&self, image: &Bitmap, x: f64, y: f64, alpha: f64);
+}
@@ -0,0 +1,1140 @@
+#![allow(unused_variables)]
+
+use crate::{
+    BaseLine, Bitmap, CanvasContext, Color, Direction, Gradient, GradientType, LayerComposite,
+    LineCap, LineJoin, LinearGradient, OffscreenContext, PatternExtend, RadialGradient, RgbaColor,
+    TextAlign, TextMetrics, TextStyle, TextWeight, Transform, Vector,
+};
+use std::{
+    cell::{Cell, RefCell},
+    f64::consts::PI,
+};
+use tiny_skia::{
+    BlendMode, ClipMask, FillRule, FilterQuality, GradientStop, Pixmap, PixmapPaint, PixmapRef,
+    Point, Shader, SpreadMode, Stroke, StrokeDash,
+};
+
+/// A bitmap filling shapes, set with `set_fill_pattern` or
+/// `set_stroke_pattern`.
+#[derive(Clone, Debug)]
+pub struct SkiaPattern {
+    pub extend: PatternExtend,
+    pub image: Bitmap,
+}
+
+impl SkiaPattern {
+    pub fn new(extend: PatternExtend, image: Bitmap) -> Self {
+        Self { extend, image }
+    }
+}
+
+#[derive(Clone)]
+enum Paint {
+    None,
+    Solid(Color),
+    Gradient(Gradient),
+    Pattern(SkiaPattern),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PathCommand {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    CubicTo(f64, f64, f64, f64, f64, f64),
+    Close,
+}
+
+#[derive(Clone)]
+struct CanvasState {
+    transform: Transform<f64>,
+    fill: Paint,
+    stroke: Paint,
+    global_alpha: f64,
+    line_width: f64,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f64,
+    line_dash: Vec<f64>,
+    line_dash_offset: f64,
+    /// The clip in device space, `None` when nothing is clipped.
+    clip: Option<ClipMask>,
+    /// Whether the clip is empty, everything is clipped then.
+    clipped_out: bool,
+    /// The name of the global composite operation and its blend mode.
+    composite_operation: String,
+    blend_mode: BlendMode,
+    // kept for the getters, they are not drawn
+    direction: Direction,
+    filter: String,
+    font: String,
+    shadow_blur: f64,
+    shadow_color: Color,
+    shadow_offset_x: f64,
+    shadow_offset_y: f64,
+    text_align: TextAlign,
+    text_baseline: BaseLine,
+}
+
+impl Default for CanvasState {
+    fn default() -> Self {
+        Self {
+            transform: Transform::identity(),
+            fill: Paint::None,
+            stroke: Paint::None,
+            global_alpha: 1.0,
+            line_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 10.0,
+            line_dash: Vec::new(),
+            line_dash_offset: 0.0,
+            clip: None,
+            clipped_out: false,
+            composite_operation: "source-over".into(),
+            blend_mode: BlendMode::SourceOver,
+            direction: Direction::Inherit,
+            filter: "none".into(),
+            font: "10px sans-serif".into(),
+            shadow_blur: 0.0,
+            shadow_color: RgbaColor::new(0, 0, 0, 0).into(),
+            shadow_offset_x: 0.0,
+            shadow_offset_y: 0.0,
+            text_align: TextAlign::Left,
+            text_baseline: BaseLine::Alphabetic,
+        }
+    }
+}
+
+/// A canvas drawing into pixels in memory with tiny-skia, to render without
+/// a display.
+///
+/// Paths are anti-aliased and filled with the non-zero rule. The canvas has
+/// no fonts: text is only drawn from the shapes of its glyphs, `fill_text`
+/// and `stroke_text` draw nothing. Shadows, filters and the text style are
+/// kept with the state of the canvas, but not drawn.
+pub struct SkiaCanvas {
+    width: u32,
+    height: u32,
+    /// The pixels of the canvas first, then of the offscreen layers.
+    layers: RefCell<Vec<Pixmap>>,
+    state: RefCell<CanvasState>,
+    saved: RefCell<Vec<CanvasState>>,
+    /// The current path, in device space as the transform applies to the
+    /// points when they are added.
+    path: RefCell<Vec<PathCommand>>,
+    /// The current point in device space.
+    current: Cell<Option<(f64, f64)>>,
+    /// The start of the current subpath in device space.
+    subpath: Cell<(f64, f64)>,
+}
+
+impl SkiaCanvas {
+    /// Returns a transparent canvas of `width` by `height` pixels, `None` if
+    /// the size is zero or too large.
+    pub fn new(width: u32, height: u32) -> Option<Self> {
+        Some(Self {
+            width,
+            height,
+            layers: RefCell::new(vec![Pixmap::new(width, height)?]),
+            state: Default::default(),
+            saved: Default::default(),
+            path: Default::default(),
+            current: Cell::new(None),
+            subpath: Cell::new((0.0, 0.0)),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the pixels of the canvas, without the layers which are not
+    /// popped yet.
+    pub fn to_bitmap(&self) -> Bitmap {
+        let data = self.layers.borrow()[0].data().to_vec();
+        Bitmap::new(self.width, self.height, data).expect("pixmap of the canvas size")
+    }
+
+    fn to_skia(transform: &Transform<f64>) -> tiny_skia::Transform {
+        let [a, b, c, d, e, f] = transform.to_array();
+        tiny_skia::Transform::from_row(a as f32, b as f32, c as f32, d as f32, e as f32, f as f32)
+    }
+
+    /// Returns the point `x`, `y` transformed by `transform`.
+    fn apply(transform: &Transform<f64>, x: f64, y: f64) -> (f64, f64) {
+        let point = transform.transform_point(crate::Point::new(x, y));
+        (point.x, point.y)
+    }
+
+    /// Returns `commands` as a tiny-skia path, with the points transformed
+    /// by `transform`.
+    fn build_path(commands: &[PathCommand], transform: &Transform<f64>) -> Option<tiny_skia::Path> {
+        let mut builder = tiny_skia::PathBuilder::new();
+        let point = |x, y| {
+            let (x, y) = Self::apply(transform, x, y);
+            (x as f32, y as f32)
+        };
+        for command in commands {
+            match *command {
+                PathCommand::MoveTo(x, y) => {
+                    let (x, y) = point(x, y);
+                    builder.move_to(x, y);
+                }
+                PathCommand::LineTo(x, y) => {
+                    let (x, y) = point(x, y);
+                    builder.line_to(x, y);
+                }
+                PathCommand::CubicTo(x1, y1, x2, y2, x, y) => {
+                    let (x1, y1) = point(x1, y1);
+                    let (x2, y2) = point(x2, y2);
+                    let (x, y) = point(x, y);
+                    builder.cubic_to(x1, y1, x2, y2, x, y);
+                }
+                PathCommand::Close => builder.close(),
+            }
+        }
+        builder.finish()
+    }
+
+    /// Returns the commands of a rectangle in device space.
+    fn rect_commands(&self, x: f64, y: f64, width: f64, height: f64) -> Vec<PathCommand> {
+        let transform = self.state.borrow().transform;
+        let point = |x, y| Self::apply(&transform, x, y);
+        let corners = [
+            point(x, y),
+            point(x + width, y),
+            point(x + width, y + height),
+            point(x, y + height),
+        ];
+        let mut commands = vec![PathCommand::MoveTo(corners[0].0, corners[0].1)];
+        for &(x, y) in &corners[1..] {
+            commands.push(PathCommand::LineTo(x, y));
+        }
+        commands.push(PathCommand::Close);
+        commands
+    }
+
+    /// Returns the shader of `paint` in the current transform, its alpha
+    /// multiplied by `alpha`.
+    fn shader<'p>(paint: &'p Paint, alpha: f64, transform: &Transform<f64>) -> Option<Shader<'p>> {
+        let color = |color: Color| {
+            let RgbaColor {
+                red,
+                green,
+                blue,
+                alpha: color_alpha,
+            } = color.into();
+            let color_alpha = (color_alpha as f64 * alpha).round().clamp(0.0, 255.0) as u8;
+            tiny_skia::Color::from_rgba8(red, green, blue, color_alpha)
+        };
+        let transform = Self::to_skia(transform);
+        match paint {
+            Paint::None => None,
+            Paint::Solid(value) => Some(Shader::SolidColor(color(*value))),
+            Paint::Gradient(gradient) => {
+                let stops = gradient
+                    .stops
+                    .borrow()
+                    .iter()
+                    .map(|stop| GradientStop::new(stop.offset as f32, color(stop.color)))
+                    .collect();
+                match gradient.kind {
+                    GradientType::Linear(LinearGradient { x0, y0, x1, y1 }) => {
+                        tiny_skia::LinearGradient::new(
+                            Point::from_xy(x0 as f32, y0 as f32),
+                            Point::from_xy(x1 as f32, y1 as f32),
+                            stops,
+                            SpreadMode::Pad,
+                            transform,
+                        )
+                    }
+                    // tiny-skia gradients start with a zero radius, at the
+                    // focal point
+                    GradientType::Radial(RadialGradient {
+                        x0, y0, x1, y1, r1, ..
+                    }) => tiny_skia::RadialGradient::new(
+                        Point::from_xy(x0 as f32, y0 as f32),
+                        Point::from_xy(x1 as f32, y1 as f32),
+                        r1 as f32,
+                        stops,
+                        SpreadMode::Pad,
+                        transform,
+                    ),
+                }
+            }
+            Paint::Pattern(pattern) => {
+                let image = &pattern.image;
+                let pixmap = PixmapRef::from_bytes(image.data(), image.width(), image.height())?;
+                let spread = match pattern.extend {
+                    PatternExtend::Repeat => SpreadMode::Repeat,
+                    PatternExtend::Reflect => SpreadMode::Reflect,
+                    // tiny-skia can't leave the outside transparent
+                    PatternExtend::Pad | PatternExtend::None => SpreadMode::Pad,
+                };
+                Some(tiny_skia::Pattern::new(
+                    pixmap,
+                    spread,
+                    FilterQuality::Bilinear,
+                    alpha as f32,
+                    transform,
+                ))
+            }
+        }
+    }
+
+    /// Fills `commands`, in device space, with the fill paint.
+    fn fill_commands(&self, commands: &[PathCommand]) {
+        let state = self.state.borrow();
+        if state.clipped_out {
+            return;
+        }
+        let path = match Self::build_path(commands, &Transform::identity()) {
+            Some(path) => path,
+            None => return,
+        };
+        if let Some(shader) = Self::shader(&state.fill, state.global_alpha, &state.transform) {
+            let paint = tiny_skia::Paint {
+                shader,
+                blend_mode: state.blend_mode,
+                anti_alias: true,
+                ..Default::default()
+            };
+            if let Some(pixmap) = self.layers.borrow_mut().last_mut() {
+                pixmap.fill_path(
+                    &path,
+                    &paint,
+                    FillRule::Winding,
+                    tiny_skia::Transform::identity(),
+                    state.clip.as_ref(),
+                );
+            }
+        }
+    }
+
+    /// Strokes `commands`, in device space, with the stroke paint and the
+    /// line style in the current transform.
+    fn stroke_commands(&self, commands: &[PathCommand]) {
+        let state = self.state.borrow();
+        if state.clipped_out || state.line_width <= 0.0 {
+            return;
+        }
+        // the path is stroked in user space, for the line width to be
+        // transformed as well
+        let path = match state
+            .transform
+            .inverse()
+            .and_then(|inverse| Self::build_path(commands, &inverse))
+        {
+            Some(path) => path,
+            None => return,
+        };
+        let shader = match Self::shader(&state.stroke, state.global_alpha, &state.transform) {
+            Some(shader) => shader,
+            None => return,
+        };
+        let mut dashes: Vec<f32> = state.line_dash.iter().map(|&dash| dash as f32).collect();
+        if dashes.len() % 2 == 1 {
+            // as on HTML canvases, odd dashes are repeated
+            dashes.extend(dashes.clone());
+        }
+        let stroke = Stroke {
+            width: state.line_width as f32,
+            miter_limit: state.miter_limit as f32,
+            line_cap: match state.line_cap {
+                LineCap::Butt => tiny_skia::LineCap::Butt,
+                LineCap::Round => tiny_skia::LineCap::Round,
+                LineCap::Square => tiny_skia::LineCap::Square,
+            },
+            line_join: match state.line_join {
+                LineJoin::Miter => tiny_skia::LineJoin::Miter,
+                LineJoin::Round => tiny_skia::LineJoin::Round,
+                LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+            },
+            dash: StrokeDash::new(dashes, state.line_dash_offset as f32),
+        };
+        let paint = tiny_skia::Paint {
+            shader,
+            blend_mode: state.blend_mode,
+            anti_alias: true,
+            ..Default::default()
+        };
+        if let Some(pixmap) = self.layers.borrow_mut().last_mut() {
+            pixmap.stroke_path(
+                &path,
+                &paint,
+                &stroke,
+                Self::to_skia(&state.transform),
+                state.clip.as_ref(),
+            );
+        }
+    }
+
+    /// Adds a point to the current path, starting a subpath at it if there
+    /// is no current point.
+    fn push_point(&self, x: f64, y: f64, start: bool) {
+        let point = Self::apply(&self.state.borrow().transform, x, y);
+        if self.current.get().is_some() && !start {
+            self.path
+                .borrow_mut()
+                .push(PathCommand::LineTo(point.0, point.1));
+        } else {
+            self.path
+                .borrow_mut()
+                .push(PathCommand::MoveTo(point.0, point.1));
+            self.subpath.set(point);
+        }
+        self.current.set(Some(point));
+    }
+
+    /// Returns the current point in user space.
+    fn current_point(&self) -> Option<(f64, f64)> {
+        let (x, y) = self.current.get()?;
+        let inverse = self.state.borrow().transform.inverse()?;
+        Some(Self::apply(&inverse, x, y))
+    }
+
+    /// Adds a cubic curve in user space to the current path.
+    fn push_curve(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        if self.current.get().is_none() {
+            self.push_point(cp1x, cp1y, true);
+        }
+        let transform = self.state.borrow().transform;
+        let (x1, y1) = Self::apply(&transform, cp1x, cp1y);
+        let (x2, y2) = Self::apply(&transform, cp2x, cp2y);
+        let (x, y) = Self::apply(&transform, x, y);
+        self.path
+            .borrow_mut()
+            .push(PathCommand::CubicTo(x1, y1, x2, y2, x, y));
+        self.current.set(Some((x, y)));
+    }
+
+    /// Adds the arc of the ellipse centered on `x`, `y` from the `start`
+    /// angle, turning by `sweep`, in curves of at most a quarter turn.
+    fn push_arc(
+        &self,
+        center: (f64, f64),
+        radii: (f64, f64),
+        rotation: f64,
+        start: f64,
+        sweep: f64,
+    ) {
+        let ((x, y), (rx, ry)) = (center, radii);
+        let (sin, cos) = rotation.sin_cos();
+        let point = |t: f64| {
+            let (px, py) = (rx * t.cos(), ry * t.sin());
+            (x + px * cos - py * sin, y + px * sin + py * cos)
+        };
+        let derivative = |t: f64| {
+            let (dx, dy) = (-rx * t.sin(), ry * t.cos());
+            (dx * cos - dy * sin, dx * sin + dy * cos)
+        };
+
+        let (x0, y0) = point(start);
+        self.push_point(x0, y0, false);
+        let count = (sweep.abs() / (PI / 2.0)).ceil().max(1.0);
+        let step = sweep / count;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        for index in 0..count as usize {
+            let t0 = start + step * index as f64;
+            let t1 = t0 + step;
+            let (p0, d0) = (point(t0), derivative(t0));
+            let (p1, d1) = (point(t1), derivative(t1));
+            self.push_curve(
+                p0.0 + k * d0.0,
+                p0.1 + k * d0.1,
+                p1.0 - k * d1.0,
+                p1.1 - k * d1.1,
+                p1.0,
+                p1.1,
+            );
+        }
+    }
+
+    /// Returns the angle an arc turns by from `start` to `end`, as HTML
+    /// canvases do.
+    fn arc_sweep(start: f64, end: f64, anticlockwise: bool) -> f64 {
+        let turn = 2.0 * PI;
+        if !anticlockwise && end - start >= turn {
+            turn
+        } else if anticlockwise && start - end >= turn {
+            -turn
+        } else if anticlockwise {
+            -(start - end).rem_euclid(turn)
+        } else {
+            (end - start).rem_euclid(turn)
+        }
+    }
+
+    /// Returns the blend mode of a composite operation of HTML canvases.
+    fn blend_mode(operation: &str) -> Option<BlendMode> {
+        Some(match operation {
+            "source-over" => BlendMode::SourceOver,
+            "source-in" => BlendMode::SourceIn,
+            "source-out" => BlendMode::SourceOut,
+            "source-atop" => BlendMode::SourceAtop,
+            "destination-over" => BlendMode::DestinationOver,
+            "destination-in" => BlendMode::DestinationIn,
+            "destination-out" => BlendMode::DestinationOut,
+            "destination-atop" => BlendMode::DestinationAtop,
+            "lighter" => BlendMode::Plus,
+            "copy" => BlendMode::Source,
+            "xor" => BlendMode::Xor,
+            "multiply" => BlendMode::Multiply,
+            "screen" => BlendMode::Screen,
+            "overlay" => BlendMode::Overlay,
+            "darken" => BlendMode::Darken,
+            "lighten" => BlendMode::Lighten,
+            "color-dodge" => BlendMode::ColorDodge,
+            "color-burn" => BlendMode::ColorBurn,
+            "hard-light" => BlendMode::HardLight,
+            "soft-light" => BlendMode::SoftLight,
+            "difference" => BlendMode::Difference,
+            "exclusion" => BlendMode::Exclusion,
+            "hue" => BlendMode::Hue,
+            "saturation" => BlendMode::Saturation,
+            "color" => BlendMode::Color,
+            "luminosity" => BlendMode::Luminosity,
+            _ => return None,
+        })
+    }
+
+    /// Replaces the colors of `pixmap` by gray, with the luminance of the
+    /// colors over black as alpha.
+    fn luminance_to_alpha(pixmap: &mut Pixmap, inverted: bool) {
+        for pixel in pixmap.data_mut().chunks_mut(4) {
+            let (r, g, b) = (pixel[0] as f64, pixel[1] as f64, pixel[2] as f64);
+            let mut luma = (0.2126 * r + 0.7152 * g + 0.0722 * b).round().min(255.0) as u8;
+            if inverted {
+                luma = 255 - luma;
+            }
+            pixel.copy_from_slice(&[luma; 4]);
+        }
+    }
+}
+
+impl CanvasContext for SkiaCanvas {
+    type Pattern = SkiaPattern;
+
+    fn get_direction(&self) -> Direction {
+        self.state.borrow().direction
+    }
+
+    fn set_direction(&self, value: Direction) -> String {
+        self.state.borrow_mut().direction = value;
+        match value {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+            Direction::Inherit => "inherit",
+        }
+        .into()
+    }
+
+    fn set_fill_color(&self, value: Color) {
+        self.state.borrow_mut().fill = Paint::Solid(value);
+    }
+
+    fn set_fill_gradient(&self, value: &Gradient) {
+        self.state.borrow_mut().fill = Paint::Gradient(value.clone());
+    }
+
+    fn set_fill_pattern(&self, value: &Self::Pattern) {
+        self.state.borrow_mut().fill = Paint::Pattern(value.clone());
+    }
+
+    fn get_filter(&self) -> String {
+        self.state.borrow().filter.clone()
+    }
+
+    fn set_filter(&self, value: &str) {
+        // filters are kept but not drawn
+        self.state.borrow_mut().filter = value.into();
+    }
+
+    fn get_font(&self) -> String {
+        self.state.borrow().font.clone()
+    }
+
+    fn set_font(&self, family: &str, style: TextStyle, weight: TextWeight, size: f64) {
+        // the canvas has no fonts, the font is only kept
+        self.state.borrow_mut().font = format!("{}px {}", size, family);
+    }
+
+    fn get_global_alpha(&self) -> f64 {
+        self.state.borrow().global_alpha
+    }
+
+    fn set_global_alpha(&self, value: f64) {
+        self.state.borrow_mut().global_alpha = value.clamp(0.0, 1.0);
+    }
+
+    fn get_global_composite_operation(&self) -> String {
+        self.state.borrow().composite_operation.clone()
+    }
+
+    /// Sets the blend mode of the operation named as on HTML canvases,
+    /// unknown operations are ignored.
+    fn set_global_composite_operation(&self, value: &str) {
+        if let Some(blend_mode) = Self::blend_mode(value) {
+            let mut state = self.state.borrow_mut();
+            state.composite_operation = value.into();
+            state.blend_mode = blend_mode;
+        }
+    }
+
+    fn is_image_smoothing_enabled(&self) -> bool {
+        true
+    }
+
+    fn set_image_smoothing(&self, value: bool) {
+        // images are always smoothed
+    }
+
+    fn get_line_cap(&self) -> LineCap {
+        self.state.borrow().line_cap
+    }
+
+    fn set_line_cap(&self, value: LineCap) {
+        self.state.borrow_mut().line_cap = value;
+    }
+
+    fn get_line_dash_offset(&self) -> f64 {
+        self.state.borrow().line_dash_offset
+    }
+
+    fn set_line_dash_offset(&self, value: f64) {
+        self.state.borrow_mut().line_dash_offset = value;
+    }
+
+    fn get_line_join(&self) -> LineJoin {
+        self.state.borrow().line_join
+    }
+
+    fn set_line_join(&self, value: LineJoin) {
+        self.state.borrow_mut().line_join = value;
+    }
+
+    fn get_line_width(&self) -> f64 {
+        self.state.borrow().line_width
+    }
+
+    fn set_line_width(&self, value: f64) {
+        self.state.borrow_mut().line_width = value;
+    }
+
+    fn get_miter_limit(&self) -> f64 {
+        self.state.borrow().miter_limit
+    }
+
+    fn set_miter_limit(&self, value: f64) {
+        self.state.borrow_mut().miter_limit = value;
+    }
+
+    fn get_shadow_blur(&self) -> f64 {
+        self.state.borrow().shadow_blur
+    }
+
+    fn set_shadow_blur(&self, value: f64) {
+        // shadows are kept but not drawn
+        self.state.borrow_mut().shadow_blur = value;
+    }
+
+    fn get_shadow_color(&self) -> Color {
+        self.state.borrow().shadow_color
+    }
+
+    fn set_shadow_color(&self, value: Color) {
+        self.state.borrow_mut().shadow_color = value;
+    }
+
+    fn get_shadow_offset_x(&self) -> f64 {
+        self.state.borrow().shadow_offset_x
+    }
+
+    fn set_shadow_offset_x(&self, value: f64) {
+        self.state.borrow_mut().shadow_offset_x = value;
+    }
+
+    fn get_shadow_offset_y(&self) -> f64 {
+        self.state.borrow().shadow_offset_y
+    }
+
+    fn set_shadow_offset_y(&self, value: f64) {
+        self.state.borrow_mut().shadow_offset_y = value;
+    }
+
+    fn set_stroke_color(&self, value: Color) {
+        self.state.borrow_mut().stroke = Paint::Solid(value);
+    }
+
+    fn set_stroke_gradient(&self, value: &Gradient) {
+        self.state.borrow_mut().stroke = Paint::Gradient(value.clone());
+    }
+
+    fn set_stroke_pattern(&self, value: &Self::Pattern) {
+        self.state.borrow_mut().stroke = Paint::Pattern(value.clone());
+    }
+
+    fn get_text_align(&self) -> TextAlign {
+        self.state.borrow().text_align
+    }
+
+    fn set_text_align(&self, value: TextAlign) {
+        self.state.borrow_mut().text_align = value;
+    }
+
+    fn get_text_baseline(&self) -> BaseLine {
+        self.state.borrow().text_baseline
+    }
+
+    fn set_text_baseline(&self, value: BaseLine) {
+        self.state.borrow_mut().text_baseline = value;
+    }
+
+    fn arc(
+        &self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        anticlockwise: bool,
+    ) {
+        let sweep = Self::arc_sweep(start_angle, end_angle, anticlockwise);
+        self.push_arc((x, y), (radius, radius), 0.0, start_angle, sweep);
+    }
+
+    fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
+        let (x0, y0) = match self.current_point() {
+            Some(point) => point,
+            None => return self.push_point(x1, y1, true),
+        };
+
+        let (dx0, dy0) = (x0 - x1, y0 - y1);
+        let (dx2, dy2) = (x2 - x1, y2 - y1);
+        let (length0, length2) = (dx0.hypot(dy0), dx2.hypot(dy2));
+        let cross = dx0 * dy2 - dy0 * dx2;
+        if radius <= 0.0 || length0 == 0.0 || length2 == 0.0 || cross.abs() < f64::EPSILON {
+            return self.push_point(x1, y1, false);
+        }
+
+        // the circle touches both lines, its center is on the bisector
+        let (ux0, uy0) = (dx0 / length0, dy0 / length0);
+        let (ux2, uy2) = (dx2 / length2, dy2 / length2);
+        let angle = (ux0 * ux2 + uy0 * uy2).clamp(-1.0, 1.0).acos();
+        let distance = radius / (angle / 2.0).tan();
+        let (bx, by) = (ux0 + ux2, uy0 + uy2);
+        let length = bx.hypot(by);
+        let center_distance = radius / (angle / 2.0).sin();
+        let (cx, cy) = (
+            x1 + bx / length * center_distance,
+            y1 + by / length * center_distance,
+        );
+        let (tx0, ty0) = (x1 + ux0 * distance, y1 + uy0 * distance);
+        let (tx2, ty2) = (x1 + ux2 * distance, y1 + uy2 * distance);
+
+        let start = (ty0 - cy).atan2(tx0 - cx);
+        let end = (ty2 - cy).atan2(tx2 - cx);
+        // the arc between the tangent points is the short one
+        let sweep = (end - start + PI).rem_euclid(2.0 * PI) - PI;
+        self.push_arc((cx, cy), (radius, radius), 0.0, start, sweep);
+    }
+
+    fn begin_path(&self) {
+        self.path.borrow_mut().clear();
+        self.current.set(None);
+    }
+
+    fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        self.push_curve(cp1x, cp1y, cp2x, cp2y, x, y);
+    }
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        let state = self.state.borrow();
+        if state.clipped_out {
+            return;
+        }
+        let commands = self.rect_commands(x, y, width, height);
+        if let Some(path) = Self::build_path(&commands, &Transform::identity()) {
+            let paint = tiny_skia::Paint {
+                blend_mode: BlendMode::Clear,
+                anti_alias: true,
+                ..Default::default()
+            };
+            if let Some(pixmap) = self.layers.borrow_mut().last_mut() {
+                pixmap.fill_path(
+                    &path,
+                    &paint,
+                    FillRule::Winding,
+                    tiny_skia::Transform::identity(),
+                    state.clip.as_ref(),
+                );
+            }
+        }
+    }
+
+    fn close_path(&self) {
+        if self.current.get().is_some() {
+            self.path.borrow_mut().push(PathCommand::Close);
+            self.current.set(Some(self.subpath.get()));
+        }
+    }
+
+    fn ellipse(
+        &self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64,
+        start_angle: f64,
+        end_angle: f64,
+        anticlockwise: bool,
+    ) {
+        let sweep = Self::arc_sweep(start_angle, end_angle, anticlockwise);
+        self.push_arc((x, y), (radius_x, radius_y), rotation, start_angle, sweep);
+    }
+
+    fn fill(&self) {
+        self.fill_commands(&self.path.borrow());
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.fill_commands(&self.rect_commands(x, y, width, height));
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) {
+        // the canvas has no fonts
+    }
+
+    fn get_line_dash(&self) -> Vec<f64> {
+        self.state.borrow().line_dash.clone()
+    }
+
+    fn line_to(&self, x: f64, y: f64) {
+        self.push_point(x, y, false);
+    }
+
+    fn measure_text(&self, text: &str) -> TextMetrics {
+        TextMetrics {
+            width: 0.0,
+            height: 0.0,
+        }
+    }
+
+    fn move_to(&self, x: f64, y: f64) {
+        self.push_point(x, y, true);
+    }
+
+    fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        // the cubic curve with the control points at 2/3 of the quadratic
+        // handles is the same curve
+        let (x0, y0) = self.current_point().unwrap_or((cpx, cpy));
+        self.push_curve(
+            x0 + 2.0 / 3.0 * (cpx - x0),
+            y0 + 2.0 / 3.0 * (cpy - y0),
+            x + 2.0 / 3.0 * (cpx - x),
+            y + 2.0 / 3.0 * (cpy - y),
+            x,
+            y,
+        );
+    }
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        let commands = self.rect_commands(x, y, width, height);
+        if let Some(&PathCommand::MoveTo(x, y)) = commands.first() {
+            self.current.set(Some((x, y)));
+            self.subpath.set((x, y));
+        }
+        self.path.borrow_mut().extend(commands);
+    }
+
+    fn reset_transform(&self) {
+        self.state.borrow_mut().transform = Transform::identity();
+    }
+
+    fn restore(&self) {
+        if let Some(state) = self.saved.borrow_mut().pop() {
+            self.state.replace(state);
+        }
+    }
+
+    fn rotate(&self, angle: f64) {
+        let (sin, cos) = angle.sin_cos();
+        self.transform(cos, sin, -sin, cos, 0.0, 0.0);
+    }
+
+    fn save(&self) {
+        let state = self.state.borrow().clone();
+        self.saved.borrow_mut().push(state);
+    }
+
+    fn scale(&self, x: f64, y: f64) {
+        self.transform(x, 0.0, 0.0, y, 0.0, 0.0);
+    }
+
+    fn set_line_dash(&self, dash: &[f64]) {
+        self.state.borrow_mut().line_dash = dash.to_vec();
+    }
+
+    fn set_transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        self.state.borrow_mut().transform = Transform::new(a, b, c, d, e, f);
+    }
+
+    fn stroke(&self) {
+        self.stroke_commands(&self.path.borrow());
+    }
+
+    fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.stroke_commands(&self.rect_commands(x, y, width, height));
+    }
+
+    fn stroke_text(&self, text: &str, x: f64, y: f64) {
+        // the canvas has no fonts
+    }
+
+    fn transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        let mut state = self.state.borrow_mut();
+        state.transform = Transform::new(a, b, c, d, e, f).then(&state.transform);
+    }
+
+    fn translate(&self, x: f64, y: f64) {
+        self.transform(1.0, 0.0, 0.0, 1.0, x, y);
+    }
+}
+
+impl OffscreenContext for SkiaCanvas {
+    fn push_layer(&self) {
+        if let Some(pixmap) = Pixmap::new(self.width, self.height) {
+            self.layers.borrow_mut().push(pixmap);
+        }
+    }
+
+    fn pop_layer(&self, composite: LayerComposite, alpha: f64) {
+        let mut layers = self.layers.borrow_mut();
+        if layers.len() < 2 {
+            return;
+        }
+        let mut layer = layers.pop().expect("offscreen layer");
+        let blend_mode = match composite {
+            LayerComposite::SourceOver => BlendMode::SourceOver,
+            // luminance masks are turned into alpha masks first
+            LayerComposite::DestinationIn | LayerComposite::LumaIn | LayerComposite::LumaOut => {
+                BlendMode::DestinationIn
+            }
+            LayerComposite::DestinationOut => BlendMode::DestinationOut,
+            LayerComposite::Xor => BlendMode::Xor,
+        };
+        if let LayerComposite::LumaIn | LayerComposite::LumaOut = composite {
+            Self::luminance_to_alpha(&mut layer, composite == LayerComposite::LumaOut);
+        }
+
+        let state = self.state.borrow();
+        if state.clipped_out {
+            return;
+        }
+        let paint = PixmapPaint {
+            opacity: alpha.clamp(0.0, 1.0) as f32,
+            blend_mode,
+            quality: FilterQuality::Nearest,
+        };
+        if let Some(pixmap) = layers.last_mut() {
+            pixmap.draw_pixmap(
+                0,
+                0,
+                layer.as_ref(),
+                &paint,
+                tiny_skia::Transform::identity(),
+                state.clip.as_ref(),
+            );
+        }
+    }
+
+    fn paint(&self) {
+        let commands = [
+            PathCommand::MoveTo(0.0, 0.0),
+            PathCommand::LineTo(self.width as f64, 0.0),
+            PathCommand::LineTo(self.width as f64, self.height as f64),
+            PathCommand::LineTo(0.0, self.height as f64),
+            PathCommand::Close,
+        ];
+        self.fill_commands(&commands);
+    }
+
+    fn clip(&self) {
+        let path = Self::build_path(&self.path.borrow(), &Transform::identity());
+        let mut state = self.state.borrow_mut();
+        let path = match path {
+            Some(path) => path,
+            None => {
+                state.clipped_out = true;
+                return;
+            }
+        };
+        let clipped = match state.clip.as_mut() {
+            Some(clip) => clip.intersect_path(&path, FillRule::Winding, true),
+            None => {
+                let mut clip = ClipMask::new();
+                let clipped =
+                    clip.set_path(self.width, self.height, &path, FillRule::Winding, true);
+                state.clip = Some(clip);
+                clipped
+            }
+        };
+        if clipped.is_none() {
+            state.clipped_out = true;
+        }
+    }
+
+    fn draw_image(&self, image: &Bitmap, x: f64, y: f64, alpha: f64) {
+        let state = self.state.borrow();
+        if state.clipped_out {
+            return;
+        }
+        let pixmap = match PixmapRef::from_bytes(image.data(), image.width(), image.height()) {
+            Some(pixmap) => pixmap,
+            None => return,
+        };
+        let transform = state.transform.pre_translate(Vector::new(x, y));
+        let paint = PixmapPaint {
+            opacity: (alpha * state.global_alpha).clamp(0.0, 1.0) as f32,
+            blend_mode: state.blend_mode,
+            quality: FilterQuality::Bilinear,
+        };
+        if let Some(target) = self.layers.borrow_mut().last_mut() {
+            target.draw_pixmap(
+                0,
+                0,
+                pixmap,
+                &paint,
+                Self::to_skia(&transform),
+                state.clip.as_ref(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alpha(canvas: &SkiaCanvas, x: u32, y: u32) -> u8 {
+        canvas.to_bitmap().data()[(y * canvas.width() + x) as usize * 4 + 3]
+    }
+
+    fn opaque(red: u8, green: u8, blue: u8) -> Color {
+        RgbaColor::new(red, green, blue, 255).into()
+    }
+
+    #[test]
+    fn fills_in_the_current_transform() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        canvas.set_fill_color(opaque(255, 0, 0));
+        canvas.translate(2.0, 2.0);
+        canvas.scale(2.0, 2.0);
+        canvas.fill_rect(0.0, 0.0, 2.0, 2.0);
+        assert_eq!(
+            &canvas.to_bitmap().data()[(3 * 10 + 3) * 4..][..4],
+            [255, 0, 0, 255]
+        );
+        assert_eq!(alpha(&canvas, 1, 1), 0);
+        assert_eq!(alpha(&canvas, 6, 6), 0);
+    }
+
+    #[test]
+    fn keeps_the_state_it_does_not_draw() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        assert_eq!(canvas.get_global_composite_operation(), "source-over");
+        assert_eq!(canvas.get_filter(), "none");
+        canvas.save();
+        canvas.set_shadow_blur(4.0);
+        canvas.set_shadow_offset_x(2.0);
+        canvas.set_font("Arial", TextStyle::Normal, TextWeight::Normal, 12.0);
+        assert_eq!(canvas.set_direction(Direction::Rtl), "rtl");
+        assert_eq!(canvas.get_shadow_blur(), 4.0);
+        assert_eq!(canvas.get_shadow_offset_x(), 2.0);
+        assert_eq!(canvas.get_font(), "12px Arial");
+        canvas.restore();
+        assert_eq!(canvas.get_shadow_blur(), 0.0);
+        assert_eq!(canvas.get_font(), "10px sans-serif");
+        assert!(matches!(canvas.get_direction(), Direction::Inherit));
+        assert!(matches!(canvas.get_text_align(), TextAlign::Left));
+    }
+
+    #[test]
+    fn blends_with_the_composite_operation() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        canvas.set_fill_color(opaque(255, 0, 0));
+        canvas.fill_rect(0.0, 0.0, 10.0, 10.0);
+        canvas.set_global_composite_operation("destination-out");
+        canvas.fill_rect(0.0, 0.0, 5.0, 10.0);
+        // unknown operations are ignored
+        canvas.set_global_composite_operation("unknown");
+        assert_eq!(canvas.get_global_composite_operation(), "destination-out");
+        assert_eq!(alpha(&canvas, 2, 5), 0);
+        assert_eq!(alpha(&canvas, 7, 5), 255);
+    }
+
+    #[test]
+    fn clips_until_restored() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        canvas.set_fill_color(opaque(0, 0, 255));
+        canvas.save();
+        canvas.begin_path();
+        canvas.arc(5.0, 5.0, 3.0, 0.0, 2.0 * PI, false);
+        canvas.clip();
+        canvas.paint();
+        assert_eq!(alpha(&canvas, 5, 5), 255);
+        assert_eq!(alpha(&canvas, 0, 0), 0);
+        canvas.restore();
+        canvas.fill_rect(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(alpha(&canvas, 0, 0), 255);
+    }
+
+    #[test]
+    fn composites_layers() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        canvas.push_layer();
+        canvas.set_fill_color(opaque(0, 0, 255));
+        canvas.fill_rect(0.0, 0.0, 10.0, 10.0);
+        canvas.push_layer();
+        canvas.fill_rect(0.0, 0.0, 5.0, 10.0);
+        canvas.pop_layer(LayerComposite::DestinationOut, 1.0);
+        // the luminance of black removes everything
+        canvas.push_layer();
+        canvas.set_fill_color(opaque(0, 0, 0));
+        canvas.fill_rect(0.0, 0.0, 10.0, 5.0);
+        canvas.set_fill_color(opaque(255, 255, 255));
+        canvas.fill_rect(0.0, 5.0, 10.0, 5.0);
+        canvas.pop_layer(LayerComposite::LumaIn, 1.0);
+        canvas.pop_layer(LayerComposite::SourceOver, 0.5);
+
+        assert_eq!(alpha(&canvas, 2, 7), 0);
+        assert_eq!(alpha(&canvas, 7, 2), 0);
+        assert_eq!(alpha(&canvas, 7, 7), 128);
+    }
+
+    #[test]
+    fn draws_images_and_dashes() {
+        let canvas = SkiaCanvas::new(10, 10).unwrap();
+        let image = Bitmap::new(2, 1, vec![255, 0, 0, 255, 0, 0, 0, 0]).unwrap();
+        canvas.translate(1.0, 1.0);
+        canvas.draw_image(&image, 2.0, 2.0, 1.0);
+        assert_eq!(alpha(&canvas, 3, 3), 255);
+        assert_eq!(alpha(&canvas, 4, 3), 0);
+
+        canvas.set_stroke_color(opaque(0, 255, 0));
+        canvas.set_line_width(2.0);
+        canvas.set_line_dash(&[2.0]);
+        canvas.begin_path();
+        canvas.move_to(-1.0, 7.0);
+        canvas.line_to(9.0, 7.0);
+        canvas.stroke();
+        assert_eq!(alpha(&canvas, 0, 8), 255);
+        assert_eq!(alpha(&canvas, 2, 8), 0);
+        assert_eq!(alpha(&canvas, 4, 8), 255);
+    }
+}
@@ -6,8 +6,10 @@ use primitives::{
     BaseLine, CanvasContext, Color, Direction, Gradient, LineCap, LineJoin, Point, Rect, RgbColor,
     Size, TextAlign, TextMetrics, TextStyle, TextWeight, ToHexString,
 };
+use std::cell::RefCell;
 
-use wasm_bindgen::JsValue;
+use super::{Bitmap, LayerComposite, OffscreenContext};
+use wasm_bindgen::{Clamped, JsCast, JsValue};
 use wasm_bindgen_test::console_log;
 use web_sys;
 
@@ -25,12 +27,113 @@ impl Pattern {
 }
 
 pub struct Canvas<'a> {
-    ctx: &'a web_sys::CanvasRenderingContext2d,
+    root: &'a web_sys::CanvasRenderingContext2d,
+    /// The contexts of the offscreen layers, the current one last.
+    layers: RefCell<Vec<web_sys::CanvasRenderingContext2d>>,
 }
 
 impl<'a> Canvas<'a> {
     pub fn new(ctx: &'a web_sys::CanvasRenderingContext2d) -> Self {
-        Self { ctx }
+        Self {
+            root: ctx,
+            layers: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the context drawn onto, the current layer or the canvas.
+    fn ctx(&self) -> web_sys::CanvasRenderingContext2d {
+        self.layers
+            .borrow()
+            .last()
+            .cloned()
+            .unwrap_or_else(|| self.root.clone())
+    }
+
+    /// Creates a transparent context as large as `ctx`, with the same
+    /// transform.
+    fn create_layer(
+        ctx: &web_sys::CanvasRenderingContext2d,
+    ) -> Result<web_sys::CanvasRenderingContext2d, JsValue> {
+        let source = ctx
+            .canvas()
+            .ok_or_else(|| JsValue::from("detached context"))?;
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from("no document"))?;
+        let canvas = document
+            .create_element("canvas")?
+            .dyn_into::<web_sys::HtmlCanvasElement>()?;
+        canvas.set_width(source.width());
+        canvas.set_height(source.height());
+
+        let layer = canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from("no 2d context"))?
+            .dyn_into::<web_sys::CanvasRenderingContext2d>()?;
+        let m = ctx.get_transform()?;
+        layer.set_transform(m.a(), m.b(), m.c(), m.d(), m.e(), m.f())?;
+        Ok(layer)
+    }
+
+    /// Replaces the colors of `layer` by black with the luminance of the
+    /// colors over black as alpha.
+    fn luminance_to_alpha(
+        layer: &web_sys::CanvasRenderingContext2d,
+        inverted: bool,
+    ) -> Result<(), JsValue> {
+        let canvas = layer
+            .canvas()
+            .ok_or_else(|| JsValue::from("detached context"))?;
+        let (width, height) = (canvas.width(), canvas.height());
+        let image = layer.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+        let mut data = image.data();
+        for pixel in data.chunks_mut(4) {
+            let (r, g, b, a) = (
+                pixel[0] as f64,
+                pixel[1] as f64,
+                pixel[2] as f64,
+                pixel[3] as f64 / 255.0,
+            );
+            let mut luma = ((0.2126 * r + 0.7152 * g + 0.0722 * b) * a)
+                .round()
+                .min(255.0) as u8;
+            if inverted {
+                luma = 255 - luma;
+            }
+            pixel.copy_from_slice(&[0, 0, 0, luma]);
+        }
+        let image = web_sys::ImageData::new_with_u8_clamped_array_and_sh(
+            Clamped(&data[..]),
+            width,
+            height,
+        )?;
+        layer.put_image_data(&image, 0.0, 0.0)
+    }
+
+    /// Returns a canvas holding the pixels of `image`, to draw it with the
+    /// transform of a context.
+    fn image_canvas(image: &Bitmap) -> Result<web_sys::HtmlCanvasElement, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from("no document"))?;
+        let canvas = document
+            .create_element("canvas")?
+            .dyn_into::<web_sys::HtmlCanvasElement>()?;
+        canvas.set_width(image.width());
+        canvas.set_height(image.height());
+
+        let ctx = canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from("no 2d context"))?
+            .dyn_into::<web_sys::CanvasRenderingContext2d>()?;
+        let data = image.unpremultiplied_data();
+        let data = web_sys::ImageData::new_with_u8_clamped_array_and_sh(
+            Clamped(&data[..]),
+            image.width(),
+            image.height(),
+        )?;
+        ctx.put_image_data(&data, 0.0, 0.0)?;
+        Ok(canvas)
     }
 }
 
@@ -53,7 +156,7 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
 
     fn set_fill_color(&self, value: Color) {
         let color = JsValue::from(value.to_hex_string());
-        self.ctx.set_fill_style(&color);
+        self.ctx().set_fill_style(&color);
     }
 
     fn set_fill_gradient(&self, value: &Gradient) {
@@ -65,48 +168,49 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     }
 
     fn get_filter(&self) -> String {
-        self.ctx.filter()
+        self.ctx().filter()
     }
 
     fn set_filter(&self, value: &str) {
-        self.ctx.set_filter(value);
+        self.ctx().set_filter(value);
     }
 
     fn get_font(&self) -> String {
-        self.ctx.font()
+        self.ctx().font()
     }
 
     fn set_font(&self, family: &str, style: TextStyle, weight: TextWeight, size: f64) {
         // TODO: handle style and weight
-        self.ctx.set_font(format!("{}px {}", size, family).as_str());
+        self.ctx()
+            .set_font(format!("{}px {}", size, family).as_str());
     }
 
     fn get_global_alpha(&self) -> f64 {
-        self.ctx.global_alpha()
+        self.ctx().global_alpha()
     }
 
     fn set_global_alpha(&self, value: f64) {
-        self.ctx.set_global_alpha(value);
+        self.ctx().set_global_alpha(value);
     }
 
     fn get_global_composite_operation(&self) -> String {
         // TODO: handle err
-        self.ctx.global_composite_operation().unwrap()
+        self.ctx().global_composite_operation().unwrap()
     }
 
     fn set_global_composite_operation(&self, value: &str) {
         // TODO: handle err
-        let _ = self.ctx.set_global_composite_operation(value);
+        let _ = self.ctx().set_global_composite_operation(value);
     }
 
     // Whether images and patterns on this canvas will be smoothed when this canvas is scaled.
     // imageSmoothingEnabled
     fn is_image_smoothing_enabled(&self) -> bool {
-        self.ctx.image_smoothing_enabled()
+        self.ctx().image_smoothing_enabled()
     }
 
     fn set_image_smoothing(&self, value: bool) {
-        self.ctx.set_image_smoothing_enabled(value);
+        self.ctx().set_image_smoothing_enabled(value);
     }
 
     // fn get_image_smoothing_quality(&self) -> String {
@@ -118,86 +222,86 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     // }
 
     fn get_line_cap(&self) -> LineCap {
-        // self.ctx.line_cap()
+        // self.ctx().line_cap()
         unimplemented!()
     }
 
     fn set_line_cap(&self, value: LineCap) {
-        // self.ctx.set_line_cap(value);
+        // self.ctx().set_line_cap(value);
     }
 
     // @SupportedBrowser(SupportedBrowser.CHROME), @SupportedBrowser(SupportedBrowser.IE, '11'), @SupportedBrowser(SupportedBrowser.SAFARI), @Unstable()
     fn get_line_dash_offset(&self) -> f64 {
-        self.ctx.line_dash_offset()
+        self.ctx().line_dash_offset()
     }
 
     fn set_line_dash_offset(&self, value: f64) {
-        self.ctx.set_line_dash_offset(value);
+        self.ctx().set_line_dash_offset(value);
     }
 
     fn get_line_join(&self) -> LineJoin {
-        // self.ctx.line_join()
+        // self.ctx().line_join()
         unimplemented!()
     }
 
     fn set_line_join(&self, value: LineJoin) {
         // TODO: complete it
-        // self.ctx.set_line_join(value)
+        // self.ctx().set_line_join(value)
     }
 
     fn get_line_width(&self) -> f64 {
-        self.ctx.line_width()
+        self.ctx().line_width()
     }
 
     fn set_line_width(&self, value: f64) {
-        self.ctx.set_line_width(value);
+        self.ctx().set_line_width(value);
     }
 
     fn get_miter_limit(&self) -> f64 {
-        self.ctx.miter_limit()
+        self.ctx().miter_limit()
     }
 
     fn set_miter_limit(&self, value: f64) {
-        self.ctx.set_miter_limit(value);
+        self.ctx().set_miter_limit(value);
     }
 
     fn get_shadow_blur(&self) -> f64 {
-        self.ctx.shadow_blur()
+        self.ctx().shadow_blur()
     }
 
     fn set_shadow_blur(&self, value: f64) {
-        self.ctx.set_shadow_blur(value);
+        self.ctx().set_shadow_blur(value);
     }
 
     fn get_shadow_color(&self) -> Color {
-        // self.ctx.shadow_color()
+        // self.ctx().shadow_color()
         unimplemented!()
     }
 
     fn set_shadow_color(&self, value: Color) {
-        // self.ctx.set_shadow_color(value);
+        // self.ctx().set_shadow_color(value);
         unimplemented!()
     }
 
     fn get_shadow_offset_x(&self) -> f64 {
-        self.ctx.shadow_offset_x()
+        self.ctx().shadow_offset_x()
     }
 
     fn set_shadow_offset_x(&self, value: f64) {
-        self.ctx.set_shadow_offset_x(value);
+        self.ctx().set_shadow_offset_x(value);
     }
 
     fn get_shadow_offset_y(&self) -> f64 {
-        self.ctx.shadow_offset_y()
+        self.ctx().shadow_offset_y()
     }
 
     fn set_shadow_offset_y(&self, value: f64) {
-        self.ctx.set_shadow_offset_y(value);
+        self.ctx().set_shadow_offset_y(value);
     }
 
     fn set_stroke_color(&self, value: Color) {
         let color = JsValue::from(value.to_hex_string());
-        self.ctx.set_stroke_style(&color);
+        self.ctx().set_stroke_style(&color);
     }
 
     fn set_stroke_gradient(&self, value: &Gradient) {
@@ -209,23 +313,23 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     }
 
     fn get_text_align(&self) -> TextAlign {
-        // self.ctx.text_align()
+        // self.ctx().text_align()
         unimplemented!()
     }
 
     fn set_text_align(&self, value: TextAlign) {
         // TODO: complete it
-        // self.ctx.set_text_align(value);
+        // self.ctx().set_text_align(value);
     }
 
     fn get_text_baseline(&self) -> BaseLine {
-        // self.ctx.text_baseline()
+        // self.ctx().text_baseline()
         unimplemented!()
     }
 
     fn set_text_baseline(&self, value: BaseLine) {
         // TODO: complete it
-        // self.ctx.set_text_baseline(value);
+        // self.ctx().set_text_baseline(value);
     }
 
     // anticlockwise: bool = false
@@ -239,8 +343,8 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
         anticlockwise: bool,
     ) {
         if anticlockwise {
-            // let _ = self.ctx.arc(x, y, radius, start_angle, end_angle);
-            let _ = self.ctx.arc_with_anticlockwise(
+            // let _ = self.ctx().arc(x, y, radius, start_angle, end_angle);
+            let _ = self.ctx().arc_with_anticlockwise(
                 x,
                 y,
                 radius,
@@ -249,8 +353,8 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
                 anticlockwise,
             );
         } else {
-            let _ = self.ctx.arc(x, y, radius, start_angle, end_angle);
-            // let _ = self.ctx.arc_with_anticlockwise(x, y, radius, start_angle, end_angle, anticlockwise);
+            let _ = self.ctx().arc(x, y, radius, start_angle, end_angle);
+            // let _ = self.ctx().arc_with_anticlockwise(x, y, radius, start_angle, end_angle, anticlockwise);
         }
 
         // pub fn arc_with_anticlockwise(
@@ -265,25 +369,25 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     }
 
     fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
-        let _ = self.ctx.arc_to(x1, y1, x2, y2, radius);
+        let _ = self.ctx().arc_to(x1, y1, x2, y2, radius);
     }
 
     fn begin_path(&self) {
-        self.ctx.begin_path()
+        self.ctx().begin_path()
     }
 
     fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
-        self.ctx.bezier_curve_to(cp1x, cp1y, cp2x, cp2y, x, y);
+        self.ctx().bezier_curve_to(cp1x, cp1y, cp2x, cp2y, x, y);
     }
 
     fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
-        self.ctx.clear_rect(x, y, width, height);
+        self.ctx().clear_rect(x, y, width, height);
     }
 
     // [path_OR_winding: dynamic, winding: String]
     // fn clip(path_OR_winding: dynamic, winding: String); // TODO:
     fn close_path(&self) {
-        self.ctx.close_path();
+        self.ctx().close_path();
     }
 
     // @Creates('ImageData|=Object')
@@ -344,16 +448,16 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     // fn fill(path_OR_winding: dynamic, winding: String); // TODO:
 
     fn fill(&self) {
-        self.ctx.fill();
+        self.ctx().fill();
     }
 
     fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
-        self.ctx.fill_rect(x, y, width, height);
+        self.ctx().fill_rect(x, y, width, height);
     }
 
     // Draws text to the canvas.
     fn fill_text(&self, text: &str, x: f64, y: f64) {
-        let _ = self.ctx.fill_text(text, x, y);
+        let _ = self.ctx().fill_text(text, x, y);
     }
 
     // fn getContextAttributes() -> Map; // TODO:
@@ -373,11 +477,11 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     // [f64? y]
     // fn isPointInStroke(path_OR_x: dynamic, x_OR_y: f64, y: f64) -> bool; // TODO:
     fn line_to(&self, x: f64, y: f64) {
-        self.ctx.line_to(x, y);
+        self.ctx().line_to(x, y);
     }
 
     fn measure_text(&self, text: &str) -> TextMetrics {
-        match self.ctx.measure_text(text) {
+        match self.ctx().measure_text(text) {
             Ok(metric) => TextMetrics {
                 width: metric.width(),
                 height: -1.,
@@ -393,37 +497,37 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     }
 
     fn move_to(&self, x: f64, y: f64) {
-        self.ctx.move_to(x, y);
+        self.ctx().move_to(x, y);
     }
 
     // [int? dirtyX, int? dirtyY, int? dirtyWidth, int? dirtyHeight]
     // fn putImageData(imagedata: ImageData, dx: i64, dy: i64, dirtyX: i64, dirtyY: i64, dirtyWidth: i64, dirtyHeight: i64); // TODO:
     fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64) {
-        self.ctx.quadratic_curve_to(cpx, cpy, x, y);
+        self.ctx().quadratic_curve_to(cpx, cpy, x, y);
     }
 
     fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
-        self.ctx.rect(x, y, width, height);
+        self.ctx().rect(x, y, width, height);
     }
 
     fn reset_transform(&self) {
-        let _ = self.ctx.reset_transform();
+        let _ = self.ctx().reset_transform();
     }
 
     fn restore(&self) {
-        self.ctx.restore();
+        self.ctx().restore();
     }
 
     fn rotate(&self, angle: f64) {
-        let _ = self.ctx.rotate(angle);
+        let _ = self.ctx().rotate(angle);
     }
 
     fn save(&self) {
-        self.ctx.save();
+        self.ctx().save();
     }
 
     fn scale(&self, x: f64, y: f64) {
-        let _ = self.ctx.scale(x, y);
+        let _ = self.ctx().scale(x, y);
     }
 
     // [Path2D? path]
@@ -435,26 +539,89 @@ impl<'a> CanvasContext<Pattern> for Canvas<'a> {
     }
 
     fn set_transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
-        let _ = self.ctx.set_transform(a, b, c, d, e, f);
+        let _ = self.ctx().set_transform(a, b, c, d, e, f);
     }
 
     fn stroke(&self) {
-        self.ctx.stroke();
+        self.ctx().stroke();
     }
 
     fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64) {
-        self.ctx.stroke_rect(x, y, width, height);
+        self.ctx().stroke_rect(x, y, width, height);
     }
 
     fn stroke_text(&self, text: &str, x: f64, y: f64) {
-        let _ = self.ctx.stroke_text(text, x, y);
+        let _ = self.ctx().stroke_text(text, x, y);
     }
 
     fn transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
-        let _ = self.ctx.transform(a, b, c, d, e, f);
+        let _ = self.ctx().transform(a, b, c, d, e, f);
     }
 
     fn translate(&self, x: f64, y: f64) {
-        let _ = self.ctx.translate(x, y);
+        let _ = self.ctx().translate(x, y);
+    }
+}
+
+impl<'a> OffscreenContext for Canvas<'a> {
+    fn push_layer(&self) {
+        // TODO: handle err
+        if let Ok(layer) = Self::create_layer(&self.ctx()) {
+            self.layers.borrow_mut().push(layer);
+        }
+    }
+
+    fn pop_layer(&self, composite: LayerComposite, alpha: f64) {
+        let layer = match self.layers.borrow_mut().pop() {
+            Some(layer) => layer,
+            None => return,
+        };
+        let operation = match composite {
+            LayerComposite::SourceOver => "source-over",
+            // luminance masks are turned into alpha masks first
+            LayerComposite::DestinationIn | LayerComposite::LumaIn | LayerComposite::LumaOut => {
+                "destination-in"
+            }
+            LayerComposite::DestinationOut => "destination-out",
+            LayerComposite::Xor => "xor",
+        };
+        if let LayerComposite::LumaIn | LayerComposite::LumaOut = composite {
+            let _ = Self::luminance_to_alpha(&layer, composite == LayerComposite::LumaOut);
+        }
+
+        let ctx = self.ctx();
+        if let Some(canvas) = layer.canvas() {
+            ctx.save();
+            let _ = ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+            ctx.set_global_alpha(alpha);
+            let _ = ctx.set_global_composite_operation(operation);
+            let _ = ctx.draw_image_with_html_canvas_element(&canvas, 0.0, 0.0);
+            ctx.restore();
+        }
+    }
+
+    fn paint(&self) {
+        let ctx = self.ctx();
+        if let Some(canvas) = ctx.canvas() {
+            ctx.save();
+            let _ = ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+            ctx.fill_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+            ctx.restore();
+        }
+    }
+
+    fn clip(&self) {
+        self.ctx().clip();
+    }
+
+    fn draw_image(&self, image: &Bitmap, x: f64, y: f64, alpha: f64) {
+        // TODO: handle err
+        if let Ok(canvas) = Self::image_canvas(image) {
+            let ctx = self.ctx();
+            ctx.save();
+            ctx.set_global_alpha(alpha);
+            let _ = ctx.draw_image_with_html_canvas_element(&canvas, x, y);
+            ctx.restore();
+        }
     }
 }
@@ -1,28 +1,364 @@
-#![allow(dead_code)]
+//! The easing curves shared by every animation system of the crate.
+//!
+//! The named curves are plain functions, [`Easing`] names them, so they can be
+//! looked up by name with [`Easing::from_name`] or converted from an
+//! `AnimationMode`. The `tween::easing` modules are the same curves under
+//! tween.js names.
+//!
+//! [`EasingFunction`] holds any of them, as well as the parametric curves:
+//! CSS `cubic-bezier()`, `steps()` and `linear()`, and tunable elastic and
+//! back curves.
 #![allow(clippy::float_cmp)]
 
-/// The easing function type.
+use std::{f64::consts::PI, fmt, rc::Rc};
+
+/// An easing function.
 ///
 /// An easing function takes an input number [t] in range 0..1, inclusive, and
-/// returns a non-negative amount. In addition, the function must return 1 for
-/// [t] = 1.
-use std::f64::consts::PI;
+/// returns the eased progress. The curves return 0 for [t] = 0 and 1 for
+/// [t] = 1, except the steps jumping at the start.
+///
+/// It is a cheap handle to a closure, so curves can hold parameters. Plain
+/// functions, closures and [`Easing`] convert into it, so all of them can be
+/// passed where an easing is accepted.
+///
+/// # Example
+///
+/// ```
+/// use animate::easing::EasingFunction;
+///
+/// let ease = EasingFunction::cubic_bezier(0.25, 0.1, 0.25, 1.0);
+/// assert!(ease.ease(0.5) > 0.5);
+/// ```
+#[derive(Clone)]
+pub struct EasingFunction(Rc<dyn Fn(f64) -> f64>);
+
+impl EasingFunction {
+    /// Constructs a new easing function from a closure.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(f64) -> f64 + 'static,
+    {
+        EasingFunction(Rc::new(f))
+    }
+
+    /// Returns the eased progress at [t].
+    pub fn ease(&self, t: f64) -> f64 {
+        (self.0)(t)
+    }
+
+    /// Constructs the CSS `cubic-bezier(x1, y1, x2, y2)` curve.
+    ///
+    /// The curve goes from (0, 0) to (1, 1) with the two control points.
+    /// `x1` and `x2` are clamped to 0..1 as CSS requires.
+    pub fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        let bezier = UnitBezier::new(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2);
+        EasingFunction::new(move |t| bezier.solve(t))
+    }
+
+    /// Constructs the CSS `steps(steps, position)` curve.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is 0, or less than 2 with [`StepPosition::JumpNone`].
+    pub fn steps(steps: usize, position: StepPosition) -> Self {
+        let jumps = match position {
+            StepPosition::JumpStart | StepPosition::JumpEnd => steps as f64,
+            StepPosition::JumpNone => steps as f64 - 1.0,
+            StepPosition::JumpBoth => steps as f64 + 1.0,
+        };
+        assert!(jumps >= 1.0 && steps > 0, "not enough steps");
+
+        EasingFunction::new(move |t| {
+            let mut step = (t * steps as f64).floor();
+            if let StepPosition::JumpStart | StepPosition::JumpBoth = position {
+                step += 1.0;
+            }
+            if t >= 0.0 && step < 0.0 {
+                step = 0.0;
+            }
+            if t <= 1.0 && step > jumps {
+                step = jumps;
+            }
+            step / jumps
+        })
+    }
+
+    /// Constructs the CSS `linear(...)` curve through the given stops.
+    ///
+    /// Every stop is an output value with an optional input progress, the
+    /// missing inputs are spread evenly between their neighbours as in
+    /// [CSS Easing Level 2]. Outside of the first and the last stop the curve
+    /// is extrapolated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than two stops.
+    ///
+    /// [CSS Easing Level 2]: https://www.w3.org/TR/css-easing-2/#the-linear-easing-function
+    pub fn linear(stops: &[(f64, Option<f64>)]) -> Self {
+        let points = linear_points(stops);
+        EasingFunction::new(move |t| {
+            let i = points
+                .iter()
+                .rposition(|&(input, _)| input <= t)
+                .unwrap_or(0)
+                .min(points.len() - 2);
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[i + 1];
+            if x0 == x1 {
+                return y1;
+            }
+            y0 + (y1 - y0) * (t - x0) / (x1 - x0)
+        })
+    }
+
+    /// Constructs an elastic curve with the given `amplitude` and `period`.
+    ///
+    /// [`elastic_in`] is `elastic_in(1.0, 0.3)`.
+    pub fn elastic_in(amplitude: f64, period: f64) -> Self {
+        EasingFunction::new(move |t| elastic_in_with(t, amplitude, period))
+    }
+
+    /// Constructs an elastic curve with the given `amplitude` and `period`.
+    ///
+    /// [`elastic_out`] is `elastic_out(1.0, 0.3)`.
+    pub fn elastic_out(amplitude: f64, period: f64) -> Self {
+        EasingFunction::new(move |t| elastic_out_with(t, amplitude, period))
+    }
 
-pub type EasingFunction = fn(t: f64) -> f64;
+    /// Constructs an elastic curve with the given `amplitude` and `period`.
+    ///
+    /// [`elastic_in_out`] is `elastic_in_out(1.0, 0.45)`.
+    pub fn elastic_in_out(amplitude: f64, period: f64) -> Self {
+        EasingFunction::new(move |t| elastic_in_out_with(t, amplitude, period))
+    }
+
+    /// Constructs a back curve with the given `overshoot`.
+    ///
+    /// [`back_in`] is `back_in(1.70158)`, which overshoots by 10%.
+    pub fn back_in(overshoot: f64) -> Self {
+        EasingFunction::new(move |t| back_in_with(t, overshoot))
+    }
+
+    /// Constructs a back curve with the given `overshoot`.
+    ///
+    /// [`back_out`] is `back_out(1.70158)`, which overshoots by 10%.
+    pub fn back_out(overshoot: f64) -> Self {
+        EasingFunction::new(move |t| back_out_with(t, overshoot))
+    }
+
+    /// Constructs a back curve with the given `overshoot`.
+    ///
+    /// [`back_in_out`] is `back_in_out(1.70158)`, which overshoots by 10%.
+    pub fn back_in_out(overshoot: f64) -> Self {
+        EasingFunction::new(move |t| back_in_out_with(t, overshoot))
+    }
+}
+
+impl Default for EasingFunction {
+    fn default() -> Self {
+        EasingFunction::new(linear)
+    }
+}
+
+impl<F> From<F> for EasingFunction
+where
+    F: Fn(f64) -> f64 + 'static,
+{
+    fn from(f: F) -> Self {
+        EasingFunction::new(f)
+    }
+}
 
-fn linear(amount: f64) -> f64 {
+impl From<Easing> for EasingFunction {
+    fn from(easing: Easing) -> Self {
+        get_easing(easing)
+    }
+}
+
+impl fmt::Debug for EasingFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EasingFunction")
+    }
+}
+
+/// Where the jumps of a [`EasingFunction::steps`] curve happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StepPosition {
+    /// The first jump happens at the start.
+    JumpStart,
+    /// The last jump happens at the end, as `steps(n)` does by default.
+    #[default]
+    JumpEnd,
+    /// No jump at the start nor at the end.
+    JumpNone,
+    /// Jumps at both the start and the end.
+    JumpBoth,
+}
+
+/// A cubic bezier from (0, 0) to (1, 1), solved as WebKit and Gecko do.
+struct UnitBezier {
+    ax: f64,
+    bx: f64,
+    cx: f64,
+    ay: f64,
+    by: f64,
+    cy: f64,
+    start_gradient: f64,
+    end_gradient: f64,
+}
+
+impl UnitBezier {
+    const EPSILON: f64 = 1e-7;
+
+    fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        let cx = 3.0 * x1;
+        let bx = 3.0 * (x2 - x1) - cx;
+        let cy = 3.0 * y1;
+        let by = 3.0 * (y2 - y1) - cy;
+
+        // the curve is extended with its end tangents outside of 0..1
+        let start_gradient = if x1 > 0.0 {
+            y1 / x1
+        } else if y1 == 0.0 && x2 > 0.0 {
+            y2 / x2
+        } else if y1 == 0.0 && y2 == 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        let end_gradient = if x2 < 1.0 {
+            (y2 - 1.0) / (x2 - 1.0)
+        } else if y2 == 1.0 && x1 < 1.0 {
+            (y1 - 1.0) / (x1 - 1.0)
+        } else if y1 == 1.0 && y2 == 1.0 {
+            1.0
+        } else {
+            0.0
+        };
+
+        UnitBezier {
+            ax: 1.0 - cx - bx,
+            bx,
+            cx,
+            ay: 1.0 - cy - by,
+            by,
+            cy,
+            start_gradient,
+            end_gradient,
+        }
+    }
+
+    fn sample_x(&self, t: f64) -> f64 {
+        ((self.ax * t + self.bx) * t + self.cx) * t
+    }
+
+    fn sample_y(&self, t: f64) -> f64 {
+        ((self.ay * t + self.by) * t + self.cy) * t
+    }
+
+    fn sample_derivative_x(&self, t: f64) -> f64 {
+        (3.0 * self.ax * t + 2.0 * self.bx) * t + self.cx
+    }
+
+    /// Finds the curve parameter for `x`, with Newton's method first and
+    /// bisection when it does not converge.
+    fn solve_x(&self, x: f64) -> f64 {
+        let mut t = x;
+        for _ in 0..8 {
+            let error = self.sample_x(t) - x;
+            if error.abs() < Self::EPSILON {
+                return t;
+            }
+            let derivative = self.sample_derivative_x(t);
+            if derivative.abs() < 1e-6 {
+                break;
+            }
+            t -= error / derivative;
+        }
+
+        let mut low = 0.0;
+        let mut high = 1.0;
+        t = x;
+        while low < high {
+            let value = self.sample_x(t);
+            if (value - x).abs() < Self::EPSILON {
+                return t;
+            }
+            if x > value {
+                low = t;
+            } else {
+                high = t;
+            }
+            t = (high - low) / 2.0 + low;
+            if high - low < Self::EPSILON {
+                break;
+            }
+        }
+        t
+    }
+
+    fn solve(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return self.start_gradient * x;
+        }
+        if x > 1.0 {
+            return 1.0 + self.end_gradient * (x - 1.0);
+        }
+        self.sample_y(self.solve_x(x))
+    }
+}
+
+/// Resolves the inputs of the `linear()` stops.
+fn linear_points(stops: &[(f64, Option<f64>)]) -> Vec<(f64, f64)> {
+    assert!(stops.len() >= 2, "not enough stops");
+
+    let last = stops.len() - 1;
+    let mut inputs: Vec<Option<f64>> = stops.iter().map(|&(_, input)| input).collect();
+    inputs[0] = inputs[0].or(Some(0.0));
+    inputs[last] = inputs[last].or(Some(1.0));
+
+    // an input is never smaller than any input before it
+    let mut largest = f64::NEG_INFINITY;
+    for input in inputs.iter_mut().flatten() {
+        largest = largest.max(*input);
+        *input = largest;
+    }
+
+    // spread the missing inputs evenly between the known ones
+    let mut start = 0;
+    for i in 1..=last {
+        if let Some(end) = inputs[i] {
+            let from = inputs[start].unwrap_or_default();
+            for (j, input) in inputs.iter_mut().enumerate().take(i).skip(start + 1) {
+                let k = (j - start) as f64 / (i - start) as f64;
+                *input = Some(from + (end - from) * k);
+            }
+            start = i;
+        }
+    }
+
+    stops
+        .iter()
+        .zip(inputs)
+        .map(|(&(output, _), input)| (input.unwrap_or_default(), output))
+        .collect()
+}
+
+pub fn linear(amount: f64) -> f64 {
     amount
 }
 
-fn quad_in(amount: f64) -> f64 {
+pub fn quad_in(amount: f64) -> f64 {
     amount * amount
 }
 
-fn quad_out(amount: f64) -> f64 {
+pub fn quad_out(amount: f64) -> f64 {
     amount * (2.0 - amount)
 }
 
-fn quad_in_out(amount: f64) -> f64 {
+pub fn quad_in_out(amount: f64) -> f64 {
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return 0.5 * amount * amount;
@@ -31,16 +367,16 @@ fn quad_in_out(amount: f64) -> f64 {
     0.5 * (1.0 - amount * (amount - 2.0))
 }
 
-fn cubic_in(amount: f64) -> f64 {
+pub fn cubic_in(amount: f64) -> f64 {
     amount * amount * amount
 }
 
-fn cubic_out(amount: f64) -> f64 {
+pub fn cubic_out(amount: f64) -> f64 {
     let amount = amount - 1.0;
     amount * amount * amount + 1.0
 }
 
-fn cubic_in_out(amount: f64) -> f64 {
+pub fn cubic_in_out(amount: f64) -> f64 {
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return 0.5 * amount * amount * amount;
@@ -49,16 +385,16 @@ fn cubic_in_out(amount: f64) -> f64 {
     0.5 * (amount * amount * amount + 2.0)
 }
 
-fn quart_in(amount: f64) -> f64 {
+pub fn quart_in(amount: f64) -> f64 {
     amount * amount * amount * amount
 }
 
-fn quart_out(amount: f64) -> f64 {
+pub fn quart_out(amount: f64) -> f64 {
     let amount = amount - 1.0;
     1.0 - amount * amount * amount * amount
 }
 
-fn quart_in_out(amount: f64) -> f64 {
+pub fn quart_in_out(amount: f64) -> f64 {
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return 0.5 * amount * amount * amount * amount;
@@ -67,16 +403,16 @@ fn quart_in_out(amount: f64) -> f64 {
     0.5 * (2.0 - amount * amount * amount * amount)
 }
 
-fn quint_in(amount: f64) -> f64 {
+pub fn quint_in(amount: f64) -> f64 {
     amount * amount * amount * amount * amount
 }
 
-fn quint_out(amount: f64) -> f64 {
+pub fn quint_out(amount: f64) -> f64 {
     let amount = amount - 1.0;
     amount * amount * amount * amount * amount + 1.0
 }
 
-fn quint_in_out(amount: f64) -> f64 {
+pub fn quint_in_out(amount: f64) -> f64 {
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return 0.5 * amount * amount * amount * amount * amount;
@@ -86,27 +422,27 @@ fn quint_in_out(amount: f64) -> f64 {
     0.5 * (amount * amount * amount * amount * amount + 2.0)
 }
 
-fn sine_in(amount: f64) -> f64 {
+pub fn sine_in(amount: f64) -> f64 {
     1.0 - (amount * PI / 2.0).cos()
 }
 
-fn sine_out(amount: f64) -> f64 {
+pub fn sine_out(amount: f64) -> f64 {
     (amount * PI / 2.0).sin()
 }
 
-fn sine_in_out(t: f64) -> f64 {
+pub fn sine_in_out(t: f64) -> f64 {
     0.5 * (1.0 - (PI * t).cos())
 }
 
-fn expo_in(amount: f64) -> f64 {
+pub fn expo_in(amount: f64) -> f64 {
     if amount == 0.0 {
-        return 1.0;
+        return 0.0;
     }
 
     f64::powf(2.0, 10.0 * (amount - 1.0))
 }
 
-fn expo_out(amount: f64) -> f64 {
+pub fn expo_out(amount: f64) -> f64 {
     if amount == 1.0 {
         return 1.0;
     }
@@ -114,7 +450,7 @@ fn expo_out(amount: f64) -> f64 {
     1.0 - f64::powf(2.0, -10.0 * amount)
 }
 
-fn expo_in_out(amount: f64) -> f64 {
+pub fn expo_in_out(amount: f64) -> f64 {
     if amount == 0.0 {
         return 0.0;
     }
@@ -130,7 +466,7 @@ fn expo_in_out(amount: f64) -> f64 {
     0.5 * (-f64::powf(2.0, -10.0 * amount) + 2.0)
 }
 
-fn circ_in(amount: f64) -> f64 {
+pub fn circ_in(amount: f64) -> f64 {
     if amount >= 1.0 {
         return amount;
     }
@@ -138,12 +474,12 @@ fn circ_in(amount: f64) -> f64 {
     1.0 - (1.0 - amount * amount).sqrt()
 }
 
-fn circ_out(amount: f64) -> f64 {
+pub fn circ_out(amount: f64) -> f64 {
     let amount = amount - 1.0;
     (1.0 - amount * amount).sqrt()
 }
 
-fn circ_in_out(amount: f64) -> f64 {
+pub fn circ_in_out(amount: f64) -> f64 {
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return -0.5 * ((1.0 - amount * amount).sqrt() - 1.0);
@@ -152,106 +488,84 @@ fn circ_in_out(amount: f64) -> f64 {
     0.5 * ((1.0 - amount * amount).sqrt() + 1.0)
 }
 
-fn elastic_in(amount: f64) -> f64 {
-    let mut p = 0.0;
-    let mut a = 1.0;
-    if amount == 0.0 {
-        return 0.0;
-    }
-    if amount == 1.0 {
-        return 1.0;
-    }
-    if p == 0.0 {
-        p = 0.3;
-    }
-
-    #[allow(unused_assignments)]
-    let mut s = 1.70158;
-    if a < 1.0 {
-        a = 1.0;
-        s = p / 4.0;
-    } else {
-        s = p / (2.0 * PI) * f64::asin(1.0 / a);
-    }
+pub fn elastic_in(amount: f64) -> f64 {
+    elastic_in_with(amount, 1.0, 0.3)
+}
 
-    let amount = amount - 1.0;
-    -(a * f64::powf(2.0, 10.0 * amount) * ((amount - s) * (2.0 * PI) / p).sin())
+pub fn elastic_out(amount: f64) -> f64 {
+    elastic_out_with(amount, 1.0, 0.3)
 }
 
-fn elastic_out(amount: f64) -> f64 {
-    let mut p = 0.0;
-    let mut a = 1.0;
+pub fn elastic_in_out(amount: f64) -> f64 {
+    elastic_in_out_with(amount, 1.0, 0.3 * 1.5)
+}
 
-    if amount == 0.0 {
-        return 0.0;
+/// Returns the amplitude and the phase shift of an elastic curve.
+fn elastic_params(amplitude: f64, period: f64) -> (f64, f64) {
+    if amplitude < 1.0 {
+        (1.0, period / 4.0)
+    } else {
+        (amplitude, period / (2.0 * PI) * f64::asin(1.0 / amplitude))
     }
+}
 
-    if amount == 1.0 {
-        return 1.0;
+fn elastic_in_with(amount: f64, amplitude: f64, period: f64) -> f64 {
+    if amount == 0.0 || amount == 1.0 {
+        return amount;
     }
 
-    if p == 0.0 {
-        p = 0.3;
-    }
+    let (a, s) = elastic_params(amplitude, period);
+    let amount = amount - 1.0;
+    -(a * f64::powf(2.0, 10.0 * amount) * ((amount - s) * (2.0 * PI) / period).sin())
+}
 
-    #[allow(unused_assignments)]
-    let mut s = 1.70158;
-    if a < 1.0 {
-        a = 1.0;
-        s = p / 4.0;
-    } else {
-        s = p / (2.0 * PI) * f64::asin(1.0 / a);
+fn elastic_out_with(amount: f64, amplitude: f64, period: f64) -> f64 {
+    if amount == 0.0 || amount == 1.0 {
+        return amount;
     }
 
-    a * f64::powf(2.0, -10.0 * amount) * ((amount - s) * (2.0 * PI) / p).sin() + 1.0
+    let (a, s) = elastic_params(amplitude, period);
+    a * f64::powf(2.0, -10.0 * amount) * ((amount - s) * (2.0 * PI) / period).sin() + 1.0
 }
 
-fn elastic_in_out(amount: f64) -> f64 {
-    let mut p = 0.0;
-    let mut a = 1.0;
-
-    if amount == 0.0 {
-        return 0.0;
+fn elastic_in_out_with(amount: f64, amplitude: f64, period: f64) -> f64 {
+    if amount == 0.0 || amount == 1.0 {
+        return amount;
     }
 
-    if amount == 1.0 {
-        return 1.0;
+    let (a, s) = elastic_params(amplitude, period);
+    let amount = 2.0 * amount - 1.0;
+    let wave = ((amount - s) * (2.0 * PI) / period).sin();
+    if amount < 0.0 {
+        return -0.5 * (a * f64::powf(2.0, 10.0 * amount) * wave);
     }
 
-    if p == 0.0 {
-        p = 1.0 * (0.3 * 1.5);
-    }
+    a * f64::powf(2.0, -10.0 * amount) * wave * 0.5 + 1.0
+}
 
-    #[allow(unused_assignments)]
-    let mut s = 1.70158;
-    if a < 1.0 {
-        a = 1.0;
-        s = p / 4.0;
-    } else {
-        s = p / (2.0 * PI) * f64::asin(1.0 / a);
-    }
+pub fn back_in(amount: f64) -> f64 {
+    back_in_with(amount, 1.70158)
+}
 
-    let amount = 2.0 * amount - 1.0;
-    if amount < 0.0 {
-        return -0.5 * (a * f64::powf(2.0, 10.0 * amount) * ((amount - s) * (2.0 * PI) / p).sin());
-    }
+pub fn back_out(amount: f64) -> f64 {
+    back_out_with(amount, 1.70158)
+}
 
-    a * f64::powf(2.0, -10.0 * amount) * ((amount - s) * (2.0 * PI) / p).sin() * 0.5 + 1.0
+pub fn back_in_out(amount: f64) -> f64 {
+    back_in_out_with(amount, 1.70158)
 }
 
-fn back_in(amount: f64) -> f64 {
-    let s = 1.70158;
+fn back_in_with(amount: f64, s: f64) -> f64 {
     amount * amount * ((s + 1.0) * amount - s)
 }
 
-fn back_out(amount: f64) -> f64 {
-    let s = 1.70158;
+fn back_out_with(amount: f64, s: f64) -> f64 {
     let amount = amount - 1.0;
     amount * amount * ((s + 1.0) * amount + s) + 1.0
 }
 
-fn back_in_out(amount: f64) -> f64 {
-    let s = 1.70158 * 1.525;
+fn back_in_out_with(amount: f64, s: f64) -> f64 {
+    let s = s * 1.525;
     let mut amount = amount * 2.0;
     if amount < 1.0 {
         return 0.5 * (amount * amount * ((s + 1.0) * amount - s));
@@ -260,11 +574,11 @@ fn back_in_out(amount: f64) -> f64 {
     0.5 * (amount * amount * ((s + 1.0) * amount + s) + 2.0)
 }
 
-fn bounce_in(amount: f64) -> f64 {
+pub fn bounce_in(amount: f64) -> f64 {
     1.0 - bounce_out(1.0 - amount)
 }
 
-fn bounce_out(t: f64) -> f64 {
+pub fn bounce_out(t: f64) -> f64 {
     let mut amount = t;
     if amount < 1.0 / 2.75 {
         7.5625 * amount * amount
@@ -280,7 +594,7 @@ fn bounce_out(t: f64) -> f64 {
     }
 }
 
-fn bounce_in_out(amount: f64) -> f64 {
+pub fn bounce_in_out(amount: f64) -> f64 {
     if amount < 0.5 {
         return bounce_in(amount * 2.0) * 0.5;
     }
@@ -288,6 +602,8 @@ fn bounce_in_out(amount: f64) -> f64 {
     bounce_out(amount * 2.0 - 1.0) * 0.5 + 1.0 * 0.5
 }
 
+/// The named easing curves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Easing {
     Linear,
     QuadIn,
@@ -322,42 +638,326 @@ pub enum Easing {
     BounceInOut,
 }
 
-/// Returns the easing function with the given [name].
-///
-/// [name] can be an [EasingFunction] or a [String] specifying the name of one
-/// of the easing functions defined above.
+impl Easing {
+    /// All the easing curves.
+    pub const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    /// Returns the name of the curve as used by CSS libraries and
+    /// After Effects, like `"easeInOutCubic"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Easing::Linear => "linear",
+            Easing::QuadIn => "easeInQuad",
+            Easing::QuadOut => "easeOutQuad",
+            Easing::QuadInOut => "easeInOutQuad",
+            Easing::CubicIn => "easeInCubic",
+            Easing::CubicOut => "easeOutCubic",
+            Easing::CubicInOut => "easeInOutCubic",
+            Easing::QuartIn => "easeInQuart",
+            Easing::QuartOut => "easeOutQuart",
+            Easing::QuartInOut => "easeInOutQuart",
+            Easing::QuintIn => "easeInQuint",
+            Easing::QuintOut => "easeOutQuint",
+            Easing::QuintInOut => "easeInOutQuint",
+            Easing::SineIn => "easeInSine",
+            Easing::SineOut => "easeOutSine",
+            Easing::SineInOut => "easeInOutSine",
+            Easing::ExpoIn => "easeInExpo",
+            Easing::ExpoOut => "easeOutExpo",
+            Easing::ExpoInOut => "easeInOutExpo",
+            Easing::CircIn => "easeInCirc",
+            Easing::CircOut => "easeOutCirc",
+            Easing::CircInOut => "easeInOutCirc",
+            Easing::ElasticIn => "easeInElastic",
+            Easing::ElasticOut => "easeOutElastic",
+            Easing::ElasticInOut => "easeInOutElastic",
+            Easing::BackIn => "easeInBack",
+            Easing::BackOut => "easeOutBack",
+            Easing::BackInOut => "easeInOutBack",
+            Easing::BounceIn => "easeInBounce",
+            Easing::BounceOut => "easeOutBounce",
+            Easing::BounceInOut => "easeInOutBounce",
+        }
+    }
+
+    /// Looks a curve up by its name, see [`Easing::name`].
+    ///
+    /// The lookup ignores the case, so the `AnimationMode` variant names like
+    /// `"EaseInOutCubic"` are found as well.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Easing::ALL
+            .iter()
+            .copied()
+            .find(|easing| easing.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the easing function of the curve.
+    pub fn function(self) -> EasingFunction {
+        get_easing(self)
+    }
+
+    fn curve(self) -> fn(f64) -> f64 {
+        match self {
+            Easing::Linear => linear,
+            Easing::QuadIn => quad_in,
+            Easing::QuadOut => quad_out,
+            Easing::QuadInOut => quad_in_out,
+            Easing::CubicIn => cubic_in,
+            Easing::CubicOut => cubic_out,
+            Easing::CubicInOut => cubic_in_out,
+            Easing::QuartIn => quart_in,
+            Easing::QuartOut => quart_out,
+            Easing::QuartInOut => quart_in_out,
+            Easing::QuintIn => quint_in,
+            Easing::QuintOut => quint_out,
+            Easing::QuintInOut => quint_in_out,
+            Easing::SineIn => sine_in,
+            Easing::SineOut => sine_out,
+            Easing::SineInOut => sine_in_out,
+            Easing::ExpoIn => expo_in,
+            Easing::ExpoOut => expo_out,
+            Easing::ExpoInOut => expo_in_out,
+            Easing::CircIn => circ_in,
+            Easing::CircOut => circ_out,
+            Easing::CircInOut => circ_in_out,
+            Easing::ElasticIn => elastic_in,
+            Easing::ElasticOut => elastic_out,
+            Easing::ElasticInOut => elastic_in_out,
+            Easing::BackIn => back_in,
+            Easing::BackOut => back_out,
+            Easing::BackInOut => back_in_out,
+            Easing::BounceIn => bounce_in,
+            Easing::BounceOut => bounce_out,
+            Easing::BounceInOut => bounce_in_out,
+        }
+    }
+}
+
+/// Returns the easing function of the given [etype].
 pub fn get_easing(etype: Easing) -> EasingFunction {
-    match etype {
-        Easing::Linear => linear,
-        Easing::QuadIn => quad_in,
-        Easing::QuadOut => quad_out,
-        Easing::QuadInOut => quad_in_out,
-        Easing::CubicIn => cubic_in,
-        Easing::CubicOut => cubic_out,
-        Easing::CubicInOut => cubic_in_out,
-        Easing::QuartIn => quart_in,
-        Easing::QuartOut => quart_out,
-        Easing::QuartInOut => quad_in_out,
-        Easing::QuintIn => quint_in,
-        Easing::QuintOut => quint_out,
-        Easing::QuintInOut => quint_in_out,
-        Easing::SineIn => sine_in,
-        Easing::SineOut => sine_out,
-        Easing::SineInOut => sine_in_out,
-        Easing::ExpoIn => expo_in,
-        Easing::ExpoOut => expo_out,
-        Easing::ExpoInOut => expo_in_out,
-        Easing::CircIn => circ_in,
-        Easing::CircOut => circ_out,
-        Easing::CircInOut => circ_in_out,
-        Easing::ElasticIn => elastic_in,
-        Easing::ElasticOut => elastic_out,
-        Easing::ElasticInOut => elastic_in_out,
-        Easing::BackIn => back_in,
-        Easing::BackOut => back_out,
-        Easing::BackInOut => back_out,
-        Easing::BounceIn => bounce_in,
-        Easing::BounceOut => bounce_out,
-        Easing::BounceInOut => bounce_in_out,
+    EasingFunction::new(etype.curve())
+}
+
+/// Returns the easing function with the given [name], see [`Easing::from_name`].
+pub fn get_easing_by_name(name: &str) -> Option<EasingFunction> {
+    Easing::from_name(name).map(get_easing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn every_curve_starts_at_zero_and_ends_at_one() {
+        for easing in Easing::ALL.iter() {
+            let function = easing.function();
+            let f = |t| function.ease(t);
+            assert!(f(0.0).abs() < 1e-9, "{:?} at 0 is {}", easing, f(0.0));
+            assert!(
+                (f(1.0) - 1.0).abs() < 1e-9,
+                "{:?} at 1 is {}",
+                easing,
+                f(1.0)
+            );
+        }
+    }
+
+    #[test]
+    fn in_out_curves_pass_the_middle() {
+        for easing in Easing::ALL.iter() {
+            if easing.name().starts_with("easeInOut") {
+                assert_near(easing.function().ease(0.5), 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn in_and_out_curves_mirror() {
+        let pairs = Easing::ALL
+            .iter()
+            .filter_map(|easing| {
+                let name = easing.name().strip_prefix("easeIn")?;
+                if name.starts_with("Out") {
+                    return None;
+                }
+                Some((*easing, Easing::from_name(&format!("easeOut{}", name))?))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pairs.len(), 10);
+
+        for (ease_in, ease_out) in pairs {
+            for i in 0..=10 {
+                let t = i as f64 / 10.0;
+                assert_near(
+                    ease_in.function().ease(t),
+                    1.0 - ease_out.function().ease(1.0 - t),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        assert_eq!(
+            Easing::from_name("easeInOutCubic"),
+            Some(Easing::CubicInOut)
+        );
+        assert_eq!(Easing::from_name("EaseOutBounce"), Some(Easing::BounceOut));
+        assert_eq!(Easing::from_name("linear"), Some(Easing::Linear));
+        assert_eq!(Easing::from_name("wobble"), None);
+
+        for easing in Easing::ALL.iter() {
+            assert_eq!(Easing::from_name(easing.name()), Some(*easing));
+        }
+    }
+
+    #[test]
+    fn in_out_functions() {
+        assert_near(get_easing(Easing::QuartInOut).ease(0.25), quart_in_out(0.25));
+        assert_near(get_easing(Easing::BackInOut).ease(0.25), back_in_out(0.25));
+        assert_near(back_in(0.5), -0.0876975);
+    }
+
+    #[test]
+    fn cubic_bezier_matches_named_curves() {
+        let ease = EasingFunction::cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        assert_near(ease.ease(0.0), 0.0);
+        assert_near(ease.ease(1.0), 1.0);
+        // reference values of the CSS `ease` keyword
+        assert!((ease.ease(0.25) - 0.408_510_6).abs() < 1e-5);
+        assert!((ease.ease(0.5) - 0.802_403_4).abs() < 1e-5);
+
+        // a bezier along the diagonal is linear
+        let linear = EasingFunction::cubic_bezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((linear.ease(t) - t).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_extrapolates() {
+        let ease_in = EasingFunction::cubic_bezier(0.42, 0.0, 1.0, 1.0);
+        assert_near(ease_in.ease(-1.0), 0.0);
+        let overshoot = EasingFunction::cubic_bezier(0.5, 1.5, 0.5, 1.5);
+        assert_near(overshoot.ease(-0.1), -0.3);
+        assert!(overshoot.ease(0.5) > 1.0);
+    }
+
+    #[test]
+    fn steps() {
+        let samples = |ease: EasingFunction| {
+            [0.0, 0.2, 0.5, 0.8, 1.0]
+                .iter()
+                .map(|t| ease.ease(*t))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            samples(EasingFunction::steps(2, StepPosition::JumpEnd)),
+            vec![0.0, 0.0, 0.5, 0.5, 1.0]
+        );
+        assert_eq!(
+            samples(EasingFunction::steps(2, StepPosition::JumpStart)),
+            vec![0.5, 0.5, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            samples(EasingFunction::steps(3, StepPosition::JumpNone)),
+            vec![0.0, 0.0, 0.5, 1.0, 1.0]
+        );
+        assert_eq!(
+            samples(EasingFunction::steps(1, StepPosition::JumpBoth)),
+            vec![0.5, 0.5, 0.5, 0.5, 1.0]
+        );
+    }
+
+    #[test]
+    fn linear_stops() {
+        // linear(0, 0.25 75%, 1)
+        let ease = EasingFunction::linear(&[(0.0, None), (0.25, Some(0.75)), (1.0, None)]);
+        assert_near(ease.ease(0.375), 0.125);
+        assert_near(ease.ease(0.875), 0.625);
+        assert_near(ease.ease(1.5), 2.5);
+
+        // missing inputs are spread evenly, linear(0, 1, 0, 1)
+        let ease = EasingFunction::linear(&[(0.0, None), (1.0, None), (0.0, None), (1.0, None)]);
+        assert_near(ease.ease(1.0 / 3.0), 1.0);
+        assert_near(ease.ease(0.5), 0.5);
+
+        // an input smaller than the previous one is raised to it
+        let ease = EasingFunction::linear(&[(0.0, None), (0.5, Some(0.6)), (1.0, Some(0.4))]);
+        assert_near(ease.ease(0.6), 1.0);
+    }
+
+    #[test]
+    fn tunable_curves() {
+        assert_near(
+            EasingFunction::elastic_out(1.0, 0.3).ease(0.3),
+            elastic_out(0.3),
+        );
+        assert_near(EasingFunction::back_in(1.70158).ease(0.5), back_in(0.5));
+
+        let soft = EasingFunction::back_out(0.5);
+        let hard = EasingFunction::back_out(3.0);
+        assert!(soft.ease(0.8) < hard.ease(0.8));
+
+        for ease in [
+            EasingFunction::elastic_in(2.0, 0.5),
+            EasingFunction::elastic_in_out(1.5, 0.2),
+            EasingFunction::back_in_out(2.5),
+        ]
+        .iter()
+        {
+            assert_near(ease.ease(0.0), 0.0);
+            assert_near(ease.ease(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let from_fn: EasingFunction = quad_in.into();
+        let from_closure: EasingFunction = (|t: f64| t * t).into();
+        let from_easing: EasingFunction = Easing::QuadIn.into();
+
+        assert_near(from_fn.ease(0.5), 0.25);
+        assert_near(from_closure.ease(0.5), 0.25);
+        assert_near(from_easing.ease(0.5), 0.25);
+        assert_near(EasingFunction::default().ease(0.3), 0.3);
     }
 }
@@ -1,7 +1,630 @@
+//! Interpolation between two values.
+//!
+//! [`Interpolate`] is the interpolation layer shared by tweens, keyframe
+//! transitions and Lottie keyframe animations.
+use crate::{
+    path::{Path, PathSegment},
+    Point, Rect, RgbaColor, Size, Transform,
+};
+
+/// A value which can be interpolated towards another value of the same type.
+pub trait Interpolate: Sized {
+    /// Returns the value at `f` between `self` at `0.0` and `other` at `1.0`.
+    ///
+    /// `f` may leave `0..=1`, for example with an easing which overshoots, so
+    /// implementations extrapolate or clamp as suits the type.
+    fn interpolate(&self, other: &Self, f: f64) -> Self;
+}
+
 /// Returns a linear interpolated value based on the start value [start], the
 /// end value [end], and the interpolation factor [f].
 ///
-/// [start] and [end] can be of any type which defines three operators +, - , *.
-pub fn lerp(start: f64, end: f64, f: f64) -> f64 {
-    start + (end - start) * f
+/// [start] and [end] can be of any type which implements [`Interpolate`].
+pub fn lerp<T: Interpolate>(start: T, end: T, f: f64) -> T {
+    start.interpolate(&end, f)
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        self + (other - self) * f
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        (*self as f64).interpolate(&(*other as f64), f) as f32
+    }
+}
+
+impl Interpolate for i32 {
+    /// Rounds to the nearest integer.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        (*self as f64).interpolate(&(*other as f64), f).round() as i32
+    }
+}
+
+impl Interpolate for Point<f64> {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Point::new(
+            self.x.interpolate(&other.x, f),
+            self.y.interpolate(&other.y, f),
+        )
+    }
+}
+
+impl Interpolate for Size<f64> {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Size::new(
+            self.width.interpolate(&other.width, f),
+            self.height.interpolate(&other.height, f),
+        )
+    }
+}
+
+impl Interpolate for Rect<f64> {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Rect {
+            origin: self.origin.interpolate(&other.origin, f),
+            size: self.size.interpolate(&other.size, f),
+        }
+    }
+}
+
+/// The color space colors are interpolated in.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ColorSpace {
+    /// Interpolates the gamma encoded channels, as CSS and most tools do.
+    #[default]
+    Srgb,
+    /// Interpolates the channels in linear light, which avoids the dark
+    /// middle of sRGB blends.
+    LinearRgb,
+    /// Interpolates hue, saturation and lightness, the hue the short way
+    /// around the color wheel.
+    Hsl,
+}
+
+/// Returns the color at `f` between `start` and `end` in the color `space`.
+///
+/// Alpha is always interpolated linearly. The result is clamped to valid
+/// channel values.
+pub fn lerp_color(start: &RgbaColor, end: &RgbaColor, f: f64, space: ColorSpace) -> RgbaColor {
+    let from = color::to_unit(start);
+    let to = color::to_unit(end);
+    let alpha = from[3].interpolate(&to[3], f);
+
+    let [r, g, b] = match space {
+        ColorSpace::Srgb => color::lerp3(&from, &to, f),
+        ColorSpace::LinearRgb => {
+            let [r, g, b] = color::lerp3(
+                &color::map_rgb(&from, color::srgb_to_linear),
+                &color::map_rgb(&to, color::srgb_to_linear),
+                f,
+            );
+            [
+                color::linear_to_srgb(r),
+                color::linear_to_srgb(g),
+                color::linear_to_srgb(b),
+            ]
+        }
+        ColorSpace::Hsl => color::hsl_to_rgb(color::lerp_hsl(
+            color::rgb_to_hsl(&from),
+            color::rgb_to_hsl(&to),
+            f,
+        )),
+    };
+
+    color::from_unit([r, g, b, alpha])
+}
+
+impl Interpolate for RgbaColor {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        lerp_color(self, other, f, ColorSpace::Srgb)
+    }
+}
+
+/// A color interpolated in linear RGB, see [`ColorSpace::LinearRgb`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearRgba(pub RgbaColor);
+
+impl Interpolate for LinearRgba {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        LinearRgba(lerp_color(&self.0, &other.0, f, ColorSpace::LinearRgb))
+    }
+}
+
+/// A color interpolated in HSL, see [`ColorSpace::Hsl`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsla(pub RgbaColor);
+
+impl Interpolate for Hsla {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Hsla(lerp_color(&self.0, &other.0, f, ColorSpace::Hsl))
+    }
+}
+
+mod color {
+    use crate::RgbaColor;
+
+    pub fn map_rgb(v: &[f64; 4], f: fn(f64) -> f64) -> [f64; 4] {
+        [f(v[0]), f(v[1]), f(v[2]), v[3]]
+    }
+
+    pub fn to_unit(color: &RgbaColor) -> [f64; 4] {
+        [
+            color.red as f64 / 255.0,
+            color.green as f64 / 255.0,
+            color.blue as f64 / 255.0,
+            color.alpha as f64 / 255.0,
+        ]
+    }
+
+    pub fn from_unit(v: [f64; 4]) -> RgbaColor {
+        let channel = |value: f64| (value * 255.0).round().clamp(0.0, 255.0) as u8;
+        RgbaColor {
+            red: channel(v[0]),
+            green: channel(v[1]),
+            blue: channel(v[2]),
+            alpha: channel(v[3]),
+        }
+    }
+
+    pub fn lerp3(from: &[f64; 4], to: &[f64; 4], f: f64) -> [f64; 3] {
+        [
+            from[0] + (to[0] - from[0]) * f,
+            from[1] + (to[1] - from[1]) * f,
+            from[2] + (to[2] - from[2]) * f,
+        ]
+    }
+
+    pub fn srgb_to_linear(c: f64) -> f64 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    pub fn linear_to_srgb(c: f64) -> f64 {
+        let c = c.clamp(0.0, 1.0);
+        if c <= 0.003_130_8 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    }
+
+    /// Returns the hue in degrees, the saturation and the lightness, the hue
+    /// is `None` for grays.
+    pub fn rgb_to_hsl(v: &[f64; 4]) -> (Option<f64>, f64, f64) {
+        let (r, g, b) = (v[0], v[1], v[2]);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return (None, 0.0, l);
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+
+        (Some(h * 60.0), s, l)
+    }
+
+    pub fn hsl_to_rgb((h, s, l): (f64, f64, f64)) -> [f64; 3] {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let m = l - c / 2.0;
+
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        [r + m, g + m, b + m]
+    }
+
+    pub fn lerp_hsl(
+        from: (Option<f64>, f64, f64),
+        to: (Option<f64>, f64, f64),
+        f: f64,
+    ) -> (f64, f64, f64) {
+        // a gray takes the hue of the other color, so no hue sweep shows up
+        let (h0, h1) = match (from.0, to.0) {
+            (Some(h0), Some(h1)) => (h0, h1),
+            (Some(h), None) | (None, Some(h)) => (h, h),
+            (None, None) => (0.0, 0.0),
+        };
+
+        let mut delta = h1 - h0;
+        if delta > 180.0 {
+            delta -= 360.0;
+        } else if delta < -180.0 {
+            delta += 360.0;
+        }
+
+        (
+            h0 + delta * f,
+            from.1 + (to.1 - from.1) * f,
+            from.2 + (to.2 - from.2) * f,
+        )
+    }
+}
+
+/// An affine transform decomposed into translation, rotation, skew and scale.
+#[derive(Clone, Copy, Debug)]
+struct Decomposed {
+    translate_x: f64,
+    translate_y: f64,
+    angle: f64,
+    skew: f64,
+    scale_x: f64,
+    scale_y: f64,
+}
+
+impl Decomposed {
+    /// Decomposes the linear part as `rotate(angle) * skew_x(skew) * scale`.
+    ///
+    /// A reflection ends up as a negative `scale_y`.
+    fn new(ts: &Transform<f64>) -> Self {
+        let scale_x = ts.m11.hypot(ts.m12);
+        let angle = if scale_x == 0.0 {
+            0.0
+        } else {
+            ts.m12.atan2(ts.m11)
+        };
+        let (sin, cos) = angle.sin_cos();
+
+        // the second column rotated back by the angle
+        let c = cos * ts.m21 + sin * ts.m22;
+        let d = cos * ts.m22 - sin * ts.m21;
+        let skew = if d == 0.0 { 0.0 } else { c / d };
+
+        Decomposed {
+            translate_x: ts.m31,
+            translate_y: ts.m32,
+            angle,
+            skew,
+            scale_x,
+            scale_y: d,
+        }
+    }
+
+    fn compose(&self) -> Transform<f64> {
+        let (sin, cos) = self.angle.sin_cos();
+        let c = self.skew * self.scale_y;
+        Transform::new(
+            self.scale_x * cos,
+            self.scale_x * sin,
+            c * cos - self.scale_y * sin,
+            c * sin + self.scale_y * cos,
+            self.translate_x,
+            self.translate_y,
+        )
+    }
+}
+
+impl Interpolate for Transform<f64> {
+    /// Interpolates the decomposed transforms, so a rotation keeps the shape
+    /// instead of shrinking through the middle. The rotation goes the short
+    /// way around.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        let from = Decomposed::new(self);
+        let to = Decomposed::new(other);
+
+        let mut delta = to.angle - from.angle;
+        if delta > std::f64::consts::PI {
+            delta -= 2.0 * std::f64::consts::PI;
+        } else if delta < -std::f64::consts::PI {
+            delta += 2.0 * std::f64::consts::PI;
+        }
+
+        Decomposed {
+            translate_x: from.translate_x.interpolate(&to.translate_x, f),
+            translate_y: from.translate_y.interpolate(&to.translate_y, f),
+            angle: from.angle + delta * f,
+            skew: from.skew.interpolate(&to.skew, f),
+            scale_x: from.scale_x.interpolate(&to.scale_x, f),
+            scale_y: from.scale_y.interpolate(&to.scale_y, f),
+        }
+        .compose()
+    }
+}
+
+/// Returns `true` if two paths can be interpolated segment by segment.
+///
+/// Paths are compatible when they have the same segments in the same order,
+/// with the same absolute or relative flags and the same arc flags.
+pub fn paths_compatible(start: &Path, end: &Path) -> bool {
+    start.len() == end.len()
+        && start
+            .iter()
+            .zip(end.iter())
+            .all(|(a, b)| lerp_segment(a, b, 0.0).is_some())
+}
+
+impl Interpolate for Path {
+    /// Interpolates compatible paths segment by segment, see
+    /// [`paths_compatible`]. Incompatible paths switch from one to the
+    /// other at the middle.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        if !paths_compatible(self, other) {
+            return if f < 0.5 { self.clone() } else { other.clone() };
+        }
+
+        self.iter()
+            .zip(other.iter())
+            .filter_map(|(a, b)| lerp_segment(a, b, f))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+fn lerp_segment(a: &PathSegment, b: &PathSegment, f: f64) -> Option<PathSegment> {
+    if a.is_absolute() != b.is_absolute() {
+        return None;
+    }
+
+    let abs = a.is_absolute();
+    let l = |a: f64, b: f64| a.interpolate(&b, f);
+
+    let segment = match (*a, *b) {
+        (PathSegment::MoveTo { x, y, .. }, PathSegment::MoveTo { x: x_, y: y_, .. }) => {
+            PathSegment::MoveTo {
+                abs,
+                x: l(x, x_),
+                y: l(y, y_),
+            }
+        }
+        (PathSegment::LineTo { x, y, .. }, PathSegment::LineTo { x: x_, y: y_, .. }) => {
+            PathSegment::LineTo {
+                abs,
+                x: l(x, x_),
+                y: l(y, y_),
+            }
+        }
+        (PathSegment::HorizontalLineTo { x, .. }, PathSegment::HorizontalLineTo { x: x_, .. }) => {
+            PathSegment::HorizontalLineTo { abs, x: l(x, x_) }
+        }
+        (PathSegment::VerticalLineTo { y, .. }, PathSegment::VerticalLineTo { y: y_, .. }) => {
+            PathSegment::VerticalLineTo { abs, y: l(y, y_) }
+        }
+        (
+            PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+                ..
+            },
+            PathSegment::CurveTo {
+                x1: x1_,
+                y1: y1_,
+                x2: x2_,
+                y2: y2_,
+                x: x_,
+                y: y_,
+                ..
+            },
+        ) => PathSegment::CurveTo {
+            abs,
+            x1: l(x1, x1_),
+            y1: l(y1, y1_),
+            x2: l(x2, x2_),
+            y2: l(y2, y2_),
+            x: l(x, x_),
+            y: l(y, y_),
+        },
+        (
+            PathSegment::SmoothCurveTo { x2, y2, x, y, .. },
+            PathSegment::SmoothCurveTo {
+                x2: x2_,
+                y2: y2_,
+                x: x_,
+                y: y_,
+                ..
+            },
+        ) => PathSegment::SmoothCurveTo {
+            abs,
+            x2: l(x2, x2_),
+            y2: l(y2, y2_),
+            x: l(x, x_),
+            y: l(y, y_),
+        },
+        (
+            PathSegment::Quadratic { x1, y1, x, y, .. },
+            PathSegment::Quadratic {
+                x1: x1_,
+                y1: y1_,
+                x: x_,
+                y: y_,
+                ..
+            },
+        ) => PathSegment::Quadratic {
+            abs,
+            x1: l(x1, x1_),
+            y1: l(y1, y1_),
+            x: l(x, x_),
+            y: l(y, y_),
+        },
+        (
+            PathSegment::SmoothQuadratic { x, y, .. },
+            PathSegment::SmoothQuadratic { x: x_, y: y_, .. },
+        ) => PathSegment::SmoothQuadratic {
+            abs,
+            x: l(x, x_),
+            y: l(y, y_),
+        },
+        (
+            PathSegment::EllipticalArc {
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+                ..
+            },
+            PathSegment::EllipticalArc {
+                rx: rx_,
+                ry: ry_,
+                x_axis_rotation: x_axis_rotation_,
+                large_arc: large_arc_,
+                sweep: sweep_,
+                x: x_,
+                y: y_,
+                ..
+            },
+        ) if large_arc == large_arc_ && sweep == sweep_ => PathSegment::EllipticalArc {
+            abs,
+            rx: l(rx, rx_),
+            ry: l(ry, ry_),
+            x_axis_rotation: l(x_axis_rotation, x_axis_rotation_),
+            large_arc,
+            sweep,
+            x: l(x, x_),
+            y: l(y, y_),
+        },
+        (PathSegment::ClosePath { .. }, PathSegment::ClosePath { .. }) => {
+            PathSegment::ClosePath { abs }
+        }
+        _ => return None,
+    };
+
+    Some(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::{rotation, FuzzyEq};
+
+    fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> RgbaColor {
+        RgbaColor {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    #[test]
+    fn lerp_numbers() {
+        assert!(lerp(0.0, 10.0, 0.25).fuzzy_eq(&2.5));
+        assert!(lerp(0.0_f32, 10.0, 0.5).fuzzy_eq(&5.0));
+        assert!(lerp(0.0, 10.0, 1.5).fuzzy_eq(&15.0));
+        assert_eq!(lerp(0, 100, 0.255), 26);
+    }
+
+    #[test]
+    fn lerp_geometry() {
+        let point = lerp(Point::new(0.0, 0.0), Point::new(10.0, 20.0), 0.5);
+        assert_eq!((point.x, point.y), (5.0, 10.0));
+
+        let rect = lerp(
+            Rect {
+                origin: Point::new(0.0, 0.0),
+                size: Size::new(10.0, 10.0),
+            },
+            Rect {
+                origin: Point::new(10.0, 0.0),
+                size: Size::new(20.0, 30.0),
+            },
+            0.5,
+        );
+        assert_eq!((rect.origin.x, rect.origin.y), (5.0, 0.0));
+        assert_eq!((rect.size.width, rect.size.height), (15.0, 20.0));
+    }
+
+    #[test]
+    fn lerp_color_spaces() {
+        let black = rgba(0, 0, 0, 255);
+        let white = rgba(255, 255, 255, 255);
+
+        assert_eq!(black.interpolate(&white, 0.5), rgba(128, 128, 128, 255));
+        assert_eq!(
+            lerp_color(&black, &white, 0.5, ColorSpace::LinearRgb),
+            rgba(188, 188, 188, 255)
+        );
+
+        // red to blue goes through magenta in HSL, not through dark purple
+        let red = rgba(255, 0, 0, 255);
+        let blue = rgba(0, 0, 255, 0);
+        assert_eq!(
+            lerp_color(&red, &blue, 0.5, ColorSpace::Hsl),
+            rgba(255, 0, 255, 128)
+        );
+        assert_eq!(lerp(Hsla(red), Hsla(red), 0.5).0, red);
+    }
+
+    #[test]
+    fn lerp_color_clamps() {
+        let color = lerp(rgba(0, 0, 0, 0), rgba(200, 200, 200, 200), 1.5);
+        assert_eq!(color, rgba(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn lerp_transform_rotation() {
+        let from = Transform::identity();
+        let to = rotation(90.0);
+        let middle = from.interpolate(&to, 0.5);
+
+        // a rotation by 45 degrees, not a squashed matrix
+        assert!(middle.approx_eq(&rotation(45.0)));
+
+        // the short way around
+        let middle = rotation(170.0).interpolate(&rotation(-170.0), 0.5);
+        let point = middle.transform_point(Point::new(1.0, 0.0));
+        assert!((point.x + 1.0).abs() < 1e-9 && point.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn lerp_transform_roundtrip() {
+        let skew_x = Transform::new(1.0, 0.0, 10_f64.to_radians().tan(), 1.0, 0.0, 0.0);
+        let ts = rotation(30.0).then(&Transform::translation(5.0, 7.0));
+        let ts = skew_x.then(&ts).pre_scale(2.0, -3.0);
+
+        let same = ts.interpolate(&ts, 0.3);
+        for (a, b) in same.to_array().iter().zip(ts.to_array().iter()) {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn lerp_path() {
+        let from: Path = "M 0 0 L 10 0 C 10 10 20 10 20 0 Z".parse().unwrap();
+        let to: Path = "M 0 10 L 20 10 C 20 20 40 20 40 10 Z".parse().unwrap();
+        let middle: Path = "M 0 5 L 15 5 C 15 15 30 15 30 5 Z".parse().unwrap();
+
+        assert!(paths_compatible(&from, &to));
+        assert_eq!(from.interpolate(&to, 0.5), middle);
+    }
+
+    #[test]
+    fn lerp_incompatible_path() {
+        let from: Path = "M 0 0 L 10 0".parse().unwrap();
+        let to: Path = "M 0 0 H 10".parse().unwrap();
+
+        assert!(!paths_compatible(&from, &to));
+        assert_eq!(from.interpolate(&to, 0.4), from);
+        assert_eq!(from.interpolate(&to, 0.6), to);
+    }
 }
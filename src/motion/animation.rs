@@ -0,0 +1,303 @@
+use super::{MotionPath, OffsetRotate, Placement};
+use crate::{
+    easing::EasingFunction,
+    path::Length,
+    tween::{Group, Playable, Tween},
+};
+use std::{cell::RefCell, fmt, rc::Rc};
+
+type Callback = Box<dyn FnMut(&Placement)>;
+
+struct MotionState {
+    path: MotionPath,
+    object: Rc<RefCell<Placement>>,
+    offset_distance: Length,
+    rotate: OffsetRotate,
+    on_update: Option<Callback>,
+}
+
+impl MotionState {
+    /// Writes the placement at `progress` into the target.
+    fn place(&mut self, progress: f64) -> Option<Placement> {
+        let placement = self
+            .path
+            .place_with_offset(self.offset_distance, progress, self.rotate)?;
+        *self.object.borrow_mut() = placement;
+        Some(placement)
+    }
+}
+
+/// Moves an object along a [`MotionPath`], driving its x, y and rotation.
+///
+/// The progress along the path is played by a `Tween` from 0 to 1, so the
+/// animation has the timeline of a tween with its duration, delay, repeats,
+/// yoyo and easing, it joins a [`Group`] and it is chained like a tween. The
+/// start of the motion is moved by the offset distance and the object is
+/// rotated as set by [`OffsetRotate`], like the CSS `offset-path` properties.
+///
+/// `MotionAnimation` is a cheap handle, clones refer to the same animation.
+///
+/// # Example
+///
+/// ```
+/// use animate::{motion::{MotionAnimation, MotionPath}, path::Path};
+///
+/// let path: Path = "M 0 0 L 100 0".parse().unwrap();
+/// let animation = MotionAnimation::new(MotionPath::new(&path));
+/// animation.duration(1000.0).start(0.0);
+///
+/// animation.update(500.0);
+/// assert_eq!(animation.get_value().x, 50.0);
+/// ```
+pub struct MotionAnimation {
+    inner: Rc<RefCell<MotionState>>,
+    timeline: Tween<f64>,
+}
+
+impl Clone for MotionAnimation {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeline: self.timeline.clone(),
+        }
+    }
+}
+
+impl MotionAnimation {
+    /// Constructs a new animation which owns its placement.
+    pub fn new(path: MotionPath) -> Self {
+        Self::with_target(path, Rc::new(RefCell::new(Placement::default())))
+    }
+
+    /// Constructs a new animation which drives a shared `target`.
+    pub fn with_target(path: MotionPath, target: Rc<RefCell<Placement>>) -> Self {
+        let inner = Rc::new(RefCell::new(MotionState {
+            path,
+            object: target,
+            offset_distance: Length::zero(),
+            rotate: OffsetRotate::default(),
+            on_update: None,
+        }));
+
+        let timeline = Tween::new(0.0);
+        timeline.from(0.0).to(1.0, 1000.0);
+        {
+            let inner = inner.clone();
+            timeline.on_update(move |progress, _| {
+                let placement = inner.borrow_mut().place(*progress);
+                let callback = inner.borrow_mut().on_update.take();
+                if let (Some(placement), Some(mut callback)) = (placement, callback) {
+                    callback(&placement);
+                    let mut state = inner.borrow_mut();
+                    if state.on_update.is_none() {
+                        state.on_update = Some(callback);
+                    }
+                }
+            });
+        }
+
+        Self { inner, timeline }
+    }
+
+    /// Returns the unique id of the animation.
+    pub fn get_id(&self) -> usize {
+        self.timeline.get_id()
+    }
+
+    /// Returns the shared placement driven by the animation.
+    pub fn get_target(&self) -> Rc<RefCell<Placement>> {
+        self.inner.borrow().object.clone()
+    }
+
+    /// Returns the current placement.
+    pub fn get_value(&self) -> Placement {
+        *self.inner.borrow().object.borrow()
+    }
+
+    /// Returns the current progress along the path in `0..=1`, eased.
+    pub fn get_progress(&self) -> f64 {
+        self.timeline.get_value()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.timeline.is_playing()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.timeline.is_paused()
+    }
+
+    /// Moves the start of the motion along the path, percentages are relative
+    /// to the length of the path.
+    pub fn offset_distance(&self, distance: Length) -> &Self {
+        self.inner.borrow_mut().offset_distance = distance;
+        self
+    }
+
+    /// Sets how the object is rotated along the path.
+    pub fn rotate(&self, rotate: OffsetRotate) -> &Self {
+        self.inner.borrow_mut().rotate = rotate;
+        self
+    }
+
+    /// Sets the duration in milliseconds to travel the whole path.
+    pub fn duration(&self, duration: f64) -> &Self {
+        self.timeline.duration(duration);
+        self
+    }
+
+    /// Sets the delay in milliseconds before the motion begins.
+    pub fn delay(&self, amount: f64) -> &Self {
+        self.timeline.delay(amount);
+        self
+    }
+
+    /// Sets how many times the motion repeats after the first run.
+    pub fn repeat(&self, times: f64) -> &Self {
+        self.timeline.repeat(times);
+        self
+    }
+
+    /// Sets the delay in milliseconds between repeats.
+    pub fn repeat_delay(&self, amount: f64) -> &Self {
+        self.timeline.repeat_delay(amount);
+        self
+    }
+
+    /// Goes back along the path on every other repeat.
+    pub fn yoyo(&self, yoyo: bool) -> &Self {
+        self.timeline.yoyo(yoyo);
+        self
+    }
+
+    /// Sets the easing of the progress along the path.
+    pub fn easing<E: Into<EasingFunction>>(&self, easing: E) -> &Self {
+        self.timeline.easing(easing);
+        self
+    }
+
+    /// Sets the group the animation joins when it is started.
+    pub fn group(&self, group: &Group) -> &Self {
+        self.timeline.group(group);
+        self
+    }
+
+    /// Appends `tween` to the playables started when the motion completes.
+    pub fn chain<P>(&self, tween: &P) -> &Self
+    where
+        P: Playable + Clone + 'static,
+    {
+        self.timeline.chain(tween);
+        self
+    }
+
+    /// Sets the callback fired on every update with the new placement.
+    pub fn on_update<F: FnMut(&Placement) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired when the motion completes.
+    pub fn on_complete<F: FnMut(&Placement) + 'static>(&self, mut callback: F) -> &Self {
+        let inner = self.inner.clone();
+        self.timeline.on_complete(move |_| {
+            let placement = *inner.borrow().object.borrow();
+            callback(&placement);
+        });
+        self
+    }
+
+    /// Starts the animation at `time` in milliseconds and places the object
+    /// at the start of the motion.
+    pub fn start(&self, time: f64) -> &Self {
+        if self.timeline.is_playing() {
+            return self;
+        }
+
+        self.timeline.start(time);
+        self.inner.borrow_mut().place(0.0);
+        self
+    }
+
+    /// Stops the animation, the object stays where it is.
+    pub fn stop(&self) -> &Self {
+        self.timeline.stop();
+        self
+    }
+
+    /// Jumps to the end of the motion and completes.
+    pub fn end(&self) -> &Self {
+        self.timeline.end();
+        self
+    }
+
+    /// Pauses the animation at `time` in milliseconds.
+    pub fn pause(&self, time: f64) -> &Self {
+        self.timeline.pause(time);
+        self
+    }
+
+    /// Resumes the animation at `time` in milliseconds.
+    pub fn resume(&self, time: f64) -> &Self {
+        self.timeline.resume(time);
+        self
+    }
+
+    /// Advances the animation to `time` in milliseconds.
+    ///
+    /// Returns `true` while the motion is playing (or paused).
+    pub fn update(&self, time: f64) -> bool {
+        self.timeline.update(time)
+    }
+}
+
+impl Playable for MotionAnimation {
+    fn get_id(&self) -> usize {
+        MotionAnimation::get_id(self)
+    }
+
+    fn is_playing(&self) -> bool {
+        MotionAnimation::is_playing(self)
+    }
+
+    fn is_paused(&self) -> bool {
+        MotionAnimation::is_paused(self)
+    }
+
+    fn start(&self, time: f64) {
+        MotionAnimation::start(self, time);
+    }
+
+    fn stop(&self) {
+        MotionAnimation::stop(self);
+    }
+
+    fn end(&self) {
+        MotionAnimation::end(self);
+    }
+
+    fn pause(&self, time: f64) {
+        MotionAnimation::pause(self, time);
+    }
+
+    fn resume(&self, time: f64) {
+        MotionAnimation::resume(self, time);
+    }
+
+    fn update(&self, time: f64) -> bool {
+        MotionAnimation::update(self, time)
+    }
+}
+
+impl fmt::Debug for MotionAnimation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.borrow();
+        let placement = *state.object.borrow();
+        f.debug_struct("MotionAnimation")
+            .field("id", &self.timeline.get_id())
+            .field("offset_distance", &state.offset_distance)
+            .field("rotate", &state.rotate)
+            .field("placement", &placement)
+            .finish()
+    }
+}
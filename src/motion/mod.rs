@@ -1 +1,20 @@
+//! Motion along a path.
+//!
+//! A [`MotionPath`] measures a `path::Path` by arc length and samples the
+//! position and direction at any distance or progress along it. Objects are
+//! placed on the path with an offset distance and an [`OffsetRotate`], the
+//! way the CSS `offset-path`, `offset-distance` and `offset-rotate`
+//! properties do, and a [`MotionAnimation`] plays the motion on a tween
+//! timeline, driving the x, y and rotation of a [`Placement`].
 
+mod animation;
+pub use animation::*;
+
+mod motion_path;
+pub use motion_path::*;
+
+mod offset;
+pub use offset::*;
+
+#[cfg(test)]
+mod tests;
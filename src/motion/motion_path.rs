@@ -0,0 +1,103 @@
+use crate::path::{Length, LengthUnit, Path, PathMeasure};
+
+pub use crate::path::PathSample;
+
+/// A [`Path`] measured by arc length, so it can be followed at a constant
+/// speed.
+///
+/// The path is measured once with a [`PathMeasure`]. Unlike the measure,
+/// distances wrap around a closed path, so an object can go round it again
+/// and again.
+///
+/// # Example
+///
+/// ```
+/// use animate::{motion::MotionPath, path::Path};
+///
+/// let path: Path = "M 0 0 L 100 0 L 100 100".parse().unwrap();
+/// let motion = MotionPath::new(&path);
+/// assert_eq!(motion.length(), 200.0);
+///
+/// let sample = motion.sample_at_length(150.0).unwrap();
+/// assert_eq!((sample.position.x, sample.position.y), (100.0, 50.0));
+/// assert_eq!(sample.angle, 90.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotionPath {
+    measure: PathMeasure,
+}
+
+impl MotionPath {
+    /// Measures `path` with the default tolerance of 0.1 units.
+    pub fn new(path: &Path) -> Self {
+        MotionPath {
+            measure: PathMeasure::new(path),
+        }
+    }
+
+    /// Measures `path`, curves are cut in pieces until they are less than
+    /// `tolerance` away from straight lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn with_tolerance(path: &Path, tolerance: f64) -> Self {
+        MotionPath {
+            measure: PathMeasure::with_tolerance(path, tolerance),
+        }
+    }
+
+    /// Returns the measure of the path.
+    pub fn measure(&self) -> &PathMeasure {
+        &self.measure
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> f64 {
+        self.measure.length()
+    }
+
+    /// Returns `true` if the path is a single closed subpath.
+    ///
+    /// Distances along a closed path wrap around, while they are clamped to
+    /// the ends of an open one.
+    pub fn is_closed(&self) -> bool {
+        self.measure.is_closed()
+    }
+
+    /// Returns `true` if the path has no point to sample.
+    pub fn is_empty(&self) -> bool {
+        self.measure.is_empty()
+    }
+
+    /// Resolves an offset distance, percentages are relative to the length
+    /// of the path.
+    ///
+    /// Other units are taken as path units, like unitless numbers.
+    pub fn resolve_distance(&self, distance: Length) -> f64 {
+        match distance.unit {
+            LengthUnit::Percent => self.length() * distance.num / 100.0,
+            _ => distance.num,
+        }
+    }
+
+    /// Returns the position and direction at `distance` along the path.
+    ///
+    /// Returns `None` if the path is empty. A path without length, like a
+    /// single MoveTo, always samples its start point heading right.
+    pub fn sample_at_length(&self, distance: f64) -> Option<PathSample> {
+        let length = self.length();
+        let distance = if self.is_closed() && length > 0.0 {
+            distance.rem_euclid(length)
+        } else {
+            distance
+        };
+        self.measure.sample_at_length(distance)
+    }
+
+    /// Returns the position and direction at `progress` in `0..=1` of the
+    /// length of the path.
+    pub fn sample(&self, progress: f64) -> Option<PathSample> {
+        self.sample_at_length(progress * self.length())
+    }
+}
@@ -0,0 +1,97 @@
+use super::{MotionPath, PathSample};
+use crate::{
+    interpolate::Interpolate,
+    path::{rotation, Length},
+    Transform,
+};
+
+/// How an object moving along a path is rotated, like the CSS
+/// [`offset-rotate`] property.
+///
+/// [`offset-rotate`]: https://drafts.fxtf.org/motion-1/#offset-rotate-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OffsetRotate {
+    /// Follows the direction of the path, plus an angle in degrees. This is
+    /// `auto` and `auto <angle>` in CSS.
+    Auto(f64),
+    /// Follows the opposite direction of the path, `reverse` in CSS.
+    Reverse,
+    /// Keeps a fixed angle in degrees whatever the direction of the path.
+    Fixed(f64),
+}
+
+impl OffsetRotate {
+    /// Returns the rotation in degrees for the direction `angle` of the path.
+    pub fn resolve(&self, angle: f64) -> f64 {
+        match *self {
+            OffsetRotate::Auto(extra) => angle + extra,
+            OffsetRotate::Reverse => angle + 180.0,
+            OffsetRotate::Fixed(fixed) => fixed,
+        }
+    }
+}
+
+impl Default for OffsetRotate {
+    fn default() -> Self {
+        OffsetRotate::Auto(0.0)
+    }
+}
+
+/// Where an object following a path is placed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Placement {
+    pub x: f64,
+    pub y: f64,
+    /// The rotation in degrees.
+    pub rotation: f64,
+}
+
+impl Placement {
+    /// Constructs a new placement.
+    pub fn new(x: f64, y: f64, rotation: f64) -> Self {
+        Placement { x, y, rotation }
+    }
+
+    /// Returns the transform moving an object from the origin to the
+    /// placement, rotating it around its origin.
+    pub fn to_transform(&self) -> Transform<f64> {
+        rotation(self.rotation).then(&Transform::translation(self.x, self.y))
+    }
+}
+
+impl Interpolate for Placement {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Placement {
+            x: self.x.interpolate(&other.x, f),
+            y: self.y.interpolate(&other.y, f),
+            rotation: self.rotation.interpolate(&other.rotation, f),
+        }
+    }
+}
+
+impl MotionPath {
+    /// Places an object at `distance` along the path and rotates it as told
+    /// by `rotate`.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn place(&self, distance: f64, rotate: OffsetRotate) -> Option<Placement> {
+        self.sample_at_length(distance)
+            .map(|PathSample { position, angle }| {
+                Placement::new(position.x, position.y, rotate.resolve(angle))
+            })
+    }
+
+    /// Places an object at the CSS-like `offset_distance` plus `progress` in
+    /// `0..=1` of the length of the path.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn place_with_offset(
+        &self,
+        offset_distance: Length,
+        progress: f64,
+        rotate: OffsetRotate,
+    ) -> Option<Placement> {
+        let distance = self.resolve_distance(offset_distance) + progress * self.length();
+        self.place(distance, rotate)
+    }
+}
@@ -0,0 +1,254 @@
+#![allow(clippy::float_cmp)]
+
+use super::*;
+use crate::{
+    path::{Length, LengthUnit, Path},
+    tween::{Group, Tween},
+    Point,
+};
+use std::{cell::RefCell, f64::consts::PI, rc::Rc};
+
+fn motion(text: &str) -> MotionPath {
+    MotionPath::new(&text.parse::<Path>().unwrap())
+}
+
+fn assert_near(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{} is not {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn polyline_length_and_samples() {
+    let motion = motion("M 10 10 h 30 v 40");
+    assert_eq!(motion.length(), 70.0);
+    assert!(!motion.is_closed());
+
+    let sample = motion.sample_at_length(15.0).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (25.0, 10.0));
+    assert_eq!(sample.angle, 0.0);
+
+    let sample = motion.sample(1.0).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (40.0, 50.0));
+    assert_eq!(sample.angle, 90.0);
+}
+
+#[test]
+fn open_path_clamps() {
+    let motion = motion("M 0 0 L 10 0");
+    assert_eq!(motion.sample_at_length(-5.0).unwrap().position.x, 0.0);
+    assert_eq!(motion.sample_at_length(50.0).unwrap().position.x, 10.0);
+}
+
+#[test]
+fn closed_path_wraps() {
+    let motion = motion("M 0 0 H 10 V 10 H 0 Z");
+    assert!(motion.is_closed());
+    assert_eq!(motion.length(), 40.0);
+
+    let sample = motion.sample_at_length(45.0).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (5.0, 0.0));
+    let sample = motion.sample_at_length(-5.0).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (0.0, 5.0));
+    assert_eq!(sample.angle, -90.0);
+}
+
+#[test]
+fn circle_of_arcs() {
+    let motion = motion("M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z");
+    assert_near(motion.length(), 20.0 * PI);
+
+    let sample = motion.sample(0.25).unwrap();
+    assert_near(sample.position.x, 0.0);
+    assert_near(sample.position.y, 10.0);
+    // heading left, either at 180 or -180 degrees
+    assert_near(sample.angle.abs(), 180.0);
+}
+
+#[test]
+fn curves_have_smooth_angles() {
+    let motion = motion("M 0 0 C 0 50 100 50 100 0");
+    let start = motion.sample(0.0).unwrap();
+    let middle = motion.sample(0.5).unwrap();
+    let end = motion.sample(1.0).unwrap();
+
+    assert_near(start.angle, 90.0);
+    assert_near(middle.position.x, 50.0);
+    assert_near(middle.position.y, 37.5);
+    assert_near(middle.angle, 0.0);
+    assert_near(end.angle, -90.0);
+}
+
+#[test]
+fn smooth_segments_reflect_controls() {
+    let smooth = motion("M 0 0 Q 10 10 20 0 T 40 0");
+    let explicit = motion("M 0 0 Q 10 10 20 0 Q 30 -10 40 0");
+    assert_near(smooth.length(), explicit.length());
+
+    let smooth = motion("M 0 0 C 0 10 10 10 10 0 S 20 -10 20 0");
+    let explicit = motion("M 0 0 C 0 10 10 10 10 0 C 10 -10 20 -10 20 0");
+    assert_near(smooth.length(), explicit.length());
+    assert_eq!(smooth.sample(0.75), explicit.sample(0.75));
+}
+
+#[test]
+fn move_to_does_not_add_length() {
+    let motion = motion("M 0 0 H 10 M 100 100 H 110");
+    assert_eq!(motion.length(), 20.0);
+    assert!(!motion.is_closed());
+
+    let sample = motion.sample_at_length(15.0).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (105.0, 100.0));
+}
+
+#[test]
+fn empty_and_single_point() {
+    assert!(motion("").is_empty());
+    assert_eq!(motion("").sample(0.5), None);
+
+    let motion = motion("M 5 6");
+    assert_eq!(motion.length(), 0.0);
+    let sample = motion.sample(0.5).unwrap();
+    assert_eq!((sample.position.x, sample.position.y), (5.0, 6.0));
+}
+
+#[test]
+fn offset_rotate() {
+    let motion = motion("M 0 0 V 10");
+    assert_eq!(
+        motion.place(5.0, OffsetRotate::Auto(0.0)).unwrap().rotation,
+        90.0
+    );
+    assert_eq!(
+        motion
+            .place(5.0, OffsetRotate::Auto(-90.0))
+            .unwrap()
+            .rotation,
+        0.0
+    );
+    assert_eq!(
+        motion.place(5.0, OffsetRotate::Reverse).unwrap().rotation,
+        270.0
+    );
+    assert_eq!(
+        motion
+            .place(5.0, OffsetRotate::Fixed(45.0))
+            .unwrap()
+            .rotation,
+        45.0
+    );
+}
+
+#[test]
+fn offset_distance() {
+    let motion = motion("M 0 0 H 200");
+    let rotate = OffsetRotate::default();
+
+    let percent = Length::new(25.0, LengthUnit::Percent);
+    assert_eq!(
+        motion.place_with_offset(percent, 0.0, rotate).unwrap().x,
+        50.0
+    );
+    assert_eq!(
+        motion.place_with_offset(percent, 0.5, rotate).unwrap().x,
+        150.0
+    );
+    assert_eq!(
+        motion
+            .place_with_offset(Length::new_number(20.0), 0.0, rotate)
+            .unwrap()
+            .x,
+        20.0
+    );
+}
+
+#[test]
+fn placement_transform() {
+    let placement = Placement::new(10.0, 20.0, 90.0);
+    let point = placement
+        .to_transform()
+        .transform_point(Point::new(1.0, 0.0));
+    assert_near(point.x, 10.0);
+    assert_near(point.y, 21.0);
+}
+
+#[test]
+fn animation_drives_placement() {
+    let target = Rc::new(RefCell::new(Placement::default()));
+    let animation = MotionAnimation::with_target(motion("M 0 0 H 100 V 100"), target.clone());
+    animation.duration(1000.0).start(0.0);
+
+    animation.update(250.0);
+    assert_eq!(*target.borrow(), Placement::new(50.0, 0.0, 0.0));
+
+    animation.update(750.0);
+    assert_eq!(*target.borrow(), Placement::new(100.0, 50.0, 90.0));
+
+    assert!(!animation.update(1000.0));
+    assert_eq!(target.borrow().y, 100.0);
+}
+
+#[test]
+fn animation_offset_on_closed_path() {
+    let animation = MotionAnimation::new(motion("M 0 0 H 10 V 10 H 0 Z"));
+    animation
+        .offset_distance(Length::new(50.0, LengthUnit::Percent))
+        .rotate(OffsetRotate::Fixed(0.0))
+        .duration(100.0)
+        .start(0.0);
+
+    assert_eq!(animation.get_value(), Placement::new(10.0, 10.0, 0.0));
+    animation.update(75.0);
+    assert_eq!(animation.get_value(), Placement::new(10.0, 0.0, 0.0));
+}
+
+#[test]
+fn animation_callbacks_and_yoyo() {
+    let updates = Rc::new(RefCell::new(Vec::new()));
+    let completed = Rc::new(RefCell::new(None));
+
+    let animation = MotionAnimation::new(motion("M 0 0 H 100"));
+    {
+        let updates = updates.clone();
+        let completed = completed.clone();
+        animation
+            .duration(100.0)
+            .repeat(1.0)
+            .yoyo(true)
+            .on_update(move |placement| updates.borrow_mut().push(placement.x))
+            .on_complete(move |placement| *completed.borrow_mut() = Some(*placement));
+    }
+    animation.start(0.0);
+
+    for time in [50.0, 100.0, 150.0, 200.0].iter() {
+        animation.update(*time);
+    }
+
+    assert_eq!(*updates.borrow(), vec![50.0, 100.0, 50.0, 0.0]);
+    assert_eq!(*completed.borrow(), Some(Placement::new(0.0, 0.0, 0.0)));
+}
+
+#[test]
+fn animation_in_group_after_tween() {
+    let group = Group::new();
+    let tween = Tween::new(0.0);
+    let animation = MotionAnimation::new(motion("M 0 0 H 100"));
+    animation.duration(100.0).group(&group);
+    tween
+        .to(1.0, 100.0)
+        .chain(&animation)
+        .group(&group)
+        .start(0.0);
+
+    let mut time = 0.0;
+    while group.update(time) {
+        time += 10.0;
+    }
+
+    assert!(time >= 200.0);
+    assert_eq!(animation.get_value().x, 100.0);
+    assert!(group.is_empty());
+}
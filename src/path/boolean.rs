@@ -0,0 +1,344 @@
+use super::curve;
+use super::Path;
+use std::collections::HashMap;
+
+/// How [`Path::op`] combines the areas of two paths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathOp {
+    /// The area of either path.
+    Union,
+    /// The area of the first path out of the second one.
+    Difference,
+    /// The area common to both paths.
+    Intersect,
+    /// The area of only one of the paths.
+    Xor,
+}
+
+type Point = (f64, f64);
+
+/// The distance from an edge at which its sides are tested, in path units.
+const SIDE: f64 = 1e-6;
+
+/// The tolerance used to flatten curves, in path units.
+const TOLERANCE: f64 = 0.05;
+
+/// A straight edge of a flattened path and the points it is split at,
+/// sorted later by their position along the edge.
+struct Edge {
+    from: Point,
+    to: Point,
+    splits: Vec<(f64, Point)>,
+}
+
+impl Path {
+    /// Returns the area of the path combined with the area of `other`.
+    ///
+    /// Both paths are filled with the non-zero rule and their open subpaths
+    /// are closed. Curves are flattened, so the result is made of lines. Its
+    /// outlines go one way around the area and the other way around holes,
+    /// so it fills the same with either fill rule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use animate::path::{Path, PathOp};
+    ///
+    /// let a: Path = "M 0 0 H 10 V 10 H 0 Z".parse().unwrap();
+    /// let b: Path = "M 5 5 H 15 V 15 H 5 Z".parse().unwrap();
+    ///
+    /// let both = a.op(&b, PathOp::Intersect);
+    /// let bounds = both.bounding_box().unwrap();
+    /// assert_eq!((bounds.origin.x, bounds.size.width), (5.0, 5.0));
+    /// ```
+    pub fn op(&self, other: &Path, op: PathOp) -> Path {
+        let first = polygons(self);
+        let second = polygons(other);
+
+        let mut edges: Vec<Edge> = first
+            .iter()
+            .chain(second.iter())
+            .flat_map(|polygon| polygon_edges(polygon))
+            .map(|(from, to)| Edge {
+                from,
+                to,
+                splits: Vec::new(),
+            })
+            .collect();
+        split_crossings(&mut edges);
+
+        let inside = |point: Point| {
+            let a = winding(&first, point) != 0;
+            let b = winding(&second, point) != 0;
+            match op {
+                PathOp::Union => a || b,
+                PathOp::Difference => a && !b,
+                PathOp::Intersect => a && b,
+                PathOp::Xor => a != b,
+            }
+        };
+
+        // the pieces of edges between the area and the outside, turned to
+        // have the area on the same side
+        let mut boundary: Vec<(Point, Point)> = Vec::new();
+        for edge in edges.iter_mut() {
+            edge.splits
+                .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+            let mut points = vec![edge.from];
+            points.extend(edge.splits.iter().map(|split| split.1));
+            points.push(edge.to);
+
+            for piece in points.windows(2) {
+                let (from, to) = (piece[0], piece[1]);
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let length = dx.hypot(dy);
+                if length == 0.0 {
+                    continue;
+                }
+                let middle = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+                let normal = (-dy / length * SIDE, dx / length * SIDE);
+                let left = inside((middle.0 + normal.0, middle.1 + normal.1));
+                let right = inside((middle.0 - normal.0, middle.1 - normal.1));
+                let piece = match (left, right) {
+                    (true, false) => (from, to),
+                    (false, true) => (to, from),
+                    _ => continue,
+                };
+                // edges shared by both paths are met twice
+                if !boundary.contains(&piece) {
+                    boundary.push(piece);
+                }
+            }
+        }
+
+        chain(&boundary)
+    }
+}
+
+/// Returns the points of the flattened subpaths of `path`.
+fn polygons(path: &Path) -> Vec<Vec<Point>> {
+    curve::subpaths(&path.flatten(TOLERANCE))
+        .into_iter()
+        .map(|subpath| {
+            let mut points = vec![subpath.start];
+            points.extend(subpath.curves.iter().map(|curve| curve.point(1.0)));
+            points
+        })
+        .filter(|points| points.len() > 2)
+        .collect()
+}
+
+/// Returns the edges of a closed polygon, without the edges of no length.
+fn polygon_edges(polygon: &[Point]) -> Vec<(Point, Point)> {
+    let closing = (polygon[polygon.len() - 1], polygon[0]);
+    polygon
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(std::iter::once(closing))
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+fn cross(a: Point, b: Point) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/// Adds the points where the edges cross or overlap to the splits of both
+/// edges, the same point for both so the pieces join exactly.
+fn split_crossings(edges: &mut [Edge]) {
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            let (a, b) = (edges[i].from, edges[i].to);
+            let (c, d) = (edges[j].from, edges[j].to);
+            let (ab, cd, ac) = (sub(b, a), sub(d, c), sub(c, a));
+            let denominator = cross(ab, cd);
+            let scale = ab.0.hypot(ab.1) * cd.0.hypot(cd.1);
+
+            if denominator.abs() > 1e-12 * scale {
+                let t = cross(ac, cd) / denominator;
+                let u = cross(ac, ab) / denominator;
+                if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                    let point = (a.0 + ab.0 * t, a.1 + ab.1 * t);
+                    edges[i].splits.push((t, point));
+                    edges[j].splits.push((u, point));
+                }
+            } else if cross(ac, ab).abs() <= 1e-12 * scale.max(1.0) {
+                // collinear edges split each other at their ends
+                let along = |point: Point, from: Point, direction: Point| {
+                    let offset = sub(point, from);
+                    (offset.0 * direction.0 + offset.1 * direction.1)
+                        / (direction.0 * direction.0 + direction.1 * direction.1)
+                };
+                for &point in [c, d].iter() {
+                    let t = along(point, a, ab);
+                    if t > 0.0 && t < 1.0 {
+                        edges[i].splits.push((t, point));
+                    }
+                }
+                for &point in [a, b].iter() {
+                    let u = along(point, c, cd);
+                    if u > 0.0 && u < 1.0 {
+                        edges[j].splits.push((u, point));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the non-zero winding number of `polygons` around `point`.
+fn winding(polygons: &[Vec<Point>], point: Point) -> i32 {
+    let mut winding = 0;
+    for polygon in polygons {
+        for (from, to) in polygon_edges(polygon) {
+            let side = cross(sub(to, from), sub(point, from));
+            if from.1 <= point.1 {
+                if to.1 > point.1 && side > 0.0 {
+                    winding += 1;
+                }
+            } else if to.1 <= point.1 && side < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+/// Links the edges into closed outlines.
+fn chain(edges: &[(Point, Point)]) -> Path {
+    let key = |point: Point| {
+        (
+            (point.0 * 1e6).round() as i64,
+            (point.1 * 1e6).round() as i64,
+        )
+    };
+    let mut starts: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (index, edge) in edges.iter().enumerate() {
+        starts.entry(key(edge.0)).or_default().push(index);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut path = Path::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = edges[first].0;
+        path.push_move_to(start.0, start.1);
+
+        let mut current = edges[first].1;
+        while key(current) != key(start) {
+            path.push_line_to(current.0, current.1);
+            let next = starts
+                .get(&key(current))
+                .and_then(|next| next.iter().copied().find(|&index| !used[index]));
+            match next {
+                Some(index) => {
+                    used[index] = true;
+                    current = edges[index].1;
+                }
+                None => break,
+            }
+        }
+        path.push_close_path();
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Returns the sum of the signed areas of the subpaths.
+    fn area(path: &Path) -> f64 {
+        polygons(path)
+            .iter()
+            .map(|polygon| {
+                polygon_edges(polygon)
+                    .iter()
+                    .map(|(from, to)| cross(*from, *to))
+                    .sum::<f64>()
+                    / 2.0
+            })
+            .sum()
+    }
+
+    fn op(a: &str, b: &str, op: PathOp) -> Path {
+        Path::from_str(a)
+            .unwrap()
+            .op(&Path::from_str(b).unwrap(), op)
+    }
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    const A: &str = "M 0 0 H 10 V 10 H 0 Z";
+    const B: &str = "M 5 5 H 15 V 15 H 5 Z";
+
+    #[test]
+    fn overlapping_squares() {
+        for (kind, expected) in [
+            (PathOp::Union, 175.0),
+            (PathOp::Difference, 75.0),
+            (PathOp::Intersect, 25.0),
+            (PathOp::Xor, 150.0),
+        ]
+        .iter()
+        {
+            assert_near(area(&op(A, B, *kind)).abs(), *expected);
+        }
+        assert_eq!(
+            op(A, B, PathOp::Intersect).bounding_box(),
+            Path::from_str("M 5 5 H 10 V 10 H 5 Z")
+                .unwrap()
+                .bounding_box()
+        );
+    }
+
+    #[test]
+    fn holes_turn_the_other_way() {
+        let ring = op(A, "M 3 3 H 7 V 7 H 3 Z", PathOp::Difference);
+        assert_near(area(&ring).abs(), 84.0);
+        assert_eq!(polygons(&ring).len(), 2);
+
+        // the square drawn backwards still counts as an area
+        let xor = op(A, "M 3 3 V 7 H 7 V 3 Z", PathOp::Xor);
+        assert_near(area(&xor).abs(), 84.0);
+    }
+
+    #[test]
+    fn shared_edges() {
+        let union = op(A, "M 10 0 H 20 V 10 H 10 Z", PathOp::Union);
+        assert_near(area(&union).abs(), 200.0);
+        assert_eq!(polygons(&union).len(), 1);
+
+        assert!(op(A, "M 10 0 H 20 V 10 H 10 Z", PathOp::Intersect).is_empty());
+        assert!(op(A, A, PathOp::Difference).is_empty());
+        assert_near(area(&op(A, A, PathOp::Union)).abs(), 100.0);
+    }
+
+    #[test]
+    fn curves_and_empty_paths() {
+        let circle = "M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z";
+        let half = op(circle, "M -20 0 H 20 V 20 H -20 Z", PathOp::Intersect);
+        // both are flattened the same way
+        let whole = area(&Path::from_str(circle).unwrap());
+        assert_near(area(&half).abs(), whole.abs() / 2.0);
+
+        assert!(op("", "", PathOp::Union).is_empty());
+        assert_near(area(&op(A, "", PathOp::Union)).abs(), 100.0);
+        assert!(op("", A, PathOp::Difference).is_empty());
+    }
+}
@@ -0,0 +1,660 @@
+use crate::path::{Path, PathCommand, PathSegment};
+use std::f64::consts::PI;
+
+/// The most pieces a single curve is flattened into.
+const MAX_PIECES: usize = 1024;
+
+/// A single absolute piece of a path which can be evaluated at `t` in
+/// `0..=1`.
+///
+/// Quadratic curves are raised to cubic ones and arcs are kept in their center
+/// parameterization, so every SVG segment maps to one curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Curve {
+    Line {
+        from: (f64, f64),
+        to: (f64, f64),
+    },
+    Cubic {
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        p3: (f64, f64),
+    },
+    Arc {
+        center: (f64, f64),
+        radii: (f64, f64),
+        rotation: f64,
+        start_angle: f64,
+        sweep_angle: f64,
+    },
+}
+
+impl Curve {
+    /// Returns the point at `t`.
+    pub fn point(&self, t: f64) -> (f64, f64) {
+        match *self {
+            Curve::Line { from, to } => {
+                (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+            }
+            Curve::Cubic { p0, p1, p2, p3 } => {
+                let mt = 1.0 - t;
+                let a = mt * mt * mt;
+                let b = 3.0 * mt * mt * t;
+                let c = 3.0 * mt * t * t;
+                let d = t * t * t;
+                (
+                    a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                    a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+                )
+            }
+            Curve::Arc {
+                center,
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+            } => {
+                let (sin, cos) = (start_angle + sweep_angle * t).sin_cos();
+                let (sin_r, cos_r) = rotation.sin_cos();
+                (
+                    center.0 + radii.0 * cos * cos_r - radii.1 * sin * sin_r,
+                    center.1 + radii.0 * cos * sin_r + radii.1 * sin * cos_r,
+                )
+            }
+        }
+    }
+
+    /// Returns the derivative at `t`, which points in the direction of the
+    /// curve.
+    pub fn derivative(&self, t: f64) -> (f64, f64) {
+        match *self {
+            Curve::Line { from, to } => (to.0 - from.0, to.1 - from.1),
+            Curve::Cubic { p0, p1, p2, p3 } => {
+                let mt = 1.0 - t;
+                let a = 3.0 * mt * mt;
+                let b = 6.0 * mt * t;
+                let c = 3.0 * t * t;
+                (
+                    a * (p1.0 - p0.0) + b * (p2.0 - p1.0) + c * (p3.0 - p2.0),
+                    a * (p1.1 - p0.1) + b * (p2.1 - p1.1) + c * (p3.1 - p2.1),
+                )
+            }
+            Curve::Arc {
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+                ..
+            } => {
+                let (sin, cos) = (start_angle + sweep_angle * t).sin_cos();
+                let (sin_r, cos_r) = rotation.sin_cos();
+                (
+                    sweep_angle * (-radii.0 * sin * cos_r - radii.1 * cos * sin_r),
+                    sweep_angle * (-radii.0 * sin * sin_r + radii.1 * cos * cos_r),
+                )
+            }
+        }
+    }
+
+    /// Returns the arc length between `t0` and `t1`.
+    pub fn length(&self, t0: f64, t1: f64) -> f64 {
+        if let Curve::Line { from, to } = *self {
+            return (to.0 - from.0).hypot(to.1 - from.1) * (t1 - t0);
+        }
+
+        // five point Gauss-Legendre quadrature of the speed
+        const NODES: [(f64, f64); 5] = [
+            (0.0, 0.568_888_888_888_888_9),
+            (-0.538_469_310_105_683, 0.478_628_670_499_366_47),
+            (0.538_469_310_105_683, 0.478_628_670_499_366_47),
+            (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+            (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+        ];
+        let half = (t1 - t0) / 2.0;
+        let middle = (t0 + t1) / 2.0;
+        NODES
+            .iter()
+            .map(|(x, w)| {
+                let (dx, dy) = self.derivative(middle + half * x);
+                w * dx.hypot(dy)
+            })
+            .sum::<f64>()
+            * half
+    }
+
+    /// Returns the smallest and the largest corners of the box around the
+    /// curve.
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let mut min = self.point(0.0);
+        let mut max = min;
+        let mut extend = |(x, y): (f64, f64)| {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        };
+        extend(self.point(1.0));
+
+        match *self {
+            Curve::Line { .. } => {}
+            Curve::Cubic { p0, p1, p2, p3 } => {
+                // the roots of the derivative on each axis
+                let axes = [(p0.0, p1.0, p2.0, p3.0), (p0.1, p1.1, p2.1, p3.1)];
+                for &(a, b, c, d) in axes.iter() {
+                    let qa = -a + 3.0 * b - 3.0 * c + d;
+                    let qb = 2.0 * (a - 2.0 * b + c);
+                    let qc = b - a;
+                    for t in quadratic_roots(qa, qb, qc).iter().flatten() {
+                        if *t > 0.0 && *t < 1.0 {
+                            extend(self.point(*t));
+                        }
+                    }
+                }
+            }
+            Curve::Arc {
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+                ..
+            } => {
+                // the angles where the ellipse turns on each axis
+                let (sin_r, cos_r) = rotation.sin_cos();
+                let x = (-radii.1 * sin_r).atan2(radii.0 * cos_r);
+                let y = (radii.1 * cos_r).atan2(radii.0 * sin_r);
+                for base in [x, y].iter() {
+                    for k in -4..=4 {
+                        let t = (base + f64::from(k) * PI - start_angle) / sweep_angle;
+                        if t > 0.0 && t < 1.0 {
+                            extend(self.point(t));
+                        }
+                    }
+                }
+            }
+        }
+
+        (min, max)
+    }
+
+    /// Splits the curve at `t`.
+    pub fn split(&self, t: f64) -> (Curve, Curve) {
+        match *self {
+            Curve::Line { from, to } => {
+                let middle = self.point(t);
+                (
+                    Curve::Line { from, to: middle },
+                    Curve::Line { from: middle, to },
+                )
+            }
+            Curve::Cubic { p0, p1, p2, p3 } => {
+                let lerp =
+                    |a: (f64, f64), b: (f64, f64)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+                let p01 = lerp(p0, p1);
+                let p12 = lerp(p1, p2);
+                let p23 = lerp(p2, p3);
+                let p012 = lerp(p01, p12);
+                let p123 = lerp(p12, p23);
+                let middle = lerp(p012, p123);
+                (
+                    Curve::Cubic {
+                        p0,
+                        p1: p01,
+                        p2: p012,
+                        p3: middle,
+                    },
+                    Curve::Cubic {
+                        p0: middle,
+                        p1: p123,
+                        p2: p23,
+                        p3,
+                    },
+                )
+            }
+            Curve::Arc {
+                center,
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+            } => (
+                Curve::Arc {
+                    center,
+                    radii,
+                    rotation,
+                    start_angle,
+                    sweep_angle: sweep_angle * t,
+                },
+                Curve::Arc {
+                    center,
+                    radii,
+                    rotation,
+                    start_angle: start_angle + sweep_angle * t,
+                    sweep_angle: sweep_angle * (1.0 - t),
+                },
+            ),
+        }
+    }
+
+    /// Returns the same curve going the other way.
+    pub fn reversed(&self) -> Curve {
+        match *self {
+            Curve::Line { from, to } => Curve::Line { from: to, to: from },
+            Curve::Cubic { p0, p1, p2, p3 } => Curve::Cubic {
+                p0: p3,
+                p1: p2,
+                p2: p1,
+                p3: p0,
+            },
+            Curve::Arc {
+                center,
+                radii,
+                rotation,
+                start_angle,
+                sweep_angle,
+            } => Curve::Arc {
+                center,
+                radii,
+                rotation,
+                start_angle: start_angle + sweep_angle,
+                sweep_angle: -sweep_angle,
+            },
+        }
+    }
+
+    /// Approximates the curve with cubic curves, an arc takes one cubic for
+    /// every quarter turn or less.
+    ///
+    /// The control points of a line are at its thirds, so it moves evenly
+    /// when interpolated with a curve.
+    pub fn to_cubics(self) -> Vec<Curve> {
+        match self {
+            Curve::Line { from, to } => {
+                let third = |f: f64| (from.0 + (to.0 - from.0) * f, from.1 + (to.1 - from.1) * f);
+                vec![Curve::Cubic {
+                    p0: from,
+                    p1: third(1.0 / 3.0),
+                    p2: third(2.0 / 3.0),
+                    p3: to,
+                }]
+            }
+            Curve::Cubic { .. } => vec![self],
+            Curve::Arc { sweep_angle, .. } => {
+                let count = (sweep_angle.abs() / (PI / 2.0) - 1e-9).ceil().max(1.0) as usize;
+                let step = 1.0 / count as f64;
+                // the length of the handles of a cubic arc
+                let kappa = 4.0 / 3.0 * (sweep_angle * step / 4.0).tan() / sweep_angle;
+
+                (0..count)
+                    .map(|i| {
+                        let (t0, t1) = (i as f64 * step, (i + 1) as f64 * step);
+                        let (p0, p3) = (self.point(t0), self.point(t1));
+                        let (d0, d3) = (self.derivative(t0), self.derivative(t1));
+                        Curve::Cubic {
+                            p0,
+                            p1: (p0.0 + kappa * d0.0, p0.1 + kappa * d0.1),
+                            p2: (p3.0 - kappa * d3.0, p3.1 - kappa * d3.1),
+                            p3,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the absolute segment drawing the curve.
+    pub fn to_segment(self) -> PathSegment {
+        match self {
+            Curve::Line { to, .. } => PathSegment::LineTo {
+                abs: true,
+                x: to.0,
+                y: to.1,
+            },
+            Curve::Cubic { p1, p2, p3, .. } => PathSegment::CurveTo {
+                abs: true,
+                x1: p1.0,
+                y1: p1.1,
+                x2: p2.0,
+                y2: p2.1,
+                x: p3.0,
+                y: p3.1,
+            },
+            Curve::Arc {
+                radii,
+                rotation,
+                sweep_angle,
+                ..
+            } => {
+                let (x, y) = self.point(1.0);
+                PathSegment::EllipticalArc {
+                    abs: true,
+                    rx: radii.0,
+                    ry: radii.1,
+                    x_axis_rotation: rotation.to_degrees(),
+                    large_arc: sweep_angle.abs() > PI,
+                    sweep: sweep_angle > 0.0,
+                    x,
+                    y,
+                }
+            }
+        }
+    }
+
+    /// Returns how many straight pieces keep the curve within `tolerance`.
+    pub fn pieces(&self, tolerance: f64) -> usize {
+        let pieces = match *self {
+            Curve::Line { .. } => return 1,
+            Curve::Cubic { p0, p1, p2, p3 } => {
+                // Wang's formula for the flattening of a cubic
+                let dd = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+                    (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1)
+                };
+                let m = dd(p0, p1, p2).max(dd(p1, p2, p3));
+                (0.75 * m / tolerance).sqrt().ceil()
+            }
+            Curve::Arc {
+                radii, sweep_angle, ..
+            } => {
+                let radius = radii.0.max(radii.1);
+                let step = if tolerance < radius {
+                    2.0 * (1.0 - tolerance / radius).acos()
+                } else {
+                    PI / 2.0
+                };
+                (sweep_angle.abs() / step).ceil()
+            }
+        };
+
+        if pieces.is_finite() {
+            (pieces as usize).clamp(1, MAX_PIECES)
+        } else {
+            MAX_PIECES
+        }
+    }
+}
+
+/// A run of connected curves started by a MoveTo.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Subpath {
+    pub start: (f64, f64),
+    pub curves: Vec<Curve>,
+    pub closed: bool,
+}
+
+impl Subpath {
+    /// Returns the point the subpath ends at.
+    pub fn end(&self) -> (f64, f64) {
+        match self.curves.last() {
+            Some(curve) if !self.closed => curve.point(1.0),
+            _ => self.start,
+        }
+    }
+
+    /// Returns the same subpath going the other way.
+    pub fn reversed(&self) -> Subpath {
+        let curves: Vec<Curve> = self.curves.iter().rev().map(Curve::reversed).collect();
+        Subpath {
+            start: self.end(),
+            curves,
+            closed: self.closed,
+        }
+    }
+}
+
+/// Builds an absolute path from subpaths.
+///
+/// A closed subpath ending with a line back to its start has the line
+/// replaced by the ClosePath drawing it.
+pub(crate) fn to_path(subpaths: &[Subpath]) -> Path {
+    let mut path = Path::new();
+    for subpath in subpaths {
+        path.push_move_to(subpath.start.0, subpath.start.1);
+
+        let mut curves = &subpath.curves[..];
+        if subpath.closed {
+            if let Some((Curve::Line { to, .. }, rest)) = curves.split_last() {
+                if *to == subpath.start {
+                    curves = rest;
+                }
+            }
+        }
+
+        path.extend(curves.iter().map(|curve| curve.to_segment()));
+        if subpath.closed {
+            path.push_close_path();
+        }
+    }
+    path
+}
+
+/// Returns the real roots of `a * t^2 + b * t + c`.
+fn quadratic_roots(a: f64, b: f64, c: f64) -> [Option<f64>; 2] {
+    if a.abs() < 1e-12 {
+        return if b.abs() < 1e-12 {
+            [None, None]
+        } else {
+            [Some(-c / b), None]
+        };
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    [Some((-b - root) / (2.0 * a)), Some((-b + root) / (2.0 * a))]
+}
+
+/// Splits `path` into subpaths of absolute curves.
+///
+/// Smooth segments are resolved against the previous control point, like a
+/// renderer does. Segments without length are dropped, but a subpath made
+/// only of a MoveTo is kept.
+pub(crate) fn subpaths(path: &Path) -> Vec<Subpath> {
+    let mut path = path.clone();
+    path.conv_to_absolute();
+
+    let mut subpaths: Vec<Subpath> = Vec::new();
+    let mut current = (0.0, 0.0);
+    // the reflected control point of a smooth curve and a smooth quadratic
+    let mut prev_cubic: Option<(f64, f64)> = None;
+    let mut prev_quad: Option<(f64, f64)> = None;
+
+    for seg in path.iter() {
+        let (cubic, quad) = (prev_cubic.take(), prev_quad.take());
+
+        if let PathSegment::MoveTo { x, y, .. } = *seg {
+            subpaths.push(Subpath {
+                start: (x, y),
+                ..Subpath::default()
+            });
+            current = (x, y);
+            continue;
+        }
+
+        let after_close = subpaths.last().map(|s| s.closed).unwrap_or(true);
+        if after_close && seg.cmd() == PathCommand::ClosePath {
+            continue;
+        }
+        if after_close {
+            // drawing goes on from the current point, which is the start of a
+            // closed subpath
+            subpaths.push(Subpath {
+                start: current,
+                ..Subpath::default()
+            });
+        }
+        let subpath = subpaths.last_mut().unwrap();
+        let from = current;
+
+        let curve = match *seg {
+            PathSegment::MoveTo { .. } => unreachable!(),
+            PathSegment::LineTo { x, y, .. } => line(from, (x, y)),
+            PathSegment::HorizontalLineTo { x, .. } => line(from, (x, from.1)),
+            PathSegment::VerticalLineTo { y, .. } => line(from, (from.0, y)),
+            PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+                ..
+            } => {
+                prev_cubic = Some((x2, y2));
+                Some(Curve::Cubic {
+                    p0: from,
+                    p1: (x1, y1),
+                    p2: (x2, y2),
+                    p3: (x, y),
+                })
+            }
+            PathSegment::SmoothCurveTo { x2, y2, x, y, .. } => {
+                let p1 = reflect(cubic, from);
+                prev_cubic = Some((x2, y2));
+                Some(Curve::Cubic {
+                    p0: from,
+                    p1,
+                    p2: (x2, y2),
+                    p3: (x, y),
+                })
+            }
+            PathSegment::Quadratic { x1, y1, x, y, .. } => {
+                prev_quad = Some((x1, y1));
+                Some(quadratic(from, (x1, y1), (x, y)))
+            }
+            PathSegment::SmoothQuadratic { x, y, .. } => {
+                let control = reflect(quad, from);
+                prev_quad = Some(control);
+                Some(quadratic(from, control, (x, y)))
+            }
+            PathSegment::EllipticalArc {
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+                ..
+            } => arc(from, rx, ry, x_axis_rotation, large_arc, sweep, (x, y)),
+            PathSegment::ClosePath { .. } => {
+                subpath.closed = true;
+                line(from, subpath.start)
+            }
+        };
+
+        if let Some(curve) = curve {
+            subpath.curves.push(curve);
+        }
+        current = match *seg {
+            PathSegment::ClosePath { .. } => subpath.start,
+            PathSegment::HorizontalLineTo { x, .. } => (x, from.1),
+            PathSegment::VerticalLineTo { y, .. } => (from.0, y),
+            _ => (seg.x().unwrap(), seg.y().unwrap()),
+        };
+    }
+
+    subpaths
+}
+
+fn line(from: (f64, f64), to: (f64, f64)) -> Option<Curve> {
+    if from == to {
+        None
+    } else {
+        Some(Curve::Line { from, to })
+    }
+}
+
+fn reflect(control: Option<(f64, f64)>, current: (f64, f64)) -> (f64, f64) {
+    match control {
+        Some(c) => (2.0 * current.0 - c.0, 2.0 * current.1 - c.1),
+        None => current,
+    }
+}
+
+fn quadratic(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64)) -> Curve {
+    Curve::Cubic {
+        p0,
+        p1: (
+            p0.0 + 2.0 / 3.0 * (p1.0 - p0.0),
+            p0.1 + 2.0 / 3.0 * (p1.1 - p0.1),
+        ),
+        p2: (
+            p2.0 + 2.0 / 3.0 * (p1.0 - p2.0),
+            p2.1 + 2.0 / 3.0 * (p1.1 - p2.1),
+        ),
+        p3: p2,
+    }
+}
+
+/// Converts an SVG arc to its center parameterization.
+///
+/// See the [implementation notes] of SVG, out of range radii are scaled up.
+///
+/// [implementation notes]: https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+pub(crate) fn arc(
+    from: (f64, f64),
+    rx: f64,
+    ry: f64,
+    x_axis_rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: (f64, f64),
+) -> Option<Curve> {
+    if from == to {
+        return None;
+    }
+
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return line(from, to);
+    }
+
+    let rotation = x_axis_rotation.to_radians();
+    let (sin, cos) = rotation.sin_cos();
+    let dx = (from.0 - to.0) / 2.0;
+    let dy = (from.1 - to.1) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coef = (num / den).max(0.0).sqrt();
+    if large_arc == sweep {
+        coef = -coef;
+    }
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+
+    let center = (
+        cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+        sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0,
+    );
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let ux = (x1 - cx1) / rx;
+    let uy = (y1 - cy1) / ry;
+    let vx = (-x1 - cx1) / rx;
+    let vy = (-y1 - cy1) / ry;
+
+    let start_angle = angle(1.0, 0.0, ux, uy);
+    let mut sweep_angle = angle(ux, uy, vx, vy);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    Some(Curve::Arc {
+        center,
+        radii: (rx, ry),
+        rotation,
+        start_angle,
+        sweep_angle,
+    })
+}
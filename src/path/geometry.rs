@@ -0,0 +1,511 @@
+use super::curve::{self, Curve, Subpath};
+use super::{Path, PathCommand, PathMeasure, PathSegment};
+use crate::{Point, Rect, Size, Transform};
+
+impl Path {
+    /// Returns the exact bounding box of the path, control points off the
+    /// curves do not count.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn bounding_box(&self) -> Option<Rect<f64>> {
+        let subpaths = curve::subpaths(self);
+        let first = subpaths.first()?.start;
+
+        let (mut min, mut max) = (first, first);
+        for subpath in subpaths.iter() {
+            let bounds = subpath.curves.iter().map(Curve::bounds);
+            for (a, b) in std::iter::once((subpath.start, subpath.start)).chain(bounds) {
+                min = (min.0.min(a.0), min.1.min(a.1));
+                max = (max.0.max(b.0), max.1.max(b.1));
+            }
+        }
+
+        Some(Rect {
+            origin: Point::new(min.0, min.1),
+            size: Size::new(max.0 - min.0, max.1 - min.1),
+        })
+    }
+
+    /// Returns the total length of the path.
+    ///
+    /// The path is measured on every call, use a [`PathMeasure`] to query
+    /// the same path many times.
+    pub fn length(&self) -> f64 {
+        PathMeasure::new(self).length()
+    }
+
+    /// Returns the point at `distance` along the path, distances out of the
+    /// path are clamped to its ends.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn point_at_length(&self, distance: f64) -> Option<Point<f64>> {
+        PathMeasure::new(self).point_at_length(distance)
+    }
+
+    /// Returns the direction of the path in degrees at `distance` along it.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn tangent_at_length(&self, distance: f64) -> Option<f64> {
+        PathMeasure::new(self).tangent_at_length(distance)
+    }
+
+    /// Splits the path at `distance` along it.
+    ///
+    /// See [`PathMeasure::split_at_length`].
+    pub fn split_at_length(&self, distance: f64) -> (Path, Path) {
+        PathMeasure::new(self).split_at_length(distance)
+    }
+
+    /// Returns the path drawn backwards, from the last subpath to the first.
+    ///
+    /// The result is made of absolute segments, quadratic and smooth curves
+    /// are written as cubic curves and lines as plain LineTo.
+    pub fn reversed(&self) -> Path {
+        let subpaths: Vec<Subpath> = curve::subpaths(self)
+            .iter()
+            .rev()
+            .map(Subpath::reversed)
+            .collect();
+        curve::to_path(&subpaths)
+    }
+
+    /// Returns the path made of lines only, curves are cut until they are less
+    /// than `tolerance` away from the lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn flatten(&self, tolerance: f64) -> Path {
+        assert!(tolerance > 0.0, "tolerance must be positive");
+
+        let subpaths: Vec<Subpath> = curve::subpaths(self)
+            .into_iter()
+            .map(|subpath| {
+                let mut curves = Vec::new();
+                for curve in subpath.curves.iter() {
+                    let pieces = curve.pieces(tolerance);
+                    let mut from = curve.point(0.0);
+                    for i in 1..=pieces {
+                        let to = curve.point(i as f64 / pieces as f64);
+                        curves.push(Curve::Line { from, to });
+                        from = to;
+                    }
+                }
+                Subpath { curves, ..subpath }
+            })
+            .collect();
+        curve::to_path(&subpaths)
+    }
+
+    /// Converts EllipticalArc segments into CurveTo segments in-place, one
+    /// curve for every quarter turn or less.
+    ///
+    /// The converted segments keep being absolute or relative.
+    pub fn conv_arcs_to_curves(&mut self) {
+        if !self
+            .iter()
+            .any(|seg| seg.cmd() == PathCommand::EllipticalArc)
+        {
+            return;
+        }
+
+        let starts = start_points(self);
+        let mut segments = Vec::with_capacity(self.len());
+        for (seg, start) in self.iter().zip(starts) {
+            let curve = match *seg {
+                PathSegment::EllipticalArc {
+                    abs,
+                    rx,
+                    ry,
+                    x_axis_rotation,
+                    large_arc,
+                    sweep,
+                    x,
+                    y,
+                } => {
+                    let to = if abs {
+                        (x, y)
+                    } else {
+                        (start.0 + x, start.1 + y)
+                    };
+                    curve::arc(start, rx, ry, x_axis_rotation, large_arc, sweep, to)
+                }
+                _ => {
+                    segments.push(*seg);
+                    continue;
+                }
+            };
+
+            // an arc ending where it starts draws nothing
+            let mut from = start;
+            for cubic in curve.into_iter().flat_map(Curve::to_cubics) {
+                let mut segment = cubic.to_segment();
+                if seg.is_relative() {
+                    transform_segment_data(&mut segment, &Transform::translation(-from.0, -from.1));
+                    segment.set_absolute(false);
+                }
+                from = cubic.point(1.0);
+                segments.push(segment);
+            }
+        }
+        self.0 = segments;
+    }
+
+    /// Applies the transform `ts` to every segment in-place.
+    ///
+    /// Segments keep being absolute or relative. Horizontal and vertical
+    /// lines become LineTo when `ts` rotates or skews, and arcs get the radii
+    /// and the rotation of the transformed ellipse.
+    pub fn transform(&mut self, ts: &Transform<f64>) {
+        let keeps_axes = ts.m12 == 0.0 && ts.m21 == 0.0;
+        let starts = if keeps_axes {
+            Vec::new()
+        } else {
+            start_points(self)
+        };
+        let linear = Transform {
+            m31: 0.0,
+            m32: 0.0,
+            ..*ts
+        };
+
+        for (index, seg) in self.iter_mut().enumerate() {
+            if !keeps_axes {
+                // the missing coordinate comes from the current point
+                let start = starts[index];
+                *seg = match *seg {
+                    PathSegment::HorizontalLineTo { abs, x } => PathSegment::LineTo {
+                        abs,
+                        x,
+                        y: if abs { start.1 } else { 0.0 },
+                    },
+                    PathSegment::VerticalLineTo { abs, y } => PathSegment::LineTo {
+                        abs,
+                        x: if abs { start.0 } else { 0.0 },
+                        y,
+                    },
+                    other => other,
+                };
+            }
+
+            // a leading relative MoveTo is a position, the other relative
+            // segments are offsets
+            if seg.is_absolute() || index == 0 {
+                transform_segment_data(seg, ts);
+            } else {
+                transform_segment_data(seg, &linear);
+            }
+        }
+    }
+}
+
+/// Returns the absolute current point before each segment.
+fn start_points(path: &Path) -> Vec<(f64, f64)> {
+    let mut absolute = path.clone();
+    absolute.conv_to_absolute();
+
+    let mut starts = Vec::with_capacity(absolute.len());
+    let mut current = (0.0, 0.0);
+    let mut subpath_start = (0.0, 0.0);
+    for seg in absolute.iter() {
+        starts.push(current);
+        current = match *seg {
+            PathSegment::ClosePath { .. } => subpath_start,
+            PathSegment::HorizontalLineTo { x, .. } => (x, current.1),
+            PathSegment::VerticalLineTo { y, .. } => (current.0, y),
+            _ => (seg.x().unwrap(), seg.y().unwrap()),
+        };
+        if seg.cmd() == PathCommand::MoveTo {
+            subpath_start = current;
+        }
+    }
+    starts
+}
+
+/// Applies `ts` to the coordinates of a segment.
+///
+/// The coordinates of horizontal and vertical lines are transformed on their
+/// own axis only, which is right as long as `ts` does not rotate or skew.
+pub(crate) fn transform_segment_data(d: &mut PathSegment, ts: &Transform<f64>) {
+    let apply = |x: &mut f64, y: &mut f64| {
+        let point = ts.transform_point(Point::new(*x, *y));
+        *x = point.x;
+        *y = point.y;
+    };
+
+    match *d {
+        PathSegment::MoveTo {
+            ref mut x,
+            ref mut y,
+            ..
+        }
+        | PathSegment::LineTo {
+            ref mut x,
+            ref mut y,
+            ..
+        }
+        | PathSegment::SmoothQuadratic {
+            ref mut x,
+            ref mut y,
+            ..
+        } => apply(x, y),
+        PathSegment::HorizontalLineTo { ref mut x, .. } => {
+            *x = ts.m11 * *x + ts.m31;
+        }
+        PathSegment::VerticalLineTo { ref mut y, .. } => {
+            *y = ts.m22 * *y + ts.m32;
+        }
+        PathSegment::CurveTo {
+            ref mut x1,
+            ref mut y1,
+            ref mut x2,
+            ref mut y2,
+            ref mut x,
+            ref mut y,
+            ..
+        } => {
+            apply(x1, y1);
+            apply(x2, y2);
+            apply(x, y);
+        }
+        PathSegment::SmoothCurveTo {
+            ref mut x2,
+            ref mut y2,
+            ref mut x,
+            ref mut y,
+            ..
+        } => {
+            apply(x2, y2);
+            apply(x, y);
+        }
+        PathSegment::Quadratic {
+            ref mut x1,
+            ref mut y1,
+            ref mut x,
+            ref mut y,
+            ..
+        } => {
+            apply(x1, y1);
+            apply(x, y);
+        }
+        PathSegment::EllipticalArc {
+            ref mut rx,
+            ref mut ry,
+            ref mut x_axis_rotation,
+            ref mut sweep,
+            ref mut x,
+            ref mut y,
+            ..
+        } => {
+            apply(x, y);
+
+            let linear = (ts.m11, ts.m12, ts.m21, ts.m22);
+            if linear != (1.0, 0.0, 0.0, 1.0) {
+                let (new_rx, new_ry, rotation) = transform_ellipse(*rx, *ry, *x_axis_rotation, ts);
+                *rx = new_rx;
+                *ry = new_ry;
+                *x_axis_rotation = rotation;
+                if ts.determinant() < 0.0 {
+                    // a mirrored arc goes the other way round
+                    *sweep = !*sweep;
+                }
+            }
+        }
+        PathSegment::ClosePath { .. } => {}
+    }
+}
+
+/// Returns the radii and the rotation in degrees of an ellipse after the
+/// linear part of `ts`.
+fn transform_ellipse(rx: f64, ry: f64, rotation: f64, ts: &Transform<f64>) -> (f64, f64, f64) {
+    // the columns of the matrix mapping the unit circle onto the ellipse
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (p, r) = (rx * cos, rx * sin);
+    let (q, s) = (-ry * sin, ry * cos);
+    let (p, q, r, s) = (
+        ts.m11 * p + ts.m21 * r,
+        ts.m11 * q + ts.m21 * s,
+        ts.m12 * p + ts.m22 * r,
+        ts.m12 * q + ts.m22 * s,
+    );
+
+    // the square roots of the eigenvalues of M * M^T are the new radii
+    let a = p * p + q * q;
+    let b = p * r + q * s;
+    let c = r * r + s * s;
+    let mean = (a + c) / 2.0;
+    let delta = (((a - c) / 2.0).powi(2) + b * b).sqrt();
+    let angle = 0.5 * (2.0 * b).atan2(a - c);
+
+    (
+        (mean + delta).sqrt(),
+        (mean - delta).max(0.0).sqrt(),
+        angle.to_degrees(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::rotation;
+    use std::str::FromStr;
+
+    fn path(text: &str) -> Path {
+        Path::from_str(text).unwrap()
+    }
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_rect(rect: Rect<f64>, x: f64, y: f64, width: f64, height: f64) {
+        assert_near(rect.origin.x, x);
+        assert_near(rect.origin.y, y);
+        assert_near(rect.size.width, width);
+        assert_near(rect.size.height, height);
+    }
+
+    #[test]
+    fn bounding_box() {
+        assert_eq!(path("").bounding_box(), None);
+        assert_rect(path("M 5 5").bounding_box().unwrap(), 5.0, 5.0, 0.0, 0.0);
+        assert_rect(
+            path("M 10 20 h 30 v -40 M 0 0").bounding_box().unwrap(),
+            0.0,
+            -20.0,
+            40.0,
+            40.0,
+        );
+        // the control points are out of the curve
+        assert_rect(
+            path("M 0 0 C 0 40 40 40 40 0").bounding_box().unwrap(),
+            0.0,
+            0.0,
+            40.0,
+            30.0,
+        );
+        assert_rect(
+            path("M 0 0 Q 10 20 20 0").bounding_box().unwrap(),
+            0.0,
+            0.0,
+            20.0,
+            10.0,
+        );
+        // a half circle under its chord
+        assert_rect(
+            path("M -10 0 A 10 10 0 0 0 10 0").bounding_box().unwrap(),
+            -10.0,
+            0.0,
+            20.0,
+            10.0,
+        );
+    }
+
+    #[test]
+    fn length_and_points() {
+        let path = path("M 0 0 H 10 V 10");
+        assert_eq!(path.length(), 20.0);
+        assert_eq!(path.point_at_length(15.0), Some(Point::new(10.0, 5.0)));
+        assert_eq!(path.tangent_at_length(15.0), Some(90.0));
+    }
+
+    #[test]
+    fn reversed() {
+        assert_eq!(
+            path("M 0 0 L 10 0 Q 20 0 20 10 M 30 30 h 5")
+                .reversed()
+                .to_string(),
+            "M 35 30 L 30 30 M 20 10 C 20 3.33333333333 16.66666666667 0 10 0 L 0 0"
+        );
+        assert_eq!(
+            path("M 0 0 H 10 V 10 Z").reversed().to_string(),
+            "M 0 0 L 10 10 L 10 0 Z"
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        assert_eq!(
+            path("M 0 0 H 10 V 10 Z").flatten(0.1).to_string(),
+            "M 0 0 L 10 0 L 10 10 Z"
+        );
+
+        let flat = path("M 0 0 C 0 40 40 40 40 0").flatten(0.1);
+        assert!(flat.len() > 5);
+        assert!(flat
+            .iter()
+            .skip(1)
+            .all(|seg| seg.cmd() == PathCommand::LineTo));
+        assert_eq!(flat.last().unwrap().x(), Some(40.0));
+    }
+
+    #[test]
+    fn arcs_to_curves() {
+        let mut absolute = path("M 10 0 A 10 10 0 0 1 -10 0");
+        absolute.conv_arcs_to_curves();
+        assert_eq!(absolute.len(), 3);
+        assert_eq!(absolute.last().unwrap().x(), Some(-10.0));
+        let middle = absolute.point_at_length(absolute.length() / 2.0).unwrap();
+        assert!((middle.x).abs() < 1e-3 && (middle.y - 10.0).abs() < 1e-3);
+
+        let mut relative = path("M 10 0 a 10 10 0 0 1 -20 0 l 5 5");
+        relative.conv_arcs_to_curves();
+        assert!(relative.iter().skip(1).all(|seg| seg.is_relative()));
+        let mut expected = absolute.clone();
+        expected.push_line_to(-5.0, 5.0);
+        relative.conv_to_absolute();
+        assert!(relative
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, b)| (a.x().unwrap() - b.x().unwrap()).abs() < 1e-9
+                && (a.y().unwrap() - b.y().unwrap()).abs() < 1e-9));
+    }
+
+    #[test]
+    fn transform_translate() {
+        let mut path = path("M 10 20 h 10 V 40 Q 0 0 5 5 A 5 5 30 0 1 0 0 z");
+        path.transform(&Transform::translation(1.0, 2.0));
+        assert_eq!(
+            path.to_string(),
+            "M 11 22 h 10 V 42 Q 1 2 6 7 A 5 5 30 0 1 1 2 z"
+        );
+    }
+
+    #[test]
+    fn transform_rotate() {
+        let mut path = path("M 10 0 h 10 V 10 l 0 5");
+        path.transform(&rotation(90.0));
+        assert_eq!(path.to_string(), "M 0 10 l 0 10 L -10 20 l -5 0");
+    }
+
+    #[test]
+    fn transform_arc() {
+        let mut path = path("M 0 0 A 10 5 0 0 1 20 0");
+        path.transform(&Transform::scale(1.0, 4.0));
+        match path[1] {
+            PathSegment::EllipticalArc {
+                rx,
+                ry,
+                x_axis_rotation,
+                sweep,
+                ..
+            } => {
+                assert_near(rx, 20.0);
+                assert_near(ry, 10.0);
+                assert_near(x_axis_rotation.abs() % 180.0, 90.0);
+                assert!(sweep);
+            }
+            _ => unreachable!(),
+        }
+
+        // a mirror flips the direction of the arc
+        let mut path = self::path("M 0 0 A 10 10 0 0 1 20 0");
+        path.transform(&Transform::scale(1.0, -1.0));
+        assert_eq!(path.to_string(), "M 0 0 A 10 10 0 0 0 20 0");
+    }
+}
@@ -0,0 +1,406 @@
+use super::curve::{self, Curve, Subpath};
+use super::Path;
+use crate::Point;
+
+/// The default tolerance in path units.
+const DEFAULT_TOLERANCE: f64 = 0.1;
+
+/// A position on a path and the direction of the path there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathSample {
+    pub position: Point<f64>,
+    /// The direction of the path in degrees, clockwise from the positive X
+    /// axis in screen coordinates.
+    pub angle: f64,
+}
+
+/// An end of a measured piece of a curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Vertex {
+    subpath: usize,
+    curve: usize,
+    t: f64,
+    distance: f64,
+}
+
+/// A [`Path`] measured by arc length.
+///
+/// The curves are cut once into small pieces measured into a table of
+/// distances, lookups then find the distance in the table and evaluate the
+/// exact curve, so positions and angles stay smooth between the table
+/// entries. Subpaths follow one another, the jump of a MoveTo does not add to
+/// the length.
+///
+/// Measuring is the costly part, keep a `PathMeasure` around to sample the
+/// same path many times.
+///
+/// # Example
+///
+/// ```
+/// use animate::path::{Path, PathMeasure};
+///
+/// let path: Path = "M 0 0 L 100 0 L 100 100".parse().unwrap();
+/// let measure = PathMeasure::new(&path);
+/// assert_eq!(measure.length(), 200.0);
+///
+/// let point = measure.point_at_length(150.0).unwrap();
+/// assert_eq!((point.x, point.y), (100.0, 50.0));
+/// assert_eq!(measure.tangent_at_length(150.0), Some(90.0));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathMeasure {
+    subpaths: Vec<Subpath>,
+    vertices: Vec<Vertex>,
+}
+
+impl PathMeasure {
+    /// Measures `path` with the default tolerance of 0.1 units.
+    pub fn new(path: &Path) -> Self {
+        Self::with_tolerance(path, DEFAULT_TOLERANCE)
+    }
+
+    /// Measures `path`, curves are cut in pieces until they are less than
+    /// `tolerance` away from straight lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn with_tolerance(path: &Path, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0, "tolerance must be positive");
+
+        let subpaths = curve::subpaths(path);
+        let mut vertices = Vec::new();
+        let mut distance = 0.0;
+        for (subpath_index, subpath) in subpaths.iter().enumerate() {
+            for (curve_index, curve) in subpath.curves.iter().enumerate() {
+                let pieces = curve.pieces(tolerance);
+                let mut vertex = Vertex {
+                    subpath: subpath_index,
+                    curve: curve_index,
+                    t: 0.0,
+                    distance,
+                };
+                vertices.push(vertex);
+
+                for i in 1..=pieces {
+                    let t = i as f64 / pieces as f64;
+                    distance += curve.length(vertex.t, t);
+                    vertex.t = t;
+                    vertex.distance = distance;
+                    vertices.push(vertex);
+                }
+            }
+        }
+
+        PathMeasure { subpaths, vertices }
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> f64 {
+        self.vertices.last().map_or(0.0, |vertex| vertex.distance)
+    }
+
+    /// Returns `true` if the path is a single closed subpath.
+    pub fn is_closed(&self) -> bool {
+        self.subpaths.len() == 1 && self.subpaths[0].closed
+    }
+
+    /// Returns `true` if the path has no point to sample.
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    /// Returns the position and direction at `distance` along the path,
+    /// distances out of the path are clamped to its ends.
+    ///
+    /// Returns `None` if the path is empty. A path without length, like a
+    /// single MoveTo, always samples its start point heading right.
+    pub fn sample_at_length(&self, distance: f64) -> Option<PathSample> {
+        let (curve, a, b, t) = match self.locate(distance) {
+            Some(location) => location,
+            None => {
+                let start = self.subpaths.first()?.start;
+                return Some(PathSample {
+                    position: Point::new(start.0, start.1),
+                    angle: 0.0,
+                });
+            }
+        };
+
+        let (x, y) = curve.point(t);
+        let (mut dx, mut dy) = curve.derivative(t);
+        if dx.hypot(dy) < 1e-9 {
+            // a cusp or a control point on the end point, follow the piece
+            let (ax, ay) = curve.point(a.t);
+            let (bx, by) = curve.point(b.t);
+            dx = bx - ax;
+            dy = by - ay;
+        }
+
+        Some(PathSample {
+            position: Point::new(x, y),
+            angle: dy.atan2(dx).to_degrees(),
+        })
+    }
+
+    /// Returns the point at `distance` along the path.
+    pub fn point_at_length(&self, distance: f64) -> Option<Point<f64>> {
+        self.sample_at_length(distance)
+            .map(|sample| sample.position)
+    }
+
+    /// Returns the direction of the path in degrees at `distance` along it.
+    pub fn tangent_at_length(&self, distance: f64) -> Option<f64> {
+        self.sample_at_length(distance).map(|sample| sample.angle)
+    }
+
+    /// Splits the path at `distance` along it.
+    ///
+    /// Both parts are made of absolute segments, quadratic and smooth curves
+    /// are written as cubic curves and lines as plain LineTo. The subpath
+    /// being cut is left open.
+    pub fn split_at_length(&self, distance: f64) -> (Path, Path) {
+        let vertex = match self.locate(distance) {
+            Some((_, a, _, t)) => Vertex { t, ..a },
+            None => return (curve::to_path(&self.subpaths), Path::new()),
+        };
+
+        let mut before = self.subpaths[..vertex.subpath].to_vec();
+        let mut after = Vec::new();
+
+        let subpath = &self.subpaths[vertex.subpath];
+        let (head, tail) = subpath.curves[vertex.curve].split(vertex.t);
+
+        let mut curves = subpath.curves[..vertex.curve].to_vec();
+        curves.push(head);
+        before.push(Subpath {
+            start: subpath.start,
+            curves,
+            closed: false,
+        });
+
+        let mut curves = vec![tail];
+        curves.extend_from_slice(&subpath.curves[vertex.curve + 1..]);
+        after.push(Subpath {
+            start: tail.point(0.0),
+            curves,
+            closed: false,
+        });
+        after.extend_from_slice(&self.subpaths[vertex.subpath + 1..]);
+
+        (curve::to_path(&before), curve::to_path(&after))
+    }
+
+    /// Returns the part of the path between the distances `start` and `end`
+    /// along it, clamped to the path.
+    ///
+    /// The part is made of absolute segments like the parts of
+    /// [`split_at_length`](PathMeasure::split_at_length). Subpaths crossed
+    /// whole keep being closed, the others are left open. The part is empty
+    /// if `start` is not before `end`.
+    pub fn segment(&self, start: f64, end: f64) -> Path {
+        let length = self.length();
+        let (start, end) = (start.max(0.0), end.min(length));
+        if start >= end {
+            return Path::new();
+        }
+        let (from, to) = match (self.locate(start), self.locate(end)) {
+            (Some((_, a, _, ta)), Some((_, b, _, tb))) => {
+                (Vertex { t: ta, ..a }, Vertex { t: tb, ..b })
+            }
+            _ => return Path::new(),
+        };
+
+        let mut subpaths = Vec::new();
+        for index in from.subpath..=to.subpath {
+            let subpath = &self.subpaths[index];
+            if subpath.curves.is_empty() {
+                continue;
+            }
+            let first = if index == from.subpath { from.curve } else { 0 };
+            let last = if index == to.subpath {
+                to.curve
+            } else {
+                subpath.curves.len() - 1
+            };
+
+            let mut curves = Vec::new();
+            for curve_index in first..=last {
+                let t0 = if (index, curve_index) == (from.subpath, from.curve) {
+                    from.t
+                } else {
+                    0.0
+                };
+                let t1 = if (index, curve_index) == (to.subpath, to.curve) {
+                    to.t
+                } else {
+                    1.0
+                };
+                if t0 >= t1 {
+                    continue;
+                }
+
+                let mut curve = subpath.curves[curve_index];
+                if t1 < 1.0 {
+                    curve = curve.split(t1).0;
+                }
+                if t0 > 0.0 {
+                    curve = curve.split(t0 / t1).1;
+                }
+                curves.push(curve);
+            }
+
+            let starts = index != from.subpath || (from.curve == 0 && from.t <= 0.0);
+            let ends = index != to.subpath || (to.curve == subpath.curves.len() - 1 && to.t >= 1.0);
+            if let Some(curve) = curves.first() {
+                subpaths.push(Subpath {
+                    start: curve.point(0.0),
+                    curves,
+                    closed: subpath.closed && starts && ends,
+                });
+            }
+        }
+        curve::to_path(&subpaths)
+    }
+
+    /// Finds the piece holding `distance`, clamped to the path, and the
+    /// parameter of the distance on its curve.
+    ///
+    /// Returns `None` if the path has no length.
+    fn locate(&self, distance: f64) -> Option<(&Curve, Vertex, Vertex, f64)> {
+        let length = self.length();
+        if length <= 0.0 {
+            return None;
+        }
+
+        let distance = distance.max(0.0).min(length);
+        // pieces without length are skipped
+        let mut index = self.vertices.partition_point(|v| v.distance <= distance);
+        if index == self.vertices.len() {
+            index = self.vertices.partition_point(|v| v.distance < length);
+        }
+        let (a, b) = (self.vertices[index - 1], self.vertices[index]);
+
+        let local = (distance - a.distance) / (b.distance - a.distance);
+        let t = a.t + (b.t - a.t) * local;
+        let curve = &self.subpaths[a.subpath].curves[a.curve];
+        Some((curve, a, b, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+    use std::str::FromStr;
+
+    fn measure(text: &str) -> PathMeasure {
+        PathMeasure::new(&Path::from_str(text).unwrap())
+    }
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let measure = measure("M 10 10 h 30 v 40");
+        assert_eq!(measure.length(), 70.0);
+        assert_eq!(measure.point_at_length(15.0), Some(Point::new(25.0, 10.0)));
+        assert_eq!(measure.tangent_at_length(15.0), Some(0.0));
+        assert_eq!(measure.point_at_length(100.0), Some(Point::new(40.0, 50.0)));
+        assert_eq!(measure.tangent_at_length(100.0), Some(90.0));
+        assert_eq!(measure.point_at_length(-5.0), Some(Point::new(10.0, 10.0)));
+    }
+
+    #[test]
+    fn circle() {
+        let measure = measure("M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z");
+        assert!(measure.is_closed());
+        assert_near(measure.length(), 20.0 * PI);
+
+        let point = measure.point_at_length(5.0 * PI).unwrap();
+        assert_near(point.x, 0.0);
+        assert_near(point.y, 10.0);
+    }
+
+    #[test]
+    fn cubic() {
+        // a quarter circle drawn as a cubic is close to its length
+        let k = 0.552_284_749_8 * 10.0;
+        let measure = measure(&format!("M 10 0 C 10 {} {} 10 0 10", k, k));
+        assert!((measure.length() - 5.0 * PI).abs() < 0.01);
+        assert_near(measure.tangent_at_length(0.0).unwrap(), 90.0);
+        assert_near(measure.tangent_at_length(100.0).unwrap(), 180.0);
+    }
+
+    #[test]
+    fn move_to() {
+        let measure = measure("M 0 0 H 10 M 100 100 H 110");
+        assert_eq!(measure.length(), 20.0);
+        assert_eq!(
+            measure.point_at_length(15.0),
+            Some(Point::new(105.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(measure("").is_empty());
+        assert_eq!(measure("").point_at_length(0.0), None);
+        assert_eq!(
+            measure("M 5 6").point_at_length(1.0),
+            Some(Point::new(5.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn split() {
+        let (before, after) = measure("M 0 0 H 10 V 10 M 20 20 H 30").split_at_length(15.0);
+        assert_eq!(before.to_string(), "M 0 0 L 10 0 L 10 5");
+        assert_eq!(after.to_string(), "M 10 5 L 10 10 M 20 20 L 30 20");
+
+        let (before, after) = measure("M 0 0 H 10 V 10 Z").split_at_length(5.0);
+        assert_eq!(before.to_string(), "M 0 0 L 5 0");
+        assert_eq!(after.to_string(), "M 5 0 L 10 0 L 10 10 L 0 0");
+    }
+
+    #[test]
+    fn split_curve() {
+        let (before, after) = measure("M 0 0 C 0 10 10 10 10 0").split_at_length(100.0);
+        assert_eq!(before.to_string(), "M 0 0 C 0 10 10 10 10 0");
+        assert_eq!(after.to_string(), "M 10 0 C 10 0 10 0 10 0");
+
+        let path = Path::from_str("M 0 0 C 0 10 10 10 10 0").unwrap();
+        let measure = PathMeasure::new(&path);
+        let (before, after) = measure.split_at_length(measure.length() / 2.0);
+        assert_near(PathMeasure::new(&before).length(), measure.length() / 2.0);
+        assert_near(PathMeasure::new(&after).length(), measure.length() / 2.0);
+    }
+
+    #[test]
+    fn segment() {
+        let open = measure("M 0 0 H 10 V 10 M 20 20 H 30");
+        assert_eq!(open.segment(5.0, 15.0).to_string(), "M 5 0 L 10 0 L 10 5");
+        assert_eq!(
+            open.segment(15.0, 25.0).to_string(),
+            "M 10 5 L 10 10 M 20 20 L 25 20"
+        );
+        assert_eq!(open.segment(-5.0, 100.0), open.segment(0.0, 30.0));
+        assert!(open.segment(8.0, 8.0).is_empty());
+
+        // a whole closed subpath stays closed
+        let closed = measure("M 0 0 H 10 V 10 H 0 Z M 20 20 H 30");
+        assert_eq!(
+            closed.segment(0.0, 41.0).to_string(),
+            "M 0 0 L 10 0 L 10 10 L 0 10 Z M 20 20 L 21 20"
+        );
+        let part = closed.segment(2.0, 6.0);
+        assert_near(PathMeasure::new(&part).length(), 4.0);
+    }
+}
@@ -1,7 +1,12 @@
 #![allow(clippy::too_many_arguments)]
 mod angle;
+mod boolean;
+mod curve;
 mod error;
+mod geometry;
 mod length;
+mod measure;
+mod morph;
 mod options;
 mod parser;
 mod segment;
@@ -9,17 +14,30 @@ mod stream;
 mod writer;
 
 pub use self::angle::*;
+pub use self::boolean::*;
 pub use self::error::*;
 pub use self::length::*;
+pub use self::measure::*;
+pub use self::morph::*;
 pub use self::options::*;
 pub use self::parser::*;
 pub use self::segment::*;
 pub use self::stream::*;
 
+use self::geometry::transform_segment_data;
+use crate::Transform;
 use float_cmp::ApproxEqUlps;
 use std::fmt;
 use std::io::Write;
 
+/// Returns a rotation by `degrees`, clockwise on a canvas where y points
+/// down, as the [`Transform`] re-exported from lyon is built from an angle
+/// type of its own.
+pub fn rotation(degrees: f64) -> Transform<f64> {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Transform::new(cos, sin, -sin, cos, 0.0, 0.0)
+}
+
 /// Representation of the SVG [path data].
 ///
 /// [path data]: https://www.w3.org/TR/SVG11/paths.html#PathData
@@ -79,7 +97,7 @@ impl Path {
             }
 
             if seg.is_relative() {
-                shift_segment_data(seg, offset_x, offset_y);
+                transform_segment_data(seg, &Transform::translation(offset_x, offset_y));
             }
 
             if seg.cmd() == PathCommand::MoveTo {
@@ -173,7 +191,7 @@ impl Path {
             }
 
             if seg.is_absolute() {
-                shift_segment_data(seg, -offset_x, -offset_y);
+                transform_segment_data(seg, &Transform::translation(-offset_x, -offset_y));
             }
 
             seg.set_absolute(false);
@@ -379,90 +397,6 @@ impl Path {
     }
 }
 
-fn shift_segment_data(d: &mut PathSegment, offset_x: f64, offset_y: f64) {
-    match *d {
-        PathSegment::MoveTo {
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::LineTo {
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::HorizontalLineTo { ref mut x, .. } => {
-            *x += offset_x;
-        }
-        PathSegment::VerticalLineTo { ref mut y, .. } => {
-            *y += offset_y;
-        }
-        PathSegment::CurveTo {
-            ref mut x1,
-            ref mut y1,
-            ref mut x2,
-            ref mut y2,
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x1 += offset_x;
-            *y1 += offset_y;
-            *x2 += offset_x;
-            *y2 += offset_y;
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::SmoothCurveTo {
-            ref mut x2,
-            ref mut y2,
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x2 += offset_x;
-            *y2 += offset_y;
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::Quadratic {
-            ref mut x1,
-            ref mut y1,
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x1 += offset_x;
-            *y1 += offset_y;
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::SmoothQuadratic {
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::EllipticalArc {
-            ref mut x,
-            ref mut y,
-            ..
-        } => {
-            *x += offset_x;
-            *y += offset_y;
-        }
-        PathSegment::ClosePath { .. } => {}
-    }
-}
-
 impl From<Vec<PathSegment>> for Path {
     #[inline]
     fn from(v: Vec<PathSegment>) -> Self {
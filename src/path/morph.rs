@@ -0,0 +1,339 @@
+use super::curve::{self, Curve};
+use super::Path;
+use crate::interpolate::Interpolate;
+
+/// A chain of cubic curves, the end of each curve starts the next one.
+#[derive(Clone, Debug, PartialEq)]
+struct Shape {
+    /// The start point followed by the controls and the end of each curve.
+    points: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+impl Shape {
+    fn curves(&self) -> usize {
+        (self.points.len() - 1) / 3
+    }
+
+    fn curve(&self, index: usize) -> Curve {
+        let p = &self.points[index * 3..];
+        Curve::Cubic {
+            p0: p[0],
+            p1: p[1],
+            p2: p[2],
+            p3: p[3],
+        }
+    }
+
+    /// Returns a shape collapsed into `point`.
+    fn point(point: (f64, f64), curves: usize, closed: bool) -> Shape {
+        Shape {
+            points: vec![point; curves * 3 + 1],
+            closed,
+        }
+    }
+
+    fn center(&self) -> (f64, f64) {
+        let first = self.points[0];
+        let (min, max) = self.points.iter().fold((first, first), |(min, max), p| {
+            (
+                (min.0.min(p.0), min.1.min(p.1)),
+                (max.0.max(p.0), max.1.max(p.1)),
+            )
+        });
+        ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0)
+    }
+
+    /// Splits the longest curves in halves until there are `count` curves.
+    fn subdivide(&mut self, count: usize) {
+        while self.curves() < count {
+            let longest = (0..self.curves())
+                .map(|index| {
+                    let p = &self.points[index * 3..index * 3 + 4];
+                    let hull: f64 = p
+                        .windows(2)
+                        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+                        .sum();
+                    (index, hull)
+                })
+                .fold((0, -1.0), |a, b| if b.1 > a.1 { b } else { a })
+                .0;
+
+            let (head, tail) = self.curve(longest).split(0.5);
+            let mut points = Vec::with_capacity(6);
+            for curve in [head, tail].iter() {
+                if let Curve::Cubic { p1, p2, p3, .. } = *curve {
+                    points.extend_from_slice(&[p1, p2, p3]);
+                }
+            }
+            let at = longest * 3 + 1;
+            self.points.splice(at..at + 3, points);
+        }
+    }
+
+    fn reversed(&self) -> Shape {
+        Shape {
+            points: self.points.iter().rev().copied().collect(),
+            closed: self.closed,
+        }
+    }
+
+    /// Starts a closed shape at the curve `index`.
+    fn rotated(&self, index: usize) -> Shape {
+        let mut points = self.points[..self.points.len() - 1].to_vec();
+        points.rotate_left(index * 3);
+        points.push(points[0]);
+        Shape {
+            points,
+            closed: self.closed,
+        }
+    }
+
+    /// Returns the sum of the squared distances between the curve ends of
+    /// two shapes with as many curves, `other` starting at the curve
+    /// `offset`.
+    fn distance(&self, other: &Shape, offset: usize) -> f64 {
+        let count = self.curves();
+        (0..count)
+            .map(|i| {
+                let a = self.points[i * 3];
+                let b = other.points[((i + offset) % count) * 3];
+                (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+            })
+            .sum()
+    }
+
+    fn to_path(&self, path: &mut Path) {
+        path.push_move_to(self.points[0].0, self.points[0].1);
+        for p in self.points[1..].chunks(3) {
+            path.push_curve_to(p[0].0, p[0].1, p[1].0, p[1].1, p[2].0, p[2].1);
+        }
+        if self.closed {
+            path.push_close_path();
+        }
+    }
+}
+
+/// Splits `path` into shapes of absolute cubic curves.
+fn shapes(path: &Path) -> Vec<Shape> {
+    curve::subpaths(path)
+        .into_iter()
+        .map(|subpath| {
+            let mut points = vec![subpath.start];
+            for cubic in subpath.curves.into_iter().flat_map(Curve::to_cubics) {
+                if let Curve::Cubic { p1, p2, p3, .. } = cubic {
+                    points.extend_from_slice(&[p1, p2, p3]);
+                }
+            }
+            if points.len() == 1 {
+                // a lone MoveTo
+                points.extend_from_slice(&[subpath.start; 3]);
+            }
+            Shape {
+                points,
+                closed: subpath.closed,
+            }
+        })
+        .collect()
+}
+
+/// Adds the subpaths missing from `shapes` as points at the center of their
+/// counterparts in `other`.
+fn grow(shapes: &mut Vec<Shape>, other: &[Shape]) {
+    let missing = other.iter().skip(shapes.len());
+    shapes.extend(missing.map(|shape| Shape::point(shape.center(), shape.curves(), shape.closed)));
+}
+
+/// Turns `to` around and starts it at the curve closest to the start of
+/// `from`, both closed and with as many curves.
+fn align(from: &Shape, to: &Shape) -> Shape {
+    let reversed = to.reversed();
+    let count = from.curves();
+
+    let mut best = (f64::INFINITY, false, 0);
+    for (is_reversed, shape) in [(false, to), (true, &reversed)].iter() {
+        for offset in 0..count {
+            let distance = from.distance(shape, offset);
+            if distance < best.0 {
+                best = (distance, *is_reversed, offset);
+            }
+        }
+    }
+
+    let (_, is_reversed, offset) = best;
+    if is_reversed {
+        reversed.rotated(offset)
+    } else {
+        to.rotated(offset)
+    }
+}
+
+/// A morph between two arbitrary paths.
+///
+/// Both paths are rewritten with absolute cubic curves only, subpaths
+/// missing on one side grow from the center of their counterpart and the
+/// curves of each pair of subpaths are split until they match. Closed
+/// subpaths are turned around and started where they are the closest to each
+/// other, so shapes do not twist on the way.
+///
+/// The rewritten paths are compatible, so they can be tweened as any path
+/// as well.
+///
+/// # Example
+///
+/// ```
+/// use animate::path::{Path, PathMorph};
+///
+/// let play: Path = "M 0 0 L 12 6 L 0 12 Z".parse().unwrap();
+/// let stop: Path = "M 0 0 H 12 V 12 H 0 Z".parse().unwrap();
+///
+/// let morph = PathMorph::new(&play, &stop);
+/// assert_eq!(morph.at(0.0).bounding_box(), play.bounding_box());
+/// assert_eq!(morph.at(1.0).bounding_box(), stop.bounding_box());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathMorph {
+    start: Path,
+    end: Path,
+}
+
+impl PathMorph {
+    /// Prepares the morph from `start` to `end`.
+    pub fn new(start: &Path, end: &Path) -> Self {
+        let mut from = shapes(start);
+        let mut to = shapes(end);
+
+        grow(&mut from, &to);
+        grow(&mut to, &from);
+
+        let mut start = Path::new();
+        let mut end = Path::new();
+        for (from, to) in from.iter_mut().zip(to.iter_mut()) {
+            let count = from.curves().max(to.curves());
+            from.subdivide(count);
+            to.subdivide(count);
+
+            // a subpath is closed on both sides, or left open on both
+            let closed = from.closed && to.closed;
+            from.closed = closed;
+            to.closed = closed;
+            if closed {
+                *to = align(from, to);
+            }
+
+            from.to_path(&mut start);
+            to.to_path(&mut end);
+        }
+
+        PathMorph { start, end }
+    }
+
+    /// Returns the start path rewritten to match the end one.
+    pub fn start(&self) -> &Path {
+        &self.start
+    }
+
+    /// Returns the end path rewritten to match the start one.
+    pub fn end(&self) -> &Path {
+        &self.end
+    }
+
+    /// Returns the path at `t` between the start at `0.0` and the end at
+    /// `1.0`.
+    pub fn at(&self, t: f64) -> Path {
+        self.start.interpolate(&self.end, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolate::paths_compatible;
+    use crate::path::PathCommand;
+    use std::str::FromStr;
+
+    fn morph(from: &str, to: &str) -> PathMorph {
+        PathMorph::new(&Path::from_str(from).unwrap(), &Path::from_str(to).unwrap())
+    }
+
+    fn ends(path: &Path) -> Vec<(f64, f64)> {
+        shapes(path)
+            .iter()
+            .flat_map(|shape| shape.points.iter().step_by(3).copied().collect::<Vec<_>>())
+            .collect()
+    }
+
+    #[test]
+    fn compatible() {
+        let morph = morph(
+            "M 0 0 L 12 6 L 0 12 Z",
+            "M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z",
+        );
+        assert!(paths_compatible(morph.start(), morph.end()));
+        assert_eq!(morph.start().len(), morph.end().len());
+        assert!(morph.start().iter().all(|seg| seg.is_absolute()
+            && [
+                PathCommand::MoveTo,
+                PathCommand::CurveTo,
+                PathCommand::ClosePath
+            ]
+            .contains(&seg.cmd())));
+    }
+
+    #[test]
+    fn keeps_geometry() {
+        let morph = morph("M 0 0 H 10 V 10 Z", "M 0 0 Q 10 0 10 10 T 20 20 L 0 20 Z");
+        assert_eq!(
+            morph.at(0.0).bounding_box(),
+            Path::from_str("M 0 0 H 10 V 10 Z").unwrap().bounding_box()
+        );
+        assert_eq!(morph.at(1.0).length(), morph.end().length());
+        assert!((morph.start().length() - (20.0 + 200f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn play_pause() {
+        let morph = morph(
+            "M 0 0 L 12 6 L 0 12 Z",
+            "M 0 0 H 4 V 12 H 0 Z M 8 0 H 12 V 12 H 8 Z",
+        );
+        assert_eq!(
+            morph
+                .start()
+                .iter()
+                .filter(|s| s.cmd() == PathCommand::MoveTo)
+                .count(),
+            2
+        );
+        // the missing bar grows from the center of the other one
+        assert_eq!(ends(morph.start())[5], (10.0, 6.0));
+        assert!(paths_compatible(morph.start(), morph.end()));
+    }
+
+    #[test]
+    fn aligns_start_points() {
+        // the same square, drawn from another corner and the other way
+        let morph = morph("M 0 0 H 10 V 10 H 0 Z", "M 10 10 V 0 H 0 V 10 Z");
+        assert_eq!(ends(morph.start()), ends(morph.end()));
+    }
+
+    #[test]
+    fn open_and_closed() {
+        let morph = morph("M 0 0 L 10 0", "M 0 0 H 10 V 10 Z");
+        assert!(morph
+            .start()
+            .iter()
+            .all(|seg| seg.cmd() != PathCommand::ClosePath));
+        assert!(paths_compatible(morph.start(), morph.end()));
+    }
+
+    #[test]
+    fn empty() {
+        let morph = morph("", "M 5 5 H 10");
+        assert_eq!(ends(morph.start()), vec![(7.5, 5.0); 2]);
+        assert_eq!(morph.at(1.0), *morph.end());
+        assert!(PathMorph::new(&Path::new(), &Path::new())
+            .at(0.5)
+            .is_empty());
+    }
+}
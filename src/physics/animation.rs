@@ -0,0 +1,354 @@
+use super::{FixedStep, Simulation};
+use crate::{
+    interpolate::Interpolate,
+    tween::{fire_callback, Group, Lifecycle, Playable},
+};
+use std::{
+    cell::{RefCell, RefMut},
+    fmt,
+    rc::Rc,
+};
+
+type Callback = Box<dyn FnMut(f64)>;
+
+struct AnimationState<S, T> {
+    lifecycle: Lifecycle,
+    simulation: S,
+    object: Rc<RefCell<T>>,
+    /// Turns the simulated value into the value of the target.
+    map: Box<dyn Fn(f64) -> T>,
+    fixed_step: Option<FixedStep>,
+    last_time: f64,
+    on_update: Option<Callback>,
+    on_complete: Option<Callback>,
+}
+
+/// Plays a [`Simulation`] on a shared target until it settles.
+///
+/// Like a tween, it is advanced with explicit timestamps in milliseconds, it
+/// can join a [`Group`] and be chained after a tween. The simulation itself
+/// is stepped in seconds, by the time between two updates or in the fixed
+/// steps set with [`PhysicsAnimation::fixed_step`].
+///
+/// The target is the simulated number itself, or any value which can be
+/// interpolated with [`PhysicsAnimation::with_interpolated_target`].
+///
+/// `PhysicsAnimation` is a cheap handle, clones refer to the same animation.
+///
+/// # Example
+///
+/// ```
+/// use animate::physics::{PhysicsAnimation, Spring};
+///
+/// let animation = PhysicsAnimation::new(Spring::with_response(0.3, 1.0).from(0.0).to(10.0));
+/// animation.start(0.0);
+///
+/// let mut time = 0.0;
+/// while animation.update(time) {
+///     time += 16.0;
+/// }
+/// assert_eq!(animation.get_value(), 10.0);
+/// ```
+pub struct PhysicsAnimation<S, T = f64> {
+    inner: Rc<RefCell<AnimationState<S, T>>>,
+}
+
+impl<S, T> Clone for PhysicsAnimation<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S> PhysicsAnimation<S>
+where
+    S: Simulation + 'static,
+{
+    /// Constructs a new animation which owns its target value.
+    pub fn new(simulation: S) -> Self {
+        let value = simulation.value();
+        Self::with_target(simulation, Rc::new(RefCell::new(value)))
+    }
+
+    /// Constructs a new animation which drives a shared `target`.
+    pub fn with_target(simulation: S, target: Rc<RefCell<f64>>) -> Self {
+        Self::with_map(simulation, target, Box::new(|value| value))
+    }
+}
+
+impl<S, T> PhysicsAnimation<S, T>
+where
+    S: Simulation + 'static,
+    T: Clone + 'static,
+{
+    /// Constructs a new animation which drives a shared `target` from
+    /// `from` to `to` as the simulated value goes from 0 to 1.
+    ///
+    /// Simulated values out of `0..=1`, like the overshoot of a spring, are
+    /// extrapolated.
+    pub fn with_interpolated_target(simulation: S, target: Rc<RefCell<T>>, from: T, to: T) -> Self
+    where
+        T: Interpolate,
+    {
+        Self::with_map(
+            simulation,
+            target,
+            Box::new(move |value| from.interpolate(&to, value)),
+        )
+    }
+
+    fn with_map(simulation: S, target: Rc<RefCell<T>>, map: Box<dyn Fn(f64) -> T>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(AnimationState {
+                lifecycle: Lifecycle::new(),
+                simulation,
+                object: target,
+                map,
+                fixed_step: None,
+                last_time: 0.0,
+                on_update: None,
+                on_complete: None,
+            })),
+        }
+    }
+
+    /// Returns the unique id of the animation.
+    pub fn get_id(&self) -> usize {
+        self.inner.borrow().lifecycle.get_id()
+    }
+
+    /// Returns the shared target driven by the animation.
+    pub fn get_target(&self) -> Rc<RefCell<T>> {
+        self.inner.borrow().object.clone()
+    }
+
+    /// Returns a copy of the current value of the target.
+    pub fn get_value(&self) -> T {
+        let target = self.get_target();
+        let value = target.borrow().clone();
+        value
+    }
+
+    /// Gives access to the simulation, for example to move the target of a
+    /// spring while it plays.
+    pub fn simulation(&self) -> RefMut<'_, S> {
+        RefMut::map(self.inner.borrow_mut(), |state| &mut state.simulation)
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.inner.borrow().lifecycle.is_playing()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.borrow().lifecycle.is_paused()
+    }
+
+    /// Steps the simulation in fixed steps of `step` milliseconds instead of
+    /// the time between updates.
+    pub fn fixed_step(&self, step: f64) -> &Self {
+        self.inner.borrow_mut().fixed_step = Some(FixedStep::new(step / 1000.0));
+        self
+    }
+
+    /// Sets the group the animation joins when it is started.
+    pub fn group(&self, group: &Group) -> &Self {
+        self.inner.borrow_mut().lifecycle.set_group(group);
+        self
+    }
+
+    /// Sets the callback fired on every update with the simulated value.
+    pub fn on_update<F: FnMut(f64) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired with the simulated value when the simulation
+    /// settles.
+    pub fn on_complete<F: FnMut(f64) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Starts the animation at `time` in milliseconds.
+    pub fn start(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if !state.lifecycle.start() {
+            return self;
+        }
+
+        state.last_time = time;
+        if let Some(ref mut fixed_step) = state.fixed_step {
+            fixed_step.reset();
+        }
+
+        let value = (state.map)(state.simulation.value());
+        *state.object.borrow_mut() = value;
+
+        let group = state.lifecycle.get_group();
+        drop(state);
+
+        if let Some(group) = group {
+            group.add(self);
+        }
+        self
+    }
+
+    /// Stops the animation, the simulation keeps its state.
+    pub fn stop(&self) -> &Self {
+        self.inner.borrow_mut().lifecycle.stop();
+        self
+    }
+
+    /// Jumps to the rest state of the simulation and completes.
+    pub fn end(&self) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if !state.lifecycle.is_playing() {
+            return self;
+        }
+
+        state.simulation.settle();
+        drop(state);
+
+        self.finish();
+        self
+    }
+
+    /// Pauses the animation at `time` in milliseconds.
+    pub fn pause(&self, time: f64) -> &Self {
+        self.inner.borrow_mut().lifecycle.pause(time);
+        self
+    }
+
+    /// Resumes the animation at `time` in milliseconds.
+    pub fn resume(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if let Some(paused) = state.lifecycle.resume(time) {
+            state.last_time += paused;
+        }
+        drop(state);
+        self
+    }
+
+    /// Advances the animation to `time` in milliseconds.
+    ///
+    /// Returns `true` while the simulation has not settled.
+    pub fn update(&self, time: f64) -> bool {
+        let mut state = self.inner.borrow_mut();
+        if !state.lifecycle.is_playing() {
+            return false;
+        }
+        if state.lifecycle.is_paused() {
+            return true;
+        }
+
+        let dt = (time - state.last_time) / 1000.0;
+        if dt > 0.0 {
+            state.last_time = time;
+            let AnimationState {
+                ref mut simulation,
+                ref mut fixed_step,
+                ..
+            } = *state;
+            match fixed_step {
+                Some(fixed_step) => {
+                    fixed_step.advance(simulation, dt);
+                }
+                None => simulation.step(dt),
+            }
+        }
+
+        let settled = state.simulation.is_settled();
+        if settled {
+            state.simulation.settle();
+        }
+        drop(state);
+
+        if settled {
+            self.finish();
+            return false;
+        }
+
+        self.write_value();
+        true
+    }
+
+    /// Writes the simulated value into the target and fires `on_update`.
+    fn write_value(&self) -> f64 {
+        let state = self.inner.borrow();
+        let value = state.simulation.value();
+        *state.object.borrow_mut() = (state.map)(value);
+        drop(state);
+
+        fire_callback(
+            &self.inner,
+            |state| &mut state.on_update,
+            |callback| callback(value),
+        );
+        value
+    }
+
+    fn finish(&self) {
+        let value = self.write_value();
+        self.stop();
+        fire_callback(
+            &self.inner,
+            |state| &mut state.on_complete,
+            |callback| callback(value),
+        );
+    }
+}
+
+impl<S, T> Playable for PhysicsAnimation<S, T>
+where
+    S: Simulation + 'static,
+    T: Clone + 'static,
+{
+    fn get_id(&self) -> usize {
+        PhysicsAnimation::get_id(self)
+    }
+
+    fn is_playing(&self) -> bool {
+        PhysicsAnimation::is_playing(self)
+    }
+
+    fn is_paused(&self) -> bool {
+        PhysicsAnimation::is_paused(self)
+    }
+
+    fn start(&self, time: f64) {
+        PhysicsAnimation::start(self, time);
+    }
+
+    fn stop(&self) {
+        PhysicsAnimation::stop(self);
+    }
+
+    fn end(&self) {
+        PhysicsAnimation::end(self);
+    }
+
+    fn pause(&self, time: f64) {
+        PhysicsAnimation::pause(self, time);
+    }
+
+    fn resume(&self, time: f64) {
+        PhysicsAnimation::resume(self, time);
+    }
+
+    fn update(&self, time: f64) -> bool {
+        PhysicsAnimation::update(self, time)
+    }
+}
+
+impl<S: fmt::Debug, T> fmt::Debug for PhysicsAnimation<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.borrow();
+        f.debug_struct("PhysicsAnimation")
+            .field("id", &state.lifecycle.get_id())
+            .field("simulation", &state.simulation)
+            .field("is_playing", &state.lifecycle.is_playing())
+            .field("is_paused", &state.lifecycle.is_paused())
+            .finish()
+    }
+}
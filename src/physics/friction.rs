@@ -0,0 +1,98 @@
+use super::Simulation;
+
+/// A value sliding with friction, like a list after a fling.
+///
+/// The velocity decays exponentially with the drag, so the value comes to
+/// rest at [`Friction::final_value`], which is known from the start. This is
+/// handy to pick a snap point before the motion ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Friction {
+    drag: f64,
+    value: f64,
+    velocity: f64,
+    rest_velocity: f64,
+}
+
+impl Friction {
+    /// Constructs a new friction simulation with the `drag` coefficient per
+    /// second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `drag` is not positive.
+    pub fn new(drag: f64) -> Self {
+        assert!(drag > 0.0, "drag must be positive");
+
+        Friction {
+            drag,
+            value: 0.0,
+            velocity: 0.0,
+            rest_velocity: 0.01,
+        }
+    }
+
+    /// Constructs a new friction simulation keeping `rate` of the velocity
+    /// each millisecond, as the scroll views deceleration rates do.
+    ///
+    /// A rate of 0.998 is a normal scroll, 0.99 a fast stop.
+    pub fn with_deceleration_rate(rate: f64) -> Self {
+        Friction::new(-rate.ln() * 1000.0)
+    }
+
+    /// Sets the current value.
+    pub fn from(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the current velocity in units per second.
+    pub fn with_velocity(mut self, velocity: f64) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Sets how slow the value has to be to be settled, by default 0.01.
+    pub fn rest_threshold(mut self, velocity: f64) -> Self {
+        self.rest_velocity = velocity;
+        self
+    }
+
+    pub fn drag(&self) -> f64 {
+        self.drag
+    }
+
+    /// Returns the value the simulation comes to rest at.
+    pub fn final_value(&self) -> f64 {
+        self.value + self.velocity / self.drag
+    }
+
+    /// Returns the velocity needed to come to rest at `value`.
+    pub fn velocity_to_reach(&self, value: f64) -> f64 {
+        (value - self.value) * self.drag
+    }
+}
+
+impl Simulation for Friction {
+    fn step(&mut self, dt: f64) {
+        let decay = (-self.drag * dt).exp();
+        self.value += self.velocity / self.drag * (1.0 - decay);
+        self.velocity *= decay;
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    fn is_settled(&self) -> bool {
+        self.velocity.abs() < self.rest_velocity
+    }
+
+    fn settle(&mut self) {
+        self.value = self.final_value();
+        self.velocity = 0.0;
+    }
+}
@@ -0,0 +1,175 @@
+use super::Simulation;
+
+/// The most bounces computed in one step. A longer step bounces forever in
+/// whole flights of the same length, or rests on the floor once the bounces
+/// lose speed.
+const MAX_BOUNCES: usize = 64;
+
+/// A value falling with a constant acceleration, optionally bouncing on a
+/// floor.
+///
+/// The floor is hit when the value passes it in the direction of the
+/// acceleration, so a positive acceleration falls down in screen
+/// coordinates. Each bounce keeps `restitution` of the speed, the value rests
+/// on the floor once a bounce is slower than the rest threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gravity {
+    acceleration: f64,
+    value: f64,
+    velocity: f64,
+    floor: Option<f64>,
+    restitution: f64,
+    rest_velocity: f64,
+    resting: bool,
+}
+
+impl Gravity {
+    /// Constructs a new gravity simulation with the `acceleration` in units
+    /// per second squared.
+    pub fn new(acceleration: f64) -> Self {
+        Gravity {
+            acceleration,
+            value: 0.0,
+            velocity: 0.0,
+            floor: None,
+            restitution: 0.0,
+            rest_velocity: 0.01,
+            resting: false,
+        }
+    }
+
+    /// Sets the current value.
+    pub fn from(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the current velocity in units per second.
+    pub fn with_velocity(mut self, velocity: f64) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Sets the floor and the part of the speed kept by each bounce, from
+    /// 0 to stop dead up to 1 to bounce forever.
+    pub fn floor(mut self, floor: f64, restitution: f64) -> Self {
+        self.floor = Some(floor);
+        self.restitution = restitution.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the slowest bounce, by default 0.01 units per second.
+    pub fn rest_threshold(mut self, velocity: f64) -> Self {
+        self.rest_velocity = velocity;
+        self
+    }
+
+    pub fn acceleration(&self) -> f64 {
+        self.acceleration
+    }
+
+    /// Returns the time in `0..=dt` when the value hits `floor`.
+    fn time_to_floor(&self, floor: f64, dt: f64) -> Option<f64> {
+        let direction = if self.acceleration != 0.0 {
+            self.acceleration.signum()
+        } else {
+            self.velocity.signum()
+        };
+
+        let end = self.value + self.velocity * dt + 0.5 * self.acceleration * dt * dt;
+        if (end - floor) * direction < 0.0 {
+            return None;
+        }
+
+        // 0.5 * a * t^2 + v * t + (x - floor) = 0
+        let (a, b, c) = (0.5 * self.acceleration, self.velocity, self.value - floor);
+        if a == 0.0 {
+            return if b == 0.0 { None } else { Some(-c / b) };
+        }
+
+        let discriminant = (b * b - 4.0 * a * c).max(0.0).sqrt();
+        let t1 = (-b - discriminant) / (2.0 * a);
+        let t2 = (-b + discriminant) / (2.0 * a);
+        // right after a bounce the value leaves the floor at 0
+        let towards = b * direction > 0.0;
+        [t1.min(t2), t1.max(t2)]
+            .iter()
+            .copied()
+            .find(|t| (*t > 0.0 || (*t == 0.0 && towards)) && *t <= dt)
+    }
+}
+
+impl Simulation for Gravity {
+    fn step(&mut self, dt: f64) {
+        if self.resting {
+            return;
+        }
+
+        let mut remaining = dt;
+        let mut bounces = 0;
+        // the value bounces until the time runs out or it comes to rest
+        while remaining > 0.0 {
+            let hit = self
+                .floor
+                .and_then(|floor| Some((floor, self.time_to_floor(floor, remaining)?)));
+
+            match hit {
+                Some((floor, t)) => {
+                    let impact = self.velocity + self.acceleration * t;
+                    self.value = floor;
+                    self.velocity = -impact * self.restitution;
+                    remaining -= t;
+
+                    // bounces shorter than the precision of the clock would
+                    // never use up the time, a slower one rests right away
+                    let flight = 2.0 * self.velocity.abs() / self.acceleration.abs();
+                    if self.velocity.abs() <= self.rest_velocity || remaining - flight == remaining
+                    {
+                        self.velocity = 0.0;
+                        self.resting = true;
+                        return;
+                    }
+
+                    bounces += 1;
+                    if bounces == MAX_BOUNCES {
+                        if self.restitution < 1.0 {
+                            self.velocity = 0.0;
+                            self.resting = true;
+                            return;
+                        }
+                        // every flight lasts as long and ends on the floor
+                        remaining %= flight;
+                    }
+                }
+                None => {
+                    self.value +=
+                        self.velocity * remaining + 0.5 * self.acceleration * remaining * remaining;
+                    self.velocity += self.acceleration * remaining;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    fn is_settled(&self) -> bool {
+        self.resting
+    }
+
+    /// Lands on the floor, a simulation without a floor never settles and is
+    /// left as it is.
+    fn settle(&mut self) {
+        if let Some(floor) = self.floor {
+            self.value = floor;
+            self.velocity = 0.0;
+            self.resting = true;
+        }
+    }
+}
@@ -1 +1,28 @@
+//! Physics based motion.
+//!
+//! A [`Simulation`] moves a value with a velocity, like a [`Spring`] pulling
+//! it to a target, [`Friction`] slowing a fling down or [`Gravity`] dropping
+//! it on a floor. Simulations are stepped by a time delta in seconds, either
+//! directly or in fixed steps with [`FixedStep`], and tell when they have
+//! settled.
+//!
+//! A [`PhysicsAnimation`] plays a simulation on a shared target with the same
+//! millisecond timestamps as tweens, and joins a `tween::Group`.
 
+mod animation;
+pub use animation::*;
+
+mod friction;
+pub use friction::*;
+
+mod gravity;
+pub use gravity::*;
+
+mod simulation;
+pub use simulation::*;
+
+mod spring;
+pub use spring::*;
+
+#[cfg(test)]
+mod tests;
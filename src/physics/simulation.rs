@@ -0,0 +1,73 @@
+/// A one-dimensional physics simulation.
+///
+/// Time deltas are in seconds, so the constants of the simulations keep
+/// their usual physical meaning.
+pub trait Simulation {
+    /// Advances the simulation by `dt` seconds.
+    fn step(&mut self, dt: f64);
+
+    /// Returns the current value.
+    fn value(&self) -> f64;
+
+    /// Returns the current velocity in units per second.
+    fn velocity(&self) -> f64;
+
+    /// Returns `true` once the simulation has come to rest.
+    fn is_settled(&self) -> bool;
+
+    /// Jumps to the state the simulation comes to rest in.
+    fn settle(&mut self);
+}
+
+/// Steps a simulation with a fixed time delta.
+///
+/// Frame times are accumulated and the simulation is advanced in whole
+/// steps, so it behaves the same at any frame rate. The time left over is
+/// carried to the next frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedStep {
+    step: f64,
+    accumulator: f64,
+}
+
+impl FixedStep {
+    /// Constructs a new fixed stepper with the `step` in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive.
+    pub fn new(step: f64) -> Self {
+        assert!(step > 0.0, "step must be positive");
+        FixedStep {
+            step,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Returns the step in seconds.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Advances `simulation` by the whole steps fitting in `dt` seconds and
+    /// the time carried from the previous call.
+    ///
+    /// Returns the number of steps taken.
+    pub fn advance<S: Simulation + ?Sized>(&mut self, simulation: &mut S, dt: f64) -> usize {
+        self.accumulator += dt;
+
+        let mut steps = 0;
+        // tolerate the rounding of the accumulated frame times
+        while self.accumulator >= self.step * (1.0 - 1e-9) {
+            simulation.step(self.step);
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Drops the time carried to the next call.
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
@@ -0,0 +1,176 @@
+use super::Simulation;
+use std::f64::consts::PI;
+
+/// A damped spring pulling a value to its target.
+///
+/// The motion is solved exactly, so any time delta gives the same result.
+///
+/// # Example
+///
+/// ```
+/// use animate::physics::{Simulation, Spring};
+///
+/// let mut spring = Spring::with_response(0.5, 1.0).from(0.0).to(100.0);
+/// while !spring.is_settled() {
+///     spring.step(1.0 / 60.0);
+/// }
+/// assert!((spring.value() - 100.0).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    mass: f64,
+    stiffness: f64,
+    damping: f64,
+    value: f64,
+    velocity: f64,
+    target: f64,
+    rest_delta: f64,
+    rest_velocity: f64,
+}
+
+impl Spring {
+    /// Constructs a new spring from its physical properties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mass` or `stiffness` are not positive or `damping` is
+    /// negative.
+    pub fn new(mass: f64, stiffness: f64, damping: f64) -> Self {
+        assert!(mass > 0.0, "mass must be positive");
+        assert!(stiffness > 0.0, "stiffness must be positive");
+        assert!(damping >= 0.0, "damping must not be negative");
+
+        Spring {
+            mass,
+            stiffness,
+            damping,
+            value: 0.0,
+            velocity: 0.0,
+            target: 1.0,
+            rest_delta: 0.01,
+            rest_velocity: 0.01,
+        }
+    }
+
+    /// Constructs a new spring of a unit mass from its `response`, the period
+    /// in seconds of the undamped oscillation, and its `damping_ratio`.
+    ///
+    /// A damping ratio of 1 is critically damped and does not overshoot, the
+    /// lower it is, the more the spring bounces.
+    pub fn with_response(response: f64, damping_ratio: f64) -> Self {
+        let stiffness = (2.0 * PI / response).powi(2);
+        let damping = 4.0 * PI * damping_ratio / response;
+        Spring::new(1.0, stiffness, damping)
+    }
+
+    /// Sets the current value.
+    pub fn from(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the target value.
+    pub fn to(mut self, target: f64) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the current velocity in units per second.
+    pub fn with_velocity(mut self, velocity: f64) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Sets how close to the target and how slow the spring has to be to be
+    /// settled, by default both are 0.01.
+    pub fn rest_threshold(mut self, delta: f64, velocity: f64) -> Self {
+        self.rest_delta = delta;
+        self.rest_velocity = velocity;
+        self
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    pub fn stiffness(&self) -> f64 {
+        self.stiffness
+    }
+
+    pub fn damping(&self) -> f64 {
+        self.damping
+    }
+
+    /// Returns the damping ratio, 1 for a critically damped spring.
+    pub fn damping_ratio(&self) -> f64 {
+        self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Moves the target, keeping the current value and velocity.
+    pub fn set_target(&mut self, target: f64) {
+        self.target = target;
+    }
+
+    /// Sets the current velocity in units per second.
+    pub fn set_velocity(&mut self, velocity: f64) {
+        self.velocity = velocity;
+    }
+}
+
+impl Simulation for Spring {
+    fn step(&mut self, dt: f64) {
+        let omega = (self.stiffness / self.mass).sqrt();
+        let zeta = self.damping_ratio();
+        let x0 = self.value - self.target;
+        let v0 = self.velocity;
+
+        let (x, v) = if (zeta - 1.0).abs() < 1e-6 {
+            let b = v0 + omega * x0;
+            let decay = (-omega * dt).exp();
+            (decay * (x0 + b * dt), decay * (v0 - omega * b * dt))
+        } else if zeta < 1.0 {
+            let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+            let b = (v0 + zeta * omega * x0) / omega_d;
+            let decay = (-zeta * omega * dt).exp();
+            let (sin, cos) = (omega_d * dt).sin_cos();
+            let x = decay * (x0 * cos + b * sin);
+            let v = decay
+                * ((b * omega_d - zeta * omega * x0) * cos
+                    - (x0 * omega_d + zeta * omega * b) * sin);
+            (x, v)
+        } else {
+            let root = (zeta * zeta - 1.0).sqrt();
+            let r1 = -omega * (zeta - root);
+            let r2 = -omega * (zeta + root);
+            let c2 = (v0 - r1 * x0) / (r2 - r1);
+            let c1 = x0 - c2;
+            let (e1, e2) = ((r1 * dt).exp(), (r2 * dt).exp());
+            (c1 * e1 + c2 * e2, r1 * c1 * e1 + r2 * c2 * e2)
+        };
+
+        self.value = self.target + x;
+        self.velocity = v;
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    fn is_settled(&self) -> bool {
+        (self.value - self.target).abs() < self.rest_delta
+            && self.velocity.abs() < self.rest_velocity
+    }
+
+    fn settle(&mut self) {
+        self.value = self.target;
+        self.velocity = 0.0;
+    }
+}
@@ -0,0 +1,306 @@
+#![allow(clippy::float_cmp)]
+
+use super::*;
+use crate::{
+    tween::{Group, Tween},
+    Point,
+};
+use std::{cell::RefCell, rc::Rc};
+
+fn run<S: Simulation>(simulation: &mut S, dt: f64, max_steps: usize) -> usize {
+    let mut steps = 0;
+    while !simulation.is_settled() && steps < max_steps {
+        simulation.step(dt);
+        steps += 1;
+    }
+    steps
+}
+
+#[test]
+fn spring_settles_at_target() {
+    for damping_ratio in [0.2, 1.0, 2.0].iter() {
+        let mut spring = Spring::with_response(0.5, *damping_ratio)
+            .from(0.0)
+            .to(100.0);
+        let steps = run(&mut spring, 1.0 / 60.0, 10_000);
+
+        assert!(steps < 10_000, "ratio {} did not settle", damping_ratio);
+        assert!((spring.value() - 100.0).abs() < 0.01);
+    }
+}
+
+#[test]
+fn spring_overshoots_when_underdamped() {
+    let mut bouncy = Spring::with_response(0.5, 0.3).from(0.0).to(1.0);
+    let mut critical = Spring::with_response(0.5, 1.0).from(0.0).to(1.0);
+
+    let mut bouncy_max = 0.0_f64;
+    let mut critical_max = 0.0_f64;
+    for _ in 0..120 {
+        bouncy.step(1.0 / 60.0);
+        critical.step(1.0 / 60.0);
+        bouncy_max = bouncy_max.max(bouncy.value());
+        critical_max = critical_max.max(critical.value());
+    }
+
+    assert!(bouncy_max > 1.2);
+    assert!(critical_max <= 1.0 + 1e-9);
+}
+
+#[test]
+fn spring_any_dt() {
+    let mut fine = Spring::new(1.0, 170.0, 26.0)
+        .from(0.0)
+        .to(1.0)
+        .with_velocity(5.0);
+    let mut coarse = fine;
+
+    for _ in 0..100 {
+        fine.step(0.001);
+    }
+    coarse.step(0.1);
+
+    assert!((fine.value() - coarse.value()).abs() < 1e-9);
+    assert!((fine.velocity() - coarse.velocity()).abs() < 1e-9);
+}
+
+#[test]
+fn spring_response() {
+    let spring = Spring::with_response(1.0, 0.5);
+    assert_eq!(spring.mass(), 1.0);
+    assert!((spring.damping_ratio() - 0.5).abs() < 1e-12);
+    assert!((spring.stiffness() - 4.0 * std::f64::consts::PI.powi(2)).abs() < 1e-9);
+}
+
+#[test]
+fn friction_comes_to_rest_at_final_value() {
+    let mut friction = Friction::new(4.0).from(10.0).with_velocity(200.0);
+    assert_eq!(friction.final_value(), 60.0);
+
+    run(&mut friction, 1.0 / 60.0, 10_000);
+    assert!(friction.is_settled());
+    assert!((friction.value() - 60.0).abs() < 0.01);
+}
+
+#[test]
+fn friction_velocity_to_reach() {
+    let friction = Friction::with_deceleration_rate(0.998).from(0.0);
+    let velocity = friction.velocity_to_reach(300.0);
+    let friction = friction.with_velocity(velocity);
+
+    assert!((friction.final_value() - 300.0).abs() < 1e-9);
+}
+
+#[test]
+fn gravity_falls() {
+    let mut gravity = Gravity::new(10.0).from(0.0);
+    gravity.step(1.0);
+    assert_eq!(gravity.value(), 5.0);
+    assert_eq!(gravity.velocity(), 10.0);
+    assert!(!gravity.is_settled());
+}
+
+#[test]
+fn gravity_bounces_and_rests() {
+    let mut gravity = Gravity::new(1000.0).from(0.0).floor(100.0, 0.5);
+
+    let mut bounced = false;
+    for _ in 0..600 {
+        gravity.step(1.0 / 60.0);
+        assert!(gravity.value() <= 100.0);
+        bounced |= gravity.velocity() < 0.0;
+        if gravity.is_settled() {
+            break;
+        }
+    }
+
+    assert!(bounced);
+    assert!(gravity.is_settled());
+    assert_eq!(gravity.value(), 100.0);
+}
+
+#[test]
+fn gravity_bounce_in_one_step() {
+    // hits the floor after 1s at 10 units/s and goes back up at half speed
+    let mut gravity = Gravity::new(10.0).from(0.0).floor(5.0, 0.5);
+    gravity.step(1.5);
+
+    assert!((gravity.value() - (5.0 - 5.0 * 0.5 + 0.5 * 10.0 * 0.25)).abs() < 1e-9);
+    assert!((gravity.velocity() - (-5.0 + 5.0)).abs() < 1e-9);
+}
+
+#[test]
+fn gravity_bounces_through_long_steps() {
+    // bounces every 2s forever, so a long step holds many bounces
+    let mut gravity = Gravity::new(10.0)
+        .from(5.0)
+        .with_velocity(-10.0)
+        .floor(5.0, 1.0)
+        .rest_threshold(0.0);
+    gravity.step(100.5);
+    assert!(!gravity.is_settled());
+    assert!((gravity.value() - (5.0 - 10.0 * 0.5 + 0.5 * 10.0 * 0.25)).abs() < 1e-6);
+    assert!((gravity.velocity() - -5.0).abs() < 1e-6);
+
+    // slower and slower bounces still come to rest within the step
+    let mut gravity = Gravity::new(1000.0)
+        .from(0.0)
+        .floor(100.0, 0.9)
+        .rest_threshold(0.0);
+    gravity.step(60.0);
+    assert!(gravity.is_settled());
+    assert_eq!(gravity.value(), 100.0);
+}
+
+#[test]
+fn gravity_bounces_are_capped_per_step() {
+    // endless bounces of 2s, a huge step takes as long as a short one
+    let mut gravity = Gravity::new(10.0)
+        .from(5.0)
+        .with_velocity(-10.0)
+        .floor(5.0, 1.0)
+        .rest_threshold(0.0);
+    gravity.step(1e12 + 0.5);
+    assert!(!gravity.is_settled());
+    assert!((gravity.value() - (5.0 - 10.0 * 0.5 + 0.5 * 10.0 * 0.25)).abs() < 1e-6);
+    assert!((gravity.velocity() - -5.0).abs() < 1e-6);
+
+    // bounces losing next to no speed rest on the floor
+    let mut gravity = Gravity::new(10.0)
+        .from(0.0)
+        .floor(5.0, 0.999_999)
+        .rest_threshold(0.0);
+    gravity.step(1e12);
+    assert!(gravity.is_settled());
+    assert_eq!(gravity.value(), 5.0);
+}
+
+#[test]
+fn fixed_step_accumulates() {
+    let mut gravity = Gravity::new(0.0).with_velocity(1.0);
+    let mut fixed = FixedStep::new(0.1);
+
+    assert_eq!(fixed.advance(&mut gravity, 0.25), 2);
+    assert!((gravity.value() - 0.2).abs() < 1e-12);
+    assert_eq!(fixed.advance(&mut gravity, 0.05), 1);
+    assert!((gravity.value() - 0.3).abs() < 1e-12);
+}
+
+#[test]
+fn animation_drives_target() {
+    let target = Rc::new(RefCell::new(0.0));
+    let animation = PhysicsAnimation::with_target(
+        Spring::with_response(0.3, 1.0).from(0.0).to(10.0),
+        target.clone(),
+    );
+    animation.start(0.0);
+
+    assert!(animation.update(100.0));
+    assert!(*target.borrow() > 0.0 && *target.borrow() < 10.0);
+
+    let mut time = 100.0;
+    while animation.update(time) {
+        time += 16.0;
+    }
+    assert_eq!(*target.borrow(), 10.0);
+    assert!(!animation.is_playing());
+}
+
+#[test]
+fn animation_drives_interpolated_target() {
+    let target = Rc::new(RefCell::new(Point::new(0.0, 0.0)));
+    let animation = PhysicsAnimation::with_interpolated_target(
+        Friction::new(2.0).with_velocity(1.0),
+        target.clone(),
+        Point::new(0.0, 0.0),
+        Point::new(100.0, 50.0),
+    );
+    animation.start(0.0);
+    animation.end();
+
+    // friction from 1 unit/s stops half a unit away
+    assert_eq!(*target.borrow(), Point::new(50.0, 25.0));
+    assert_eq!(animation.get_value(), Point::new(50.0, 25.0));
+    assert!(!animation.is_playing());
+}
+
+#[test]
+fn animation_fixed_step() {
+    let animation = PhysicsAnimation::new(Gravity::new(0.0).with_velocity(1000.0));
+    animation.fixed_step(10.0).start(0.0);
+
+    animation.update(25.0);
+    assert!((animation.get_value() - 20.0).abs() < 1e-9);
+    animation.update(30.0);
+    assert!((animation.get_value() - 30.0).abs() < 1e-9);
+}
+
+#[test]
+fn animation_pause_resume() {
+    let animation = PhysicsAnimation::new(Gravity::new(0.0).with_velocity(1000.0));
+    animation.start(0.0);
+
+    animation.update(10.0);
+    animation.pause(10.0);
+    assert!(animation.update(100.0));
+    animation.resume(100.0);
+    animation.update(110.0);
+    assert!((animation.get_value() - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn animation_end_and_callbacks() {
+    let completed = Rc::new(RefCell::new(None));
+    let animation = PhysicsAnimation::new(Friction::new(2.0).with_velocity(100.0));
+    {
+        let completed = completed.clone();
+        animation.on_complete(move |value| *completed.borrow_mut() = Some(value));
+    }
+    animation.start(0.0);
+    animation.end();
+
+    assert_eq!(*completed.borrow(), Some(50.0));
+    assert_eq!(animation.get_value(), 50.0);
+    assert!(!animation.update(100.0));
+}
+
+#[test]
+fn animation_retarget() {
+    let animation = PhysicsAnimation::new(Spring::with_response(0.3, 1.0).from(0.0).to(10.0));
+    animation.start(0.0);
+    animation.update(50.0);
+    animation.simulation().set_target(-10.0);
+
+    let mut time = 50.0;
+    while animation.update(time) {
+        time += 16.0;
+    }
+    assert_eq!(animation.get_value(), -10.0);
+}
+
+#[test]
+fn animation_in_group_after_tween() {
+    let group = Group::new();
+    let target = Rc::new(RefCell::new(0.0));
+
+    let tween = Tween::with_target(target.clone());
+    let spring = PhysicsAnimation::with_target(
+        Spring::with_response(0.2, 1.0).from(10.0).to(0.0),
+        target.clone(),
+    );
+    spring.group(&group);
+    tween
+        .to(10.0, 100.0)
+        .chain(&spring)
+        .group(&group)
+        .start(0.0);
+
+    let mut time = 0.0;
+    while group.update(time) {
+        time += 16.0;
+    }
+
+    assert!(time > 100.0);
+    assert_eq!(*target.borrow(), 0.0);
+    assert!(group.is_empty());
+}
@@ -1,4 +1,5 @@
-use std::fmt;
+use crate::easing::{Easing, EasingFunction, StepPosition};
+use std::{convert::TryFrom, fmt};
 
 /// Controls how a `Actor` should align itself inside the extra space
 /// assigned to it during the allocation.
@@ -241,6 +242,106 @@ impl fmt::Display for AnimationMode {
     }
 }
 
+impl From<Easing> for AnimationMode {
+    fn from(easing: Easing) -> Self {
+        match easing {
+            Easing::Linear => AnimationMode::Linear,
+            Easing::QuadIn => AnimationMode::EaseInQuad,
+            Easing::QuadOut => AnimationMode::EaseOutQuad,
+            Easing::QuadInOut => AnimationMode::EaseInOutQuad,
+            Easing::CubicIn => AnimationMode::EaseInCubic,
+            Easing::CubicOut => AnimationMode::EaseOutCubic,
+            Easing::CubicInOut => AnimationMode::EaseInOutCubic,
+            Easing::QuartIn => AnimationMode::EaseInQuart,
+            Easing::QuartOut => AnimationMode::EaseOutQuart,
+            Easing::QuartInOut => AnimationMode::EaseInOutQuart,
+            Easing::QuintIn => AnimationMode::EaseInQuint,
+            Easing::QuintOut => AnimationMode::EaseOutQuint,
+            Easing::QuintInOut => AnimationMode::EaseInOutQuint,
+            Easing::SineIn => AnimationMode::EaseInSine,
+            Easing::SineOut => AnimationMode::EaseOutSine,
+            Easing::SineInOut => AnimationMode::EaseInOutSine,
+            Easing::ExpoIn => AnimationMode::EaseInExpo,
+            Easing::ExpoOut => AnimationMode::EaseOutExpo,
+            Easing::ExpoInOut => AnimationMode::EaseInOutExpo,
+            Easing::CircIn => AnimationMode::EaseInCirc,
+            Easing::CircOut => AnimationMode::EaseOutCirc,
+            Easing::CircInOut => AnimationMode::EaseInOutCirc,
+            Easing::ElasticIn => AnimationMode::EaseInElastic,
+            Easing::ElasticOut => AnimationMode::EaseOutElastic,
+            Easing::ElasticInOut => AnimationMode::EaseInOutElastic,
+            Easing::BackIn => AnimationMode::EaseInBack,
+            Easing::BackOut => AnimationMode::EaseOutBack,
+            Easing::BackInOut => AnimationMode::EaseInOutBack,
+            Easing::BounceIn => AnimationMode::EaseInBounce,
+            Easing::BounceOut => AnimationMode::EaseOutBounce,
+            Easing::BounceInOut => AnimationMode::EaseInOutBounce,
+        }
+    }
+}
+
+/// Fails with the given mode when it is not one of the named easing curves.
+impl TryFrom<AnimationMode> for Easing {
+    type Error = AnimationMode;
+
+    fn try_from(mode: AnimationMode) -> Result<Self, Self::Error> {
+        let easing = match mode {
+            AnimationMode::Linear => Easing::Linear,
+            AnimationMode::EaseInQuad => Easing::QuadIn,
+            AnimationMode::EaseOutQuad => Easing::QuadOut,
+            AnimationMode::EaseInOutQuad => Easing::QuadInOut,
+            AnimationMode::EaseInCubic => Easing::CubicIn,
+            AnimationMode::EaseOutCubic => Easing::CubicOut,
+            AnimationMode::EaseInOutCubic => Easing::CubicInOut,
+            AnimationMode::EaseInQuart => Easing::QuartIn,
+            AnimationMode::EaseOutQuart => Easing::QuartOut,
+            AnimationMode::EaseInOutQuart => Easing::QuartInOut,
+            AnimationMode::EaseInQuint => Easing::QuintIn,
+            AnimationMode::EaseOutQuint => Easing::QuintOut,
+            AnimationMode::EaseInOutQuint => Easing::QuintInOut,
+            AnimationMode::EaseInSine => Easing::SineIn,
+            AnimationMode::EaseOutSine => Easing::SineOut,
+            AnimationMode::EaseInOutSine => Easing::SineInOut,
+            AnimationMode::EaseInExpo => Easing::ExpoIn,
+            AnimationMode::EaseOutExpo => Easing::ExpoOut,
+            AnimationMode::EaseInOutExpo => Easing::ExpoInOut,
+            AnimationMode::EaseInCirc => Easing::CircIn,
+            AnimationMode::EaseOutCirc => Easing::CircOut,
+            AnimationMode::EaseInOutCirc => Easing::CircInOut,
+            AnimationMode::EaseInElastic => Easing::ElasticIn,
+            AnimationMode::EaseOutElastic => Easing::ElasticOut,
+            AnimationMode::EaseInOutElastic => Easing::ElasticInOut,
+            AnimationMode::EaseInBack => Easing::BackIn,
+            AnimationMode::EaseOutBack => Easing::BackOut,
+            AnimationMode::EaseInOutBack => Easing::BackInOut,
+            AnimationMode::EaseInBounce => Easing::BounceIn,
+            AnimationMode::EaseOutBounce => Easing::BounceOut,
+            AnimationMode::EaseInOutBounce => Easing::BounceInOut,
+            _ => return Err(mode),
+        };
+        Ok(easing)
+    }
+}
+
+/// Fails with the given mode when it needs parameters, like
+/// `AnimationMode::CubicBezier` and `AnimationMode::Steps`.
+impl TryFrom<AnimationMode> for EasingFunction {
+    type Error = AnimationMode;
+
+    fn try_from(mode: AnimationMode) -> Result<Self, Self::Error> {
+        let easing = match mode {
+            AnimationMode::Ease => EasingFunction::cubic_bezier(0.25, 0.1, 0.25, 1.0),
+            AnimationMode::EaseIn => EasingFunction::cubic_bezier(0.42, 0.0, 1.0, 1.0),
+            AnimationMode::EaseOut => EasingFunction::cubic_bezier(0.0, 0.0, 0.58, 1.0),
+            AnimationMode::EaseInOut => EasingFunction::cubic_bezier(0.42, 0.0, 0.58, 1.0),
+            AnimationMode::StepStart => EasingFunction::steps(1, StepPosition::JumpStart),
+            AnimationMode::StepEnd => EasingFunction::steps(1, StepPosition::JumpEnd),
+            _ => Easing::try_from(mode)?.into(),
+        };
+        Ok(easing)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 pub enum BinAlignment {
     Fixed,
@@ -1341,6 +1442,15 @@ pub enum StepMode {
     End,
 }
 
+impl From<StepMode> for StepPosition {
+    fn from(mode: StepMode) -> Self {
+        match mode {
+            StepMode::Start => StepPosition::JumpStart,
+            StepMode::End => StepPosition::JumpEnd,
+        }
+    }
+}
+
 impl fmt::Display for StepMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
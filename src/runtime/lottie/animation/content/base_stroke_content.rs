@@ -1 +1,112 @@
-pub struct BaseStrokeContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            DoubleKeyframeAnimation, IntegerKeyframeAnimation, KeyframeAnimation,
+        },
+        model::{
+            animatable::{AnimatableDoubleValue, AnimatableIntegerValue},
+            content::{LineCapType, LineJoinType},
+        },
+        LottieProperty,
+    },
+    CanvasContext,
+};
+
+/// The parts shared by solid and gradient strokes: the width, the opacity
+/// and the line style.
+pub struct BaseStrokeContent {
+    width: DoubleKeyframeAnimation,
+    opacity: IntegerKeyframeAnimation,
+    cap_type: LineCapType,
+    join_type: LineJoinType,
+    miter_limit: f64,
+    dash_pattern: Vec<DoubleKeyframeAnimation>,
+    dash_offset: Option<DoubleKeyframeAnimation>,
+}
+
+impl BaseStrokeContent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        width: &AnimatableDoubleValue,
+        opacity: &AnimatableIntegerValue,
+        cap_type: LineCapType,
+        join_type: LineJoinType,
+        miter_limit: f64,
+        dash_pattern: &[AnimatableDoubleValue],
+        dash_offset: Option<&AnimatableDoubleValue>,
+    ) -> Self {
+        Self {
+            width: width.create_animation(),
+            opacity: opacity.create_animation(),
+            cap_type,
+            join_type,
+            miter_limit,
+            dash_pattern: dash_pattern
+                .iter()
+                .map(|value| value.create_animation())
+                .collect(),
+            dash_offset: dash_offset.map(|value| value.create_animation()),
+        }
+    }
+
+    /// Overrides the opacity or the width with the callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Opacity(callback) => {
+                self.opacity.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::StrokeWidth(callback) => {
+                self.width.set_value_callback(Some(callback.clone()))
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the opacity at `frame` from `0.0` to `1.0`.
+    pub fn get_opacity(&self, frame: f64) -> f64 {
+        self.opacity
+            .value_at(frame)
+            .map_or(1.0, |opacity| opacity as f64 / 100.0)
+    }
+
+    /// Returns the width at `frame`.
+    pub fn get_width(&self, frame: f64) -> f64 {
+        self.width.value_at(frame).unwrap_or(0.0)
+    }
+
+    /// Returns the dash and gap lengths at `frame`, dashes are at least `1.0`
+    /// long and gaps at least `0.1`.
+    pub fn get_dash_pattern(&self, frame: f64) -> Vec<f64> {
+        self.dash_pattern
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let length = value.value_at(frame).unwrap_or(0.0);
+                if index % 2 == 0 {
+                    length.max(1.0)
+                } else {
+                    length.max(0.1)
+                }
+            })
+            .collect()
+    }
+
+    /// Sets the line style of `canvas` at `frame`.
+    pub fn apply<C: CanvasContext>(&self, canvas: &C, frame: f64) {
+        canvas.set_line_width(self.get_width(frame));
+        canvas.set_line_cap(self.cap_type.to_line_cap());
+        canvas.set_line_join(self.join_type.to_line_join());
+        canvas.set_miter_limit(self.miter_limit);
+
+        let dashes = self.get_dash_pattern(frame);
+        if !dashes.is_empty() {
+            canvas.set_line_dash(&dashes);
+            let offset = self
+                .dash_offset
+                .as_ref()
+                .and_then(|offset| offset.value_at(frame))
+                .unwrap_or(0.0);
+            canvas.set_line_dash_offset(offset);
+        }
+    }
+}
@@ -1 +1,55 @@
-pub struct CompoundTrimPathContent;
+use super::Trim;
+use crate::{lottie::model::content::TrimPathType, path::Path};
+
+/// The trim paths applying to a content, from the outer groups in.
+///
+/// Simultaneous trims cut each path on its own, individual trims cut the
+/// paths of a group as one path.
+#[derive(Clone, Debug, Default)]
+pub struct CompoundTrimPathContent {
+    trims: Vec<Trim>,
+}
+
+impl CompoundTrimPathContent {
+    pub fn add_trim(&mut self, trim: Trim) {
+        self.trims.push(trim);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trims.is_empty()
+    }
+
+    /// Returns the simultaneous trims only.
+    pub fn simultaneous(&self) -> Self {
+        Self {
+            trims: self
+                .trims
+                .iter()
+                .filter(|trim| trim.kind == TrimPathType::Simultaneously)
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Returns `path` cut by the simultaneous trims, one after another.
+    pub fn apply(&self, path: &Path) -> Path {
+        let mut path = path.clone();
+        for trim in self.trims.iter() {
+            if trim.kind == TrimPathType::Simultaneously {
+                path = trim.apply(&path);
+            }
+        }
+        path
+    }
+
+    /// Returns `paths` cut by the individual trims as one path.
+    pub fn apply_together(&self, paths: Vec<Path>) -> Vec<Path> {
+        let mut paths = paths;
+        for trim in self.trims.iter() {
+            if trim.kind == TrimPathType::Individually {
+                paths = trim.apply_together(&paths);
+            }
+        }
+        paths
+    }
+}
@@ -1 +1,293 @@
-pub struct ContentGroup;
+use super::{CompoundTrimPathContent, Content, MergePathsContent, PathContent, RepeaterContent};
+use crate::{
+    lottie::{
+        animation::keyframe::TransformKeyframeAnimation,
+        model::{
+            content::{ContentModel, ShapeGroup, TrimPathType},
+            KeyPath, KeyPathElement,
+        },
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext, Transform,
+};
+
+/// A group of contents, drawn with the transform and opacity of the group.
+///
+/// Fills and strokes paint the paths listed before them in the group,
+/// items listed first are drawn on top. Trim paths cut the paths listed
+/// before them, in the group and in the groups before them.
+pub struct ContentGroup {
+    pub name: Option<String>,
+    contents: Vec<Content>,
+    transform: Option<TransformKeyframeAnimation>,
+}
+
+impl ContentGroup {
+    /// Creates a group of `items`, hidden items are left out.
+    ///
+    /// A repeater takes in the contents before it, merge paths take in the
+    /// paths before them. Merge paths are left out unless
+    /// `enable_merge_paths` is set, the paths are then drawn unmerged.
+    pub fn new(name: Option<String>, items: &[ContentModel], enable_merge_paths: bool) -> Self {
+        let transform = items.iter().find_map(|item| match item {
+            ContentModel::Transform(transform) => Some(TransformKeyframeAnimation::new(transform)),
+            _ => None,
+        });
+        let mut contents: Vec<Content> = Vec::with_capacity(items.len());
+        for item in items.iter().filter(|item| !item.is_hidden()) {
+            match item {
+                ContentModel::Repeater(repeater) => {
+                    let group = Self::from_contents(Some("Repeater".into()), contents);
+                    let repeater = RepeaterContent::new(repeater, group);
+                    contents = vec![Content::Repeater(Box::new(repeater))];
+                }
+                ContentModel::MergePaths(_) if !enable_merge_paths => {
+                    warn!("Animation contains merge paths but they are disabled.");
+                }
+                ContentModel::MergePaths(merge) => {
+                    let (paths, others) = contents.into_iter().partition(Content::is_path);
+                    contents = others;
+                    contents.push(Content::MergePaths(MergePathsContent::new(merge, paths)));
+                }
+                _ => contents.extend(Content::from_model(item, enable_merge_paths)),
+            }
+        }
+        Self {
+            name,
+            contents,
+            transform,
+        }
+    }
+
+    /// Creates a group of `contents` without transform.
+    pub fn from_contents(name: Option<String>, contents: Vec<Content>) -> Self {
+        Self {
+            name,
+            contents,
+            transform: None,
+        }
+    }
+
+    pub fn from_group(group: &ShapeGroup, enable_merge_paths: bool) -> Self {
+        Self::new(group.name.clone(), &group.items, enable_merge_paths)
+    }
+
+    pub fn get_contents(&self) -> &[Content] {
+        &self.contents
+    }
+
+    /// Resolves `key_path` in the contents of the group at `depth`, as if
+    /// they had no group around them.
+    pub fn resolve_contents_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        for content in self.contents.iter() {
+            content.resolve_key_path(key_path, depth, accumulator, current_partial_key_path);
+        }
+    }
+
+    /// Sets the callback of `property` on the contents of the group
+    /// matching `key_path` at `depth`, as if they had no group around them.
+    pub fn add_contents_value_callback(
+        &mut self,
+        key_path: &KeyPath,
+        depth: usize,
+        property: &LottieProperty,
+    ) {
+        for content in self.contents.iter_mut() {
+            content.add_value_callback(key_path, depth, property);
+        }
+    }
+
+    /// Returns the transform of the group at `frame`.
+    pub fn get_matrix(&self, frame: f64) -> Transform<f64> {
+        self.transform
+            .as_ref()
+            .map_or_else(Transform::identity, |transform| transform.get_matrix(frame))
+    }
+
+    /// Returns the opacity of the group at `frame`, from `0.0` to `1.0`.
+    pub fn get_opacity(&self, frame: f64) -> f64 {
+        self.transform
+            .as_ref()
+            .map_or(1.0, |transform| transform.get_opacity(frame))
+    }
+
+    /// Returns the trim paths applying to the content at `index`, `trims` of
+    /// the outer groups and the trim paths after it. Individual trims are
+    /// left out unless `individually`.
+    fn trims_for(
+        &self,
+        index: usize,
+        frame: f64,
+        trims: &CompoundTrimPathContent,
+        individually: bool,
+    ) -> CompoundTrimPathContent {
+        let mut content_trims = if individually {
+            trims.clone()
+        } else {
+            trims.simultaneous()
+        };
+        for content in self.contents[index + 1..].iter().rev() {
+            if let Content::TrimPath(trim) = content {
+                if individually || trim.get_kind() == TrimPathType::Simultaneously {
+                    content_trims.add_trim(trim.get_trim(frame));
+                }
+            }
+        }
+        content_trims
+    }
+
+    /// Returns the path of each content at `frame`, `None` for the contents
+    /// without path.
+    ///
+    /// Simultaneous trims cut each shape, individual trims cut the paths
+    /// before them as one path.
+    fn get_paths(&self, frame: f64, trims: &CompoundTrimPathContent) -> Vec<Option<Path>> {
+        let mut paths: Vec<Option<Path>> = self
+            .contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| {
+                let content_trims = self.trims_for(index, frame, trims, false);
+                content.get_trimmed_path(frame, &content_trims)
+            })
+            .collect();
+
+        let mut trim_together = |end: usize, trims: &CompoundTrimPathContent| {
+            let indices: Vec<usize> = (0..end).filter(|&i| paths[i].is_some()).collect();
+            let before = indices.iter().filter_map(|&i| paths[i].take()).collect();
+            for (index, path) in indices.into_iter().zip(trims.apply_together(before)) {
+                paths[index] = Some(path);
+            }
+        };
+        trim_together(self.contents.len(), trims);
+        for (index, content) in self.contents.iter().enumerate() {
+            if let Content::TrimPath(trim) = content {
+                if trim.get_kind() == TrimPathType::Individually {
+                    let mut own = CompoundTrimPathContent::default();
+                    own.add_trim(trim.get_trim(frame));
+                    trim_together(index, &own);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Returns the path of the group at `frame` in the coordinates of its
+    /// parent, cut by `trims`.
+    pub fn get_trimmed_path(&self, frame: f64, trims: &CompoundTrimPathContent) -> Path {
+        let mut path = Path::new();
+        for content_path in self.get_paths(frame, trims).into_iter().flatten() {
+            path.extend(content_path.0);
+        }
+        if self.transform.is_some() {
+            path.transform(&self.get_matrix(frame));
+        }
+        path
+    }
+
+    /// Draws the group at `frame` in the current transform of `canvas`.
+    pub fn draw<C: CanvasContext>(&self, canvas: &C, frame: f64, parent_alpha: f64) {
+        self.draw_trimmed(
+            canvas,
+            frame,
+            parent_alpha,
+            &CompoundTrimPathContent::default(),
+        );
+    }
+
+    /// Draws the group at `frame` like [`draw`](ContentGroup::draw), the
+    /// paths cut by `trims`.
+    pub fn draw_trimmed<C: CanvasContext>(
+        &self,
+        canvas: &C,
+        frame: f64,
+        parent_alpha: f64,
+        trims: &CompoundTrimPathContent,
+    ) {
+        let alpha = parent_alpha * self.get_opacity(frame);
+        if alpha <= 0.0 {
+            return;
+        }
+
+        canvas.save();
+        let [a, b, c, d, e, f] = self.get_matrix(frame).to_array();
+        canvas.transform(a, b, c, d, e, f);
+        let mut paths = None;
+        for (index, content) in self.contents.iter().enumerate().rev() {
+            match content {
+                Content::Group(group) => {
+                    let trims = self.trims_for(index, frame, trims, true);
+                    group.draw_trimmed(canvas, frame, alpha, &trims);
+                }
+                Content::Repeater(repeater) => {
+                    let trims = self.trims_for(index, frame, trims, true);
+                    repeater.draw(canvas, frame, alpha, &trims);
+                }
+                _ if content.is_drawing() => {
+                    let paths = paths.get_or_insert_with(|| self.get_paths(frame, trims));
+                    let mut path = Path::new();
+                    for content_path in paths[..index].iter().flatten() {
+                        path.extend(content_path.0.iter().cloned());
+                    }
+                    if !path.is_empty() {
+                        content.draw(canvas, &path, frame, alpha);
+                    }
+                }
+                _ => {}
+            }
+        }
+        canvas.restore();
+    }
+}
+
+impl PathContent for ContentGroup {
+    fn get_path(&self, frame: f64) -> Path {
+        self.get_trimmed_path(frame, &CompoundTrimPathContent::default())
+    }
+}
+
+impl KeyPathElement for ContentGroup {
+    fn resolve_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        let name = self.name.as_deref().unwrap_or_default();
+        if !key_path.matches(name, depth) {
+            return;
+        }
+        let current_partial_key_path = current_partial_key_path.add_key(name);
+        if key_path.fully_resolves_to(name, depth) {
+            accumulator.push(current_partial_key_path.clone());
+        }
+        if let Some(depth) = key_path.children_depth(name, depth) {
+            self.resolve_contents_key_path(key_path, depth, accumulator, &current_partial_key_path);
+        }
+    }
+
+    /// Sets the callback on the transform of the group if it matches, and
+    /// on the contents matching after it.
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        let name = self.name.as_deref().unwrap_or_default();
+        if !key_path.matches(name, depth) {
+            return;
+        }
+        if key_path.fully_resolves_to(name, depth) {
+            if let Some(transform) = &mut self.transform {
+                transform.apply_value_callback(property);
+            }
+        }
+        if let Some(depth) = key_path.children_depth(name, depth) {
+            self.add_contents_value_callback(key_path, depth, property);
+        }
+    }
+}
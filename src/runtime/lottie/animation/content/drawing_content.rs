@@ -1 +1,8 @@
-pub struct DrawingContent;
+use crate::{path::Path, CanvasContext};
+
+/// A content which paints the paths before it in its group.
+pub trait DrawingContent {
+    /// Paints `path` at `frame`, `parent_alpha` is the opacity of the
+    /// enclosing groups and layers from `0.0` to `1.0`.
+    fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64);
+}
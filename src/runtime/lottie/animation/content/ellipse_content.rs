@@ -1 +1,106 @@
-pub struct EllipseContent;
+use super::PathContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{KeyframeAnimation, PointKeyframeAnimation},
+        model::{animatable::AnimatableValue, content::CircleShape},
+        LottieProperty,
+    },
+    path::Path,
+    Point,
+};
+
+/// The length of the control handles of a quarter of a circle, relative to
+/// its radius.
+pub(crate) const ELLIPSE_CONTROL_POINT_PERCENTAGE: f64 = 0.55228;
+
+/// An ellipse, from its center and size.
+pub struct EllipseContent {
+    pub name: Option<String>,
+    position: Box<dyn KeyframeAnimation<Value = Point<f64>>>,
+    size: PointKeyframeAnimation,
+    is_reversed: bool,
+}
+
+impl EllipseContent {
+    pub fn new(shape: &CircleShape) -> Self {
+        Self {
+            name: shape.name.clone(),
+            position: shape.position.create_animation(),
+            size: PointKeyframeAnimation::new(shape.size.keyframes().to_vec())
+                .with_expression(shape.size.get_expression().cloned()),
+            is_reversed: shape.is_reversed,
+        }
+    }
+
+    /// Overrides the position or the size with the callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Position(callback) => {
+                self.position.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::EllipseSize(callback) => {
+                self.size.set_value_callback(Some(callback.clone()))
+            }
+            _ => {}
+        }
+    }
+}
+
+impl PathContent for EllipseContent {
+    /// Returns four curves starting at the top of the ellipse, clockwise
+    /// unless the ellipse is reversed.
+    fn get_path(&self, frame: f64) -> Path {
+        let mut path = Path::new();
+        let size = match self.size.value_at(frame) {
+            Some(size) => size,
+            None => return path,
+        };
+        let center = self
+            .position
+            .value_at(frame)
+            .unwrap_or_else(|| Point::new(0.0, 0.0));
+
+        let (half_width, half_height) = (size.x / 2.0, size.y / 2.0);
+        let cp_w = half_width * ELLIPSE_CONTROL_POINT_PERCENTAGE;
+        let cp_h = half_height * ELLIPSE_CONTROL_POINT_PERCENTAGE;
+        // the X axis is mirrored to go counterclockwise
+        let dir = if self.is_reversed { -1.0 } else { 1.0 };
+        let (x, y) = (center.x, center.y);
+
+        path.push_move_to(x, y - half_height);
+        path.push_curve_to(
+            x + dir * cp_w,
+            y - half_height,
+            x + dir * half_width,
+            y - cp_h,
+            x + dir * half_width,
+            y,
+        );
+        path.push_curve_to(
+            x + dir * half_width,
+            y + cp_h,
+            x + dir * cp_w,
+            y + half_height,
+            x,
+            y + half_height,
+        );
+        path.push_curve_to(
+            x - dir * cp_w,
+            y + half_height,
+            x - dir * half_width,
+            y + cp_h,
+            x - dir * half_width,
+            y,
+        );
+        path.push_curve_to(
+            x - dir * half_width,
+            y - cp_h,
+            x - dir * cp_w,
+            y - half_height,
+            x,
+            y - half_height,
+        );
+        path.push_close_path();
+        path
+    }
+}
@@ -1 +1,67 @@
-pub struct FillContent;
+use super::DrawingContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            set_optional_value_callback, ColorKeyframeAnimation, IntegerKeyframeAnimation,
+            KeyframeAnimation,
+        },
+        model::content::{FillType, ShapeFill},
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext,
+};
+
+/// A solid fill of the paths before it in its group.
+pub struct FillContent {
+    pub name: Option<String>,
+    fill_type: FillType,
+    color: Option<ColorKeyframeAnimation>,
+    opacity: Option<IntegerKeyframeAnimation>,
+}
+
+impl FillContent {
+    pub fn new(fill: &ShapeFill) -> Self {
+        Self {
+            name: fill.name.clone(),
+            fill_type: fill.fill_type,
+            color: fill.color.as_ref().map(|color| color.create_animation()),
+            opacity: fill
+                .opacity
+                .as_ref()
+                .map(|opacity| opacity.create_animation()),
+        }
+    }
+
+    /// Overrides the color or the opacity with the callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Color(callback) => {
+                set_optional_value_callback(&mut self.color, callback)
+            }
+            LottieProperty::Opacity(callback) => {
+                set_optional_value_callback(&mut self.opacity, callback)
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DrawingContent for FillContent {
+    fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64) {
+        let color = match self.color.as_ref().and_then(|color| color.value_at(frame)) {
+            Some(color) => color,
+            None => return,
+        };
+        let opacity = self
+            .opacity
+            .as_ref()
+            .and_then(|opacity| opacity.value_at(frame))
+            .map_or(1.0, |opacity| opacity as f64 / 100.0);
+
+        canvas.set_fill_color(MiscUtils::with_alpha(color, opacity * parent_alpha));
+        MiscUtils::trace_path(canvas, path);
+        canvas.fill();
+    }
+}
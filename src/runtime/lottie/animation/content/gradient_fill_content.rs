@@ -1 +1,175 @@
-pub struct GradientFillContent;
+use super::DrawingContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            DoubleKeyframeAnimation, GradientColorKeyframeAnimation, IntegerKeyframeAnimation,
+            KeyframeAnimation, PointKeyframeAnimation,
+        },
+        model::{
+            animatable::{
+                AnimatableDoubleValue, AnimatableGradientColorValue, AnimatablePointValue,
+                AnimatableValue,
+            },
+            content::{FillType, GradientColor, GradientFill, GradientType},
+        },
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext, ColorStop, Gradient, LinearGradient, RadialGradient, RgbaColor,
+};
+
+/// The number of parts the span between two stops of different colors is
+/// cut into, the canvas blends the parts between gamma encoded colors.
+const GAMMA_STEPS: usize = 8;
+
+/// The gradient of a gradient fill or stroke.
+pub(crate) struct GradientPaint {
+    gradient_type: GradientType,
+    gradient_color: GradientColorKeyframeAnimation,
+    start_point: PointKeyframeAnimation,
+    end_point: PointKeyframeAnimation,
+    highlight_length: Option<DoubleKeyframeAnimation>,
+    highlight_angle: Option<DoubleKeyframeAnimation>,
+}
+
+impl GradientPaint {
+    pub fn new(
+        gradient_type: GradientType,
+        gradient_color: &AnimatableGradientColorValue,
+        start_point: &AnimatablePointValue,
+        end_point: &AnimatablePointValue,
+        highlight_length: Option<&AnimatableDoubleValue>,
+        highlight_angle: Option<&AnimatableDoubleValue>,
+    ) -> Self {
+        Self {
+            gradient_type,
+            gradient_color: gradient_color.create_animation(),
+            start_point: PointKeyframeAnimation::new(start_point.keyframes().to_vec())
+                .with_expression(start_point.get_expression().cloned()),
+            end_point: PointKeyframeAnimation::new(end_point.keyframes().to_vec())
+                .with_expression(end_point.get_expression().cloned()),
+            highlight_length: highlight_length.map(|length| length.create_animation()),
+            highlight_angle: highlight_angle.map(|angle| angle.create_animation()),
+        }
+    }
+
+    /// Returns the gradient at `frame` with the alpha of its stops
+    /// multiplied by `alpha`.
+    ///
+    /// A linear gradient goes from the start to the end point, a radial one
+    /// is centered on the start point and reaches the end point. The focal
+    /// point of a radial gradient is moved from the center by the highlight
+    /// length, in percents of the radius, at the highlight angle from the
+    /// end point.
+    pub fn gradient_at(&self, frame: f64, alpha: f64) -> Option<Gradient> {
+        let colors = self.gradient_color.value_at(frame)?;
+        let start = self.start_point.value_at(frame)?;
+        let end = self.end_point.value_at(frame)?;
+
+        let gradient = match self.gradient_type {
+            GradientType::Linear => Gradient::new(crate::GradientType::Linear(LinearGradient {
+                x0: start.x,
+                y0: start.y,
+                x1: end.x,
+                y1: end.y,
+            })),
+            GradientType::Radial => {
+                let radius = (end.x - start.x).hypot(end.y - start.y).max(0.001);
+                let value = |animation: &Option<DoubleKeyframeAnimation>| {
+                    animation
+                        .as_ref()
+                        .and_then(|animation| animation.value_at(frame))
+                        .unwrap_or(0.0)
+                };
+                // the focal point stays inside the circle
+                let length = (value(&self.highlight_length) / 100.0).clamp(-0.99, 0.99) * radius;
+                let angle = (end.y - start.y).atan2(end.x - start.x)
+                    + value(&self.highlight_angle).to_radians();
+                Gradient::new(crate::GradientType::Radial(RadialGradient {
+                    x0: start.x + angle.cos() * length,
+                    y0: start.y + angle.sin() * length,
+                    r0: 0.0,
+                    x1: start.x,
+                    y1: start.y,
+                    r1: radius,
+                }))
+            }
+        };
+        for (position, color) in Self::stops(&colors) {
+            let color = MiscUtils::with_alpha(color, alpha);
+            gradient.add_color_stop(ColorStop::new(position, color));
+        }
+        Some(gradient)
+    }
+
+    /// Returns the stops of `colors` with stops added between the stops of
+    /// different colors, for the colors to blend in linear light.
+    fn stops(colors: &GradientColor) -> Vec<(f64, RgbaColor)> {
+        let mut stops = Vec::with_capacity(colors.len());
+        for (&position, &color) in colors.positions.iter().zip(colors.colors.iter()) {
+            if let Some(&(previous, previous_color)) = stops.last() {
+                let rgb = |color: RgbaColor| (color.red, color.green, color.blue);
+                if rgb(color) != rgb(previous_color) && position > previous {
+                    for step in 1..GAMMA_STEPS {
+                        let f = step as f64 / GAMMA_STEPS as f64;
+                        let between = previous + (position - previous) * f;
+                        stops.push((between, colors.color_at(between)));
+                    }
+                }
+            }
+            stops.push((position, color));
+        }
+        stops
+    }
+}
+
+/// A gradient fill of the paths before it in its group.
+pub struct GradientFillContent {
+    pub name: Option<String>,
+    fill_type: FillType,
+    gradient: GradientPaint,
+    opacity: IntegerKeyframeAnimation,
+}
+
+impl GradientFillContent {
+    pub fn new(fill: &GradientFill) -> Self {
+        Self {
+            name: fill.name.clone(),
+            fill_type: fill.fill_type,
+            gradient: GradientPaint::new(
+                fill.gradient_type,
+                &fill.gradient_color,
+                &fill.start_point,
+                &fill.end_point,
+                fill.highlight_length.as_ref(),
+                fill.highlight_angle.as_ref(),
+            ),
+            opacity: fill.opacity.create_animation(),
+        }
+    }
+
+    /// Overrides the opacity with the callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        if let LottieProperty::Opacity(callback) = property {
+            self.opacity.set_value_callback(Some(callback.clone()));
+        }
+    }
+}
+
+impl DrawingContent for GradientFillContent {
+    fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64) {
+        let opacity = self
+            .opacity
+            .value_at(frame)
+            .map_or(1.0, |opacity| opacity as f64 / 100.0);
+        let gradient = match self.gradient.gradient_at(frame, opacity * parent_alpha) {
+            Some(gradient) => gradient,
+            None => return,
+        };
+
+        canvas.set_fill_gradient(&gradient);
+        MiscUtils::trace_path(canvas, path);
+        canvas.fill();
+    }
+}
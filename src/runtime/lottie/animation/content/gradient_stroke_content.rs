@@ -1 +1,63 @@
-pub struct GradientStrokeContent;
+use super::{BaseStrokeContent, DrawingContent, GradientPaint};
+use crate::{
+    lottie::{model::content::GradientStroke, utils::MiscUtils, LottieProperty},
+    path::Path,
+    CanvasContext,
+};
+
+/// A gradient stroke of the paths before it in its group.
+pub struct GradientStrokeContent {
+    pub name: Option<String>,
+    base: BaseStrokeContent,
+    gradient: GradientPaint,
+}
+
+impl GradientStrokeContent {
+    pub fn new(stroke: &GradientStroke) -> Self {
+        Self {
+            name: stroke.name.clone(),
+            base: BaseStrokeContent::new(
+                &stroke.width,
+                &stroke.opacity,
+                stroke.cap_type,
+                stroke.join_type,
+                stroke.miter_limit,
+                &stroke.line_dash_pattern,
+                stroke.dash_offset.as_ref(),
+            ),
+            gradient: GradientPaint::new(
+                stroke.gradient_type,
+                &stroke.gradient_color,
+                &stroke.start_point,
+                &stroke.end_point,
+                stroke.highlight_length.as_ref(),
+                stroke.highlight_angle.as_ref(),
+            ),
+        }
+    }
+
+    /// Overrides the opacity or the width with the callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        self.base.apply_value_callback(property);
+    }
+}
+
+impl DrawingContent for GradientStrokeContent {
+    fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64) {
+        let alpha = self.base.get_opacity(frame) * parent_alpha;
+        let gradient = match self.gradient.gradient_at(frame, alpha) {
+            Some(gradient) => gradient,
+            None => return,
+        };
+        if self.base.get_width(frame) <= 0.0 {
+            return;
+        }
+
+        canvas.save();
+        self.base.apply(canvas, frame);
+        canvas.set_stroke_gradient(&gradient);
+        MiscUtils::trace_path(canvas, path);
+        canvas.stroke();
+        canvas.restore();
+    }
+}
@@ -1 +1,109 @@
-pub struct MergePathsContent;
+use super::{CompoundTrimPathContent, Content};
+use crate::{
+    lottie::{
+        model::{
+            content::{MergePaths, MergePathsMode},
+            KeyPath, KeyPathElement,
+        },
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::{Path, PathOp},
+};
+use std::cell::RefCell;
+
+/// Combines the paths of the contents before it in its group into one
+/// path.
+///
+/// The path listed last before the merge is combined with the union of the
+/// paths listed before it.
+pub struct MergePathsContent {
+    pub name: Option<String>,
+    mode: MergePathsMode,
+    contents: Vec<Content>,
+    /// The last paths merged and their merged path, reused while the paths
+    /// are not animated.
+    cache: RefCell<Option<(Vec<Path>, Path)>>,
+}
+
+impl MergePathsContent {
+    /// Creates a merge of the paths of `contents`, in the order of the
+    /// group.
+    pub fn new(merge: &MergePaths, contents: Vec<Content>) -> Self {
+        Self {
+            name: merge.name.clone(),
+            mode: merge.mode,
+            contents,
+            cache: RefCell::new(None),
+        }
+    }
+
+    pub fn get_contents(&self) -> &[Content] {
+        &self.contents
+    }
+
+    /// Returns the merged path at `frame`, the paths are cut by `trims`
+    /// first.
+    pub fn get_path(&self, frame: f64, trims: &CompoundTrimPathContent) -> Path {
+        let paths: Vec<Path> = self
+            .contents
+            .iter()
+            .filter_map(|content| content.get_trimmed_path(frame, trims))
+            .collect();
+
+        let op = match self.mode {
+            MergePathsMode::Merge => None,
+            MergePathsMode::Add => Some(PathOp::Union),
+            MergePathsMode::Subtract => Some(PathOp::Difference),
+            MergePathsMode::Intersect => Some(PathOp::Intersect),
+            MergePathsMode::ExcludeIntersections => Some(PathOp::Xor),
+        };
+        let op = match op {
+            Some(op) if !paths.is_empty() => op,
+            _ => {
+                let mut path = Path::new();
+                for other in paths {
+                    path.extend(other.0);
+                }
+                return path;
+            }
+        };
+
+        if let Some((inputs, merged)) = self.cache.borrow().as_ref() {
+            if *inputs == paths {
+                return merged.clone();
+            }
+        }
+        let (first, others) = paths.split_last().unwrap();
+        let mut rest = Path::new();
+        for other in others {
+            rest.extend(other.0.iter().cloned());
+        }
+        let merged = first.op(&rest, op);
+        *self.cache.borrow_mut() = Some((paths, merged.clone()));
+        merged
+    }
+}
+
+/// The merged contents are matched as if they were next to the merge.
+impl KeyPathElement for MergePathsContent {
+    fn resolve_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        let name = self.name.as_deref().unwrap_or_default();
+        MiscUtils::resolve_key_path(key_path, depth, accumulator, current_partial_key_path, name);
+        for content in self.contents.iter() {
+            content.resolve_key_path(key_path, depth, accumulator, current_partial_key_path);
+        }
+    }
+
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        for content in self.contents.iter_mut() {
+            content.add_value_callback(key_path, depth, property);
+        }
+    }
+}
@@ -55,4 +55,198 @@ pub use stroke_content::*;
 mod trim_path_content;
 pub use trim_path_content::*;
 
-pub struct Content;
+use crate::{
+    lottie::{
+        model::{content::ContentModel, KeyPath, KeyPathElement},
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext,
+};
+
+/// An item of a shape layer or a shape group, ready to be drawn.
+pub enum Content {
+    Group(ContentGroup),
+    Shape(ShapeContent),
+    Rectangle(RectangleContent),
+    Ellipse(EllipseContent),
+    Polystar(PolystarContent),
+    Fill(FillContent),
+    Stroke(StrokeContent),
+    GradientFill(GradientFillContent),
+    GradientStroke(GradientStrokeContent),
+    TrimPath(TrimPathContent),
+    Repeater(Box<RepeaterContent>),
+    MergePaths(MergePathsContent),
+}
+
+impl Content {
+    /// Creates the content of an item, `None` for the transform of a group.
+    ///
+    /// Repeaters and merge paths take in the contents before them, they are
+    /// created by their group instead and `None` here as well.
+    pub fn from_model(model: &ContentModel, enable_merge_paths: bool) -> Option<Content> {
+        let content = match model {
+            ContentModel::Group(group) => {
+                Content::Group(ContentGroup::from_group(group, enable_merge_paths))
+            }
+            ContentModel::Path(shape) => Content::Shape(ShapeContent::new(shape)),
+            ContentModel::Rectangle(shape) => Content::Rectangle(RectangleContent::new(shape)),
+            ContentModel::Ellipse(shape) => Content::Ellipse(EllipseContent::new(shape)),
+            ContentModel::Polystar(shape) => Content::Polystar(PolystarContent::new(shape)),
+            ContentModel::Fill(fill) => Content::Fill(FillContent::new(fill)),
+            ContentModel::Stroke(stroke) => Content::Stroke(StrokeContent::new(stroke)),
+            ContentModel::GradientFill(fill) => {
+                Content::GradientFill(GradientFillContent::new(fill))
+            }
+            ContentModel::GradientStroke(stroke) => {
+                Content::GradientStroke(GradientStrokeContent::new(stroke))
+            }
+            ContentModel::TrimPath(trim) => Content::TrimPath(TrimPathContent::new(trim)),
+            ContentModel::Transform(_)
+            | ContentModel::Repeater(_)
+            | ContentModel::MergePaths(_) => return None,
+        };
+        Some(content)
+    }
+
+    /// Returns the name given in the composition.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Content::Group(content) => content.name.as_deref(),
+            Content::Shape(content) => content.name.as_deref(),
+            Content::Rectangle(content) => content.name.as_deref(),
+            Content::Ellipse(content) => content.name.as_deref(),
+            Content::Polystar(content) => content.name.as_deref(),
+            Content::Fill(content) => content.name.as_deref(),
+            Content::Stroke(content) => content.name.as_deref(),
+            Content::GradientFill(content) => content.name.as_deref(),
+            Content::GradientStroke(content) => content.name.as_deref(),
+            Content::TrimPath(content) => content.name.as_deref(),
+            Content::Repeater(content) => content.name.as_deref(),
+            Content::MergePaths(content) => content.name.as_deref(),
+        }
+    }
+
+    /// Overrides a property of a shape, a fill, a stroke or a repeater with
+    /// the callback of `property`, does nothing for other contents.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match self {
+            Content::Rectangle(content) => content.apply_value_callback(property),
+            Content::Ellipse(content) => content.apply_value_callback(property),
+            Content::Polystar(content) => content.apply_value_callback(property),
+            Content::Fill(content) => content.apply_value_callback(property),
+            Content::Stroke(content) => content.apply_value_callback(property),
+            Content::GradientFill(content) => content.apply_value_callback(property),
+            Content::GradientStroke(content) => content.apply_value_callback(property),
+            Content::Repeater(content) => content.apply_value_callback(property),
+            _ => {}
+        }
+    }
+
+    /// Returns the path of a shape, a group, a repeater or merge paths at
+    /// `frame`, `None` for a fill, a stroke or a trim path.
+    pub fn get_path(&self, frame: f64) -> Option<Path> {
+        self.get_trimmed_path(frame, &CompoundTrimPathContent::default())
+    }
+
+    /// Returns the path at `frame` like [`get_path`](Content::get_path),
+    /// cut by `trims`.
+    pub fn get_trimmed_path(&self, frame: f64, trims: &CompoundTrimPathContent) -> Option<Path> {
+        let path = match self {
+            Content::Group(content) => return Some(content.get_trimmed_path(frame, trims)),
+            Content::Repeater(content) => return Some(content.get_path(frame, trims)),
+            Content::MergePaths(content) => return Some(content.get_path(frame, trims)),
+            Content::Shape(content) => content.get_path(frame),
+            Content::Rectangle(content) => content.get_path(frame),
+            Content::Ellipse(content) => content.get_path(frame),
+            Content::Polystar(content) => content.get_path(frame),
+            _ => return None,
+        };
+        if trims.is_empty() {
+            Some(path)
+        } else {
+            Some(trims.apply(&path))
+        }
+    }
+
+    /// Returns `true` for a content which adds a path to its group.
+    pub fn is_path(&self) -> bool {
+        matches!(
+            self,
+            Content::Group(_)
+                | Content::Shape(_)
+                | Content::Rectangle(_)
+                | Content::Ellipse(_)
+                | Content::Polystar(_)
+                | Content::Repeater(_)
+                | Content::MergePaths(_)
+        )
+    }
+
+    /// Returns `true` for a fill or a stroke.
+    pub fn is_drawing(&self) -> bool {
+        matches!(
+            self,
+            Content::Fill(_)
+                | Content::Stroke(_)
+                | Content::GradientFill(_)
+                | Content::GradientStroke(_)
+        )
+    }
+
+    /// Paints `path` with a fill or a stroke, does nothing for other
+    /// contents.
+    pub fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64) {
+        match self {
+            Content::Fill(content) => content.draw(canvas, path, frame, parent_alpha),
+            Content::Stroke(content) => content.draw(canvas, path, frame, parent_alpha),
+            Content::GradientFill(content) => content.draw(canvas, path, frame, parent_alpha),
+            Content::GradientStroke(content) => content.draw(canvas, path, frame, parent_alpha),
+            _ => {}
+        }
+    }
+}
+
+impl KeyPathElement for Content {
+    fn resolve_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        match self {
+            Content::Group(content) => {
+                content.resolve_key_path(key_path, depth, accumulator, current_partial_key_path)
+            }
+            Content::Repeater(content) => {
+                content.resolve_key_path(key_path, depth, accumulator, current_partial_key_path)
+            }
+            Content::MergePaths(content) => {
+                content.resolve_key_path(key_path, depth, accumulator, current_partial_key_path)
+            }
+            _ => MiscUtils::resolve_key_path(
+                key_path,
+                depth,
+                accumulator,
+                current_partial_key_path,
+                self.name().unwrap_or_default(),
+            ),
+        }
+    }
+
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        match self {
+            Content::Group(content) => content.add_value_callback(key_path, depth, property),
+            Content::Repeater(content) => content.add_value_callback(key_path, depth, property),
+            Content::MergePaths(content) => content.add_value_callback(key_path, depth, property),
+            _ => {
+                if key_path.fully_resolves_to(self.name().unwrap_or_default(), depth) {
+                    self.apply_value_callback(property);
+                }
+            }
+        }
+    }
+}
@@ -1 +1,7 @@
-pub struct PathContent;
+use crate::path::Path;
+
+/// A content which adds a path to its group.
+pub trait PathContent {
+    /// Returns the path at `frame`, in the coordinates of the group.
+    fn get_path(&self, frame: f64) -> Path;
+}
@@ -1 +1,241 @@
-pub struct PolystarContent;
+use super::PathContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            set_optional_value_callback, DoubleKeyframeAnimation, KeyframeAnimation,
+        },
+        model::content::{PolystarShape, PolystarShapeType},
+        LottieProperty,
+    },
+    path::Path,
+    Point, Transform,
+};
+use std::f64::consts::PI;
+
+/// The length of the control handles of the rounded points of a star,
+/// relative to their radius.
+const POLYSTAR_MAGIC_NUMBER: f64 = 0.47829;
+/// The length of the control handles of the rounded points of a polygon,
+/// relative to their radius.
+const POLYGON_MAGIC_NUMBER: f64 = 0.25;
+
+/// A star or a regular polygon.
+pub struct PolystarContent {
+    pub name: Option<String>,
+    kind: PolystarShapeType,
+    is_reversed: bool,
+    points: DoubleKeyframeAnimation,
+    position: Box<dyn KeyframeAnimation<Value = Point<f64>>>,
+    rotation: DoubleKeyframeAnimation,
+    inner_radius: Option<DoubleKeyframeAnimation>,
+    outer_radius: DoubleKeyframeAnimation,
+    inner_roundness: Option<DoubleKeyframeAnimation>,
+    outer_roundness: DoubleKeyframeAnimation,
+}
+
+/// The control points of a rounded side from `from` to `to`, each handle
+/// is perpendicular to the radius of its point.
+fn rounded_side(from: (f64, f64), to: (f64, f64), cp1_len: f64, cp2_len: f64) -> [f64; 4] {
+    let cp1_theta = from.1.atan2(from.0) - PI / 2.0;
+    let cp2_theta = to.1.atan2(to.0) - PI / 2.0;
+    [
+        from.0 - cp1_len * cp1_theta.cos(),
+        from.1 - cp1_len * cp1_theta.sin(),
+        to.0 + cp2_len * cp2_theta.cos(),
+        to.1 + cp2_len * cp2_theta.sin(),
+    ]
+}
+
+impl PolystarContent {
+    pub fn new(shape: &PolystarShape) -> Self {
+        Self {
+            name: shape.name.clone(),
+            kind: shape.kind,
+            is_reversed: shape.is_reversed,
+            points: shape.points.create_animation(),
+            position: shape.position.create_animation(),
+            rotation: shape.rotation.create_animation(),
+            inner_radius: shape.inner_radius.as_ref().map(|v| v.create_animation()),
+            outer_radius: shape.outer_radius.create_animation(),
+            inner_roundness: shape.inner_roundness.as_ref().map(|v| v.create_animation()),
+            outer_roundness: shape.outer_roundness.create_animation(),
+        }
+    }
+
+    /// Overrides the position or a property of the polystar with the
+    /// callback of `property`.
+    ///
+    /// The inner radius and roundness only change stars.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Position(callback) => {
+                self.position.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::PolystarPoints(callback) => {
+                self.points.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::PolystarRotation(callback) => {
+                self.rotation.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::PolystarInnerRadius(callback) => {
+                set_optional_value_callback(&mut self.inner_radius, callback)
+            }
+            LottieProperty::PolystarOuterRadius(callback) => {
+                self.outer_radius.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::PolystarInnerRoundedness(callback) => {
+                set_optional_value_callback(&mut self.inner_roundness, callback)
+            }
+            LottieProperty::PolystarOuterRoundedness(callback) => self
+                .outer_roundness
+                .set_value_callback(Some(callback.clone())),
+            _ => {}
+        }
+    }
+
+    fn value(animation: Option<&DoubleKeyframeAnimation>, frame: f64) -> f64 {
+        animation
+            .and_then(|animation| animation.value_at(frame))
+            .unwrap_or(0.0)
+    }
+
+    /// Returns a star around the origin, a fractional number of points
+    /// grows the last point from the inner radius.
+    fn star_path(&self, frame: f64) -> Path {
+        let mut path = Path::new();
+        let points = Self::value(Some(&self.points), frame);
+        if points <= 0.0 {
+            return path;
+        }
+
+        let mut current_angle = (Self::value(Some(&self.rotation), frame) - 90.0).to_radians();
+        let mut angle_per_point = 2.0 * PI / points;
+        if self.is_reversed {
+            angle_per_point = -angle_per_point;
+        }
+        let half_angle_per_point = angle_per_point / 2.0;
+        let partial_point_amount = points.fract();
+        if partial_point_amount != 0.0 {
+            current_angle += half_angle_per_point * (1.0 - partial_point_amount);
+        }
+
+        let outer_radius = Self::value(Some(&self.outer_radius), frame);
+        let inner_radius = Self::value(self.inner_radius.as_ref(), frame);
+        let inner_roundness = Self::value(self.inner_roundness.as_ref(), frame) / 100.0;
+        let outer_roundness = Self::value(Some(&self.outer_roundness), frame) / 100.0;
+
+        let mut partial_point_radius = 0.0;
+        let (mut x, mut y);
+        if partial_point_amount != 0.0 {
+            partial_point_radius =
+                inner_radius + partial_point_amount * (outer_radius - inner_radius);
+            x = partial_point_radius * current_angle.cos();
+            y = partial_point_radius * current_angle.sin();
+            current_angle += angle_per_point * partial_point_amount / 2.0;
+        } else {
+            x = outer_radius * current_angle.cos();
+            y = outer_radius * current_angle.sin();
+            current_angle += half_angle_per_point;
+        }
+        path.push_move_to(x, y);
+
+        // sides alternate from an outer to an inner point and back
+        let mut long_segment = false;
+        let num_points = points.ceil() as usize * 2;
+        for i in 0..num_points {
+            let mut radius = if long_segment {
+                outer_radius
+            } else {
+                inner_radius
+            };
+            let mut d_theta = half_angle_per_point;
+            if partial_point_radius != 0.0 && i == num_points - 2 {
+                d_theta = angle_per_point * partial_point_amount / 2.0;
+            }
+            if partial_point_radius != 0.0 && i == num_points - 1 {
+                radius = partial_point_radius;
+            }
+            let previous = (x, y);
+            x = radius * current_angle.cos();
+            y = radius * current_angle.sin();
+
+            if inner_roundness == 0.0 && outer_roundness == 0.0 {
+                path.push_line_to(x, y);
+            } else {
+                let (cp1_roundness, cp1_radius, cp2_roundness, cp2_radius) = if long_segment {
+                    (inner_roundness, inner_radius, outer_roundness, outer_radius)
+                } else {
+                    (outer_roundness, outer_radius, inner_roundness, inner_radius)
+                };
+                let mut cp1_len = cp1_radius * cp1_roundness * POLYSTAR_MAGIC_NUMBER;
+                let mut cp2_len = cp2_radius * cp2_roundness * POLYSTAR_MAGIC_NUMBER;
+                if partial_point_amount != 0.0 {
+                    if i == 0 {
+                        cp1_len *= partial_point_amount;
+                    } else if i == num_points - 1 {
+                        cp2_len *= partial_point_amount;
+                    }
+                }
+                let [x1, y1, x2, y2] = rounded_side(previous, (x, y), cp1_len, cp2_len);
+                path.push_curve_to(x1, y1, x2, y2, x, y);
+            }
+
+            current_angle += d_theta;
+            long_segment = !long_segment;
+        }
+        path.push_close_path();
+        path
+    }
+
+    /// Returns a regular polygon around the origin, the number of points is
+    /// rounded down.
+    fn polygon_path(&self, frame: f64) -> Path {
+        let mut path = Path::new();
+        let points = Self::value(Some(&self.points), frame).floor();
+        if points <= 0.0 {
+            return path;
+        }
+
+        let mut current_angle = (Self::value(Some(&self.rotation), frame) - 90.0).to_radians();
+        let mut angle_per_point = 2.0 * PI / points;
+        if self.is_reversed {
+            angle_per_point = -angle_per_point;
+        }
+        let roundness = Self::value(Some(&self.outer_roundness), frame) / 100.0;
+        let radius = Self::value(Some(&self.outer_radius), frame);
+
+        let mut x = radius * current_angle.cos();
+        let mut y = radius * current_angle.sin();
+        path.push_move_to(x, y);
+        current_angle += angle_per_point;
+
+        for _ in 0..points as usize {
+            let previous = (x, y);
+            x = radius * current_angle.cos();
+            y = radius * current_angle.sin();
+            if roundness != 0.0 {
+                let cp_len = radius * roundness * POLYGON_MAGIC_NUMBER;
+                let [x1, y1, x2, y2] = rounded_side(previous, (x, y), cp_len, cp_len);
+                path.push_curve_to(x1, y1, x2, y2, x, y);
+            } else {
+                path.push_line_to(x, y);
+            }
+            current_angle += angle_per_point;
+        }
+        path.push_close_path();
+        path
+    }
+}
+
+impl PathContent for PolystarContent {
+    fn get_path(&self, frame: f64) -> Path {
+        let mut path = match self.kind {
+            PolystarShapeType::Star => self.star_path(frame),
+            PolystarShapeType::Polygon => self.polygon_path(frame),
+        };
+        if let Some(position) = self.position.value_at(frame) {
+            path.transform(&Transform::translation(position.x, position.y));
+        }
+        path
+    }
+}
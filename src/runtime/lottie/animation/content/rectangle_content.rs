@@ -1 +1,111 @@
-pub struct RectangleContent;
+use super::{PathContent, ELLIPSE_CONTROL_POINT_PERCENTAGE};
+use crate::{
+    lottie::{
+        animation::keyframe::{DoubleKeyframeAnimation, KeyframeAnimation, PointKeyframeAnimation},
+        model::{animatable::AnimatableValue, content::RectangleShape},
+        LottieProperty,
+    },
+    path::Path,
+    Point,
+};
+
+/// A rectangle, from its center and size, with rounded corners.
+pub struct RectangleContent {
+    pub name: Option<String>,
+    position: Box<dyn KeyframeAnimation<Value = Point<f64>>>,
+    size: PointKeyframeAnimation,
+    corner_radius: DoubleKeyframeAnimation,
+    is_reversed: bool,
+}
+
+impl RectangleContent {
+    pub fn new(shape: &RectangleShape) -> Self {
+        Self {
+            name: shape.name.clone(),
+            position: shape.position.create_animation(),
+            size: PointKeyframeAnimation::new(shape.size.keyframes().to_vec())
+                .with_expression(shape.size.get_expression().cloned()),
+            corner_radius: shape.corner_radius.create_animation(),
+            is_reversed: shape.is_reversed,
+        }
+    }
+
+    /// Overrides the position, the size or the corner radius with the
+    /// callback of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Position(callback) => {
+                self.position.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::RectangleSize(callback) => {
+                self.size.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::CornerRadius(callback) => self
+                .corner_radius
+                .set_value_callback(Some(callback.clone())),
+            _ => {}
+        }
+    }
+}
+
+impl PathContent for RectangleContent {
+    /// Returns the rectangle starting at the top of its right side,
+    /// clockwise unless the rectangle is reversed.
+    ///
+    /// The corner radius is at most half of the smallest side.
+    fn get_path(&self, frame: f64) -> Path {
+        let mut path = Path::new();
+        let size = match self.size.value_at(frame) {
+            Some(size) => size,
+            None => return path,
+        };
+        let center = self
+            .position
+            .value_at(frame)
+            .unwrap_or_else(|| Point::new(0.0, 0.0));
+
+        let (half_width, half_height) = (size.x / 2.0, size.y / 2.0);
+        let radius = self
+            .corner_radius
+            .value_at(frame)
+            .unwrap_or(0.0)
+            .min(half_width)
+            .min(half_height)
+            .max(0.0);
+        let cp = radius * (1.0 - ELLIPSE_CONTROL_POINT_PERCENTAGE);
+        let (left, right) = (center.x - half_width, center.x + half_width);
+        let (top, bottom) = (center.y - half_height, center.y + half_height);
+
+        path.push_move_to(right, top + radius);
+        path.push_line_to(right, bottom - radius);
+        if radius > 0.0 {
+            path.push_curve_to(
+                right,
+                bottom - cp,
+                right - cp,
+                bottom,
+                right - radius,
+                bottom,
+            );
+        }
+        path.push_line_to(left + radius, bottom);
+        if radius > 0.0 {
+            path.push_curve_to(left + cp, bottom, left, bottom - cp, left, bottom - radius);
+        }
+        path.push_line_to(left, top + radius);
+        if radius > 0.0 {
+            path.push_curve_to(left, top + cp, left + cp, top, left + radius, top);
+        }
+        path.push_line_to(right - radius, top);
+        if radius > 0.0 {
+            path.push_curve_to(right - cp, top, right, top + cp, right, top + radius);
+        }
+        path.push_close_path();
+
+        if self.is_reversed {
+            path.reversed()
+        } else {
+            path
+        }
+    }
+}
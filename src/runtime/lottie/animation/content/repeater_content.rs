@@ -1 +1,129 @@
-pub struct RepeaterContent;
+use super::{CompoundTrimPathContent, ContentGroup};
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            DoubleKeyframeAnimation, KeyframeAnimation, TransformKeyframeAnimation,
+        },
+        model::{content::Repeater, KeyPath, KeyPathElement},
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext, Transform,
+};
+
+/// Draws copies of the contents before it in its group, each copy moved by
+/// the transform once more than the copy before it.
+pub struct RepeaterContent {
+    pub name: Option<String>,
+    copies: DoubleKeyframeAnimation,
+    offset: DoubleKeyframeAnimation,
+    transform: TransformKeyframeAnimation,
+    group: ContentGroup,
+}
+
+impl RepeaterContent {
+    /// Creates a repeater of `group`, the contents before it.
+    pub fn new(repeater: &Repeater, group: ContentGroup) -> Self {
+        Self {
+            name: repeater.name.clone(),
+            copies: repeater.copies.create_animation(),
+            offset: repeater.offset.create_animation(),
+            transform: TransformKeyframeAnimation::new(&repeater.transform),
+            group,
+        }
+    }
+
+    /// Overrides the copies, the offset or the transform with the callback
+    /// of `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::RepeaterCopies(callback) => {
+                self.copies.set_value_callback(Some(callback.clone()))
+            }
+            LottieProperty::RepeaterOffset(callback) => {
+                self.offset.set_value_callback(Some(callback.clone()))
+            }
+            _ => {
+                self.transform.apply_value_callback(property);
+            }
+        }
+    }
+
+    /// Returns the number of copies at `frame`.
+    pub fn get_copies(&self, frame: f64) -> usize {
+        self.copies.value_at(frame).unwrap_or(0.0).max(0.0) as usize
+    }
+
+    /// Returns the matrix of the copy at `index`.
+    fn get_matrix(&self, index: usize, frame: f64) -> Transform<f64> {
+        let offset = self.offset.value_at(frame).unwrap_or(0.0);
+        self.transform
+            .get_matrix_for_repeater(index as f64 + offset, frame)
+    }
+
+    /// Returns the paths of the copies at `frame`, cut by `trims`.
+    pub fn get_path(&self, frame: f64, trims: &CompoundTrimPathContent) -> Path {
+        let mut path = Path::new();
+        for index in (0..self.get_copies(frame)).rev() {
+            let mut copy = self.group.get_trimmed_path(frame, trims);
+            copy.transform(&self.get_matrix(index, frame));
+            path.extend(copy.0);
+        }
+        path
+    }
+
+    /// Draws the copies at `frame`, the first copy on top. The opacity goes
+    /// from the start opacity of the first copy to the end opacity of the
+    /// last one.
+    pub fn draw<C: CanvasContext>(
+        &self,
+        canvas: &C,
+        frame: f64,
+        parent_alpha: f64,
+        trims: &CompoundTrimPathContent,
+    ) {
+        let copies = self.get_copies(frame);
+        let start_opacity = self.transform.get_start_opacity(frame);
+        let end_opacity = self.transform.get_end_opacity(frame);
+        for index in (0..copies).rev() {
+            let progress = index as f64 / copies as f64;
+            let alpha = parent_alpha * (start_opacity + (end_opacity - start_opacity) * progress);
+
+            canvas.save();
+            let [a, b, c, d, e, f] = self.get_matrix(index, frame).to_array();
+            canvas.transform(a, b, c, d, e, f);
+            self.group.draw_trimmed(canvas, frame, alpha, trims);
+            canvas.restore();
+        }
+    }
+}
+
+/// The repeated contents are matched as if they were next to the repeater.
+impl KeyPathElement for RepeaterContent {
+    fn resolve_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        let name = self.name.as_deref().unwrap_or_default();
+        MiscUtils::resolve_key_path(key_path, depth, accumulator, current_partial_key_path, name);
+        self.group.resolve_contents_key_path(
+            key_path,
+            depth,
+            accumulator,
+            current_partial_key_path,
+        );
+    }
+
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        let name = self.name.as_deref().unwrap_or_default();
+        if key_path.fully_resolves_to(name, depth) {
+            self.apply_value_callback(property);
+        }
+        self.group
+            .add_contents_value_callback(key_path, depth, property);
+    }
+}
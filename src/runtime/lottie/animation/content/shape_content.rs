@@ -1 +1,33 @@
-pub struct ShapeContent;
+use super::PathContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{KeyframeAnimation, ShapeKeyframeAnimation},
+        model::content::ShapePath,
+        utils::MiscUtils,
+    },
+    path::Path,
+};
+
+/// A Bezier path, animated by morphing its points.
+pub struct ShapeContent {
+    pub name: Option<String>,
+    shape: ShapeKeyframeAnimation,
+}
+
+impl ShapeContent {
+    pub fn new(shape: &ShapePath) -> Self {
+        Self {
+            name: shape.name.clone(),
+            shape: shape.shape.create_animation(),
+        }
+    }
+}
+
+impl PathContent for ShapeContent {
+    fn get_path(&self, frame: f64) -> Path {
+        self.shape
+            .value_at(frame)
+            .map(|shape| MiscUtils::get_path_from_data(&shape))
+            .unwrap_or_default()
+    }
+}
@@ -1 +1,67 @@
-pub struct StrokeContent;
+use super::{BaseStrokeContent, DrawingContent};
+use crate::{
+    lottie::{
+        animation::keyframe::{ColorKeyframeAnimation, KeyframeAnimation},
+        model::content::ShapeStroke,
+        utils::MiscUtils,
+        LottieProperty,
+    },
+    path::Path,
+    CanvasContext,
+};
+
+/// A solid stroke of the paths before it in its group.
+pub struct StrokeContent {
+    pub name: Option<String>,
+    base: BaseStrokeContent,
+    color: ColorKeyframeAnimation,
+}
+
+impl StrokeContent {
+    pub fn new(stroke: &ShapeStroke) -> Self {
+        Self {
+            name: stroke.name.clone(),
+            base: BaseStrokeContent::new(
+                &stroke.width,
+                &stroke.opacity,
+                stroke.cap_type,
+                stroke.join_type,
+                stroke.miter_limit,
+                &stroke.line_dash_pattern,
+                stroke.dash_offset.as_ref(),
+            ),
+            color: stroke.color.create_animation(),
+        }
+    }
+
+    /// Overrides the color, the opacity or the width with the callback of
+    /// `property`.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::StrokeColor(callback) => {
+                self.color.set_value_callback(Some(callback.clone()))
+            }
+            _ => self.base.apply_value_callback(property),
+        }
+    }
+}
+
+impl DrawingContent for StrokeContent {
+    fn draw<C: CanvasContext>(&self, canvas: &C, path: &Path, frame: f64, parent_alpha: f64) {
+        let color = match self.color.value_at(frame) {
+            Some(color) => color,
+            None => return,
+        };
+        if self.base.get_width(frame) <= 0.0 {
+            return;
+        }
+
+        canvas.save();
+        self.base.apply(canvas, frame);
+        let alpha = self.base.get_opacity(frame) * parent_alpha;
+        canvas.set_stroke_color(MiscUtils::with_alpha(color, alpha));
+        MiscUtils::trace_path(canvas, path);
+        canvas.stroke();
+        canvas.restore();
+    }
+}
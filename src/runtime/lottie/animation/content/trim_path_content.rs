@@ -1 +1,68 @@
-pub struct TrimPathContent;
+use crate::{
+    lottie::{
+        animation::keyframe::{DoubleKeyframeAnimation, KeyframeAnimation},
+        model::content::{ShapeTrimPath, TrimPathType},
+        utils::DashPath,
+    },
+    path::Path,
+};
+
+/// The values of a trim path at a frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trim {
+    pub kind: TrimPathType,
+    /// The start in fractions of the path length.
+    pub start: f64,
+    /// The end in fractions of the path length.
+    pub end: f64,
+    /// The offset in fractions of the path length.
+    pub offset: f64,
+}
+
+impl Trim {
+    /// Returns the part of `path` kept by the trim.
+    pub fn apply(&self, path: &Path) -> Path {
+        DashPath::trim(path, self.start, self.end, self.offset)
+    }
+
+    /// Returns the part kept of each of `paths`, trimmed as one path.
+    pub fn apply_together(&self, paths: &[Path]) -> Vec<Path> {
+        DashPath::trim_together(paths, self.start, self.end, self.offset)
+    }
+}
+
+/// Keeps a part of the paths before it in its group, and of the paths of
+/// the groups before it.
+pub struct TrimPathContent {
+    pub name: Option<String>,
+    kind: TrimPathType,
+    start: DoubleKeyframeAnimation,
+    end: DoubleKeyframeAnimation,
+    offset: DoubleKeyframeAnimation,
+}
+
+impl TrimPathContent {
+    pub fn new(trim: &ShapeTrimPath) -> Self {
+        Self {
+            name: trim.name.clone(),
+            kind: trim.kind,
+            start: trim.start.create_animation(),
+            end: trim.end.create_animation(),
+            offset: trim.offset.create_animation(),
+        }
+    }
+
+    pub fn get_kind(&self) -> TrimPathType {
+        self.kind
+    }
+
+    /// Returns the values of the trim at `frame`.
+    pub fn get_trim(&self, frame: f64) -> Trim {
+        Trim {
+            kind: self.kind,
+            start: self.start.value_at(frame).unwrap_or(0.0) / 100.0,
+            end: self.end.value_at(frame).unwrap_or(100.0) / 100.0,
+            offset: self.offset.value_at(frame).unwrap_or(0.0) / 360.0,
+        }
+    }
+}
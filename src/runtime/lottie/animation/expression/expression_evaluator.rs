@@ -0,0 +1,287 @@
+use super::{
+    expression_functions::{self as functions, FUNCTIONS, MATH},
+    BinaryOperator, ExpressionParser, ExpressionValue, Node, Statement, UnaryOperator,
+};
+use crate::lottie::LottieError;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// The values an expression is evaluated with.
+pub struct ExpressionContext<'a> {
+    /// The frame the property is evaluated at.
+    pub frame: f64,
+    pub frame_rate: f64,
+    /// The frames of the keyframes of the property, the first one first.
+    pub key_frames: &'a [f64],
+    /// Returns the value of the keyframes of the property at a frame.
+    pub value_at: &'a dyn Fn(f64) -> Option<ExpressionValue>,
+}
+
+impl<'a> ExpressionContext<'a> {
+    /// Returns the time in seconds, `time` in expressions.
+    pub fn get_time(&self) -> f64 {
+        self.frame / self.frame_rate
+    }
+
+    /// Returns the value of the keyframes at the frame, `value` in
+    /// expressions.
+    pub fn value(&self) -> Option<ExpressionValue> {
+        (self.value_at)(self.frame)
+    }
+}
+
+/// An After Effects expression computing the value of a property.
+///
+/// The supported subset covers `time`, `value`, `wiggle`, `loopIn` and
+/// `loopOut`, the `linear` and `ease` remaps, vector math and `Math`. The
+/// names an expression uses are checked when it is parsed, so an expression
+/// which parses only fails on values of the wrong type.
+#[derive(Clone, Debug)]
+pub struct Expression {
+    source: String,
+    statements: Vec<Statement>,
+    /// Seeds the noise of `wiggle`.
+    seed: u64,
+}
+
+impl Expression {
+    /// Parses `source`, fails if it uses syntax or names which are not
+    /// supported.
+    pub fn parse(source: &str) -> Result<Self, LottieError> {
+        let statements = ExpressionParser::parse(source)?;
+        let mut variables = Vec::new();
+        for statement in statements.iter() {
+            match statement {
+                Statement::Assign(name, node) => {
+                    Self::check(node, &variables)?;
+                    variables.push(name.clone());
+                }
+                Statement::Expression(node) => Self::check(node, &variables)?,
+            }
+        }
+
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        Ok(Self {
+            source: source.into(),
+            statements,
+            seed: hasher.finish(),
+        })
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Checks that `node` only uses known names and the variables assigned
+    /// before it.
+    fn check(node: &Node, variables: &[String]) -> Result<(), LottieError> {
+        let unsupported = |name: &str| Err(LottieError::UnsupportedExpression(name.into()));
+        match node {
+            Node::Number(_) | Node::String(_) => Ok(()),
+            Node::Array(nodes) => nodes
+                .iter()
+                .try_for_each(|node| Self::check(node, variables)),
+            Node::Identifier(name) => {
+                if variables.contains(name) || ["time", "value", "numKeys"].contains(&&**name) {
+                    Ok(())
+                } else {
+                    unsupported(name)
+                }
+            }
+            Node::Member(object, name) => match &**object {
+                Node::Identifier(object) if object == "Math" => {
+                    if MATH.contains(&&**name) {
+                        Ok(())
+                    } else {
+                        unsupported(&format!("Math.{}", name))
+                    }
+                }
+                Node::Identifier(object) if object == "thisComp" => {
+                    if name == "frameDuration" {
+                        Ok(())
+                    } else {
+                        unsupported(&format!("thisComp.{}", name))
+                    }
+                }
+                object if name == "length" => Self::check(object, variables),
+                _ => unsupported(name),
+            },
+            Node::Index(object, index) => {
+                Self::check(object, variables)?;
+                Self::check(index, variables)
+            }
+            Node::Call(function, arguments) => {
+                match &**function {
+                    Node::Identifier(name) if FUNCTIONS.contains(&&**name) => {}
+                    Node::Member(object, name)
+                        if **object == Node::Identifier("Math".into())
+                            && MATH.contains(&&**name) => {}
+                    Node::Identifier(name) | Node::Member(_, name) => return unsupported(name),
+                    _ => return unsupported("calls of values"),
+                }
+                arguments
+                    .iter()
+                    .try_for_each(|node| Self::check(node, variables))
+            }
+            Node::Unary(_, operand) => Self::check(operand, variables),
+            Node::Binary(_, left, right) => {
+                Self::check(left, variables)?;
+                Self::check(right, variables)
+            }
+            Node::Conditional(condition, then, otherwise) => {
+                Self::check(condition, variables)?;
+                Self::check(then, variables)?;
+                Self::check(otherwise, variables)
+            }
+        }
+    }
+
+    /// Returns the value of the expression in `context`, `None` if it
+    /// computes a value of the wrong type.
+    ///
+    /// The value is the one of `$bm_rt` when the expression assigns it, as
+    /// exported expressions do, or else the value of the last statement.
+    pub fn evaluate(&self, context: &ExpressionContext) -> Option<ExpressionValue> {
+        let mut variables = HashMap::new();
+        let mut result = None;
+        for statement in self.statements.iter() {
+            let value = match statement {
+                Statement::Assign(name, node) => {
+                    let value = self.evaluate_node(node, context, &variables)?;
+                    variables.insert(name.clone(), value.clone());
+                    value
+                }
+                Statement::Expression(node) => self.evaluate_node(node, context, &variables)?,
+            };
+            result = Some(value);
+        }
+        variables.remove("$bm_rt").or(result)
+    }
+
+    fn evaluate_node(
+        &self,
+        node: &Node,
+        context: &ExpressionContext,
+        variables: &HashMap<String, ExpressionValue>,
+    ) -> Option<ExpressionValue> {
+        let evaluate = |node: &Node| self.evaluate_node(node, context, variables);
+        match node {
+            Node::Number(number) => Some(ExpressionValue::Number(*number)),
+            Node::String(string) => Some(ExpressionValue::String(string.clone())),
+            Node::Array(nodes) => {
+                let components: Option<Vec<f64>> = nodes
+                    .iter()
+                    .map(|node| evaluate(node)?.as_number())
+                    .collect();
+                components.map(ExpressionValue::Vector)
+            }
+            Node::Identifier(name) => match name.as_str() {
+                _ if variables.contains_key(name) => variables.get(name).cloned(),
+                "time" => Some(ExpressionValue::Number(context.get_time())),
+                "value" => context.value(),
+                "numKeys" => Some(ExpressionValue::Number(context.key_frames.len() as f64)),
+                _ => None,
+            },
+            Node::Member(object, name) => match &**object {
+                Node::Identifier(object) if object == "Math" => functions::math_constant(name),
+                Node::Identifier(object) if object == "thisComp" => {
+                    Some(ExpressionValue::Number(1.0 / context.frame_rate))
+                }
+                object => match evaluate(object)? {
+                    ExpressionValue::Vector(vector) => {
+                        Some(ExpressionValue::Number(vector.len() as f64))
+                    }
+                    ExpressionValue::String(string) => {
+                        Some(ExpressionValue::Number(string.chars().count() as f64))
+                    }
+                    _ => None,
+                },
+            },
+            Node::Index(object, index) => {
+                let index = evaluate(index)?.as_number()?;
+                match evaluate(object)? {
+                    ExpressionValue::Vector(vector) if index >= 0.0 => vector
+                        .get(index as usize)
+                        .copied()
+                        .map(ExpressionValue::Number),
+                    _ => None,
+                }
+            }
+            Node::Call(function, arguments) => {
+                let arguments: Option<Vec<ExpressionValue>> =
+                    arguments.iter().map(evaluate).collect();
+                let arguments = arguments?;
+                match &**function {
+                    Node::Member(_, name) => functions::call_math(name, &arguments),
+                    Node::Identifier(name) => functions::call(name, &arguments, context, self.seed),
+                    _ => None,
+                }
+            }
+            Node::Unary(operator, operand) => {
+                let operand = evaluate(operand)?;
+                match operator {
+                    UnaryOperator::Negate => functions::negate(&operand),
+                    UnaryOperator::Plus => Some(operand),
+                    UnaryOperator::Not => Some(ExpressionValue::Boolean(!operand.is_truthy())),
+                }
+            }
+            Node::Binary(BinaryOperator::And, left, right) => {
+                let left = evaluate(left)?;
+                if left.is_truthy() {
+                    evaluate(right)
+                } else {
+                    Some(left)
+                }
+            }
+            Node::Binary(BinaryOperator::Or, left, right) => {
+                let left = evaluate(left)?;
+                if left.is_truthy() {
+                    Some(left)
+                } else {
+                    evaluate(right)
+                }
+            }
+            Node::Binary(operator, left, right) => {
+                Self::binary(*operator, &evaluate(left)?, &evaluate(right)?)
+            }
+            Node::Conditional(condition, then, otherwise) => {
+                if evaluate(condition)?.is_truthy() {
+                    evaluate(then)
+                } else {
+                    evaluate(otherwise)
+                }
+            }
+        }
+    }
+
+    fn binary(
+        operator: BinaryOperator,
+        left: &ExpressionValue,
+        right: &ExpressionValue,
+    ) -> Option<ExpressionValue> {
+        let compare = |f: fn(f64, f64) -> bool| {
+            Some(ExpressionValue::Boolean(f(
+                left.as_number()?,
+                right.as_number()?,
+            )))
+        };
+        match operator {
+            BinaryOperator::Add => functions::add(left, right),
+            BinaryOperator::Subtract => functions::subtract(left, right),
+            BinaryOperator::Multiply => functions::multiply(left, right),
+            BinaryOperator::Divide => functions::divide(left, right),
+            BinaryOperator::Remainder => functions::remainder(left, right),
+            BinaryOperator::Less => compare(|a, b| a < b),
+            BinaryOperator::LessOrEqual => compare(|a, b| a <= b),
+            BinaryOperator::Greater => compare(|a, b| a > b),
+            BinaryOperator::GreaterOrEqual => compare(|a, b| a >= b),
+            BinaryOperator::Equal => Some(ExpressionValue::Boolean(left == right)),
+            BinaryOperator::NotEqual => Some(ExpressionValue::Boolean(left != right)),
+            // the logical operators are evaluated lazily
+            BinaryOperator::And | BinaryOperator::Or => None,
+        }
+    }
+}
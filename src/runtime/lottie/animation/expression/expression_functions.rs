@@ -0,0 +1,427 @@
+use super::{ExpressionContext, ExpressionValue};
+use crate::easing::EasingFunction;
+
+/// The functions expressions can call.
+pub(super) const FUNCTIONS: &[&str] = &[
+    "wiggle",
+    "loopIn",
+    "loopOut",
+    "linear",
+    "ease",
+    "easeIn",
+    "easeOut",
+    "clamp",
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "sum",
+    "$bm_sum",
+    "$bm_sub",
+    "$bm_mul",
+    "$bm_div",
+    "$bm_mod",
+    "$bm_neg",
+    "length",
+    "normalize",
+    "dot",
+    "degreesToRadians",
+    "radiansToDegrees",
+    "valueAtTime",
+    "timeToFrames",
+    "framesToTime",
+];
+
+/// The functions and the constants of `Math` expressions can use.
+pub(super) const MATH: &[&str] = &[
+    "abs", "acos", "asin", "atan", "atan2", "ceil", "cos", "exp", "floor", "log", "max", "min",
+    "pow", "round", "sign", "sin", "sqrt", "tan", "PI", "E",
+];
+
+/// How far before or after the last keyframe the velocity is measured, for
+/// the `continue` loops.
+const VELOCITY_FRAMES: f64 = 0.001;
+
+/// Returns `f(a, b)` for numbers, or `f` of each component of vectors.
+///
+/// A number is applied to each component of a vector, the missing
+/// components of the shorter of two vectors are 0.
+fn components<F: Fn(f64, f64) -> f64>(
+    a: &ExpressionValue,
+    b: &ExpressionValue,
+    f: F,
+) -> Option<ExpressionValue> {
+    match (a, b) {
+        (ExpressionValue::Vector(a), ExpressionValue::Vector(b)) => {
+            let component = |vector: &Vec<f64>, index| vector.get(index).copied().unwrap_or(0.0);
+            Some(ExpressionValue::Vector(
+                (0..a.len().max(b.len()))
+                    .map(|index| f(component(a, index), component(b, index)))
+                    .collect(),
+            ))
+        }
+        (ExpressionValue::Vector(a), b) => {
+            let b = b.as_number()?;
+            Some(ExpressionValue::Vector(
+                a.iter().map(|&a| f(a, b)).collect(),
+            ))
+        }
+        (a, ExpressionValue::Vector(b)) => {
+            let a = a.as_number()?;
+            Some(ExpressionValue::Vector(
+                b.iter().map(|&b| f(a, b)).collect(),
+            ))
+        }
+        (a, b) => Some(ExpressionValue::Number(f(a.as_number()?, b.as_number()?))),
+    }
+}
+
+/// Adds numbers or vectors, or joins strings.
+pub(super) fn add(a: &ExpressionValue, b: &ExpressionValue) -> Option<ExpressionValue> {
+    match (a, b) {
+        (ExpressionValue::String(a), b) => Some(ExpressionValue::String(format!(
+            "{}{}",
+            a,
+            b.as_str()
+                .map_or_else(|| b.as_number().unwrap_or(0.0).to_string(), str::to_string)
+        ))),
+        (a, ExpressionValue::String(b)) => {
+            Some(ExpressionValue::String(format!("{}{}", a.as_number()?, b)))
+        }
+        _ => components(a, b, |a, b| a + b),
+    }
+}
+
+pub(super) fn subtract(a: &ExpressionValue, b: &ExpressionValue) -> Option<ExpressionValue> {
+    components(a, b, |a, b| a - b)
+}
+
+/// Multiplies numbers, or scales a vector, vectors can't be multiplied.
+pub(super) fn multiply(a: &ExpressionValue, b: &ExpressionValue) -> Option<ExpressionValue> {
+    match (a, b) {
+        (ExpressionValue::Vector(_), ExpressionValue::Vector(_)) => None,
+        _ => components(a, b, |a, b| a * b),
+    }
+}
+
+pub(super) fn divide(a: &ExpressionValue, b: &ExpressionValue) -> Option<ExpressionValue> {
+    match (a, b) {
+        (ExpressionValue::Vector(_), ExpressionValue::Vector(_)) => None,
+        _ => components(a, b, |a, b| a / b),
+    }
+}
+
+pub(super) fn remainder(a: &ExpressionValue, b: &ExpressionValue) -> Option<ExpressionValue> {
+    Some(ExpressionValue::Number(a.as_number()? % b.as_number()?))
+}
+
+pub(super) fn negate(value: &ExpressionValue) -> Option<ExpressionValue> {
+    multiply(value, &ExpressionValue::Number(-1.0))
+}
+
+/// Returns the value at `f` between `start` and `end`.
+fn interpolate(start: &ExpressionValue, end: &ExpressionValue, f: f64) -> Option<ExpressionValue> {
+    components(start, end, |start, end| start + (end - start) * f)
+}
+
+/// Remaps `t` from `t_min..t_max` to `start..end` along `easing`, `t` is
+/// clamped to the range.
+fn remap(arguments: &[ExpressionValue], easing: Option<EasingFunction>) -> Option<ExpressionValue> {
+    let number = |index: usize| arguments.get(index).and_then(ExpressionValue::as_number);
+    let (t, t_min, t_max, start, end) = match arguments.len() {
+        3 => (number(0)?, 0.0, 1.0, &arguments[1], &arguments[2]),
+        5 => (
+            number(0)?,
+            number(1)?,
+            number(2)?,
+            &arguments[3],
+            &arguments[4],
+        ),
+        _ => return None,
+    };
+    let (t_min, t_max, start, end) = if t_min > t_max {
+        (t_max, t_min, end, start)
+    } else {
+        (t_min, t_max, start, end)
+    };
+    let progress = if t_max > t_min {
+        ((t - t_min) / (t_max - t_min)).clamp(0.0, 1.0)
+    } else if t < t_min {
+        0.0
+    } else {
+        1.0
+    };
+    let progress = easing.map_or(progress, |easing| easing.ease(progress));
+    interpolate(start, end, progress)
+}
+
+/// Returns a smooth noise from -1 to 1 along `x`, different for each
+/// `seed`.
+fn noise(x: f64, seed: u64) -> f64 {
+    let random = |index: f64| {
+        // splitmix64, to spread neighbouring integers
+        let mut z = (index as i64 as u64) ^ seed;
+        z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+    };
+    let index = x.floor();
+    let f = x - index;
+    let f = f * f * (3.0 - 2.0 * f);
+    random(index) + (random(index + 1.0) - random(index)) * f
+}
+
+/// `wiggle(freq, amp, octaves = 1, amp_mult = 0.5, t = time)`: moves each
+/// component of the value randomly `freq` times a second by up to about
+/// `amp`.
+///
+/// The noise is the same at the same time for the same expression.
+fn wiggle(
+    arguments: &[ExpressionValue],
+    context: &ExpressionContext,
+    seed: u64,
+) -> Option<ExpressionValue> {
+    let number = |index: usize, default: f64| match arguments.get(index) {
+        Some(argument) => argument.as_number(),
+        None => Some(default),
+    };
+    let frequency = number(0, 0.0)?;
+    let amplitude = number(1, 0.0)?;
+    let octaves = number(2, 1.0)?.clamp(1.0, 10.0) as usize;
+    let amplitude_multiplier = number(3, 0.5)?;
+    let time = number(4, context.get_time())?;
+
+    let value = context.value()?;
+    let offset = |dimension: usize| {
+        let mut offset = 0.0;
+        let mut scale = 1.0;
+        let mut frequency = frequency;
+        for octave in 0..octaves {
+            let seed = seed ^ ((dimension as u64) << 32) ^ ((octave as u64) << 48);
+            offset += noise(time * frequency, seed) * scale;
+            scale *= amplitude_multiplier;
+            frequency *= 2.0;
+        }
+        offset * amplitude
+    };
+    match value {
+        ExpressionValue::Vector(vector) => Some(ExpressionValue::Vector(
+            vector
+                .iter()
+                .enumerate()
+                .map(|(dimension, component)| component + offset(dimension))
+                .collect(),
+        )),
+        value => Some(ExpressionValue::Number(value.as_number()? + offset(0))),
+    }
+}
+
+/// `loopIn(type = "cycle", numKeyframes = 0)` and `loopOut(...)`: repeats
+/// the keyframes before the first or after the last one.
+///
+/// `numKeyframes` limits the loop to the last keyframes for `loopOut` and
+/// the first ones for `loopIn`, 0 loops over all of them. The types are
+/// `cycle`, `pingpong`, `offset` and `continue`.
+fn loop_keyframes(
+    arguments: &[ExpressionValue],
+    context: &ExpressionContext,
+    out: bool,
+) -> Option<ExpressionValue> {
+    let kind = match arguments.first() {
+        Some(kind) => kind.as_str()?,
+        None => "cycle",
+    };
+    let count = match arguments.get(1) {
+        Some(count) => count.as_number()?.max(0.0) as usize,
+        None => 0,
+    };
+    let frame = context.frame;
+    let key_frames = context.key_frames;
+    if key_frames.len() < 2 {
+        return context.value();
+    }
+    let last_index = key_frames.len() - 1;
+    let (first, last) = if out {
+        let first = if count > 0 {
+            last_index.saturating_sub(count)
+        } else {
+            0
+        };
+        (key_frames[first], key_frames[last_index])
+    } else {
+        let last = if count > 0 {
+            count.min(last_index)
+        } else {
+            last_index
+        };
+        (key_frames[0], key_frames[last])
+    };
+    let duration = last - first;
+    if (out && frame <= last) || (!out && frame >= first) || duration <= 0.0 {
+        return context.value();
+    }
+
+    let value_at = |frame: f64| (context.value_at)(frame);
+    let looped = first + (frame - first).rem_euclid(duration);
+    match kind {
+        "cycle" => value_at(looped),
+        "pingpong" => {
+            let cycles = ((frame - first) / duration).floor() as i64;
+            if cycles % 2 == 0 {
+                value_at(looped)
+            } else {
+                value_at(last - (looped - first))
+            }
+        }
+        "offset" => {
+            let cycles = ((frame - first) / duration).floor();
+            let change = subtract(&value_at(last)?, &value_at(first)?)?;
+            add(
+                &value_at(looped)?,
+                &multiply(&change, &ExpressionValue::Number(cycles))?,
+            )
+        }
+        "continue" => {
+            let (edge, inside) = if out {
+                (last, last - VELOCITY_FRAMES)
+            } else {
+                (first, first + VELOCITY_FRAMES)
+            };
+            let edge_value = value_at(edge)?;
+            let velocity = divide(
+                &subtract(&edge_value, &value_at(inside)?)?,
+                &ExpressionValue::Number(edge - inside),
+            )?;
+            add(
+                &edge_value,
+                &multiply(&velocity, &ExpressionValue::Number(frame - edge))?,
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Calls the function `name` with `arguments`.
+pub(super) fn call(
+    name: &str,
+    arguments: &[ExpressionValue],
+    context: &ExpressionContext,
+    seed: u64,
+) -> Option<ExpressionValue> {
+    let number = |index: usize| arguments.get(index).and_then(ExpressionValue::as_number);
+    let pair = || Some((arguments.first()?, arguments.get(1)?));
+    match name {
+        "wiggle" => wiggle(arguments, context, seed),
+        "loopIn" => loop_keyframes(arguments, context, false),
+        "loopOut" => loop_keyframes(arguments, context, true),
+        "linear" => remap(arguments, None),
+        // the easings of After Effects
+        "ease" => remap(
+            arguments,
+            Some(EasingFunction::cubic_bezier(0.33, 0.0, 0.667, 1.0)),
+        ),
+        "easeIn" => remap(
+            arguments,
+            Some(EasingFunction::cubic_bezier(0.333, 0.0, 0.833, 0.833)),
+        ),
+        "easeOut" => remap(
+            arguments,
+            Some(EasingFunction::cubic_bezier(0.167, 0.167, 0.667, 1.0)),
+        ),
+        "clamp" => {
+            let (low, high) = (arguments.get(1)?, arguments.get(2)?);
+            let value = components(arguments.first()?, low, f64::max)?;
+            components(&value, high, f64::min)
+        }
+        "add" | "sum" | "$bm_sum" => pair().and_then(|(a, b)| add(a, b)),
+        "sub" | "$bm_sub" => pair().and_then(|(a, b)| subtract(a, b)),
+        "mul" | "$bm_mul" => pair().and_then(|(a, b)| multiply(a, b)),
+        "div" | "$bm_div" => pair().and_then(|(a, b)| divide(a, b)),
+        "$bm_mod" => pair().and_then(|(a, b)| remainder(a, b)),
+        "$bm_neg" => negate(arguments.first()?),
+        "length" => {
+            let vector = match arguments.get(1) {
+                Some(b) => subtract(arguments.first()?, b)?.as_vector()?,
+                None => arguments.first()?.as_vector()?,
+            };
+            Some(ExpressionValue::Number(
+                vector.iter().map(|c| c * c).sum::<f64>().sqrt(),
+            ))
+        }
+        "normalize" => {
+            let vector = arguments.first()?.as_vector()?;
+            let length = vector.iter().map(|c| c * c).sum::<f64>().sqrt();
+            if length == 0.0 {
+                return Some(ExpressionValue::Vector(vector));
+            }
+            Some(ExpressionValue::Vector(
+                vector.iter().map(|c| c / length).collect(),
+            ))
+        }
+        "dot" => {
+            let (a, b) = pair()?;
+            let (a, b) = (a.as_vector()?, b.as_vector()?);
+            Some(ExpressionValue::Number(
+                a.iter().zip(b.iter()).map(|(a, b)| a * b).sum(),
+            ))
+        }
+        "degreesToRadians" => Some(ExpressionValue::Number(number(0)?.to_radians())),
+        "radiansToDegrees" => Some(ExpressionValue::Number(number(0)?.to_degrees())),
+        "valueAtTime" => (context.value_at)(number(0)? * context.frame_rate),
+        "timeToFrames" => {
+            let time = arguments
+                .first()
+                .map_or(Some(context.get_time()), |t| t.as_number())?;
+            Some(ExpressionValue::Number(time * context.frame_rate))
+        }
+        "framesToTime" => Some(ExpressionValue::Number(number(0)? / context.frame_rate)),
+        _ => None,
+    }
+}
+
+/// Calls the function `name` of `Math` with `arguments`.
+pub(super) fn call_math(name: &str, arguments: &[ExpressionValue]) -> Option<ExpressionValue> {
+    let numbers: Option<Vec<f64>> = arguments.iter().map(ExpressionValue::as_number).collect();
+    let numbers = numbers?;
+    let x = numbers.first().copied().unwrap_or(f64::NAN);
+    let y = numbers.get(1).copied().unwrap_or(f64::NAN);
+    let result = match name {
+        "abs" => x.abs(),
+        "acos" => x.acos(),
+        "asin" => x.asin(),
+        "atan" => x.atan(),
+        "atan2" => x.atan2(y),
+        "ceil" => x.ceil(),
+        "cos" => x.cos(),
+        "exp" => x.exp(),
+        "floor" => x.floor(),
+        "log" => x.ln(),
+        "max" => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        "min" => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+        "pow" => x.powf(y),
+        "round" => (x + 0.5).floor(),
+        "sign" => {
+            if x == 0.0 {
+                0.0
+            } else {
+                x.signum()
+            }
+        }
+        "sin" => x.sin(),
+        "sqrt" => x.sqrt(),
+        "tan" => x.tan(),
+        _ => return None,
+    };
+    Some(ExpressionValue::Number(result))
+}
+
+/// Returns the constant `name` of `Math`.
+pub(super) fn math_constant(name: &str) -> Option<ExpressionValue> {
+    match name {
+        "PI" => Some(ExpressionValue::Number(std::f64::consts::PI)),
+        "E" => Some(ExpressionValue::Number(std::f64::consts::E)),
+        _ => None,
+    }
+}
@@ -0,0 +1,382 @@
+use crate::lottie::LottieError;
+
+/// The deepest nesting of an expression, deeper expressions are refused
+/// rather than risking the stack.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum UnaryOperator {
+    Negate,
+    Plus,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+/// A node of the syntax tree of an expression.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Node {
+    Number(f64),
+    String(String),
+    Array(Vec<Node>),
+    Identifier(String),
+    /// A property of an object, such as `Math.PI`.
+    Member(Box<Node>, String),
+    Index(Box<Node>, Box<Node>),
+    Call(Box<Node>, Vec<Node>),
+    Unary(UnaryOperator, Box<Node>),
+    Binary(BinaryOperator, Box<Node>, Box<Node>),
+    Conditional(Box<Node>, Box<Node>, Box<Node>),
+}
+
+/// A statement of an expression, the value of an expression is the value
+/// of its last statement.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Statement {
+    /// `var name = value` or `name = value`.
+    Assign(String, Node),
+    Expression(Node),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Identifier(String),
+    /// An operator or a punctuation mark.
+    Symbol(&'static str),
+}
+
+/// The symbols of the expression language, longer symbols first.
+const SYMBOLS: &[&str] = &[
+    "===", "!==", "==", "!=", "<=", ">=", "&&", "||", "(", ")", "[", "]", ",", ";", ".", "?", ":",
+    "+", "-", "*", "/", "%", "!", "<", ">", "=",
+];
+
+/// Parses the subset of JavaScript used by After Effects expressions:
+/// variables, arithmetic, comparisons, conditionals, arrays and calls.
+///
+/// Loops, functions and objects are not supported, so an expression always
+/// ends.
+pub(crate) struct ExpressionParser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl ExpressionParser {
+    pub fn parse(source: &str) -> Result<Vec<Statement>, LottieError> {
+        let mut parser = Self {
+            tokens: Self::tokenize(source)?,
+            position: 0,
+            depth: 0,
+        };
+        let mut statements = Vec::new();
+        while parser.position < parser.tokens.len() {
+            if parser.eat(";") {
+                continue;
+            }
+            statements.push(parser.statement()?);
+        }
+        if statements.is_empty() {
+            return Err(Self::error("empty expression"));
+        }
+        Ok(statements)
+    }
+
+    fn error(reason: &str) -> LottieError {
+        LottieError::UnsupportedExpression(reason.into())
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>, LottieError> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut index = 0;
+        while index < chars.len() {
+            let c = chars[index];
+            let rest: String = chars[index..chars.len().min(index + 3)].iter().collect();
+            if c.is_whitespace() {
+                index += 1;
+            } else if rest.starts_with("//") {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            } else if rest.starts_with("/*") {
+                let end = (index + 2..chars.len().saturating_sub(1))
+                    .find(|&end| chars[end] == '*' && chars[end + 1] == '/')
+                    .ok_or_else(|| Self::error("unterminated comment"))?;
+                index = end + 2;
+            } else if c.is_ascii_digit()
+                || (c == '.' && matches!(chars.get(index + 1), Some(d) if d.is_ascii_digit()))
+            {
+                let start = index;
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                if index < chars.len() && (chars[index] == 'e' || chars[index] == 'E') {
+                    index += 1;
+                    if index < chars.len() && (chars[index] == '+' || chars[index] == '-') {
+                        index += 1;
+                    }
+                    while index < chars.len() && chars[index].is_ascii_digit() {
+                        index += 1;
+                    }
+                }
+                let number: String = chars[start..index].iter().collect();
+                let number = number
+                    .parse()
+                    .map_err(|_| Self::error(&format!("invalid number {}", number)))?;
+                tokens.push(Token::Number(number));
+            } else if c == '"' || c == '\'' {
+                let start = index + 1;
+                let end = (start..chars.len())
+                    .find(|&end| chars[end] == c)
+                    .ok_or_else(|| Self::error("unterminated string"))?;
+                tokens.push(Token::String(chars[start..end].iter().collect()));
+                index = end + 1;
+            } else if c.is_alphabetic() || c == '_' || c == '$' {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].is_alphanumeric()
+                        || chars[index] == '_'
+                        || chars[index] == '$')
+                {
+                    index += 1;
+                }
+                tokens.push(Token::Identifier(chars[start..index].iter().collect()));
+            } else {
+                let symbol = SYMBOLS
+                    .iter()
+                    .find(|symbol| rest.starts_with(**symbol))
+                    .ok_or_else(|| Self::error(&format!("unexpected {:?}", c)))?;
+                tokens.push(Token::Symbol(symbol));
+                index += symbol.len();
+            }
+        }
+        Ok(tokens)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Skips the symbol `symbol` if it is the next token.
+    fn eat(&mut self, symbol: &str) -> bool {
+        if self.peek() == Some(&Token::Symbol(Self::symbol(symbol))) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), LottieError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(Self::error(&format!("expected {}", symbol)))
+        }
+    }
+
+    fn symbol(symbol: &str) -> &'static str {
+        SYMBOLS
+            .iter()
+            .find(|known| **known == symbol)
+            .copied()
+            .unwrap_or("")
+    }
+
+    fn statement(&mut self) -> Result<Statement, LottieError> {
+        let declaration = self.peek() == Some(&Token::Identifier("var".into()))
+            || self.peek() == Some(&Token::Identifier("let".into()))
+            || self.peek() == Some(&Token::Identifier("const".into()));
+        if declaration {
+            self.position += 1;
+        }
+        if let (Some(Token::Identifier(name)), Some(Token::Symbol("="))) = (
+            self.tokens.get(self.position),
+            self.tokens.get(self.position + 1),
+        ) {
+            let name = name.clone();
+            self.position += 2;
+            return Ok(Statement::Assign(name, self.expression()?));
+        }
+        if declaration {
+            // a declaration without a value, as in `var $bm_rt;`
+            if let Some(Token::Identifier(_)) = self.peek() {
+                self.position += 1;
+                return Ok(Statement::Expression(Node::Number(0.0)));
+            }
+            return Err(Self::error("expected a variable name"));
+        }
+        match self.peek() {
+            Some(Token::Identifier(keyword))
+                if ["if", "for", "while", "do", "function", "return", "switch"]
+                    .contains(&keyword.as_str()) =>
+            {
+                Err(Self::error(&format!("{} statements", keyword)))
+            }
+            _ => Ok(Statement::Expression(self.expression()?)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, LottieError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Self::error("too deeply nested"));
+        }
+        let node = self.conditional();
+        self.depth -= 1;
+        node
+    }
+
+    fn conditional(&mut self) -> Result<Node, LottieError> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+        let then = self.expression()?;
+        self.expect(":")?;
+        let otherwise = self.expression()?;
+        Ok(Node::Conditional(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    /// Parses the binary operators from the precedence `level` up.
+    fn binary(&mut self, level: usize) -> Result<Node, LottieError> {
+        const LEVELS: &[&[(&str, BinaryOperator)]] = &[
+            &[("||", BinaryOperator::Or)],
+            &[("&&", BinaryOperator::And)],
+            &[
+                ("===", BinaryOperator::Equal),
+                ("!==", BinaryOperator::NotEqual),
+                ("==", BinaryOperator::Equal),
+                ("!=", BinaryOperator::NotEqual),
+            ],
+            &[
+                ("<=", BinaryOperator::LessOrEqual),
+                (">=", BinaryOperator::GreaterOrEqual),
+                ("<", BinaryOperator::Less),
+                (">", BinaryOperator::Greater),
+            ],
+            &[("+", BinaryOperator::Add), ("-", BinaryOperator::Subtract)],
+            &[
+                ("*", BinaryOperator::Multiply),
+                ("/", BinaryOperator::Divide),
+                ("%", BinaryOperator::Remainder),
+            ],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        'operators: loop {
+            for (symbol, operator) in LEVELS[level] {
+                if self.eat(symbol) {
+                    let right = self.binary(level + 1)?;
+                    left = Node::Binary(*operator, Box::new(left), Box::new(right));
+                    continue 'operators;
+                }
+            }
+            return Ok(left);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, LottieError> {
+        let operator = if self.eat("-") {
+            UnaryOperator::Negate
+        } else if self.eat("+") {
+            UnaryOperator::Plus
+        } else if self.eat("!") {
+            UnaryOperator::Not
+        } else {
+            return self.postfix();
+        };
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Self::error("too deeply nested"));
+        }
+        let operand = self.unary()?;
+        self.depth -= 1;
+        Ok(Node::Unary(operator, Box::new(operand)))
+    }
+
+    /// Parses a primary node followed by calls, properties and indices.
+    fn postfix(&mut self) -> Result<Node, LottieError> {
+        let mut node = self.primary()?;
+        loop {
+            if self.eat("(") {
+                let arguments = self.list(")")?;
+                node = Node::Call(Box::new(node), arguments);
+            } else if self.eat(".") {
+                match self.peek().cloned() {
+                    Some(Token::Identifier(name)) => {
+                        self.position += 1;
+                        node = Node::Member(Box::new(node), name);
+                    }
+                    _ => return Err(Self::error("expected a property name")),
+                }
+            } else if self.eat("[") {
+                let index = self.expression()?;
+                self.expect("]")?;
+                node = Node::Index(Box::new(node), Box::new(index));
+            } else {
+                return Ok(node);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, LottieError> {
+        let token = self
+            .peek()
+            .cloned()
+            .ok_or_else(|| Self::error("unexpected end"))?;
+        self.position += 1;
+        match token {
+            Token::Number(number) => Ok(Node::Number(number)),
+            Token::String(string) => Ok(Node::String(string)),
+            Token::Identifier(name) => Ok(Node::Identifier(name)),
+            Token::Symbol("(") => {
+                let node = self.expression()?;
+                self.expect(")")?;
+                Ok(node)
+            }
+            Token::Symbol("[") => Ok(Node::Array(self.list("]")?)),
+            Token::Symbol(symbol) => Err(Self::error(&format!("unexpected {}", symbol))),
+        }
+    }
+
+    /// Parses the expressions separated by commas up to `end`.
+    fn list(&mut self, end: &str) -> Result<Vec<Node>, LottieError> {
+        let mut nodes = Vec::new();
+        if self.eat(end) {
+            return Ok(nodes);
+        }
+        loop {
+            nodes.push(self.expression()?);
+            if self.eat(end) {
+                return Ok(nodes);
+            }
+            self.expect(",")?;
+        }
+    }
+}
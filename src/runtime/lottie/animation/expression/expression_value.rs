@@ -0,0 +1,132 @@
+use crate::{Point, RgbaColor};
+
+/// A value of an expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionValue {
+    Number(f64),
+    /// An array of numbers, such as a position or a color.
+    Vector(Vec<f64>),
+    String(String),
+    Boolean(bool),
+}
+
+impl ExpressionValue {
+    /// Returns the number, or the first component of a vector.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ExpressionValue::Number(number) => Some(*number),
+            ExpressionValue::Vector(vector) => vector.first().copied(),
+            ExpressionValue::Boolean(boolean) => Some(if *boolean { 1.0 } else { 0.0 }),
+            ExpressionValue::String(_) => None,
+        }
+    }
+
+    /// Returns the components of a vector, or a number as a vector of one
+    /// component.
+    pub fn as_vector(&self) -> Option<Vec<f64>> {
+        match self {
+            ExpressionValue::Vector(vector) => Some(vector.clone()),
+            _ => self.as_number().map(|number| vec![number]),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ExpressionValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the value with its numbers multiplied by `factor`.
+    pub fn scale(&self, factor: f64) -> Self {
+        match self {
+            ExpressionValue::Number(number) => ExpressionValue::Number(number * factor),
+            ExpressionValue::Vector(vector) => {
+                ExpressionValue::Vector(vector.iter().map(|component| component * factor).collect())
+            }
+            value => value.clone(),
+        }
+    }
+
+    /// Returns `true` if the value counts as true in a condition, as in
+    /// JavaScript.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ExpressionValue::Number(number) => *number != 0.0 && !number.is_nan(),
+            ExpressionValue::Vector(_) => true,
+            ExpressionValue::String(string) => !string.is_empty(),
+            ExpressionValue::Boolean(boolean) => *boolean,
+        }
+    }
+}
+
+/// A type of property which expressions can compute.
+///
+/// Values are given to expressions in the units of After Effects: scales
+/// and opacities in percents, colors with channels from 0 to 1.
+pub trait ExpressionType: Sized {
+    fn to_expression_value(&self) -> ExpressionValue;
+
+    /// Returns the property value of `value`, `None` if it has the wrong
+    /// shape.
+    fn from_expression_value(value: &ExpressionValue) -> Option<Self>;
+}
+
+impl ExpressionType for f64 {
+    fn to_expression_value(&self) -> ExpressionValue {
+        ExpressionValue::Number(*self)
+    }
+
+    fn from_expression_value(value: &ExpressionValue) -> Option<Self> {
+        value.as_number().filter(|number| number.is_finite())
+    }
+}
+
+impl ExpressionType for i32 {
+    fn to_expression_value(&self) -> ExpressionValue {
+        ExpressionValue::Number(*self as f64)
+    }
+
+    /// Rounds to the nearest integer.
+    fn from_expression_value(value: &ExpressionValue) -> Option<Self> {
+        f64::from_expression_value(value).map(|number| number.round() as i32)
+    }
+}
+
+impl ExpressionType for Point<f64> {
+    fn to_expression_value(&self) -> ExpressionValue {
+        ExpressionValue::Vector(vec![self.x, self.y])
+    }
+
+    /// A number is used for both axes.
+    fn from_expression_value(value: &ExpressionValue) -> Option<Self> {
+        let vector = value.as_vector()?;
+        let x = *vector.first()?;
+        let y = vector.get(1).copied().unwrap_or(x);
+        Some(Point::new(x, y)).filter(|point| point.x.is_finite() && point.y.is_finite())
+    }
+}
+
+impl ExpressionType for RgbaColor {
+    fn to_expression_value(&self) -> ExpressionValue {
+        let channel = |channel: u8| channel as f64 / 255.0;
+        ExpressionValue::Vector(vec![
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue),
+            channel(self.alpha),
+        ])
+    }
+
+    /// Colors without alpha are opaque.
+    fn from_expression_value(value: &ExpressionValue) -> Option<Self> {
+        let vector = value.as_vector().filter(|vector| vector.len() >= 3)?;
+        let channel = |channel: f64| (channel * 255.0).round().clamp(0.0, 255.0) as u8;
+        Some(RgbaColor::new(
+            channel(vector[0]),
+            channel(vector[1]),
+            channel(vector[2]),
+            channel(vector.get(3).copied().unwrap_or(1.0)),
+        ))
+    }
+}
@@ -0,0 +1,13 @@
+mod expression_evaluator;
+pub use expression_evaluator::*;
+
+mod expression_functions;
+
+mod expression_parser;
+pub(crate) use expression_parser::*;
+
+mod expression_value;
+pub use expression_value::*;
+
+mod property_expression;
+pub use property_expression::*;
@@ -0,0 +1,101 @@
+use super::{Expression, ExpressionContext, ExpressionType, ExpressionValue};
+use crate::lottie::value::Keyframe;
+use std::{fmt, rc::Rc};
+
+/// Returns the frames of `keyframes`, with the end of the last one.
+///
+/// A static value has no keyframe.
+pub(crate) fn key_frames<T>(keyframes: &[Keyframe<T>]) -> Vec<f64> {
+    let mut frames: Vec<f64> = keyframes
+        .iter()
+        .filter(|keyframe| keyframe.start_frame > f64::MIN)
+        .map(|keyframe| keyframe.start_frame)
+        .collect();
+    if let Some(end) = keyframes.last().and_then(|keyframe| keyframe.end_frame) {
+        frames.push(end);
+    }
+    frames
+}
+
+/// An expression computing the values of a property of type `T` from the
+/// values of its keyframes.
+pub struct PropertyExpression<T> {
+    expression: Rc<Expression>,
+    frame_rate: f64,
+    /// The values of the property are multiplied by `unit` for the
+    /// expression, scales are factors in the model and percents in
+    /// expressions.
+    unit: f64,
+    to_value: fn(&T) -> ExpressionValue,
+    from_value: fn(&ExpressionValue) -> Option<T>,
+}
+
+impl<T: ExpressionType> PropertyExpression<T> {
+    /// Returns the expression of a property of a composition running at
+    /// `frame_rate`.
+    pub fn new(expression: Rc<Expression>, frame_rate: f64) -> Self {
+        Self {
+            expression,
+            frame_rate,
+            unit: 1.0,
+            to_value: T::to_expression_value,
+            from_value: T::from_expression_value,
+        }
+    }
+}
+
+impl<T> PropertyExpression<T> {
+    pub fn with_unit(mut self, unit: f64) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn get_expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// Returns the value of the expression at `frame`, `value_at` gives the
+    /// values of the keyframes at `key_frames`.
+    ///
+    /// The value of the keyframes is kept when the expression computes a
+    /// value of the wrong type.
+    pub fn value_at<F>(&self, frame: f64, key_frames: &[f64], value_at: F) -> Option<T>
+    where
+        F: Fn(f64) -> Option<T>,
+    {
+        let expression_value_at =
+            |frame: f64| value_at(frame).map(|value| (self.to_value)(&value).scale(self.unit));
+        let context = ExpressionContext {
+            frame,
+            frame_rate: self.frame_rate,
+            key_frames,
+            value_at: &expression_value_at,
+        };
+        self.expression
+            .evaluate(&context)
+            .and_then(|value| (self.from_value)(&value.scale(1.0 / self.unit)))
+            .or_else(|| value_at(frame))
+    }
+}
+
+impl<T> Clone for PropertyExpression<T> {
+    fn clone(&self) -> Self {
+        Self {
+            expression: self.expression.clone(),
+            frame_rate: self.frame_rate,
+            unit: self.unit,
+            to_value: self.to_value,
+            from_value: self.from_value,
+        }
+    }
+}
+
+impl<T> fmt::Debug for PropertyExpression<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PropertyExpression")
+            .field("expression", &self.expression.get_source())
+            .field("frame_rate", &self.frame_rate)
+            .field("unit", &self.unit)
+            .finish()
+    }
+}
@@ -1 +1,109 @@
-pub struct BaseKeyframeAnimation;
+use super::{callback_value, KeyframeAnimation};
+use crate::{
+    interpolate::Interpolate,
+    lottie::{
+        animation::expression::{key_frames, PropertyExpression},
+        value::{Keyframe, LottieValueCallback},
+    },
+};
+
+/// Returns the index of the keyframe active at `frame`: the last one which
+/// starts at or before `frame`, or the first one before any keyframe.
+pub(crate) fn keyframe_index<T>(keyframes: &[Keyframe<T>], frame: f64) -> Option<usize> {
+    if keyframes.is_empty() {
+        return None;
+    }
+    Some(
+        keyframes
+            .iter()
+            .rposition(|keyframe| keyframe.start_frame <= frame)
+            .unwrap_or(0),
+    )
+}
+
+/// Interpolates the values of keyframes along their easing.
+///
+/// Before the first keyframe the value is its start value, after the last one
+/// it is its end value. Hold keyframes keep their start value until the next
+/// keyframe.
+///
+/// An expression computes the value from the values of the keyframes.
+#[derive(Clone, Debug)]
+pub struct BaseKeyframeAnimation<T> {
+    keyframes: Vec<Keyframe<T>>,
+    expression: Option<PropertyExpression<T>>,
+    value_callback: Option<LottieValueCallback<T>>,
+}
+
+impl<T> BaseKeyframeAnimation<T> {
+    pub fn new(keyframes: Vec<Keyframe<T>>) -> Self {
+        Self {
+            keyframes,
+            expression: None,
+            value_callback: None,
+        }
+    }
+
+    pub fn with_expression(mut self, expression: Option<PropertyExpression<T>>) -> Self {
+        self.expression = expression;
+        self
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    /// Returns `true` if the value is the same at every frame.
+    pub fn is_static(&self) -> bool {
+        self.value_callback.is_none()
+            && self.expression.is_none()
+            && self.keyframes.len() <= 1
+            && self.keyframes.iter().all(Keyframe::is_static)
+    }
+}
+
+/// An animation without keyframes, for a property missing from the
+/// composition which is given a value callback.
+impl<T> Default for BaseKeyframeAnimation<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T: Interpolate + Clone> BaseKeyframeAnimation<T> {
+    /// Returns the value of the keyframes at `frame`.
+    fn keyframe_value(&self, frame: f64) -> Option<T> {
+        let keyframe = &self.keyframes[keyframe_index(&self.keyframes, frame)?];
+        let start = keyframe.start_value.as_ref()?;
+        let end = match &keyframe.end_value {
+            Some(end) if !keyframe.hold => end,
+            _ => return Some(start.clone()),
+        };
+
+        let interpolator = keyframe
+            .interpolator
+            .as_ref()
+            .or(keyframe.x_interpolator.as_ref());
+        let progress = keyframe.interpolated_progress(interpolator, frame);
+        Some(start.interpolate(end, progress))
+    }
+}
+
+impl<T: Interpolate + Clone> KeyframeAnimation for BaseKeyframeAnimation<T> {
+    type Value = T;
+
+    fn value_at(&self, frame: f64) -> Option<T> {
+        let keyframe = keyframe_index(&self.keyframes, frame).map(|index| &self.keyframes[index]);
+        let value = match &self.expression {
+            Some(expression) => expression.value_at(frame, &key_frames(&self.keyframes), |frame| {
+                self.keyframe_value(frame)
+            }),
+            None => self.keyframe_value(frame),
+        };
+        callback_value(self.value_callback.as_ref(), keyframe, frame, value)
+    }
+
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<T>>) {
+        self.value_callback = callback;
+    }
+}
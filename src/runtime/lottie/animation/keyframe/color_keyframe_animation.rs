@@ -1 +1,4 @@
-pub struct ColorKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+use crate::RgbaColor;
+
+pub type ColorKeyframeAnimation = BaseKeyframeAnimation<RgbaColor>;
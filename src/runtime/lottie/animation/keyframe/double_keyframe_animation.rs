@@ -1 +1,3 @@
-pub struct DoubleKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+
+pub type DoubleKeyframeAnimation = BaseKeyframeAnimation<f64>;
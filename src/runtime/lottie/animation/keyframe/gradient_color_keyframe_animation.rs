@@ -1 +1,4 @@
-pub struct GradientColorKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+use crate::lottie::model::content::GradientColor;
+
+pub type GradientColorKeyframeAnimation = BaseKeyframeAnimation<GradientColor>;
@@ -1 +1,3 @@
-pub struct IntegerKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+
+pub type IntegerKeyframeAnimation = BaseKeyframeAnimation<i32>;
@@ -1 +1,48 @@
-pub struct KeyframeAnimation;
+use crate::lottie::value::{Keyframe, LottieFrameInfo, LottieValueCallback};
+
+/// A property animated by keyframes.
+///
+/// Animations are evaluated at a frame rather than holding a progress, so the
+/// same animation can be drawn at several times, as precompositions do.
+pub trait KeyframeAnimation {
+    type Value;
+
+    /// Returns the value at `frame`, `None` when the property has no
+    /// keyframes.
+    fn value_at(&self, frame: f64) -> Option<Self::Value>;
+
+    /// Sets the callback overriding the values of the animation, `None`
+    /// removes it.
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<Self::Value>>);
+}
+
+/// Returns `value`, the value of an animation at `frame` within `keyframe`,
+/// overridden by `callback`.
+pub(crate) fn callback_value<T: Clone>(
+    callback: Option<&LottieValueCallback<T>>,
+    keyframe: Option<&Keyframe<T>>,
+    frame: f64,
+    value: Option<T>,
+) -> Option<T> {
+    match callback {
+        Some(callback) => {
+            let frame_info = LottieFrameInfo::new(keyframe, frame, value.clone());
+            callback.get_value(&frame_info).or(value)
+        }
+        None => value,
+    }
+}
+
+/// Sets `callback` on `animation`, created without keyframes for a property
+/// missing from the composition.
+pub(crate) fn set_optional_value_callback<A>(
+    animation: &mut Option<A>,
+    callback: &LottieValueCallback<A::Value>,
+) where
+    A: KeyframeAnimation + Default,
+    A::Value: Clone,
+{
+    animation
+        .get_or_insert_with(A::default)
+        .set_value_callback(Some(callback.clone()));
+}
@@ -1 +1,76 @@
-pub struct MaskKeyframeAnimation;
+use super::{
+    DoubleKeyframeAnimation, IntegerKeyframeAnimation, KeyframeAnimation, ShapeKeyframeAnimation,
+};
+use crate::{
+    lottie::{
+        model::content::{Mask, MaskMode},
+        utils::MiscUtils,
+    },
+    path::Path,
+};
+
+/// Animates the masks of a layer.
+pub struct MaskKeyframeAnimation {
+    masks: Vec<Mask>,
+    paths: Vec<ShapeKeyframeAnimation>,
+    opacities: Vec<IntegerKeyframeAnimation>,
+    expansions: Vec<Option<DoubleKeyframeAnimation>>,
+}
+
+impl MaskKeyframeAnimation {
+    pub fn new(masks: &[Mask]) -> Self {
+        Self {
+            masks: masks.to_vec(),
+            paths: masks
+                .iter()
+                .map(|mask| mask.path.create_animation())
+                .collect(),
+            opacities: masks
+                .iter()
+                .map(|mask| mask.opacity.create_animation())
+                .collect(),
+            expansions: masks
+                .iter()
+                .map(|mask| {
+                    mask.expansion
+                        .as_ref()
+                        .map(|value| value.create_animation())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_masks(&self) -> &[Mask] {
+        &self.masks
+    }
+
+    /// Returns `true` if every mask is disabled.
+    pub fn are_all_masks_none(&self) -> bool {
+        self.masks.iter().all(|mask| mask.mode == MaskMode::None)
+    }
+
+    /// Returns the path of the mask at `index` at `frame`.
+    pub fn get_path(&self, index: usize, frame: f64) -> Path {
+        self.paths[index]
+            .value_at(frame)
+            .map(|shape| MiscUtils::get_path_from_data(&shape))
+            .unwrap_or_default()
+    }
+
+    /// Returns the opacity of the mask at `index` at `frame`, from `0.0` to
+    /// `1.0`.
+    pub fn get_opacity(&self, index: usize, frame: f64) -> f64 {
+        self.opacities[index]
+            .value_at(frame)
+            .map_or(1.0, |opacity| opacity as f64 / 100.0)
+    }
+
+    /// Returns how much the mask at `index` grows at `frame`, it shrinks when
+    /// negative.
+    pub fn get_expansion(&self, index: usize, frame: f64) -> f64 {
+        self.expansions[index]
+            .as_ref()
+            .and_then(|expansion| expansion.value_at(frame))
+            .unwrap_or(0.0)
+    }
+}
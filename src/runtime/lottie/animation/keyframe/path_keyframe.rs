@@ -1 +1,53 @@
-pub struct PathKeyframe;
+use crate::{
+    lottie::value::Keyframe,
+    path::{Path, PathMeasure},
+    Point,
+};
+
+/// A keyframe of a position which moves along a curve.
+///
+/// The curve starts at the start value, leaves it along the out tangent and
+/// reaches the end value along the in tangent. Positions are sampled by arc
+/// length, so the easing of the keyframe is the speed along the curve.
+#[derive(Clone, Debug)]
+pub struct PathKeyframe {
+    pub keyframe: Keyframe<Point<f64>>,
+    /// The measured curve, `None` when the position moves in a straight line.
+    measure: Option<PathMeasure>,
+}
+
+impl PathKeyframe {
+    pub fn new(keyframe: Keyframe<Point<f64>>) -> Self {
+        let measure = match (
+            keyframe.start_value,
+            keyframe.end_value,
+            keyframe.path_cp1,
+            keyframe.path_cp2,
+        ) {
+            (Some(start), Some(end), Some(cp1), Some(cp2))
+                if start != end && (cp1 != Point::new(0.0, 0.0) || cp2 != Point::new(0.0, 0.0)) =>
+            {
+                let mut path = Path::new();
+                path.push_move_to(start.x, start.y);
+                path.push_curve_to(
+                    start.x + cp1.x,
+                    start.y + cp1.y,
+                    end.x + cp2.x,
+                    end.y + cp2.y,
+                    end.x,
+                    end.y,
+                );
+                Some(PathMeasure::new(&path))
+            }
+            _ => None,
+        };
+        Self { keyframe, measure }
+    }
+
+    /// Returns the point at `progress` along the curve, `None` when the
+    /// position moves in a straight line.
+    pub fn point_at(&self, progress: f64) -> Option<Point<f64>> {
+        let measure = self.measure.as_ref()?;
+        measure.point_at_length(progress * measure.length())
+    }
+}
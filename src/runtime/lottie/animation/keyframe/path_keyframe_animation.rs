@@ -1 +1,87 @@
-pub struct PathKeyframeAnimation;
+use super::{callback_value, KeyframeAnimation, PathKeyframe};
+use crate::{
+    interpolate::Interpolate,
+    lottie::{
+        animation::expression::PropertyExpression,
+        value::{Keyframe, LottieValueCallback},
+    },
+    Point,
+};
+
+/// Moves a position along the curves of its keyframes.
+#[derive(Clone, Debug, Default)]
+pub struct PathKeyframeAnimation {
+    keyframes: Vec<PathKeyframe>,
+    expression: Option<PropertyExpression<Point<f64>>>,
+    value_callback: Option<LottieValueCallback<Point<f64>>>,
+}
+
+impl PathKeyframeAnimation {
+    pub fn new(keyframes: Vec<Keyframe<Point<f64>>>) -> Self {
+        Self {
+            keyframes: keyframes.into_iter().map(PathKeyframe::new).collect(),
+            expression: None,
+            value_callback: None,
+        }
+    }
+
+    pub fn with_expression(mut self, expression: Option<PropertyExpression<Point<f64>>>) -> Self {
+        self.expression = expression;
+        self
+    }
+
+    /// Returns the keyframe active at `frame`.
+    fn path_at(&self, frame: f64) -> Option<&PathKeyframe> {
+        let index = self
+            .keyframes
+            .iter()
+            .rposition(|path| path.keyframe.start_frame <= frame)
+            .unwrap_or(0);
+        self.keyframes.get(index)
+    }
+
+    /// Returns the position along the keyframes at `frame`.
+    fn value_of_keyframes(&self, frame: f64) -> Option<Point<f64>> {
+        let path = self.path_at(frame)?;
+        let keyframe = &path.keyframe;
+        let start = keyframe.start_value?;
+        let end = match keyframe.end_value {
+            Some(end) if !keyframe.hold => end,
+            _ => return Some(start),
+        };
+
+        let progress = keyframe.interpolated_progress(keyframe.interpolator.as_ref(), frame);
+        path.point_at(progress)
+            .or_else(|| Some(start.interpolate(&end, progress)))
+    }
+}
+
+impl KeyframeAnimation for PathKeyframeAnimation {
+    type Value = Point<f64>;
+
+    fn value_at(&self, frame: f64) -> Option<Point<f64>> {
+        let value = match &self.expression {
+            Some(expression) => {
+                let mut key_frames: Vec<f64> = self
+                    .keyframes
+                    .iter()
+                    .map(|path| path.keyframe.start_frame)
+                    .filter(|&frame| frame > f64::MIN)
+                    .collect();
+                key_frames.extend(
+                    self.keyframes
+                        .last()
+                        .and_then(|path| path.keyframe.end_frame),
+                );
+                expression.value_at(frame, &key_frames, |frame| self.value_of_keyframes(frame))
+            }
+            None => self.value_of_keyframes(frame),
+        };
+        let keyframe = self.path_at(frame).map(|path| &path.keyframe);
+        callback_value(self.value_callback.as_ref(), keyframe, frame, value)
+    }
+
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<Point<f64>>>) {
+        self.value_callback = callback;
+    }
+}
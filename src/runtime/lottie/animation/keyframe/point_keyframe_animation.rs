@@ -1 +1,81 @@
-pub struct PointKeyframeAnimation;
+use super::{callback_value, keyframe_index, KeyframeAnimation};
+use crate::{
+    interpolate::Interpolate,
+    lottie::{
+        animation::expression::{key_frames, PropertyExpression},
+        value::{Keyframe, LottieValueCallback},
+    },
+    Point,
+};
+
+/// Interpolates points, with an easing per axis when the keyframes have one.
+#[derive(Clone, Debug, Default)]
+pub struct PointKeyframeAnimation {
+    keyframes: Vec<Keyframe<Point<f64>>>,
+    expression: Option<PropertyExpression<Point<f64>>>,
+    value_callback: Option<LottieValueCallback<Point<f64>>>,
+}
+
+impl PointKeyframeAnimation {
+    pub fn new(keyframes: Vec<Keyframe<Point<f64>>>) -> Self {
+        Self {
+            keyframes,
+            expression: None,
+            value_callback: None,
+        }
+    }
+
+    pub fn with_expression(mut self, expression: Option<PropertyExpression<Point<f64>>>) -> Self {
+        self.expression = expression;
+        self
+    }
+
+    /// Returns the value of the keyframes at `frame`.
+    fn value_of_keyframes(&self, frame: f64) -> Option<Point<f64>> {
+        let keyframe = &self.keyframes[keyframe_index(&self.keyframes, frame)?];
+        Self::keyframe_value(keyframe, frame)
+    }
+
+    fn keyframe_value(keyframe: &Keyframe<Point<f64>>, frame: f64) -> Option<Point<f64>> {
+        let start = keyframe.start_value?;
+        let end = match keyframe.end_value {
+            Some(end) if !keyframe.hold => end,
+            _ => return Some(start),
+        };
+
+        match (&keyframe.x_interpolator, &keyframe.y_interpolator) {
+            (Some(x), Some(y)) => Some(Point::new(
+                start
+                    .x
+                    .interpolate(&end.x, keyframe.interpolated_progress(Some(x), frame)),
+                start
+                    .y
+                    .interpolate(&end.y, keyframe.interpolated_progress(Some(y), frame)),
+            )),
+            _ => {
+                let progress =
+                    keyframe.interpolated_progress(keyframe.interpolator.as_ref(), frame);
+                Some(start.interpolate(&end, progress))
+            }
+        }
+    }
+}
+
+impl KeyframeAnimation for PointKeyframeAnimation {
+    type Value = Point<f64>;
+
+    fn value_at(&self, frame: f64) -> Option<Point<f64>> {
+        let keyframe = keyframe_index(&self.keyframes, frame).map(|index| &self.keyframes[index]);
+        let value = match &self.expression {
+            Some(expression) => expression.value_at(frame, &key_frames(&self.keyframes), |frame| {
+                self.value_of_keyframes(frame)
+            }),
+            None => self.value_of_keyframes(frame),
+        };
+        callback_value(self.value_callback.as_ref(), keyframe, frame, value)
+    }
+
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<Point<f64>>>) {
+        self.value_callback = callback;
+    }
+}
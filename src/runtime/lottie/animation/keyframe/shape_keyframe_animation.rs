@@ -1 +1,4 @@
-pub struct ShapeKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+use crate::lottie::model::content::ShapeData;
+
+pub type ShapeKeyframeAnimation = BaseKeyframeAnimation<ShapeData>;
@@ -1 +1,36 @@
-pub struct SplitDimensionPathKeyframeAnimation;
+use super::{callback_value, DoubleKeyframeAnimation, KeyframeAnimation};
+use crate::{lottie::value::LottieValueCallback, Point};
+
+/// A position whose coordinates are animated separately.
+#[derive(Clone, Debug)]
+pub struct SplitDimensionPathKeyframeAnimation {
+    x: DoubleKeyframeAnimation,
+    y: DoubleKeyframeAnimation,
+    value_callback: Option<LottieValueCallback<Point<f64>>>,
+}
+
+impl SplitDimensionPathKeyframeAnimation {
+    pub fn new(x: DoubleKeyframeAnimation, y: DoubleKeyframeAnimation) -> Self {
+        Self {
+            x,
+            y,
+            value_callback: None,
+        }
+    }
+}
+
+impl KeyframeAnimation for SplitDimensionPathKeyframeAnimation {
+    type Value = Point<f64>;
+
+    fn value_at(&self, frame: f64) -> Option<Point<f64>> {
+        let value = Point::new(
+            self.x.value_at(frame).unwrap_or(0.0),
+            self.y.value_at(frame).unwrap_or(0.0),
+        );
+        callback_value(self.value_callback.as_ref(), None, frame, Some(value))
+    }
+
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<Point<f64>>>) {
+        self.value_callback = callback;
+    }
+}
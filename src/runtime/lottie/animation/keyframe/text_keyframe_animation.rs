@@ -1 +1,6 @@
-pub struct TextKeyframeAnimation;
+use super::BaseKeyframeAnimation;
+use crate::lottie::model::DocumentData;
+
+/// Text documents are not interpolated, each keyframe holds its document
+/// until the next one.
+pub type TextKeyframeAnimation = BaseKeyframeAnimation<DocumentData>;
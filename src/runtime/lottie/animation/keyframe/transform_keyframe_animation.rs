@@ -1 +1,230 @@
-pub struct TransformKeyframeAnimation;
+use super::{
+    set_optional_value_callback, DoubleKeyframeAnimation, IntegerKeyframeAnimation,
+    KeyframeAnimation, PathKeyframeAnimation, PointKeyframeAnimation,
+    ValueCallbackKeyframeAnimation,
+};
+use crate::{
+    lottie::{
+        model::animatable::{AnimatableTransform, AnimatableValue},
+        LottieProperty,
+    },
+    path, Point, Transform, Vector,
+};
+
+/// Animates the transform of a layer, a shape group or a repeater copy.
+pub struct TransformKeyframeAnimation {
+    anchor_point: Option<PathKeyframeAnimation>,
+    position: Option<Box<dyn KeyframeAnimation<Value = Point<f64>>>>,
+    scale: Option<PointKeyframeAnimation>,
+    rotation: Option<DoubleKeyframeAnimation>,
+    opacity: Option<IntegerKeyframeAnimation>,
+    skew: Option<DoubleKeyframeAnimation>,
+    skew_angle: Option<DoubleKeyframeAnimation>,
+    start_opacity: Option<DoubleKeyframeAnimation>,
+    end_opacity: Option<DoubleKeyframeAnimation>,
+}
+
+impl TransformKeyframeAnimation {
+    pub fn new(transform: &AnimatableTransform) -> Self {
+        Self {
+            anchor_point: transform.anchor_point.as_ref().map(|value| {
+                PathKeyframeAnimation::new(value.keyframes().to_vec())
+                    .with_expression(value.get_expression().cloned())
+            }),
+            position: transform
+                .position
+                .as_ref()
+                .map(|value| value.create_animation()),
+            scale: transform.scale.as_ref().map(|value| {
+                PointKeyframeAnimation::new(value.keyframes().to_vec())
+                    .with_expression(value.get_expression().cloned())
+            }),
+            rotation: transform
+                .rotation
+                .as_ref()
+                .map(|value| value.create_animation()),
+            opacity: transform
+                .opacity
+                .as_ref()
+                .map(|value| value.create_animation()),
+            skew: transform
+                .skew
+                .as_ref()
+                .map(|value| value.create_animation()),
+            skew_angle: transform
+                .skew_angle
+                .as_ref()
+                .map(|value| value.create_animation()),
+            start_opacity: transform
+                .start_opacity
+                .as_ref()
+                .map(|value| value.create_animation()),
+            end_opacity: transform
+                .end_opacity
+                .as_ref()
+                .map(|value| value.create_animation()),
+        }
+    }
+
+    /// Overrides the property of the transform with the callback of
+    /// `property`, returns `false` if it is not a transform property.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) -> bool {
+        match property {
+            LottieProperty::TransformAnchorPoint(callback) => {
+                set_optional_value_callback(&mut self.anchor_point, callback)
+            }
+            LottieProperty::TransformPosition(callback) => match &mut self.position {
+                Some(position) => position.set_value_callback(Some(callback.clone())),
+                None => {
+                    let position = ValueCallbackKeyframeAnimation::new(callback.clone());
+                    self.position = Some(Box::new(position));
+                }
+            },
+            LottieProperty::TransformScale(callback) => {
+                set_optional_value_callback(&mut self.scale, callback)
+            }
+            LottieProperty::TransformRotation(callback) => {
+                set_optional_value_callback(&mut self.rotation, callback)
+            }
+            LottieProperty::TransformOpacity(callback) => {
+                set_optional_value_callback(&mut self.opacity, callback)
+            }
+            LottieProperty::TransformSkew(callback) => {
+                set_optional_value_callback(&mut self.skew, callback)
+            }
+            LottieProperty::TransformSkewAngle(callback) => {
+                set_optional_value_callback(&mut self.skew_angle, callback)
+            }
+            LottieProperty::TransformStartOpacity(callback) => {
+                set_optional_value_callback(&mut self.start_opacity, callback)
+            }
+            LottieProperty::TransformEndOpacity(callback) => {
+                set_optional_value_callback(&mut self.end_opacity, callback)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Returns the opacity at `frame`, from `0.0` to `1.0`.
+    pub fn get_opacity(&self, frame: f64) -> f64 {
+        self.opacity
+            .as_ref()
+            .and_then(|opacity| opacity.value_at(frame))
+            .map_or(1.0, |opacity| opacity as f64 / 100.0)
+    }
+
+    /// Returns the opacity of the first copy of a repeater at `frame`, from
+    /// `0.0` to `1.0`.
+    pub fn get_start_opacity(&self, frame: f64) -> f64 {
+        self.start_opacity
+            .as_ref()
+            .and_then(|opacity| opacity.value_at(frame))
+            .map_or(1.0, |opacity| opacity / 100.0)
+    }
+
+    /// Returns the opacity of the last copy of a repeater at `frame`, from
+    /// `0.0` to `1.0`.
+    pub fn get_end_opacity(&self, frame: f64) -> f64 {
+        self.end_opacity
+            .as_ref()
+            .and_then(|opacity| opacity.value_at(frame))
+            .map_or(1.0, |opacity| opacity / 100.0)
+    }
+
+    /// Returns the matrix of a repeater copy at `frame`, the transform
+    /// applied `amount` times.
+    ///
+    /// The copy is rotated around the anchor point, then scaled and moved.
+    pub fn get_matrix_for_repeater(&self, amount: f64, frame: f64) -> Transform<f64> {
+        let mut matrix = Transform::identity();
+
+        if let Some(position) = self.position.as_ref().and_then(|p| p.value_at(frame)) {
+            matrix = matrix.pre_translate(Vector::new(position.x * amount, position.y * amount));
+        }
+
+        if let Some(scale) = self.scale.as_ref().and_then(|scale| scale.value_at(frame)) {
+            matrix = matrix.pre_scale(scale.x.powf(amount), scale.y.powf(amount));
+        }
+
+        let rotation = self
+            .rotation
+            .as_ref()
+            .and_then(|rotation| rotation.value_at(frame))
+            .unwrap_or(0.0);
+        if rotation != 0.0 {
+            let anchor = self
+                .anchor_point
+                .as_ref()
+                .and_then(|anchor| anchor.value_at(frame))
+                .unwrap_or_else(|| Point::new(0.0, 0.0));
+            let rotate = path::rotation(rotation * amount);
+            matrix = rotate
+                .pre_translate(Vector::new(-anchor.x, -anchor.y))
+                .then(&matrix.pre_translate(Vector::new(anchor.x, anchor.y)));
+        }
+
+        matrix
+    }
+
+    /// Returns the matrix at `frame`.
+    ///
+    /// The anchor point is moved to the origin, then the layer is scaled,
+    /// skewed, rotated and moved to its position.
+    pub fn get_matrix(&self, frame: f64) -> Transform<f64> {
+        let mut matrix = Transform::identity();
+
+        if let Some(position) = self.position.as_ref().and_then(|p| p.value_at(frame)) {
+            matrix = matrix.pre_translate(Vector::new(position.x, position.y));
+        }
+
+        let rotation = self
+            .rotation
+            .as_ref()
+            .and_then(|rotation| rotation.value_at(frame))
+            .unwrap_or(0.0);
+        if rotation != 0.0 {
+            matrix = path::rotation(rotation).then(&matrix);
+        }
+
+        let skew = self
+            .skew
+            .as_ref()
+            .and_then(|skew| skew.value_at(frame))
+            .unwrap_or(0.0);
+        if skew != 0.0 {
+            let angle = self
+                .skew_angle
+                .as_ref()
+                .and_then(|angle| angle.value_at(frame));
+            // the skew is along the Y axis turned to the skew angle
+            let (sin, cos) = match angle {
+                Some(angle) => (90.0 - angle).to_radians().sin_cos(),
+                None => (1.0, 0.0),
+            };
+            let tan = skew.to_radians().tan();
+            let rotate = Transform::new(cos, sin, -sin, cos, 0.0, 0.0);
+            let skew = Transform::new(1.0, tan, 0.0, 1.0, 0.0, 0.0);
+            let unrotate = Transform::new(cos, -sin, sin, cos, 0.0, 0.0);
+            matrix = unrotate.then(&skew).then(&rotate).then(&matrix);
+        }
+
+        if let Some(scale) = self.scale.as_ref().and_then(|scale| scale.value_at(frame)) {
+            if scale != Point::new(1.0, 1.0) {
+                matrix = matrix.pre_scale(scale.x, scale.y);
+            }
+        }
+
+        if let Some(anchor) = self
+            .anchor_point
+            .as_ref()
+            .and_then(|anchor| anchor.value_at(frame))
+        {
+            if anchor != Point::new(0.0, 0.0) {
+                matrix = matrix.pre_translate(Vector::new(-anchor.x, -anchor.y));
+            }
+        }
+
+        matrix
+    }
+}
@@ -1 +1,30 @@
-pub struct ValueCallbackKeyframeAnimation;
+use super::KeyframeAnimation;
+use crate::lottie::value::{LottieFrameInfo, LottieValueCallback};
+
+/// The animation of a property missing from the composition, its values
+/// only come from its value callback.
+#[derive(Clone, Debug)]
+pub struct ValueCallbackKeyframeAnimation<T> {
+    value_callback: Option<LottieValueCallback<T>>,
+}
+
+impl<T> ValueCallbackKeyframeAnimation<T> {
+    pub fn new(callback: LottieValueCallback<T>) -> Self {
+        Self {
+            value_callback: Some(callback),
+        }
+    }
+}
+
+impl<T: Clone> KeyframeAnimation for ValueCallbackKeyframeAnimation<T> {
+    type Value = T;
+
+    fn value_at(&self, frame: f64) -> Option<T> {
+        let frame_info = LottieFrameInfo::new(None, frame, None);
+        self.value_callback.as_ref()?.get_value(&frame_info)
+    }
+
+    fn set_value_callback(&mut self, callback: Option<LottieValueCallback<T>>) {
+        self.value_callback = callback;
+    }
+}
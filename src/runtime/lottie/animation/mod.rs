@@ -1,2 +1,3 @@
 pub(crate) mod content;
+pub(crate) mod expression;
 pub(crate) mod keyframe;
@@ -1,128 +1,289 @@
-use crate::Rect;
+use crate::{Point, Rect, Size};
 use bytes::Bytes;
-use std::{collections::HashMap, fmt, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+    time::Duration,
+};
 
 use super::{
-    model::{layer::Layer, Font, FontCharacter, Marker},
-    LottieImageAsset,
+    model::{
+        layer::{Layer, LayerType},
+        Font, FontCharacter, Marker,
+    },
+    parser::LottieCompositionParser,
+    providers::LottieImageProviderFactory,
+    FrameRate, LottieError, LottieImageAsset, PerformanceTracker,
 };
 
-#[derive(Default, Clone)]
-pub struct CompositionParameters;
+/// The parsed contents of a composition.
+#[derive(Clone)]
+pub struct CompositionParameters {
+    pub bounds: Rect<i32>,
+    pub start_frame: f64,
+    pub end_frame: f64,
+    pub frame_rate: f64,
+    pub layers: Vec<Layer>,
+    /// Map of layer ids to their index in `layers`.
+    pub layer_map: HashMap<i64, usize>,
+    /// Map of precomposition ids to their layers.
+    pub precomps: HashMap<String, Vec<Layer>>,
+    /// Map of image ids to images.
+    pub images: HashMap<String, LottieImageAsset>,
+    /// Map of character hashes to glyphs.
+    pub characters: HashMap<u64, FontCharacter>,
+    /// Map of font names to fonts.
+    pub fonts: HashMap<String, Font>,
+    pub markers: Vec<Marker>,
+}
 
+impl Default for CompositionParameters {
+    fn default() -> Self {
+        Self {
+            bounds: Rect {
+                origin: Point::new(0, 0),
+                size: Size::new(0, 0),
+            },
+            start_frame: 0.0,
+            end_frame: 0.0,
+            frame_rate: 0.0,
+            layers: Vec::new(),
+            layer_map: HashMap::new(),
+            precomps: HashMap::new(),
+            images: HashMap::new(),
+            characters: HashMap::new(),
+            fonts: HashMap::new(),
+            markers: Vec::new(),
+        }
+    }
+}
+
+/// A parsed Lottie animation.
+///
+/// Clones are cheap and share the same contents and warnings.
 #[derive(Default, Clone)]
 pub struct LottieComposition {
     name: Option<String>,
-    performance_tracker: Option<String>, // Some trash
+    performance_tracker: Rc<PerformanceTracker>,
     /// This is stored as a set to avoid duplicates.
-    warnings: Vec<String>,
-    /// Map of font names to fonts
-    parameters: CompositionParameters,
+    warnings: Rc<RefCell<Vec<String>>>,
+    parameters: Rc<CompositionParameters>,
     /// Used to determine if an animation can be drawn with hardware acceleration.
-    has_dash_pattern: bool, // false
+    has_dash_pattern: Rc<Cell<bool>>,
     /// Counts the number of mattes and masks. Before Android switched to SKIA
     /// for drawing in Oreo (API 28), using hardware acceleration with mattes and masks
     /// was only faster until you had ~4 masks after which it would actually become slower.
-    mask_and_matte_count: u32,
+    mask_and_matte_count: Rc<Cell<u32>>,
 }
 
 impl LottieComposition {
-    fn from_byte_data(data: &Bytes) -> Self {
-        unimplemented!()
+    /// Parses a composition from the bytes of a Bodymovin JSON file.
+    ///
+    /// Unsupported features do not fail the parsing, they are listed in the
+    /// warnings of the composition.
+    pub fn from_byte_data(data: &Bytes) -> Result<Self, LottieError> {
+        Self::from_bytes(data)
+    }
+
+    /// Parses a composition from the bytes of a Bodymovin JSON file.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LottieError> {
+        let json: serde_json::Value = serde_json::from_slice(data)?;
+        Self::from_json(&json)
+    }
+
+    /// Parses a composition from Bodymovin JSON.
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, LottieError> {
+        LottieCompositionParser::parse(json)
+    }
+
+    pub(crate) fn set_parameters(
+        &mut self,
+        name: Option<String>,
+        parameters: CompositionParameters,
+    ) {
+        self.name = name;
+        self.parameters = Rc::new(parameters);
+    }
+
+    /// Returns the name of the composition.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn add_warning(&self, warning: &str) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.iter().any(|w| w == warning) {
+            warn!("{}", warning);
+            warnings.push(warning.into());
+        }
+    }
+
+    pub fn increment_matte_or_mask_count(&self, amount: u32) {
+        self.mask_and_matte_count
+            .set(self.mask_and_matte_count.get() + amount);
+    }
+
+    pub fn get_mask_and_matte_count(&self) -> u32 {
+        self.mask_and_matte_count.get()
+    }
+
+    pub fn set_has_dash_pattern(&self, has_dash_pattern: bool) {
+        self.has_dash_pattern.set(has_dash_pattern);
+    }
+
+    pub fn has_dash_pattern(&self) -> bool {
+        self.has_dash_pattern.get()
+    }
+
+    /// Returns the unsupported features met while parsing or playing the
+    /// composition.
+    pub fn get_warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
     }
 
-    fn from_bytes(data: &Bytes) -> Self {
-        unimplemented!()
+    pub fn get_performance_tracking_enabled(&self) -> bool {
+        self.performance_tracker.is_enabled()
     }
 
-    fn add_warning(&self, warning: &str) {
-        unimplemented!()
+    /// Sets whether the render times of the layers are tracked, the
+    /// tracking is shared by the clones of the composition.
+    pub fn set_performance_tracking_enabled(&self, enabled: bool) {
+        self.performance_tracker.set_enabled(enabled);
     }
 
-    fn increment_matte_or_mask_count(&self, amount: u32) {
-        unimplemented!()
+    pub fn get_performance_tracker(&self) -> Rc<PerformanceTracker> {
+        self.performance_tracker.clone()
     }
 
-    fn get_mask_and_matte_count(&self) -> u32 {
-        unimplemented!()
+    pub fn layer_model_for_id(&self, id: i64) -> Option<&Layer> {
+        self.parameters
+            .layer_map
+            .get(&id)
+            .map(|&index| &self.parameters.layers[index])
     }
 
-    fn get_warnings(&self) -> Vec<String> {
-        unimplemented!()
+    pub fn get_bounds(&self) -> Rect<i32> {
+        self.parameters.bounds
     }
 
-    fn get_performance_tracking_enabled(&self) -> bool {
-        unimplemented!()
+    pub fn get_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.get_seconds())
     }
 
-    fn set_performance_tracking_enabled(enabled: bool) {
-        unimplemented!()
+    pub fn get_seconds(&self) -> f64 {
+        if self.parameters.frame_rate > 0.0 {
+            self.get_duration_frames() / self.parameters.frame_rate
+        } else {
+            0.0
+        }
     }
 
-    fn get_performance_tracker(&self) -> Option<String> {
-        unimplemented!()
+    pub fn get_start_frame(&self) -> f64 {
+        self.parameters.start_frame
     }
 
-    fn layer_model_for_id(&self, id: u32) {
-        unimplemented!()
+    pub fn get_end_frame(&self) -> f64 {
+        self.parameters.end_frame
     }
 
-    fn get_bounds(&self) -> Rect<i32> {
-        unimplemented!()
+    pub fn get_duration_frames(&self) -> f64 {
+        self.parameters.end_frame - self.parameters.start_frame
     }
 
-    fn get_duration(&self) -> Duration {
-        unimplemented!()
+    pub fn get_frame_rate(&self) -> f64 {
+        self.parameters.frame_rate
     }
 
-    fn get_seconds(&self) -> f64 {
-        unimplemented!()
+    pub fn get_layers(&self) -> &[Layer] {
+        &self.parameters.layers
     }
 
-    fn get_start_frame(&self) -> f64 {
-        unimplemented!()
+    pub fn get_precomps(&self, id: &str) -> Option<&[Layer]> {
+        self.parameters.precomps.get(id).map(Vec::as_slice)
     }
 
-    fn get_end_frame(&self) -> f64 {
-        unimplemented!()
+    /// Returns `true` if the composition or one of its precompositions has
+    /// a text layer.
+    pub fn has_text_layers(&self) -> bool {
+        let parameters = &self.parameters;
+        parameters
+            .layers
+            .iter()
+            .chain(parameters.precomps.values().flatten())
+            .any(|layer| layer.layer_type == LayerType::Text)
     }
 
-    fn get_layers(&self) -> Vec<Layer> {
-        unimplemented!()
+    pub fn get_characters(&self) -> &HashMap<u64, FontCharacter> {
+        &self.parameters.characters
     }
 
-    fn get_precomps(id: String) -> Vec<Layer> {
-        unimplemented!()
+    pub fn get_fonts(&self) -> &HashMap<String, Font> {
+        &self.parameters.fonts
     }
 
-    fn get_characters(&self) -> HashMap<String, FontCharacter> {
-        unimplemented!()
+    pub fn get_markers(&self) -> &[Marker] {
+        &self.parameters.markers
     }
 
-    fn get_fonts(&self) -> HashMap<String, Font> {
-        unimplemented!()
+    pub fn get_marker(&self, marker_name: &str) -> Option<&Marker> {
+        self.parameters
+            .markers
+            .iter()
+            .find(|marker| marker.matches_name(marker_name))
     }
 
-    fn get_marker(&self, marker_name: &str) -> Option<Marker> {
-        unimplemented!()
+    pub fn has_images(&self) -> bool {
+        !self.parameters.images.is_empty()
     }
 
-    fn has_images(&self) {
-        unimplemented!()
+    pub fn images(&self) -> &HashMap<String, LottieImageAsset> {
+        &self.parameters.images
     }
 
-    fn images(&self) -> HashMap<String, LottieImageAsset> {
-        unimplemented!()
+    /// Loads the images of the image assets with `factory`, the images
+    /// which can't be loaded are left out with a warning.
+    ///
+    /// Drawables created after this draw the images.
+    pub fn load_images(&mut self, factory: &LottieImageProviderFactory) {
+        let mut missing = Vec::new();
+        let parameters = Rc::make_mut(&mut self.parameters);
+        for (id, image) in parameters.images.iter_mut() {
+            image.set_loaded_image(factory.load_image(image));
+            if image.get_loaded_image().is_none() {
+                missing.push(id.clone());
+            }
+        }
+        missing.sort();
+        for id in missing {
+            self.add_warning(&format!("Unable to load image {}", id));
+        }
     }
 
-    /// Returns a "rounded" progress value according to the frameRate
-    fn round_progress(&self, progress: f64) {
-        unimplemented!()
+    /// Returns `progress` rounded down to the last frame shown at
+    /// `frame_rate`.
+    pub fn round_progress(&self, progress: f64, frame_rate: FrameRate) -> f64 {
+        let frames_per_second = match frame_rate {
+            FrameRate::Max => return progress,
+            FrameRate::Composition => self.get_frame_rate(),
+            FrameRate::Fps(frames_per_second) => frames_per_second,
+        };
+        let frame_count = self.get_seconds() * frames_per_second;
+        if frame_count <= 0.0 || !frame_count.is_finite() {
+            return progress;
+        }
+        // a progress computed from a frame lands on it despite rounding errors
+        (frame_count * progress + 1e-6).floor() / frame_count
     }
 }
 
 impl fmt::Display for LottieComposition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "LottieComposition")
+        writeln!(f, "LottieComposition:")?;
+        for layer in self.get_layers() {
+            writeln!(f, "\t{}", layer.name)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,84 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+/// List of errors of the Lottie runtime.
+#[derive(Debug)]
+pub enum LottieError {
+    /// The data is not valid JSON.
+    Json(serde_json::Error),
+
+    /// The JSON is not a Lottie composition.
+    InvalidComposition(String),
+
+    /// The data is not a font.
+    InvalidFont(String),
+
+    /// The data of a composition can't be loaded.
+    Load(String),
+
+    /// The data is not a dotLottie file.
+    InvalidDotLottie(String),
+
+    /// The composition has no marker of this name.
+    UnknownMarker(String),
+
+    /// The expression uses syntax or names which are not supported.
+    UnsupportedExpression(String),
+
+    /// The composition can't be drawn into an image.
+    Render(String),
+
+    /// An image of a composition can't be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for LottieError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LottieError::Json(ref error) => write!(f, "invalid JSON: {}", error),
+            LottieError::InvalidComposition(ref reason) => {
+                write!(f, "invalid composition: {}", reason)
+            }
+            LottieError::InvalidFont(ref reason) => write!(f, "invalid font: {}", reason),
+            LottieError::Load(ref reason) => write!(f, "unable to load: {}", reason),
+            LottieError::InvalidDotLottie(ref reason) => {
+                write!(f, "invalid dotLottie: {}", reason)
+            }
+            LottieError::UnknownMarker(ref name) => write!(f, "unknown marker: {}", name),
+            LottieError::UnsupportedExpression(ref reason) => {
+                write!(f, "unsupported expression: {}", reason)
+            }
+            LottieError::Render(ref reason) => write!(f, "unable to render: {}", reason),
+            LottieError::Io(ref error) => write!(f, "I/O error: {}", error),
+        }
+    }
+}
+
+impl error::Error for LottieError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            LottieError::Json(ref error) => Some(error),
+            LottieError::Io(ref error) => Some(error),
+            LottieError::InvalidComposition(_)
+            | LottieError::InvalidFont(_)
+            | LottieError::Load(_)
+            | LottieError::InvalidDotLottie(_)
+            | LottieError::UnknownMarker(_)
+            | LottieError::UnsupportedExpression(_)
+            | LottieError::Render(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for LottieError {
+    fn from(error: serde_json::Error) -> Self {
+        LottieError::Json(error)
+    }
+}
+
+impl From<io::Error> for LottieError {
+    fn from(error: io::Error) -> Self {
+        LottieError::Io(error)
+    }
+}
@@ -1,2 +1,12 @@
-#[derive(Default, Clone)]
-pub struct FrameRate;
+/// The rate at which the progress of a composition moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FrameRate {
+    /// The frame rate of the composition, the progress only moves from one
+    /// of its frames to the next.
+    #[default]
+    Composition,
+    /// The progress moves on every tick.
+    Max,
+    /// A number of frames per second.
+    Fps(f64),
+}
@@ -1,2 +1,32 @@
+use super::{LottieFontProvider, ValueDelegate};
+use std::rc::Rc;
+
+/// Returns the text drawn instead of the text of a composition.
+pub type TextDelegate = Rc<dyn Fn(&str) -> String>;
+
+/// Callbacks customizing a composition when it is drawn.
 #[derive(Default, Clone)]
-pub struct LottieDelegates;
+pub struct LottieDelegates {
+    /// Replaces the text of the text layers, given the text of the
+    /// composition.
+    ///
+    /// The new text may use characters without glyphs in the composition,
+    /// so text layers are drawn with fonts when it is set.
+    pub text: Option<TextDelegate>,
+    /// Resolves the fonts of the text layers drawn without glyphs, the
+    /// canvas measures the text without it.
+    pub fonts: Option<Rc<dyn LottieFontProvider>>,
+    /// Override the properties of the contents matching their key paths, a
+    /// later delegate wins over an earlier one for the same property.
+    pub values: Vec<ValueDelegate>,
+}
+
+impl LottieDelegates {
+    /// Returns the text drawn instead of `text`.
+    pub fn get_text(&self, text: &str) -> String {
+        match &self.text {
+            Some(delegate) => delegate(text),
+            None => text.into(),
+        }
+    }
+}
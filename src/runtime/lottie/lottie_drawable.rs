@@ -1,13 +1,15 @@
-use crate::{Canvas, Pattern, Rect, Size};
+use crate::{OffscreenContext, Rect, Size};
 // use dx::Matrix; // not available in wasm
 // use crate::Image; // not available in wasm
-use primitives::CanvasContext;
 
 use super::{
-    model::{layer::CompositionLayer, KeyPath},
-    LottieComposition, LottieDelegates,
+    model::{layer::CompositionLayer, KeyPath, KeyPathElement},
+    LottieComposition, LottieDelegates, LottieProperty, ValueDelegate,
 };
+use std::collections::HashSet;
 
+/// The family and the style of a font of a composition.
+#[derive(Clone, Debug, PartialEq)]
 pub struct LottieFontStyle {
     font_family: String,
     style: String,
@@ -20,15 +22,30 @@ impl LottieFontStyle {
             style: style.into(),
         }
     }
+
+    pub fn get_font_family(&self) -> &str {
+        &self.font_family
+    }
+
+    /// Returns the style of the font, such as `"Bold Italic"`.
+    pub fn get_style(&self) -> &str {
+        &self.style
+    }
 }
 
+/// Draws a composition at a given progress onto any canvas.
 pub struct LottieDrawable {
     composition: LottieComposition,
+    /// The progress from the start to the end of the composition, from
+    /// `0.0` to `1.0`.
+    progress: f64,
     // matrix: Matrix,// not available in wasm
     composition_layer: CompositionLayer,
     size: Size<f64>,
     delegates: LottieDelegates,
     is_dirty: bool,
+    /// Whether merge paths are drawn, their paths are drawn unmerged
+    /// otherwise.
     enable_merge_paths: bool,
     /// Gives a suggestion whether to paint with anti-aliasing, or not.
     /// Default is true.
@@ -48,24 +65,104 @@ pub struct LottieDrawable {
 }
 
 impl LottieDrawable {
+    pub fn new(composition: LottieComposition) -> Self {
+        let composition_layer = CompositionLayer::new(
+            composition.get_layers(),
+            &composition,
+            false,
+            &mut HashSet::new(),
+        );
+        let bounds = composition.get_bounds();
+        Self {
+            size: Size::new(bounds.size.width as f64, bounds.size.height as f64),
+            composition,
+            progress: 0.0,
+            composition_layer,
+            delegates: LottieDelegates::default(),
+            is_dirty: false,
+            enable_merge_paths: false,
+            anti_aliasing_suggested: true,
+            is_applying_opacity_to_layers_enabled: false,
+        }
+    }
+
+    pub fn get_composition(&self) -> &LottieComposition {
+        &self.composition
+    }
+
     pub fn get_progress(&self) -> f64 {
-        unimplemented!()
+        self.progress
+    }
+
+    /// Sets the progress, clamped to `0.0..=1.0`, and returns `true` if it
+    /// changed.
+    pub fn set_progress(&mut self, value: f64) -> bool {
+        let value = value.clamp(0.0, 1.0);
+        if value == self.progress {
+            return false;
+        }
+        self.progress = value;
+        self.is_dirty = true;
+        true
+    }
+
+    /// Returns the frame of the current progress.
+    pub fn get_frame(&self) -> f64 {
+        self.composition.get_start_frame() + self.progress * self.composition.get_duration_frames()
     }
 
-    pub fn set_progress(&self, value: f64) -> f64 {
-        unimplemented!()
+    /// Sets the progress to `frame` and returns `true` if it changed.
+    pub fn set_frame(&mut self, frame: f64) -> bool {
+        let duration = self.composition.get_duration_frames();
+        if duration <= 0.0 {
+            return self.set_progress(0.0);
+        }
+        self.set_progress((frame - self.composition.get_start_frame()) / duration)
+    }
+
+    pub fn get_delegates(&self) -> &LottieDelegates {
+        &self.delegates
+    }
+
+    /// Sets the delegates, the value delegates replace the ones set before.
+    pub fn set_delegates(&mut self, delegates: LottieDelegates) {
+        self.update_value_delegates(&delegates.values);
+        self.delegates = delegates;
+        self.is_dirty = true;
     }
 
-    pub fn get_delegates(&self) -> LottieDelegates {
-        unimplemented!()
+    pub fn get_enable_merge_paths(&self) -> bool {
+        self.enable_merge_paths
     }
 
-    pub fn set_delegates(&self, delegates: LottieDelegates) -> LottieDelegates {
-        unimplemented!()
+    /// Sets whether merge paths are drawn, they are disabled by default.
+    ///
+    /// Merging paths is expensive, the paths of a disabled merge are drawn
+    /// as they are.
+    pub fn set_enable_merge_paths(&mut self, enable: bool) {
+        if enable == self.enable_merge_paths {
+            return;
+        }
+        self.enable_merge_paths = enable;
+        let values = std::mem::take(&mut self.delegates.values);
+        self.update_value_delegates(&values);
+        self.delegates.values = values;
+        self.is_dirty = true;
     }
 
+    /// Returns `true` if text is drawn with the glyphs embedded in the
+    /// composition, `false` if it is drawn with fonts.
+    ///
+    /// Fonts are used when the text is replaced by a delegate, the new text
+    /// may use characters without glyphs.
     pub fn get_use_text_glyphs(&self) -> bool {
-        unimplemented!()
+        let replaces_text = self.delegates.text.is_some()
+            || self
+                .delegates
+                .values
+                .iter()
+                .any(|value| matches!(value.get_property(), LottieProperty::Text(_)));
+        !replaces_text && !self.composition.get_characters().is_empty()
     }
 
     // pub fn get_image_asset(&self, key: &str) -> Image {
@@ -76,23 +173,54 @@ impl LottieDrawable {
     //     unimplemented!()
     // }
 
-    // fn update_value_delegates(&self, new_delegates: Vec<ValueDelegate>) {
-    //     unimplemented!()
-    // }
+    /// Creates the layers again and sets the callbacks of `new_delegates`
+    /// on them, so the callbacks of removed delegates are dropped.
+    fn update_value_delegates(&mut self, new_delegates: &[ValueDelegate]) {
+        let composition = &self.composition;
+        self.composition_layer = CompositionLayer::new(
+            composition.get_layers(),
+            composition,
+            self.enable_merge_paths,
+            &mut HashSet::new(),
+        );
+        for delegate in new_delegates {
+            self.composition_layer.add_value_callback(
+                delegate.get_key_path(),
+                0,
+                delegate.get_property(),
+            );
+        }
+    }
 
-    /// Takes a {@link KeyPath}, potentially with wildcards or globstars and resolve it to a list of
-    /// zero or more actual {@link KeyPath Keypaths} that exist in the current animation.
-    /// <p>
-    /// If you want to set value callbacks for any of these values, it is recommend to use the
-    /// returned {@link KeyPath} objects because they will be internally resolved to their content
-    /// and won't trigger a tree walk of the animation contents when applied.
-    fn resolve_key_path(&self, key_path: KeyPath) -> Vec<KeyPath> {
-        unimplemented!()
+    /// Takes a [`KeyPath`], potentially with wildcards or globstars and
+    /// resolves it to a list of zero or more actual key paths that exist in
+    /// the current animation.
+    ///
+    /// The resolved key paths only hold names, they can be listed to find
+    /// the names of the contents or to set value delegates on them.
+    pub fn resolve_key_path(&self, key_path: &KeyPath) -> Vec<KeyPath> {
+        let mut key_paths = Vec::new();
+        self.composition_layer
+            .resolve_key_path(key_path, 0, &mut key_paths, &KeyPath::default());
+        key_paths
     }
 
-    // )))
+    /// Draws the composition at the current progress, stretched to fill
+    /// `rect`.
     // should add `fit` and `alignment`
-    pub fn draw(&self, canvas: Canvas, rect: Rect<f64>) {
-        unimplemented!()
+    pub fn draw<C: OffscreenContext>(&self, canvas: &C, rect: Rect<f64>) {
+        if self.size.width <= 0.0 || self.size.height <= 0.0 {
+            return;
+        }
+
+        canvas.save();
+        canvas.translate(rect.origin.x, rect.origin.y);
+        canvas.scale(
+            rect.size.width / self.size.width,
+            rect.size.height / self.size.height,
+        );
+        self.composition_layer
+            .draw(canvas, &self.delegates, 1.0, self.get_frame());
+        canvas.restore();
     }
 }
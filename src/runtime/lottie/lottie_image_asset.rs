@@ -1,13 +1,60 @@
-use std::fmt;
-// use crate::Image;// not available in wasm
+use crate::Bitmap;
+use std::{fmt, rc::Rc};
 
+/// An image asset of a composition.
+#[derive(Clone, Debug, PartialEq)]
 pub struct LottieImageAsset {
     width: i32,
     height: i32,
     id: String,
     file_name: String,
     dir_name: String,
-    // loaded_image: Image,
+    /// The image loaded by
+    /// [`LottieComposition::load_images`](super::LottieComposition::load_images).
+    loaded_image: Option<Rc<Bitmap>>,
+}
+
+impl LottieImageAsset {
+    pub fn new(width: i32, height: i32, id: &str, file_name: &str, dir_name: &str) -> Self {
+        Self {
+            width,
+            height,
+            id: id.into(),
+            file_name: file_name.into(),
+            dir_name: dir_name.into(),
+            loaded_image: None,
+        }
+    }
+
+    pub fn get_width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the file name of the image, or a data URI when the image is
+    /// embedded in the composition.
+    pub fn get_file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    pub fn get_dir_name(&self) -> &str {
+        &self.dir_name
+    }
+
+    pub fn get_loaded_image(&self) -> Option<&Rc<Bitmap>> {
+        self.loaded_image.as_ref()
+    }
+
+    pub fn set_loaded_image(&mut self, image: Option<Bitmap>) {
+        self.loaded_image = image.map(Rc::new);
+    }
 }
 
 impl fmt::Display for LottieImageAsset {
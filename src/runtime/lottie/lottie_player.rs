@@ -0,0 +1,487 @@
+use super::{model::Marker, FrameRate, LottieComposition, LottieError};
+use crate::tween::{fire_callback, Group, Lifecycle, Playable};
+use std::{cell::RefCell, fmt, rc::Rc};
+
+type MarkerCallback = Box<dyn FnMut(&Marker)>;
+
+struct PlayerState {
+    lifecycle: Lifecycle,
+    composition: LottieComposition,
+    frame_rate: FrameRate,
+    speed: f64,
+    repeat: f64,
+    initial_repeat: f64,
+    yoyo: bool,
+    /// The segment played, in frames of the composition.
+    min_frame: f64,
+    max_frame: f64,
+    /// The frame of the playhead, not rounded to the frame rate.
+    frame: f64,
+    /// Whether yoyo has turned the playback around.
+    reversed: bool,
+    last_time: f64,
+    on_update: Option<Box<dyn FnMut(f64)>>,
+    on_marker: Option<MarkerCallback>,
+    on_complete: Option<Box<dyn FnMut()>>,
+}
+
+impl PlayerState {
+    fn is_forward(&self) -> bool {
+        (self.speed >= 0.0) != self.reversed
+    }
+
+    /// Returns the frame the playback starts from.
+    fn start_frame(&self) -> f64 {
+        if self.speed >= 0.0 {
+            self.min_frame
+        } else {
+            self.max_frame
+        }
+    }
+
+    fn progress(&self) -> f64 {
+        let duration = self.composition.get_duration_frames();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+        let progress = (self.frame - self.composition.get_start_frame()) / duration;
+        self.composition.round_progress(progress, self.frame_rate)
+    }
+
+    /// Moves the playhead `frames` along the segment, turning around or
+    /// going back to the start of the segment at its end while there are
+    /// repeats left.
+    ///
+    /// Returns the markers reached and whether the playback completed.
+    fn advance(&mut self, frames: f64) -> (Vec<Marker>, bool) {
+        let mut reached = Vec::new();
+        let mut remaining = frames.abs();
+        loop {
+            let forward = self.is_forward();
+            let end = if forward {
+                self.max_frame
+            } else {
+                self.min_frame
+            };
+            let distance = (end - self.frame).abs();
+            if remaining < distance {
+                let to = self.frame + if forward { remaining } else { -remaining };
+                self.reach_markers(to, &mut reached);
+                self.frame = to;
+                return (reached, false);
+            }
+
+            remaining -= distance;
+            self.reach_markers(end, &mut reached);
+            if self.repeat < 1.0 {
+                self.frame = end;
+                return (reached, true);
+            }
+            if self.repeat.is_finite() {
+                self.repeat -= 1.0;
+            }
+            if self.yoyo {
+                self.reversed = !self.reversed;
+                self.frame = end;
+            } else {
+                self.frame = if forward {
+                    self.min_frame
+                } else {
+                    self.max_frame
+                };
+            }
+            // an empty segment is never left
+            if self.max_frame <= self.min_frame {
+                return (reached, false);
+            }
+        }
+    }
+
+    /// Adds the markers starting from the playhead up to `to`, excluded, to
+    /// `reached`.
+    ///
+    /// Markers starting at the end of the segment, usually where the next
+    /// segment begins, are not part of it and never reached.
+    fn reach_markers(&self, to: f64, reached: &mut Vec<Marker>) {
+        let from = self.frame;
+        let markers = self.composition.get_markers().iter().filter(|marker| {
+            let frame = marker.start_frame;
+            let in_segment = frame >= self.min_frame && frame < self.max_frame;
+            let passed = if from <= to {
+                frame >= from && frame < to
+            } else {
+                frame <= from && frame > to
+            };
+            in_segment && passed
+        });
+        let start = reached.len();
+        reached.extend(markers.cloned());
+        // in the order the playhead reaches them
+        reached[start..].sort_by(|a, b| {
+            let order = a.start_frame.total_cmp(&b.start_frame);
+            if from <= to {
+                order
+            } else {
+                order.reverse()
+            }
+        });
+    }
+}
+
+/// Plays a composition, or a segment of it, by frames or between markers.
+///
+/// The playhead moves at the frame rate of the composition times the speed,
+/// driven by the time given to [`LottiePlayer::update`] like a `Tween`, and
+/// it joins a [`Group`] as a `Playable`. The progress is rounded to the
+/// [`FrameRate`], a segment is looped with `repeat` and played back and
+/// forth with `yoyo`. A callback is fired whenever the playhead reaches the
+/// start of a marker.
+///
+/// `LottiePlayer` is a cheap handle, clones refer to the same player.
+#[derive(Clone)]
+pub struct LottiePlayer {
+    inner: Rc<RefCell<PlayerState>>,
+}
+
+impl LottiePlayer {
+    /// Constructs a new player of the whole composition.
+    pub fn new(composition: LottieComposition) -> Self {
+        let min_frame = composition.get_start_frame();
+        let max_frame = composition.get_end_frame();
+        Self {
+            inner: Rc::new(RefCell::new(PlayerState {
+                lifecycle: Lifecycle::new(),
+                composition,
+                frame_rate: FrameRate::default(),
+                speed: 1.0,
+                repeat: 0.0,
+                initial_repeat: 0.0,
+                yoyo: false,
+                min_frame,
+                max_frame,
+                frame: min_frame,
+                reversed: false,
+                last_time: 0.0,
+                on_update: None,
+                on_marker: None,
+                on_complete: None,
+            })),
+        }
+    }
+
+    /// Returns the unique id of the player.
+    pub fn get_id(&self) -> usize {
+        self.inner.borrow().lifecycle.get_id()
+    }
+
+    pub fn get_composition(&self) -> LottieComposition {
+        self.inner.borrow().composition.clone()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.inner.borrow().lifecycle.is_playing()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.borrow().lifecycle.is_paused()
+    }
+
+    /// Returns the progress in the whole composition, rounded to the frame
+    /// rate, to draw it with `LottieDrawable::set_progress`.
+    pub fn get_progress(&self) -> f64 {
+        self.inner.borrow().progress()
+    }
+
+    /// Returns the frame of the progress.
+    pub fn get_frame(&self) -> f64 {
+        let state = self.inner.borrow();
+        state.composition.get_start_frame()
+            + state.progress() * state.composition.get_duration_frames()
+    }
+
+    /// Moves the playhead to `frame`, kept in the segment.
+    pub fn set_frame(&self, frame: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        state.frame = frame.max(state.min_frame).min(state.max_frame);
+        self
+    }
+
+    /// Returns the first and the last frames of the segment.
+    pub fn get_segment(&self) -> (f64, f64) {
+        let state = self.inner.borrow();
+        (state.min_frame, state.max_frame)
+    }
+
+    /// Plays the frames from `min_frame` to `max_frame`, kept in the
+    /// composition.
+    pub fn segment(&self, min_frame: f64, max_frame: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        let (start, end) = (
+            state.composition.get_start_frame(),
+            state.composition.get_end_frame(),
+        );
+        let (min_frame, max_frame) = (min_frame.min(max_frame), min_frame.max(max_frame));
+        state.min_frame = min_frame.max(start).min(end);
+        state.max_frame = max_frame.max(start).min(end);
+        state.frame = state.frame.max(state.min_frame).min(state.max_frame);
+        self
+    }
+
+    /// Plays the segment of the marker `name`.
+    pub fn marker(&self, name: &str) -> Result<&Self, LottieError> {
+        let marker = self.find_marker(name)?;
+        Ok(self.segment(
+            marker.start_frame,
+            marker.start_frame + marker.duration_frames,
+        ))
+    }
+
+    /// Plays from the start of the marker `start` to the start of the
+    /// marker `end`, the first frame of `end` included if
+    /// `play_end_marker_start_frame`.
+    pub fn between_markers(
+        &self,
+        start: &str,
+        end: &str,
+        play_end_marker_start_frame: bool,
+    ) -> Result<&Self, LottieError> {
+        let start = self.find_marker(start)?;
+        let end = self.find_marker(end)?;
+        let extra = if play_end_marker_start_frame {
+            1.0
+        } else {
+            0.0
+        };
+        Ok(self.segment(start.start_frame, end.start_frame + extra))
+    }
+
+    fn find_marker(&self, name: &str) -> Result<Marker, LottieError> {
+        self.inner
+            .borrow()
+            .composition
+            .get_marker(name)
+            .cloned()
+            .ok_or_else(|| LottieError::UnknownMarker(name.into()))
+    }
+
+    /// Sets the rate the progress moves at.
+    pub fn frame_rate(&self, frame_rate: FrameRate) -> &Self {
+        self.inner.borrow_mut().frame_rate = frame_rate;
+        self
+    }
+
+    /// Sets the speed, `1.0` plays at the frame rate of the composition and
+    /// a negative speed plays backward.
+    pub fn speed(&self, speed: f64) -> &Self {
+        self.inner.borrow_mut().speed = speed;
+        self
+    }
+
+    /// Sets how many times the segment repeats after the first run,
+    /// `f64::INFINITY` to loop.
+    pub fn repeat(&self, times: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        state.initial_repeat = times;
+        state.repeat = times;
+        self
+    }
+
+    /// Plays the segment backward on every other repeat.
+    pub fn yoyo(&self, yoyo: bool) -> &Self {
+        self.inner.borrow_mut().yoyo = yoyo;
+        self
+    }
+
+    /// Sets the group the player joins when it is started.
+    ///
+    /// The group does not keep the player after it has completed, and the
+    /// player does not keep the group alive.
+    pub fn group(&self, group: &Group) -> &Self {
+        self.inner.borrow_mut().lifecycle.set_group(group);
+        self
+    }
+
+    /// Sets the callback fired on every update with the progress.
+    pub fn on_update<F: FnMut(f64) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired when the playhead reaches the start of a
+    /// marker.
+    pub fn on_marker<F: FnMut(&Marker) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_marker = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired when the playback completes.
+    pub fn on_complete<F: FnMut() + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Starts playing the segment from its start at `time` in milliseconds.
+    pub fn start(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if !state.lifecycle.start() {
+            return self;
+        }
+        state.repeat = state.initial_repeat;
+        state.reversed = false;
+        state.frame = state.start_frame();
+        state.last_time = time;
+
+        let group = state.lifecycle.get_group();
+        drop(state);
+
+        if let Some(group) = group {
+            group.add(self);
+        }
+        self
+    }
+
+    /// Stops playing, the playhead stays where it is.
+    pub fn stop(&self) -> &Self {
+        self.inner.borrow_mut().lifecycle.stop();
+        self
+    }
+
+    /// Jumps to the end of the segment and completes.
+    pub fn end(&self) -> &Self {
+        {
+            let mut state = self.inner.borrow_mut();
+            state.reversed = false;
+            state.frame = if state.speed >= 0.0 {
+                state.max_frame
+            } else {
+                state.min_frame
+            };
+            state.lifecycle.stop();
+        }
+        self.fire_update();
+        self.fire_complete();
+        self
+    }
+
+    /// Pauses playing at `time` in milliseconds.
+    pub fn pause(&self, time: f64) -> &Self {
+        self.inner.borrow_mut().lifecycle.pause(time);
+        self
+    }
+
+    /// Resumes playing at `time` in milliseconds, the time spent paused is
+    /// skipped.
+    pub fn resume(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if let Some(paused) = state.lifecycle.resume(time) {
+            state.last_time += paused;
+        }
+        drop(state);
+        self
+    }
+
+    /// Advances the playhead to `time` in milliseconds.
+    ///
+    /// Returns `true` while the player is playing (or paused) and `false`
+    /// once it has completed.
+    pub fn update(&self, time: f64) -> bool {
+        let (reached, completed) = {
+            let mut state = self.inner.borrow_mut();
+            if !state.lifecycle.is_playing() {
+                return false;
+            }
+            if state.lifecycle.is_paused() {
+                return true;
+            }
+            let elapsed = (time - state.last_time).max(0.0);
+            state.last_time = time;
+            let frames = elapsed / 1000.0 * state.composition.get_frame_rate() * state.speed.abs();
+            let (reached, completed) = state.advance(frames);
+            if completed {
+                state.lifecycle.finish();
+            }
+            (reached, completed)
+        };
+
+        if !reached.is_empty() {
+            fire_callback(
+                &self.inner,
+                |state| &mut state.on_marker,
+                |callback| reached.iter().for_each(callback),
+            );
+        }
+        self.fire_update();
+        if completed {
+            self.fire_complete();
+        }
+        !completed
+    }
+
+    fn fire_update(&self) {
+        let progress = self.get_progress();
+        fire_callback(
+            &self.inner,
+            |state| &mut state.on_update,
+            |callback| callback(progress),
+        );
+    }
+
+    fn fire_complete(&self) {
+        fire_callback(
+            &self.inner,
+            |state| &mut state.on_complete,
+            |callback| callback(),
+        );
+    }
+}
+
+impl Playable for LottiePlayer {
+    fn get_id(&self) -> usize {
+        LottiePlayer::get_id(self)
+    }
+
+    fn is_playing(&self) -> bool {
+        LottiePlayer::is_playing(self)
+    }
+
+    fn is_paused(&self) -> bool {
+        LottiePlayer::is_paused(self)
+    }
+
+    fn start(&self, time: f64) {
+        LottiePlayer::start(self, time);
+    }
+
+    fn stop(&self) {
+        LottiePlayer::stop(self);
+    }
+
+    fn end(&self) {
+        LottiePlayer::end(self);
+    }
+
+    fn pause(&self, time: f64) {
+        LottiePlayer::pause(self, time);
+    }
+
+    fn resume(&self, time: f64) {
+        LottiePlayer::resume(self, time);
+    }
+
+    fn update(&self, time: f64) -> bool {
+        LottiePlayer::update(self, time)
+    }
+}
+
+impl fmt::Debug for LottiePlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.borrow();
+        f.debug_struct("LottiePlayer")
+            .field("id", &state.lifecycle.get_id())
+            .field("frame", &state.frame)
+            .field("segment", &(state.min_frame, state.max_frame))
+            .field("speed", &state.speed)
+            .field("is_playing", &state.lifecycle.is_playing())
+            .finish()
+    }
+}
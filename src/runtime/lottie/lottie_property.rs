@@ -1 +1,52 @@
-pub struct LottieProperty;
+use super::value::LottieValueCallback;
+use crate::{Point, RgbaColor};
+
+/// A property of the contents of a composition, with the callback
+/// overriding its values.
+///
+/// Opacities are integers from `0` to `100`, except the start and end
+/// opacities of repeaters from `0.0` to `100.0`. Angles are in degrees,
+/// scales are fractions.
+#[derive(Clone, Debug)]
+pub enum LottieProperty {
+    /// The color of a fill or of the fill of a text.
+    Color(LottieValueCallback<RgbaColor>),
+    /// The color of a stroke or of the stroke of a text.
+    StrokeColor(LottieValueCallback<RgbaColor>),
+    /// The opacity of a fill or a stroke.
+    Opacity(LottieValueCallback<i32>),
+    /// The width of a stroke or of the stroke of a text.
+    StrokeWidth(LottieValueCallback<f64>),
+
+    TransformAnchorPoint(LottieValueCallback<Point<f64>>),
+    TransformPosition(LottieValueCallback<Point<f64>>),
+    TransformScale(LottieValueCallback<Point<f64>>),
+    TransformRotation(LottieValueCallback<f64>),
+    TransformOpacity(LottieValueCallback<i32>),
+    TransformSkew(LottieValueCallback<f64>),
+    TransformSkewAngle(LottieValueCallback<f64>),
+    /// The opacity of the first copy of a repeater.
+    TransformStartOpacity(LottieValueCallback<f64>),
+    /// The opacity of the last copy of a repeater.
+    TransformEndOpacity(LottieValueCallback<f64>),
+
+    /// The center of a rectangle, an ellipse or a polystar.
+    Position(LottieValueCallback<Point<f64>>),
+    EllipseSize(LottieValueCallback<Point<f64>>),
+    RectangleSize(LottieValueCallback<Point<f64>>),
+    CornerRadius(LottieValueCallback<f64>),
+    PolystarPoints(LottieValueCallback<f64>),
+    PolystarRotation(LottieValueCallback<f64>),
+    PolystarInnerRadius(LottieValueCallback<f64>),
+    PolystarOuterRadius(LottieValueCallback<f64>),
+    PolystarInnerRoundedness(LottieValueCallback<f64>),
+    PolystarOuterRoundedness(LottieValueCallback<f64>),
+    RepeaterCopies(LottieValueCallback<f64>),
+    RepeaterOffset(LottieValueCallback<f64>),
+
+    /// The extra space between the characters of a text, in thousandths of
+    /// an em.
+    TextTracking(LottieValueCallback<f64>),
+    /// The text of a text layer, drawn with fonts once overridden.
+    Text(LottieValueCallback<String>),
+}
@@ -0,0 +1,111 @@
+use crate::{Bitmap, Point, Rect, Size, SkiaCanvas};
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use super::{LottieComposition, LottieDelegates, LottieDrawable, LottieError};
+
+/// Draws the frames of a composition into pixels without a display, to make
+/// thumbnails or export the frames of an animation.
+///
+/// The canvas has no fonts, text is only drawn from the glyphs embedded in
+/// the composition. Text drawn with fonts, without glyphs or replaced by a
+/// delegate, is left out and a warning is added to the composition.
+///
+/// ```no_run
+/// # use animate::lottie::{LottieComposition, LottieError, LottieRasterizer};
+/// # fn export(composition: LottieComposition) -> Result<(), LottieError> {
+/// let mut rasterizer = LottieRasterizer::new(composition, 256, 256);
+/// let poster = rasterizer.rasterize_png(0.0)?;
+/// let frames = rasterizer.get_frames();
+/// rasterizer.export_frames("frames", frames)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct LottieRasterizer {
+    drawable: LottieDrawable,
+    width: u32,
+    height: u32,
+}
+
+impl LottieRasterizer {
+    /// Returns a rasterizer drawing `composition` stretched to `width` by
+    /// `height` pixels.
+    pub fn new(composition: LottieComposition, width: u32, height: u32) -> Self {
+        Self {
+            drawable: LottieDrawable::new(composition),
+            width,
+            height,
+        }
+    }
+
+    pub fn with_delegates(mut self, delegates: LottieDelegates) -> Self {
+        self.drawable.set_delegates(delegates);
+        self
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the whole frames of the composition, from its start frame up
+    /// to its end frame.
+    pub fn get_frames(&self) -> Range<u32> {
+        let composition = self.drawable.get_composition();
+        let start = composition.get_start_frame().max(0.0).ceil() as u32;
+        let end = composition.get_end_frame().max(0.0).ceil() as u32;
+        start..end.max(start)
+    }
+
+    /// Draws the composition at `frame`, clamped to the frames of the
+    /// composition, into premultiplied RGBA pixels.
+    pub fn rasterize(&mut self, frame: f64) -> Result<Bitmap, LottieError> {
+        let canvas = SkiaCanvas::new(self.width, self.height).ok_or_else(|| {
+            LottieError::Render(format!("invalid size {}x{}", self.width, self.height))
+        })?;
+        let composition = self.drawable.get_composition();
+        if !self.drawable.get_use_text_glyphs() && composition.has_text_layers() {
+            composition.add_warning("Text drawn with fonts is not rasterized.");
+        }
+        self.drawable.set_frame(frame);
+        let rect = Rect {
+            origin: Point::new(0.0, 0.0),
+            size: Size::new(self.width as f64, self.height as f64),
+        };
+        self.drawable.draw(&canvas, rect);
+        Ok(canvas.to_bitmap())
+    }
+
+    /// Draws the composition at `frame` into a PNG image.
+    pub fn rasterize_png(&mut self, frame: f64) -> Result<Vec<u8>, LottieError> {
+        self.rasterize(frame)?
+            .to_png()
+            .map_err(|error| LottieError::Render(error.to_string()))
+    }
+
+    /// Writes a PNG image of each of `frames` into `directory`, named after
+    /// the frame as `frame_0042.png`, and returns the paths of the images.
+    ///
+    /// The directory is created if it doesn't exist.
+    pub fn export_frames<P: AsRef<Path>>(
+        &mut self,
+        directory: P,
+        frames: Range<u32>,
+    ) -> Result<Vec<PathBuf>, LottieError> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+        frames
+            .map(|frame| {
+                let path = directory.join(format!("frame_{:04}.png", frame));
+                fs::write(&path, self.rasterize_png(frame as f64)?)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
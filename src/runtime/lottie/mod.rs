@@ -4,22 +4,31 @@
 #![allow(clippy::new_without_default)]
 
 use crate::prelude::*;
+use crate::{OffscreenContext, Rect};
 use bytes::Bytes;
 use ruex::prelude::*;
-use std::{cell::RefCell, fs::File, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    fs::File,
+    path::Path,
+    rc::Rc,
+};
 
 pub(crate) mod animation;
-pub(crate) mod model;
+pub mod model;
 pub(crate) mod parser;
 pub(crate) mod providers;
 pub(crate) mod utils;
 pub(crate) mod value;
 
 mod composition;
-pub(crate) use composition::*;
+pub use composition::*;
+
+mod error;
+pub use error::*;
 
 mod frame_rate;
-pub(crate) use frame_rate::*;
+pub use frame_rate::*;
 
 mod l;
 pub(crate) use l::*;
@@ -31,22 +40,28 @@ mod lottie_builder;
 pub(crate) use lottie_builder::*;
 
 mod lottie_delegates;
-pub(crate) use lottie_delegates::*;
+pub use lottie_delegates::*;
 
 mod lottie_drawable;
-pub(crate) use lottie_drawable::*;
+pub use lottie_drawable::*;
 
 mod lottie_image_asset;
-pub(crate) use lottie_image_asset::*;
+pub use lottie_image_asset::*;
+
+mod lottie_player;
+pub use lottie_player::*;
 
 mod lottie_property;
-pub(crate) use lottie_property::*;
+pub use lottie_property::*;
+
+mod lottie_rasterizer;
+pub use lottie_rasterizer::*;
 
 mod options;
-pub(crate) use options::*;
+pub use options::*;
 
 mod performance_tracker;
-pub(crate) use performance_tracker::*;
+pub use performance_tracker::*;
 
 mod raw_lottie;
 pub(crate) use raw_lottie::*;
@@ -55,31 +70,47 @@ mod render_lottie;
 pub(crate) use render_lottie::*;
 
 mod value_delegate;
-pub(crate) use value_delegate::*;
+pub use value_delegate::*;
+
+pub use self::providers::{
+    AssetLottie, DirectoryAssetBundle, DotLottie, DotLottieArchive, FileLottie, LottieAssetBundle,
+    LottieCache, LottieFetch, LottieFontProvider, LottieFonts, LottieImageProvider,
+    LottieImageProviderFactory, LottieProvider, MemoryLottie, NetworkLottie,
+};
+pub use self::value::{
+    LottieFrameInfo, LottieRelativeDoubleValueCallback, LottieRelativeIntegerValueCallback,
+    LottieRelativePointValueCallback, LottieValueCallback,
+};
 
-pub(crate) use self::providers::LottieImageProviderFactory;
-use self::providers::LottieProvider;
+#[cfg(test)]
+mod tests;
 
 // Animation, AssetBundle, BoxFit, AnimatedBuilder
 
+/// Builds the provider of a source with an image provider factory.
+type ProviderBuilder = Rc<dyn Fn(LottieImageProviderFactory) -> Rc<dyn LottieProvider>>;
+
 /// A widget to display a loaded `LottieComposition`.
 /// The `controller` property allows to specify a custom AnimationController that
 /// will drive the animation. If `controller` is None, the animation will play
 /// automatically and the behavior could be adjusted with the properties `animate`,
 /// `repeat` and `reverse`.
-#[derive(Default, Clone)]
+///
+/// The composition is played by a [`LottiePlayer`], created when it is
+/// loaded and shared by the clones until their source changes.
+#[derive(Clone)]
 pub struct Lottie {
     // RawLottie
     composition: LottieComposition,
     // controller: Option<Animation<f64>>,
-    animate: bool,
-    frame_rate: FrameRate,
-    repeat: bool,
-    reverse: bool,
-    delegates: LottieDelegates,
-    options: LottieOptions,
+    animate: Cell<bool>,
+    frame_rate: Cell<FrameRate>,
+    repeat: Cell<bool>,
+    reverse: Cell<bool>,
+    delegates: RefCell<LottieDelegates>,
+    options: RefCell<LottieOptions>,
     // onload: FnOnce(LottieComposition),
-    image_provider_factory: LottieImageProviderFactory,
+    image_provider_factory: RefCell<LottieImageProviderFactory>,
     key: String,
     // bundle: AssetBundle,
     frame_builder: LottieFrameBuilder,
@@ -89,25 +120,60 @@ pub struct Lottie {
     // alignment: Alignment,
     package: String,
     add_repaint_boundary: bool,
-    provider: Rc<Option<Box<dyn LottieProvider>>>,
+    /// Builds the provider of the source with the image provider factory.
+    provider: RefCell<Option<ProviderBuilder>>,
+    player: RefCell<Option<LottiePlayer>>,
+    /// Whether the player starts on the next update.
+    autostart: Cell<bool>,
+    /// Draws the composition of the player, shared with it.
+    drawable: RefCell<Option<Rc<RefCell<LottieDrawable>>>>,
+}
+
+impl Default for Lottie {
+    fn default() -> Self {
+        Self {
+            composition: Default::default(),
+            animate: Cell::new(true),
+            frame_rate: Default::default(),
+            repeat: Cell::new(true),
+            reverse: Cell::new(false),
+            delegates: Default::default(),
+            options: Default::default(),
+            image_provider_factory: Default::default(),
+            key: Default::default(),
+            frame_builder: Default::default(),
+            width: Default::default(),
+            height: Default::default(),
+            package: Default::default(),
+            add_repaint_boundary: Default::default(),
+            provider: Default::default(),
+            player: Default::default(),
+            autostart: Default::default(),
+            drawable: Default::default(),
+        }
+    }
 }
 
 pub trait LottieBuilderExt {
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from an `AssetBundle`.
-    fn asset(&self, name: &str) -> &Self;
+    fn asset(&self, name: &str, bundle: Rc<dyn LottieAssetBundle>) -> &Self;
 
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from a `File`.
-    fn file(&self, file: File) -> &Self;
+    fn file<P: AsRef<Path>>(&self, path: P) -> &Self;
 
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from a `Uint8List`.
     fn memory(&self, byf: Bytes) -> &Self;
 
     /// Creates a widget that displays an `LottieComposition`
-    /// obtained from the network.
-    fn network(&self, url: &str) -> &Self;
+    /// obtained from the network, with the `fetch` function of the application.
+    fn network(&self, url: &str, fetch: LottieFetch) -> &Self;
+
+    /// Creates a widget that displays the animation `animation_id` of a
+    /// dotLottie file, or the active animation of its manifest.
+    fn dot_lottie(&self, bytes: Bytes, animation_id: Option<&str>) -> &Self;
 
     // /// The animation controller to animate the Lottie animation.
     // /// If null, a controller is automatically created by this class and is configured
@@ -147,8 +213,8 @@ pub trait LottieBuilderExt {
     fn delegates(&self, delegates: LottieDelegates) -> &Self;
 
     /// Some options to enable/disable some feature of Lottie
-    /// - enableMergePaths: Enable merge path support
-    fn options(&self, options: LottieDelegates) -> &Self;
+    /// - `enable_merge_paths`: Enable merge path support
+    fn options(&self, options: LottieOptions) -> &Self;
     fn onloaded(&self, f: &str) -> &Self;
     fn image_provider_factory(&self, factory: LottieImageProviderFactory) -> &Self;
     fn key(&self, key: &str) -> &Self;
@@ -200,29 +266,60 @@ pub trait LottieBuilderExt {
     fn add_repaint_boundary(&self, value: bool) -> &Self;
 }
 
-impl LottieBuilderExt for Builder<Lottie> {
+impl LottieBuilderExt for Lottie {
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from an `AssetBundle`.
-    fn asset(&self, name: &str) -> &Self {
-        unimplemented!()
+    fn asset(&self, name: &str, bundle: Rc<dyn LottieAssetBundle>) -> &Self {
+        let provider = AssetLottie::new(name, bundle);
+        self.set_provider(move |factory| {
+            let mut provider = provider.clone();
+            provider.image_provider_factory = factory;
+            Rc::new(provider)
+        })
     }
 
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from a `File`.
-    fn file(&self, file: File) -> &Self {
-        unimplemented!()
+    fn file<P: AsRef<Path>>(&self, path: P) -> &Self {
+        let provider = FileLottie::new(path);
+        self.set_provider(move |factory| {
+            let mut provider = provider.clone();
+            provider.image_provider_factory = factory;
+            Rc::new(provider)
+        })
     }
 
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from a `Uint8List`.
     fn memory(&self, byf: Bytes) -> &Self {
-        unimplemented!()
+        let provider = MemoryLottie::new(byf);
+        self.set_provider(move |factory| {
+            let mut provider = provider.clone();
+            provider.image_provider_factory = factory;
+            Rc::new(provider)
+        })
     }
 
     /// Creates a widget that displays an `LottieComposition`
     /// obtained from the network.
-    fn network(&self, url: &str) -> &Self {
-        unimplemented!()
+    fn network(&self, url: &str, fetch: LottieFetch) -> &Self {
+        let provider = NetworkLottie::new(url, fetch);
+        self.set_provider(move |factory| {
+            let mut provider = provider.clone();
+            provider.image_provider_factory = factory;
+            Rc::new(provider)
+        })
+    }
+
+    /// Creates a widget that displays the animation `animation_id` of a
+    /// dotLottie file, or the active animation of its manifest.
+    fn dot_lottie(&self, bytes: Bytes, animation_id: Option<&str>) -> &Self {
+        let provider = DotLottie::new(bytes, animation_id);
+        self.set_provider(move |factory| {
+            let mut provider = provider.clone();
+            provider.image_provider_factory = factory;
+            Rc::new(provider)
+        })
     }
 
     // fn controller(&self, controller: Animation<f64>) -> &Self {
@@ -230,27 +327,46 @@ impl LottieBuilderExt for Builder<Lottie> {
     // }
 
     fn framerate(&self, framerate: FrameRate) -> &Self {
-        unimplemented!()
+        self.frame_rate.set(framerate);
+        self.configure_player();
+        self
     }
 
     fn animate(&self, animate: bool) -> &Self {
-        unimplemented!()
+        self.animate.set(animate);
+        let playing = matches!(&*self.player.borrow(), Some(player) if player.is_playing());
+        self.autostart.set(animate && !playing);
+        self
     }
 
     fn repeat(&self, repeat: bool) -> &Self {
-        unimplemented!()
+        self.repeat.set(repeat);
+        self.configure_player();
+        self
     }
 
     fn reverse(&self, reverse: bool) -> &Self {
-        unimplemented!()
+        self.reverse.set(reverse);
+        self.configure_player();
+        self
     }
 
     fn delegates(&self, delegates: LottieDelegates) -> &Self {
-        unimplemented!()
+        if let Some(drawable) = self.drawable.borrow().as_ref() {
+            drawable.borrow_mut().set_delegates(delegates.clone());
+        }
+        self.delegates.replace(delegates);
+        self
     }
 
-    fn options(&self, options: LottieDelegates) -> &Self {
-        unimplemented!()
+    fn options(&self, options: LottieOptions) -> &Self {
+        if let Some(drawable) = self.drawable.borrow().as_ref() {
+            drawable
+                .borrow_mut()
+                .set_enable_merge_paths(options.enable_merge_paths);
+        }
+        self.options.replace(options);
+        self
     }
 
     // should be fn
@@ -258,8 +374,11 @@ impl LottieBuilderExt for Builder<Lottie> {
         unimplemented!()
     }
 
+    /// Sets the factory of the images of the composition, it is used when
+    /// the composition is loaded.
     fn image_provider_factory(&self, factory: LottieImageProviderFactory) -> &Self {
-        unimplemented!()
+        self.image_provider_factory.replace(factory);
+        self
     }
 
     fn key(&self, key: &str) -> &Self {
@@ -300,7 +419,143 @@ impl LottieBuilderExt for Builder<Lottie> {
 }
 
 impl Lottie {
-    fn new() -> Self {
-        unimplemented!()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the composition of the source set with `asset`, `file`,
+    /// `memory` or `network`.
+    ///
+    /// Compositions are kept in the cache shared by the thread, a source
+    /// is only loaded once, with the images of the image provider factory
+    /// set at that time.
+    pub fn load(&self) -> Result<LottieComposition, LottieError> {
+        let provider = self
+            .provider
+            .borrow()
+            .as_ref()
+            .map(|build| build(self.image_provider_factory.borrow().clone()))
+            .ok_or_else(|| LottieError::Load("no source".into()))?;
+        LottieCache::with_shared(|cache| provider.load_cached(cache))
+    }
+
+    /// Returns the player of the composition, the composition is loaded
+    /// the first time.
+    ///
+    /// The player can join a `Group` to be driven with other animations
+    /// instead of [`Lottie::update`].
+    pub fn get_player(&self) -> Result<LottiePlayer, LottieError> {
+        if let Some(player) = self.player.borrow().as_ref() {
+            return Ok(player.clone());
+        }
+        let player = LottiePlayer::new(self.load()?);
+        self.player.replace(Some(player.clone()));
+        self.autostart.set(self.animate.get());
+        self.configure_player();
+        Ok(player)
+    }
+
+    /// Plays the segment of the marker `name`.
+    pub fn marker(&self, name: &str) -> Result<&Self, LottieError> {
+        self.get_player()?.marker(name)?;
+        Ok(self)
+    }
+
+    /// Plays from the start of the marker `start` to the start of the
+    /// marker `end`, the first frame of `end` included if
+    /// `play_end_marker_start_frame`.
+    pub fn between_markers(
+        &self,
+        start: &str,
+        end: &str,
+        play_end_marker_start_frame: bool,
+    ) -> Result<&Self, LottieError> {
+        self.get_player()?
+            .between_markers(start, end, play_end_marker_start_frame)?;
+        Ok(self)
+    }
+
+    /// Plays the frames from `min_frame` to `max_frame`.
+    pub fn segment(&self, min_frame: f64, max_frame: f64) -> Result<&Self, LottieError> {
+        self.get_player()?.segment(min_frame, max_frame);
+        Ok(self)
+    }
+
+    /// Sets the callback fired when the playhead reaches the start of a
+    /// marker.
+    pub fn on_marker<F: FnMut(&model::Marker) + 'static>(
+        &self,
+        callback: F,
+    ) -> Result<&Self, LottieError> {
+        self.get_player()?.on_marker(callback);
+        Ok(self)
+    }
+
+    /// Advances the animation to `time` in milliseconds, it starts on the
+    /// first update if it plays automatically.
+    ///
+    /// Returns `true` while the animation is playing.
+    pub fn update(&self, time: f64) -> Result<bool, LottieError> {
+        let player = self.get_player()?;
+        if self.autostart.replace(false) {
+            player.start(time);
+        }
+        Ok(player.update(time))
+    }
+
+    /// Draws the composition at the progress of its player, stretched to
+    /// fill `rect`, with the delegates.
+    pub fn draw<C: OffscreenContext>(
+        &self,
+        canvas: &C,
+        rect: Rect<f64>,
+    ) -> Result<(), LottieError> {
+        let player = self.get_player()?;
+        let drawable = self.get_drawable(&player);
+        let mut drawable = drawable.borrow_mut();
+        drawable.set_progress(player.get_progress());
+        drawable.draw(canvas, rect);
+        Ok(())
+    }
+
+    /// Returns the drawable of the composition of `player`, it is created
+    /// with the delegates the first time.
+    fn get_drawable(&self, player: &LottiePlayer) -> Rc<RefCell<LottieDrawable>> {
+        if let Some(drawable) = self.drawable.borrow().as_ref() {
+            return drawable.clone();
+        }
+        let mut drawable = LottieDrawable::new(player.get_composition());
+        drawable.set_delegates(self.delegates.borrow().clone());
+        drawable.set_enable_merge_paths(self.options.borrow().enable_merge_paths);
+        let drawable = Rc::new(RefCell::new(drawable));
+        self.drawable.replace(Some(drawable.clone()));
+        drawable
+    }
+
+    /// Sets the source of the composition, the player and the drawable of
+    /// the previous one are dropped.
+    fn set_provider<F>(&self, build: F) -> &Self
+    where
+        F: Fn(LottieImageProviderFactory) -> Rc<dyn LottieProvider> + 'static,
+    {
+        self.provider.replace(Some(Rc::new(build)));
+        self.player.replace(None);
+        self.drawable.replace(None);
+        self
+    }
+
+    /// Sets the frame rate and the repeats of the player, if it was created.
+    fn configure_player(&self) {
+        if let Some(player) = self.player.borrow().as_ref() {
+            let repeat = if self.repeat.get() {
+                f64::INFINITY
+            } else {
+                0.0
+            };
+            player
+                .frame_rate(self.frame_rate.get())
+                .repeat(repeat)
+                .yoyo(self.reverse.get());
+        }
     }
 }
@@ -1 +1,4 @@
-pub struct AnimatableColorValue;
+use super::BaseAnimatableValue;
+use crate::RgbaColor;
+
+pub type AnimatableColorValue = BaseAnimatableValue<RgbaColor>;
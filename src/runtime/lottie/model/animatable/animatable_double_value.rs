@@ -1 +1,3 @@
-pub struct AnimatableDoubleValue;
+use super::BaseAnimatableValue;
+
+pub type AnimatableDoubleValue = BaseAnimatableValue<f64>;
@@ -1 +1,4 @@
-pub struct AnimatableGradientColorValue;
+use super::BaseAnimatableValue;
+use crate::lottie::model::content::GradientColor;
+
+pub type AnimatableGradientColorValue = BaseAnimatableValue<GradientColor>;
@@ -1 +1,4 @@
-pub struct AnimatableIntegerValue;
+use super::BaseAnimatableValue;
+
+/// Integer properties, like opacities in percents.
+pub type AnimatableIntegerValue = BaseAnimatableValue<i32>;
@@ -1 +1,40 @@
-pub struct AnimatablePathValue;
+use super::{AnimatableSplitDimensionPathValue, AnimatableValue, BaseAnimatableValue};
+use crate::{
+    lottie::animation::keyframe::{
+        KeyframeAnimation, PathKeyframeAnimation, SplitDimensionPathKeyframeAnimation,
+    },
+    Point,
+};
+
+/// Positions moving along spatial curves, the control points are kept in
+/// the `path_cp1` and `path_cp2` of the keyframes.
+pub type AnimatablePathValue = BaseAnimatableValue<Point<f64>>;
+
+/// A position animated as a point, or with separate X and Y values.
+#[derive(Clone, Debug)]
+pub enum AnimatablePosition {
+    Path(AnimatablePathValue),
+    Split(AnimatableSplitDimensionPathValue),
+}
+
+impl Default for AnimatablePosition {
+    fn default() -> Self {
+        AnimatablePosition::Path(AnimatablePathValue::new_static(Point::new(0.0, 0.0)))
+    }
+}
+
+impl AnimatablePosition {
+    /// Returns an animation of the position.
+    pub fn create_animation(&self) -> Box<dyn KeyframeAnimation<Value = Point<f64>>> {
+        match self {
+            AnimatablePosition::Path(value) => Box::new(
+                PathKeyframeAnimation::new(value.keyframes().to_vec())
+                    .with_expression(value.get_expression().cloned()),
+            ),
+            AnimatablePosition::Split(value) => Box::new(SplitDimensionPathKeyframeAnimation::new(
+                value.x.create_animation(),
+                value.y.create_animation(),
+            )),
+        }
+    }
+}
@@ -1 +1,4 @@
-pub struct AnimatablePointValue;
+use super::BaseAnimatableValue;
+use crate::Point;
+
+pub type AnimatablePointValue = BaseAnimatableValue<Point<f64>>;
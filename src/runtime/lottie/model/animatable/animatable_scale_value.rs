@@ -1 +1,5 @@
-pub struct AnimatableScaleValue;
+use super::BaseAnimatableValue;
+use crate::Point;
+
+/// Scale factors on both axes, `1.0` for 100%.
+pub type AnimatableScaleValue = BaseAnimatableValue<Point<f64>>;
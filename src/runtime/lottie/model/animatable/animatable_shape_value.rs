@@ -1 +1,4 @@
-pub struct AnimatableShapeValue;
+use super::BaseAnimatableValue;
+use crate::lottie::model::content::ShapeData;
+
+pub type AnimatableShapeValue = BaseAnimatableValue<ShapeData>;
@@ -1 +1,14 @@
-pub struct AnimatableSplitDimensionPathValue;
+use super::AnimatableDoubleValue;
+
+/// A position whose X and Y values have their own keyframes.
+#[derive(Clone, Debug)]
+pub struct AnimatableSplitDimensionPathValue {
+    pub x: AnimatableDoubleValue,
+    pub y: AnimatableDoubleValue,
+}
+
+impl AnimatableSplitDimensionPathValue {
+    pub fn new(x: AnimatableDoubleValue, y: AnimatableDoubleValue) -> Self {
+        Self { x, y }
+    }
+}
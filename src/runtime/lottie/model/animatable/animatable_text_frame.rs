@@ -1 +1,5 @@
-pub struct AnimatableTextFrame;
+use super::BaseAnimatableValue;
+use crate::lottie::model::DocumentData;
+
+/// The text documents of a text layer, each one held until the next.
+pub type AnimatableTextFrame = BaseAnimatableValue<DocumentData>;
@@ -1 +1,10 @@
-pub struct AnimatableTextProperties;
+use super::{AnimatableColorValue, AnimatableDoubleValue};
+
+/// The properties a text animator overrides.
+#[derive(Clone, Debug, Default)]
+pub struct AnimatableTextProperties {
+    pub color: Option<AnimatableColorValue>,
+    pub stroke: Option<AnimatableColorValue>,
+    pub stroke_width: Option<AnimatableDoubleValue>,
+    pub tracking: Option<AnimatableDoubleValue>,
+}
@@ -1 +1,27 @@
-pub struct AnimatableTransform;
+use super::{
+    AnimatableDoubleValue, AnimatableIntegerValue, AnimatablePathValue, AnimatablePosition,
+    AnimatableScaleValue,
+};
+
+/// The transform of a layer, a shape group or a repeater copy.
+///
+/// Missing properties keep their identity value: no anchor point, position,
+/// rotation or skew, a scale of 100% and full opacity.
+#[derive(Clone, Debug, Default)]
+pub struct AnimatableTransform {
+    pub anchor_point: Option<AnimatablePathValue>,
+    pub position: Option<AnimatablePosition>,
+    pub scale: Option<AnimatableScaleValue>,
+    /// The rotation in degrees.
+    pub rotation: Option<AnimatableDoubleValue>,
+    /// The opacity in percents.
+    pub opacity: Option<AnimatableIntegerValue>,
+    /// The skew in degrees.
+    pub skew: Option<AnimatableDoubleValue>,
+    /// The direction of the skew in degrees.
+    pub skew_angle: Option<AnimatableDoubleValue>,
+    /// The opacity of the first copy of a repeater, in percents.
+    pub start_opacity: Option<AnimatableDoubleValue>,
+    /// The opacity of the last copy of a repeater, in percents.
+    pub end_opacity: Option<AnimatableDoubleValue>,
+}
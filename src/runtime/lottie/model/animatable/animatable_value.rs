@@ -1 +1,14 @@
-pub struct AnimatableValue;
+use crate::lottie::value::Keyframe;
+
+/// An animatable property of the composition model.
+pub trait AnimatableValue {
+    type Value;
+
+    fn keyframes(&self) -> &[Keyframe<Self::Value>];
+
+    /// Returns `true` if the property keeps the same value at every frame.
+    fn is_static(&self) -> bool {
+        let keyframes = self.keyframes();
+        keyframes.is_empty() || (keyframes.len() == 1 && keyframes[0].is_static())
+    }
+}
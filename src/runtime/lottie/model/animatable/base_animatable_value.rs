@@ -1 +1,51 @@
-pub struct BaseAnimatableValue;
+use super::AnimatableValue;
+use crate::lottie::{
+    animation::{expression::PropertyExpression, keyframe::BaseKeyframeAnimation},
+    value::Keyframe,
+};
+
+/// The keyframes of an animatable property, as parsed from the composition.
+#[derive(Clone, Debug)]
+pub struct BaseAnimatableValue<T> {
+    keyframes: Vec<Keyframe<T>>,
+    /// The expression computing the value from the keyframes.
+    expression: Option<PropertyExpression<T>>,
+}
+
+impl<T> BaseAnimatableValue<T> {
+    pub fn new(keyframes: Vec<Keyframe<T>>) -> Self {
+        Self {
+            keyframes,
+            expression: None,
+        }
+    }
+
+    pub fn with_expression(mut self, expression: Option<PropertyExpression<T>>) -> Self {
+        self.expression = expression;
+        self
+    }
+
+    pub fn get_expression(&self) -> Option<&PropertyExpression<T>> {
+        self.expression.as_ref()
+    }
+
+    /// Returns a property which has `value` at every frame.
+    pub fn new_static(value: T) -> Self {
+        Self::new(vec![Keyframe::new_static(value)])
+    }
+}
+
+impl<T: Clone> BaseAnimatableValue<T> {
+    /// Returns an animation of the keyframes.
+    pub fn create_animation(&self) -> BaseKeyframeAnimation<T> {
+        BaseKeyframeAnimation::new(self.keyframes.clone()).with_expression(self.expression.clone())
+    }
+}
+
+impl<T> AnimatableValue for BaseAnimatableValue<T> {
+    type Value = T;
+
+    fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+}
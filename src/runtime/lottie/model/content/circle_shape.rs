@@ -1 +1,12 @@
-pub struct CircleShape;
+use crate::lottie::model::animatable::{AnimatablePointValue, AnimatablePosition};
+
+/// An ellipse, from its center and size.
+#[derive(Clone, Debug)]
+pub struct CircleShape {
+    pub name: Option<String>,
+    pub position: AnimatablePosition,
+    pub size: AnimatablePointValue,
+    /// Drawn counterclockwise.
+    pub is_reversed: bool,
+    pub hidden: bool,
+}
@@ -1 +1,64 @@
-pub struct ContentModel;
+use super::{
+    CircleShape, GradientFill, GradientStroke, MergePaths, PolystarShape, RectangleShape, Repeater,
+    ShapeFill, ShapeGroup, ShapePath, ShapeStroke, ShapeTrimPath,
+};
+use crate::lottie::model::animatable::AnimatableTransform;
+
+/// An item of a shape layer or a shape group.
+#[derive(Clone, Debug)]
+pub enum ContentModel {
+    Group(ShapeGroup),
+    Rectangle(RectangleShape),
+    Ellipse(CircleShape),
+    Polystar(PolystarShape),
+    Path(ShapePath),
+    Fill(ShapeFill),
+    Stroke(ShapeStroke),
+    GradientFill(GradientFill),
+    GradientStroke(GradientStroke),
+    /// The transform of the enclosing group.
+    Transform(AnimatableTransform),
+    TrimPath(ShapeTrimPath),
+    Repeater(Repeater),
+    MergePaths(MergePaths),
+}
+
+impl ContentModel {
+    /// Returns the name given in the composition.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ContentModel::Group(group) => group.name.as_deref(),
+            ContentModel::Rectangle(shape) => shape.name.as_deref(),
+            ContentModel::Ellipse(shape) => shape.name.as_deref(),
+            ContentModel::Polystar(shape) => shape.name.as_deref(),
+            ContentModel::Path(shape) => shape.name.as_deref(),
+            ContentModel::Fill(fill) => fill.name.as_deref(),
+            ContentModel::Stroke(stroke) => stroke.name.as_deref(),
+            ContentModel::GradientFill(fill) => fill.name.as_deref(),
+            ContentModel::GradientStroke(stroke) => stroke.name.as_deref(),
+            ContentModel::Transform(_) => Some("Transform"),
+            ContentModel::TrimPath(trim) => trim.name.as_deref(),
+            ContentModel::Repeater(repeater) => repeater.name.as_deref(),
+            ContentModel::MergePaths(merge) => merge.name.as_deref(),
+        }
+    }
+
+    /// Returns `true` if the item is hidden and not drawn.
+    pub fn is_hidden(&self) -> bool {
+        match self {
+            ContentModel::Group(group) => group.hidden,
+            ContentModel::Rectangle(shape) => shape.hidden,
+            ContentModel::Ellipse(shape) => shape.hidden,
+            ContentModel::Polystar(shape) => shape.hidden,
+            ContentModel::Path(shape) => shape.hidden,
+            ContentModel::Fill(fill) => fill.hidden,
+            ContentModel::Stroke(stroke) => stroke.hidden,
+            ContentModel::GradientFill(fill) => fill.hidden,
+            ContentModel::GradientStroke(stroke) => stroke.hidden,
+            ContentModel::Transform(_) => false,
+            ContentModel::TrimPath(trim) => trim.hidden,
+            ContentModel::Repeater(repeater) => repeater.hidden,
+            ContentModel::MergePaths(merge) => merge.hidden,
+        }
+    }
+}
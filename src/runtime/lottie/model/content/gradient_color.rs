@@ -1 +1,93 @@
-pub struct GradientColor;
+use crate::{interpolate::Interpolate, lottie::utils::GammaEvaluator, RgbaColor};
+
+/// The stops of a gradient, opacity stops are already merged into the
+/// colors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GradientColor {
+    pub positions: Vec<f64>,
+    pub colors: Vec<RgbaColor>,
+}
+
+impl GradientColor {
+    pub fn new(positions: Vec<f64>, colors: Vec<RgbaColor>) -> Self {
+        Self { positions, colors }
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the color of the gradient at `position`, interpolated in
+    /// linear light between the stops around it.
+    ///
+    /// The first and the last colors extend past the first and the last
+    /// stops.
+    pub fn color_at(&self, position: f64) -> RgbaColor {
+        let stops = self.positions.len().min(self.colors.len());
+        if stops == 0 {
+            return RgbaColor::new(0, 0, 0, 0);
+        }
+        match self.positions[..stops]
+            .iter()
+            .position(|&stop| stop >= position)
+        {
+            None => self.colors[stops - 1],
+            Some(0) => self.colors[0],
+            Some(index) => {
+                let (from, to) = (self.positions[index - 1], self.positions[index]);
+                let fraction = if to > from {
+                    (position - from) / (to - from)
+                } else {
+                    1.0
+                };
+                GammaEvaluator::evaluate(fraction, self.colors[index - 1], self.colors[index])
+            }
+        }
+    }
+}
+
+impl Interpolate for GradientColor {
+    /// Interpolates the positions and the colors of the stops, colors in
+    /// linear light.
+    ///
+    /// Gradients with a different number of stops are both sampled at the
+    /// positions of all their stops first.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        if self.len() == other.len() {
+            return Self {
+                positions: self
+                    .positions
+                    .iter()
+                    .zip(other.positions.iter())
+                    .map(|(start, end)| start.interpolate(end, f))
+                    .collect(),
+                colors: self
+                    .colors
+                    .iter()
+                    .zip(other.colors.iter())
+                    .map(|(&start, &end)| GammaEvaluator::evaluate(f, start, end))
+                    .collect(),
+            };
+        }
+
+        let mut positions: Vec<f64> = self
+            .positions
+            .iter()
+            .chain(other.positions.iter())
+            .copied()
+            .collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        positions.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+        let colors = positions
+            .iter()
+            .map(|&position| {
+                GammaEvaluator::evaluate(f, self.color_at(position), other.color_at(position))
+            })
+            .collect();
+        Self { positions, colors }
+    }
+}
@@ -1 +1,24 @@
-pub struct GradientFill;
+use super::{FillType, GradientType};
+use crate::lottie::model::animatable::{
+    AnimatableDoubleValue, AnimatableGradientColorValue, AnimatableIntegerValue,
+    AnimatablePointValue,
+};
+
+/// A gradient fill of the paths before it in its group.
+#[derive(Clone, Debug)]
+pub struct GradientFill {
+    pub name: Option<String>,
+    pub gradient_type: GradientType,
+    pub fill_type: FillType,
+    pub gradient_color: AnimatableGradientColorValue,
+    /// The opacity in percents.
+    pub opacity: AnimatableIntegerValue,
+    pub start_point: AnimatablePointValue,
+    pub end_point: AnimatablePointValue,
+    /// The distance of the focal point of a radial gradient from its
+    /// center, in percents of the radius.
+    pub highlight_length: Option<AnimatableDoubleValue>,
+    /// The direction of the focal point of a radial gradient in degrees.
+    pub highlight_angle: Option<AnimatableDoubleValue>,
+    pub hidden: bool,
+}
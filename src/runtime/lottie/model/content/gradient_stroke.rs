@@ -1 +1,26 @@
-pub struct GradientStroke;
+use super::{GradientType, LineCapType, LineJoinType};
+use crate::lottie::model::animatable::{
+    AnimatableDoubleValue, AnimatableGradientColorValue, AnimatableIntegerValue,
+    AnimatablePointValue,
+};
+
+/// A gradient stroke of the paths before it in its group.
+#[derive(Clone, Debug)]
+pub struct GradientStroke {
+    pub name: Option<String>,
+    pub gradient_type: GradientType,
+    pub gradient_color: AnimatableGradientColorValue,
+    /// The opacity in percents.
+    pub opacity: AnimatableIntegerValue,
+    pub start_point: AnimatablePointValue,
+    pub end_point: AnimatablePointValue,
+    pub width: AnimatableDoubleValue,
+    pub cap_type: LineCapType,
+    pub join_type: LineJoinType,
+    pub miter_limit: f64,
+    pub line_dash_pattern: Vec<AnimatableDoubleValue>,
+    pub dash_offset: Option<AnimatableDoubleValue>,
+    pub highlight_length: Option<AnimatableDoubleValue>,
+    pub highlight_angle: Option<AnimatableDoubleValue>,
+    pub hidden: bool,
+}
@@ -1 +1,27 @@
-pub struct Mask;
+use crate::lottie::model::animatable::{
+    AnimatableDoubleValue, AnimatableIntegerValue, AnimatableShapeValue,
+};
+
+/// How a mask combines with the masks before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaskMode {
+    Add,
+    Subtract,
+    Intersect,
+    Lighten,
+    Darken,
+    Difference,
+    None,
+}
+
+/// A mask of a layer.
+#[derive(Clone, Debug)]
+pub struct Mask {
+    pub mode: MaskMode,
+    pub path: AnimatableShapeValue,
+    /// The opacity in percents.
+    pub opacity: AnimatableIntegerValue,
+    /// Grows the mask outwards, or shrinks it when negative.
+    pub expansion: Option<AnimatableDoubleValue>,
+    pub inverted: bool,
+}
@@ -1 +1,29 @@
-pub struct MergePaths;
\ No newline at end of file
+/// How merge paths combine the paths before it in its group.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MergePathsMode {
+    Merge,
+    Add,
+    Subtract,
+    Intersect,
+    ExcludeIntersections,
+}
+
+impl MergePathsMode {
+    pub fn for_id(id: i64) -> Self {
+        match id {
+            2 => MergePathsMode::Add,
+            3 => MergePathsMode::Subtract,
+            4 => MergePathsMode::Intersect,
+            5 => MergePathsMode::ExcludeIntersections,
+            _ => MergePathsMode::Merge,
+        }
+    }
+}
+
+/// Combines the paths before it in its group into one path.
+#[derive(Clone, Debug)]
+pub struct MergePaths {
+    pub name: Option<String>,
+    pub mode: MergePathsMode,
+    pub hidden: bool,
+}
@@ -19,6 +19,9 @@ pub use gradient_type::*;
 mod mask;
 pub use mask::*;
 
+mod merge_paths;
+pub use merge_paths::*;
+
 mod polystar_shape;
 pub use polystar_shape::*;
 
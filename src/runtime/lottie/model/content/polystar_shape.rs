@@ -1 +1,39 @@
-pub struct PolystarShapeType;
+use crate::lottie::model::animatable::{AnimatableDoubleValue, AnimatablePosition};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PolystarShapeType {
+    Star,
+    Polygon,
+}
+
+impl PolystarShapeType {
+    pub fn for_value(value: i64) -> Option<Self> {
+        match value {
+            1 => Some(PolystarShapeType::Star),
+            2 => Some(PolystarShapeType::Polygon),
+            _ => None,
+        }
+    }
+}
+
+/// A star or a regular polygon.
+///
+/// Polygons have no inner radius or roundness.
+#[derive(Clone, Debug)]
+pub struct PolystarShape {
+    pub name: Option<String>,
+    pub kind: PolystarShapeType,
+    pub points: AnimatableDoubleValue,
+    pub position: AnimatablePosition,
+    /// The rotation in degrees.
+    pub rotation: AnimatableDoubleValue,
+    pub inner_radius: Option<AnimatableDoubleValue>,
+    pub outer_radius: AnimatableDoubleValue,
+    /// The roundness of the inner points in percents.
+    pub inner_roundness: Option<AnimatableDoubleValue>,
+    /// The roundness of the outer points in percents.
+    pub outer_roundness: AnimatableDoubleValue,
+    /// Drawn counterclockwise.
+    pub is_reversed: bool,
+    pub hidden: bool,
+}
@@ -1 +1,15 @@
-pub struct RectangleShape;
+use crate::lottie::model::animatable::{
+    AnimatableDoubleValue, AnimatablePointValue, AnimatablePosition,
+};
+
+/// A rectangle, from its center and size, with rounded corners.
+#[derive(Clone, Debug)]
+pub struct RectangleShape {
+    pub name: Option<String>,
+    pub position: AnimatablePosition,
+    pub size: AnimatablePointValue,
+    pub corner_radius: AnimatableDoubleValue,
+    /// Drawn counterclockwise.
+    pub is_reversed: bool,
+    pub hidden: bool,
+}
@@ -1 +1,12 @@
-pub struct Repeater;
+use crate::lottie::model::animatable::{AnimatableDoubleValue, AnimatableTransform};
+
+/// Draws copies of the contents before it in its group.
+#[derive(Clone, Debug)]
+pub struct Repeater {
+    pub name: Option<String>,
+    pub copies: AnimatableDoubleValue,
+    pub offset: AnimatableDoubleValue,
+    /// The transform applied once more to each next copy.
+    pub transform: AnimatableTransform,
+    pub hidden: bool,
+}
@@ -1 +1,37 @@
-pub struct ShapeData;
+use crate::{interpolate::Interpolate, lottie::model::CubicCurveData, Point};
+
+/// A Bezier shape: a start point followed by cubic curves.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShapeData {
+    pub initial_point: Point<f64>,
+    pub curves: Vec<CubicCurveData>,
+    pub closed: bool,
+}
+
+impl ShapeData {
+    pub fn new(initial_point: Point<f64>, closed: bool, curves: Vec<CubicCurveData>) -> Self {
+        Self {
+            initial_point,
+            curves,
+            closed,
+        }
+    }
+}
+
+impl Interpolate for ShapeData {
+    /// Interpolates the points of both shapes, shapes with a different number
+    /// of curves only interpolate their common curves. The shape is closed if
+    /// either shape is.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Self {
+            initial_point: self.initial_point.interpolate(&other.initial_point, f),
+            curves: self
+                .curves
+                .iter()
+                .zip(other.curves.iter())
+                .map(|(start, end)| start.interpolate(end, f))
+                .collect(),
+            closed: self.closed || other.closed,
+        }
+    }
+}
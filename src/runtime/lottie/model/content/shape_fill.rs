@@ -1 +1,21 @@
-pub struct ShapeFill;
+use crate::lottie::model::animatable::{AnimatableColorValue, AnimatableIntegerValue};
+
+/// The rule deciding which parts of a path are inside.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FillType {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+/// A solid fill of the paths before it in its group.
+#[derive(Clone, Debug)]
+pub struct ShapeFill {
+    pub name: Option<String>,
+    pub fill_enabled: bool,
+    pub fill_type: FillType,
+    pub color: Option<AnimatableColorValue>,
+    /// The opacity in percents.
+    pub opacity: Option<AnimatableIntegerValue>,
+    pub hidden: bool,
+}
@@ -1 +1,9 @@
-pub struct ShapeGroup;
+use super::ContentModel;
+
+/// A group of contents, drawn in order with the transform of the group.
+#[derive(Clone, Debug, Default)]
+pub struct ShapeGroup {
+    pub name: Option<String>,
+    pub items: Vec<ContentModel>,
+    pub hidden: bool,
+}
@@ -1 +1,10 @@
-pub struct ShapePath;
+use crate::lottie::model::animatable::AnimatableShapeValue;
+
+/// A Bezier path.
+#[derive(Clone, Debug)]
+pub struct ShapePath {
+    pub name: Option<String>,
+    pub index: i64,
+    pub shape: AnimatableShapeValue,
+    pub hidden: bool,
+}
@@ -1 +1,74 @@
-pub struct ShapeStroke;
+use crate::{
+    lottie::model::animatable::{
+        AnimatableColorValue, AnimatableDoubleValue, AnimatableIntegerValue,
+    },
+    LineCap, LineJoin,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineCapType {
+    Butt,
+    Round,
+    Square,
+}
+
+impl LineCapType {
+    pub fn for_id(id: i64) -> Self {
+        match id {
+            2 => LineCapType::Round,
+            3 => LineCapType::Square,
+            _ => LineCapType::Butt,
+        }
+    }
+
+    pub fn to_line_cap(self) -> LineCap {
+        match self {
+            LineCapType::Butt => LineCap::Butt,
+            LineCapType::Round => LineCap::Round,
+            LineCapType::Square => LineCap::Square,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineJoinType {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoinType {
+    pub fn for_id(id: i64) -> Self {
+        match id {
+            2 => LineJoinType::Round,
+            3 => LineJoinType::Bevel,
+            _ => LineJoinType::Miter,
+        }
+    }
+
+    pub fn to_line_join(self) -> LineJoin {
+        match self {
+            LineJoinType::Miter => LineJoin::Miter,
+            LineJoinType::Round => LineJoin::Round,
+            LineJoinType::Bevel => LineJoin::Bevel,
+        }
+    }
+}
+
+/// A solid stroke of the paths before it in its group.
+#[derive(Clone, Debug)]
+pub struct ShapeStroke {
+    pub name: Option<String>,
+    /// The offset of the dash pattern.
+    pub dash_offset: Option<AnimatableDoubleValue>,
+    /// Alternating dash and gap lengths.
+    pub line_dash_pattern: Vec<AnimatableDoubleValue>,
+    pub color: AnimatableColorValue,
+    /// The opacity in percents.
+    pub opacity: AnimatableIntegerValue,
+    pub width: AnimatableDoubleValue,
+    pub cap_type: LineCapType,
+    pub join_type: LineJoinType,
+    pub miter_limit: f64,
+    pub hidden: bool,
+}
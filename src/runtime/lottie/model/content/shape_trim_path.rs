@@ -1 +1,34 @@
-pub struct ShapeTrimPath;
+use crate::lottie::model::animatable::AnimatableDoubleValue;
+
+/// How a trim path applies to several paths.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrimPathType {
+    /// Trims every path by the same amount.
+    Simultaneously,
+    /// Trims the paths as if they were one path following another.
+    Individually,
+}
+
+impl TrimPathType {
+    pub fn for_id(id: i64) -> Option<Self> {
+        match id {
+            1 => Some(TrimPathType::Simultaneously),
+            2 => Some(TrimPathType::Individually),
+            _ => None,
+        }
+    }
+}
+
+/// Trims the paths before it in its group.
+#[derive(Clone, Debug)]
+pub struct ShapeTrimPath {
+    pub name: Option<String>,
+    pub kind: TrimPathType,
+    /// The start in percents of the path length.
+    pub start: AnimatableDoubleValue,
+    /// The end in percents of the path length.
+    pub end: AnimatableDoubleValue,
+    /// The offset in degrees, a full turn offsets the whole path length.
+    pub offset: AnimatableDoubleValue,
+    pub hidden: bool,
+}
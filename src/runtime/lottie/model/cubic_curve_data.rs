@@ -1 +1,30 @@
-pub struct CubicCurveData;
+use crate::{interpolate::Interpolate, Point};
+
+/// One cubic curve of a [`ShapeData`](super::content::ShapeData), from the
+/// end of the previous curve to `vertex`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CubicCurveData {
+    pub control_point1: Point<f64>,
+    pub control_point2: Point<f64>,
+    pub vertex: Point<f64>,
+}
+
+impl CubicCurveData {
+    pub fn new(control_point1: Point<f64>, control_point2: Point<f64>, vertex: Point<f64>) -> Self {
+        Self {
+            control_point1,
+            control_point2,
+            vertex,
+        }
+    }
+}
+
+impl Interpolate for CubicCurveData {
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        Self {
+            control_point1: self.control_point1.interpolate(&other.control_point1, f),
+            control_point2: self.control_point2.interpolate(&other.control_point2, f),
+            vertex: self.vertex.interpolate(&other.vertex, f),
+        }
+    }
+}
@@ -1,25 +1,28 @@
-#![allow(clippy::clippy::too_many_arguments)]
-use crate::Color;
+#![allow(clippy::too_many_arguments)]
+use crate::{interpolate::Interpolate, RgbaColor};
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Justification {
     LeftAlign,
     RightAlign,
     Center,
 }
 
+/// A text and its style, at a keyframe of a text layer.
+#[derive(Clone, Debug)]
 pub struct DocumentData {
-    text: String,
-    font_name: String,
-    size: f64,
-    justification: Justification,
-    tracking: i32,
-    line_height: f64,
-    baseline_shift: f64,
-    color: Color,
-    stroke_color: Color,
-    stroke_width: f64,
-    stroke_over_fill: bool,
+    pub text: String,
+    pub font_name: String,
+    pub size: f64,
+    pub justification: Justification,
+    /// The extra space between characters, in thousandths of an em.
+    pub tracking: i32,
+    pub line_height: f64,
+    pub baseline_shift: f64,
+    pub color: RgbaColor,
+    pub stroke_color: RgbaColor,
+    pub stroke_width: f64,
+    pub stroke_over_fill: bool,
 }
 
 impl DocumentData {
@@ -31,8 +34,8 @@ impl DocumentData {
         tracking: i32,
         line_height: f64,
         baseline_shift: f64,
-        color: Color,
-        stroke_color: Color,
+        color: RgbaColor,
+        stroke_color: RgbaColor,
         stroke_width: f64,
         stroke_over_fill: bool,
     ) -> Self {
@@ -69,3 +72,15 @@ impl PartialEq for DocumentData {
 }
 
 impl Eq for DocumentData {}
+
+impl Interpolate for DocumentData {
+    /// Documents are not interpolated, the start document holds until the
+    /// end.
+    fn interpolate(&self, other: &Self, f: f64) -> Self {
+        if f < 1.0 {
+            self.clone()
+        } else {
+            other.clone()
+        }
+    }
+}
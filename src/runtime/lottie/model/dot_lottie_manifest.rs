@@ -0,0 +1,36 @@
+/// An animation listed in the manifest of a dotLottie file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DotLottieAnimation {
+    /// The id of the animation, its JSON is `animations/<id>.json`.
+    pub id: String,
+    pub speed: f64,
+    pub looping: bool,
+    pub autoplay: bool,
+    /// `1` to play forward, `-1` to play backward.
+    pub direction: i32,
+}
+
+/// The `manifest.json` of a dotLottie file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DotLottieManifest {
+    pub version: Option<String>,
+    pub generator: Option<String>,
+    pub author: Option<String>,
+    /// The animation shown by default, the first one if there is none.
+    pub active_animation_id: Option<String>,
+    pub animations: Vec<DotLottieAnimation>,
+}
+
+impl DotLottieManifest {
+    pub fn get_animation(&self, id: &str) -> Option<&DotLottieAnimation> {
+        self.animations.iter().find(|animation| animation.id == id)
+    }
+
+    /// Returns the animation shown by default.
+    pub fn get_active_animation(&self) -> Option<&DotLottieAnimation> {
+        self.active_animation_id
+            .as_deref()
+            .and_then(|id| self.get_animation(id))
+            .or_else(|| self.animations.first())
+    }
+}
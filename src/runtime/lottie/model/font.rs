@@ -1,8 +1,9 @@
+#[derive(Clone, Debug, PartialEq)]
 pub struct Font {
-    family: String,
-    name: String,
-    style: String,
-    ascent: f64,
+    pub family: String,
+    pub name: String,
+    pub style: String,
+    pub ascent: f64,
 }
 
 impl Font {
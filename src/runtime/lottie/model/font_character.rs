@@ -1,12 +1,18 @@
 use super::content::ShapeGroup;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
+/// The shapes of a glyph embedded in the composition.
+#[derive(Clone, Debug)]
 pub struct FontCharacter {
-    shapes: Vec<ShapeGroup>,
-    character: String,
-    size: f64,
-    width: f64,
-    style: String,
-    font_family: String,
+    pub shapes: Vec<ShapeGroup>,
+    pub character: String,
+    pub size: f64,
+    pub width: f64,
+    pub style: String,
+    pub font_family: String,
 }
 
 impl FontCharacter {
@@ -28,11 +34,16 @@ impl FontCharacter {
         }
     }
 
+    /// Returns the key of a glyph in the characters of a composition.
     pub fn hash_for(character: &str, font_family: &str, style: &str) -> u64 {
-        unimplemented!()
+        let mut hasher = DefaultHasher::new();
+        character.hash(&mut hasher);
+        font_family.hash(&mut hasher);
+        style.hash(&mut hasher);
+        hasher.finish()
     }
 
-    fn get_hash_code(&self) -> u64 {
+    pub fn get_hash_code(&self) -> u64 {
         FontCharacter::hash_for(&self.character, &self.font_family, &self.style)
     }
 }
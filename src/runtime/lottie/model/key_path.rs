@@ -1,6 +1,9 @@
-use super::KeyPathElement;
 use std::fmt;
 
+/// The name of the groups created around the layers of a composition and
+/// the contents of a shape layer, left out of key paths.
+const CONTAINER: &str = "__container";
+
 /// Defines which content to target.
 /// The keypath can contain wildcards ('*') with match exactly 1 item.
 /// or globstars ('**') which match 0 or more items.
@@ -22,59 +25,54 @@ use std::fmt;
 ///
 /// You could:
 ///     Match Gabriel left hand fill:
-///        new KeyPath("Gabriel", "Body", "Left Hand", "Fill");
+///        KeyPath::from_keys(&["Gabriel", "Body", "Left Hand", "Fill"]);
 ///     Match Gabriel and Brandon's left hand fill:
-///        new KeyPath("*", "Body", Left Hand", "Fill");
+///        KeyPath::from_keys(&["*", "Body", "Left Hand", "Fill"]);
 ///     Match anything with the name Fill:
-///        new KeyPath("**", "Fill");
-///
+///        KeyPath::from_keys(&["**", "Fill"]);
 ///
-/// NOTE: Content that are part of merge paths or repeaters cannot currently be resolved with
-/// a {@link KeyPath}. This may be fixed in the future.
-
+/// The contents taken in by merge paths and repeaters are matched as if
+/// they were next to them in their group.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct KeyPath {
     keys: Vec<String>,
-    // resolved_element: Option<Box<dyn KeyPathElement>>,
 }
 
 impl KeyPath {
     pub fn new(keys: Vec<String>) -> Self {
-        Self {
-            // resolved_element: None,
-            keys,
-        }
+        Self { keys }
     }
 
-    /// Copy constructor. Copies keys as well.
-    // KeyPath.copy(KeyPath keyPath)
-    //     : keys = keyPath.keys.toList(),
-    //         _resolvedElement = keyPath._resolvedElement;
+    pub fn from_keys<S: AsRef<str>>(keys: &[S]) -> Self {
+        Self::new(keys.iter().map(|key| key.as_ref().into()).collect())
+    }
+
+    pub fn get_keys(&self) -> &[String] {
+        &self.keys
+    }
 
     /// Returns a new KeyPath with the key added.
     /// This is used during keypath resolution. Children normally don't know about all of their parent
     /// elements so this is used to keep track of the fully qualified keypath.
     /// This returns a key keypath because during resolution, the full keypath element tree is walked
     /// and if this modified the original copy, it would remain after popping back up the element tree.
-    //@CheckResult
-
-    fn add_key(&self, key: String) {
-        unimplemented!()
-    }
-
-    /// Return a new KeyPath with the element resolved to the specified {@link KeyPathElement}.
-    fn resolve(&self, element: impl KeyPathElement) -> KeyPath {
-        unimplemented!()
+    pub fn add_key(&self, key: &str) -> KeyPath {
+        let mut key_path = self.clone();
+        key_path.keys.push(key.into());
+        key_path
     }
 
-    // /// Returns a {@link KeyPathElement} that this has been resolved to. KeyPaths get resolved with
-    // /// resolveKeyPath on LottieDrawable or LottieAnimationView.
-    // fn get_resolved_element(&self) -> impl KeyPathElement {
-    //     unimplemented!()
-    // }
-
     /// Returns whether they key matches at the specified depth.
-    fn matches(&self, key: String, depth: i64) -> bool {
-        unimplemented!()
+    pub fn matches(&self, key: &str, depth: usize) -> bool {
+        if Self::is_container(key) {
+            return true;
+        }
+        match self.keys.get(depth) {
+            Some(key_at_depth) => {
+                key_at_depth == key || key_at_depth == "**" || key_at_depth == "*"
+            }
+            None => false,
+        }
     }
 
     /// For a given key and depth, returns how much the depth should be incremented by when
@@ -82,40 +80,90 @@ impl KeyPath {
     ///
     /// This can be 0 or 2 when there is a globstar and the next key either matches or doesn't match
     /// the current key.
-    fn increment_depth_by(&self, key: String, depth: i64) -> i64 {
-        unimplemented!()
+    pub fn increment_depth_by(&self, key: &str, depth: usize) -> usize {
+        if Self::is_container(key) {
+            return 0;
+        }
+        if self.keys[depth] != "**" {
+            return 1;
+        }
+        if depth == self.keys.len() - 1 {
+            return 0;
+        }
+        if self.keys[depth + 1] == key {
+            return 2;
+        }
+        0
     }
 
     /// Returns whether the key at specified depth is fully specific enough to match the full set of
     /// keys in this keypath.
-    fn fully_resolves_to(&self, key: String, depth: i64) -> bool {
-        unimplemented!()
+    pub fn fully_resolves_to(&self, key: &str, depth: usize) -> bool {
+        if depth >= self.keys.len() {
+            return false;
+        }
+        let len = self.keys.len();
+        let is_last_depth = depth == len - 1;
+        let key_at_depth = &self.keys[depth];
+        if key_at_depth != "**" {
+            let matches = key_at_depth == key || key_at_depth == "*";
+            return (is_last_depth || (depth + 2 == len && self.ends_with_globstar())) && matches;
+        }
+
+        let is_globstar_but_next_key_matches = !is_last_depth && self.keys[depth + 1] == key;
+        if is_globstar_but_next_key_matches {
+            return depth + 2 == len || (depth + 3 == len && self.ends_with_globstar());
+        }
+        if is_last_depth {
+            return true;
+        }
+        if depth + 1 < len - 1 {
+            // we are a globstar but there is more than 1 key after the globstar
+            // we we can't fully match
+            return false;
+        }
+        // Return whether the next key (which we now know is the last one) is the same as the current
+        // key.
+        self.keys[depth + 1] == key
     }
 
     /// Returns whether the keypath resolution should propagate to children. Some keypaths resolve
     /// to content other than leaf contents (such as a layer or content group transform) so sometimes
     /// this will return false.
-    fn propogate_to_children(&self, key: String, depth: i64) -> bool {
-        unimplemented!()
+    pub fn propagate_to_children(&self, key: &str, depth: usize) -> bool {
+        if Self::is_container(key) {
+            return true;
+        }
+        depth + 1 < self.keys.len() || self.keys.get(depth).map(String::as_str) == Some("**")
+    }
+
+    /// Returns the depth the children of the element named `key` at
+    /// `depth` are matched at, `None` if they are not matched.
+    pub(crate) fn children_depth(&self, key: &str, depth: usize) -> Option<usize> {
+        if self.matches(key, depth) && self.propagate_to_children(key, depth) {
+            Some(depth + self.increment_depth_by(key, depth))
+        } else {
+            None
+        }
     }
 
     /// We artificially create some container groups (like a root ContentGroup for the entire animation
     /// and for the contents of a ShapeLayer).
-    fn is_container(&self, key: String) -> bool {
-        unimplemented!()
+    pub(crate) fn is_container(key: &str) -> bool {
+        key == CONTAINER
     }
-    fn ends_with_globstart(&self) -> bool {
-        unimplemented!()
+
+    fn ends_with_globstar(&self) -> bool {
+        self.keys.last().map(String::as_str) == Some("**")
     }
 
-    fn keys_to_string(&self) -> String {
-        unimplemented!()
+    pub fn keys_to_string(&self) -> String {
+        self.keys.join(", ")
     }
 }
 
 impl fmt::Display for KeyPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        //keys={}, self.keys , resolved={{{}}}, self.resolved_element
-        write!(f, "KeyPath[]")
+        write!(f, "KeyPath[{}]", self.keys_to_string())
     }
 }
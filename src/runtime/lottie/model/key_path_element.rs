@@ -1,31 +1,34 @@
 use super::KeyPath;
-use crate::runtime::lottie::value::LottieValueCallback;
-// import '../value/lottie_value_callback.dart';
+use crate::lottie::LottieProperty;
 
-/// Any item that can be a part of a {@link KeyPath} should implement this.
+/// Any item that can be a part of a [`KeyPath`] should implement this.
 pub trait KeyPathElement {
     /// Called recursively during keypath resolution.
     ///
-    /// The overridden method should just call:
-    ///        MiscUtils.resolveKeyPath(keyPath, depth, accumulator, currentPartialKeyPath, this);
+    /// Leaf contents should just call
+    /// [`MiscUtils::resolve_key_path`](crate::lottie::utils::MiscUtils::resolve_key_path).
     ///
-    /// @param keyPath The full keypath being resolved.
-    /// @param depth The current depth that this element should be checked at in the keypath.
-    /// @param accumulator A list of fully resolved keypaths. If this element fully matches the
-    ///                    keypath then it should add itself to this list.
-    /// @param currentPartialKeyPath A keypath that contains all parent element of this one.
-    ///                              This element should create a copy of this and append itself
-    ///                              with KeyPath#addKey when it adds itself to the accumulator
-    ///                              or propagates resolution to its children.
+    /// - `key_path`: the full keypath being resolved.
+    /// - `depth`: the current depth that this element should be checked at
+    ///   in the keypath.
+    /// - `accumulator`: a list of fully resolved keypaths. If this element
+    ///   fully matches the keypath then it should add itself to this list.
+    /// - `current_partial_key_path`: a keypath that contains all parent
+    ///   elements of this one. This element should append itself with
+    ///   [`KeyPath::add_key`] when it adds itself to the accumulator or
+    ///   propagates resolution to its children.
     fn resolve_key_path(
         &self,
-        key_path: KeyPath,
-        depth: i64,
-        accumulator: Vec<KeyPath>,
-        current_partial_pey_path: KeyPath,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
     );
 
-    /// The overridden method should handle appropriate properties and set value callbacks on their
-    /// animations.
-    fn add_value_callback<T>(&self, property: T, callback: LottieValueCallback<T>);
+    /// Sets the callback of `property` on this element and its children
+    /// where they fully match `key_path`, walking the elements like
+    /// [`resolve_key_path`](KeyPathElement::resolve_key_path).
+    ///
+    /// Elements without the property are left as they are.
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty);
 }
@@ -1 +1,338 @@
-pub struct BaseLayer;
+use super::{
+    CompositionLayer, ImageLayer, Layer, LayerType, NullLayer, ShapeLayer, SolidLayer, TextLayer,
+};
+use crate::{
+    lottie::{
+        animation::keyframe::{
+            DoubleKeyframeAnimation, KeyframeAnimation, MaskKeyframeAnimation,
+            TransformKeyframeAnimation,
+        },
+        model::{content::MaskMode, KeyPath, KeyPathElement},
+        utils::MiscUtils,
+        LottieComposition, LottieDelegates, LottieProperty,
+    },
+    CanvasContext, LayerComposite, LineJoin, OffscreenContext, RgbaColor, Transform,
+};
+use std::collections::HashSet;
+
+/// What a layer draws.
+pub enum LayerKind {
+    PreComp(CompositionLayer),
+    Solid(SolidLayer),
+    Image(ImageLayer),
+    Null(NullLayer),
+    Shape(Box<ShapeLayer>),
+    Text(Box<TextLayer>),
+}
+
+/// A layer of a composition, ready to be drawn.
+pub struct BaseLayer {
+    layer: Layer,
+    transform: TransformKeyframeAnimation,
+    /// The masks, `None` without any enabled mask.
+    masks: Option<MaskKeyframeAnimation>,
+    /// The time of the contents of a precomposition, in seconds.
+    time_remapping: Option<DoubleKeyframeAnimation>,
+    /// The frame rate of the composition, to turn remapped times into
+    /// frames.
+    frame_rate: f64,
+    kind: LayerKind,
+}
+
+impl BaseLayer {
+    /// Creates the layer of `layer`, `None` for a layer of unknown type.
+    ///
+    /// Merge paths are drawn only with `enable_merge_paths`. `precomps`
+    /// holds the ids of the precompositions the layer is in, a
+    /// precomposition inside itself is left empty.
+    pub fn new(
+        layer: &Layer,
+        composition: &LottieComposition,
+        enable_merge_paths: bool,
+        precomps: &mut HashSet<String>,
+    ) -> Option<Self> {
+        let kind = match layer.layer_type {
+            LayerType::PreComp => {
+                let id = layer.ref_id.as_deref().unwrap_or_default();
+                let precomp = if precomps.insert(id.to_owned()) {
+                    let layers = composition.get_precomps(id).unwrap_or(&[]);
+                    let precomp =
+                        CompositionLayer::new(layers, composition, enable_merge_paths, precomps);
+                    precomps.remove(id);
+                    precomp
+                } else {
+                    composition.add_warning(&format!("Precomposition {} contains itself.", id));
+                    CompositionLayer::new(&[], composition, enable_merge_paths, precomps)
+                };
+                LayerKind::PreComp(precomp)
+            }
+            LayerType::Solid => LayerKind::Solid(SolidLayer::new(layer)),
+            LayerType::Image => LayerKind::Image(ImageLayer::new(layer, composition)),
+            LayerType::Null => LayerKind::Null(NullLayer),
+            LayerType::Shape => {
+                LayerKind::Shape(Box::new(ShapeLayer::new(layer, enable_merge_paths)))
+            }
+            LayerType::Text => LayerKind::Text(Box::new(TextLayer::new(
+                layer,
+                composition,
+                enable_merge_paths,
+            ))),
+            LayerType::Unknown => return None,
+        };
+        let masks = Some(MaskKeyframeAnimation::new(&layer.masks))
+            .filter(|masks| !masks.are_all_masks_none());
+        Some(Self {
+            layer: layer.clone(),
+            transform: TransformKeyframeAnimation::new(&layer.transform),
+            masks,
+            time_remapping: layer
+                .time_remapping
+                .as_ref()
+                .map(|time_remapping| time_remapping.create_animation()),
+            frame_rate: composition.get_frame_rate(),
+            kind,
+        })
+    }
+
+    /// Returns the model of the layer.
+    pub fn get_layer(&self) -> &Layer {
+        &self.layer
+    }
+
+    pub fn get_kind(&self) -> &LayerKind {
+        &self.kind
+    }
+
+    pub fn get_kind_mut(&mut self) -> &mut LayerKind {
+        &mut self.kind
+    }
+
+    /// Returns the transform of the layer at `frame`, without its parents.
+    pub fn get_matrix(&self, frame: f64) -> Transform<f64> {
+        self.transform.get_matrix(frame)
+    }
+
+    /// Returns the frame of the contents of a precomposition at `frame` of
+    /// the composition around it.
+    ///
+    /// The contents start at the start frame of the layer and run slower
+    /// with a time stretch above 1, unless their time is remapped.
+    pub fn get_precomp_frame(&self, frame: f64) -> f64 {
+        let remapped = self
+            .time_remapping
+            .as_ref()
+            .and_then(|time_remapping| time_remapping.value_at(frame));
+        match remapped {
+            Some(seconds) => seconds * self.frame_rate,
+            None => (frame - self.layer.start_frame) / self.layer.time_stretch,
+        }
+    }
+
+    /// Returns `true` if the layer is shown at `frame`.
+    pub fn is_visible(&self, frame: f64) -> bool {
+        !self.layer.hidden && frame >= self.layer.in_frame && frame < self.layer.out_frame
+    }
+
+    /// Returns the number of enabled masks of the layer.
+    pub fn get_mask_count(&self) -> u32 {
+        self.masks.as_ref().map_or(0, |masks| {
+            masks
+                .get_masks()
+                .iter()
+                .filter(|mask| mask.mode != MaskMode::None)
+                .count() as u32
+        })
+    }
+
+    /// Returns the number of layers drawn offscreen to draw the layer with
+    /// its masks at `frame`, as [`draw`](Self::draw) pushes them.
+    pub fn get_offscreen_pass_count(&self, frame: f64) -> u32 {
+        let masks = match &self.masks {
+            Some(masks) => masks,
+            None => return 0,
+        };
+        let mut count = 2;
+        for (index, mask) in masks.get_masks().iter().enumerate() {
+            if mask.mode == MaskMode::None {
+                continue;
+            }
+            count += 1;
+            if mask.inverted {
+                count += 1;
+            }
+            if masks.get_expansion(index, frame) < 0.0 {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Draws the layer at `frame` with `matrix`, its transform and the
+    /// transforms of its parents.
+    ///
+    /// A layer with masks is drawn offscreen, then masked.
+    pub fn draw<C: OffscreenContext>(
+        &self,
+        canvas: &C,
+        delegates: &LottieDelegates,
+        matrix: &Transform<f64>,
+        parent_alpha: f64,
+        frame: f64,
+    ) {
+        if !self.is_visible(frame) {
+            return;
+        }
+        let alpha = parent_alpha * self.transform.get_opacity(frame);
+        if alpha <= 0.0 {
+            return;
+        }
+
+        canvas.save();
+        let [a, b, c, d, e, f] = matrix.to_array();
+        canvas.transform(a, b, c, d, e, f);
+        if self.masks.is_some() {
+            canvas.push_layer();
+        }
+        match &self.kind {
+            LayerKind::PreComp(layer) => {
+                let (width, height) = (self.layer.pre_comp_width, self.layer.pre_comp_height);
+                // the contents are clipped to the bounds of the precomposition
+                if width > 0.0 && height > 0.0 {
+                    canvas.begin_path();
+                    canvas.rect(0.0, 0.0, width, height);
+                    canvas.clip();
+                    canvas.begin_path();
+                }
+                layer.draw(canvas, delegates, alpha, self.get_precomp_frame(frame));
+            }
+            LayerKind::Solid(layer) => layer.draw(canvas, alpha),
+            LayerKind::Shape(layer) => layer.draw(canvas, alpha, frame),
+            LayerKind::Text(layer) => layer.draw(canvas, delegates, alpha, frame),
+            LayerKind::Image(layer) => layer.draw(canvas, alpha),
+            LayerKind::Null(_) => {}
+        }
+        if let Some(masks) = &self.masks {
+            canvas.push_layer();
+            Self::draw_masks(canvas, masks, frame);
+            canvas.pop_layer(LayerComposite::DestinationIn, 1.0);
+            canvas.pop_layer(LayerComposite::SourceOver, 1.0);
+        }
+        canvas.restore();
+    }
+
+    /// Draws the coverage of `masks` as alpha, each mask combined with the
+    /// masks before it.
+    ///
+    /// Lighten, darken and difference are exact for opaque masks only, they
+    /// are drawn as a union, an intersection and an exclusion.
+    fn draw_masks<C: OffscreenContext>(canvas: &C, masks: &MaskKeyframeAnimation, frame: f64) {
+        let white = MiscUtils::with_alpha(RgbaColor::new(255, 255, 255, 255), 1.0);
+        let mut first = true;
+        for (index, mask) in masks.get_masks().iter().enumerate() {
+            let composite = match mask.mode {
+                MaskMode::None => continue,
+                MaskMode::Add | MaskMode::Lighten => LayerComposite::SourceOver,
+                MaskMode::Subtract => LayerComposite::DestinationOut,
+                MaskMode::Intersect | MaskMode::Darken => LayerComposite::DestinationIn,
+                MaskMode::Difference => LayerComposite::Xor,
+            };
+            // masks which remove coverage start from a fully covered layer
+            if first && composite != LayerComposite::SourceOver {
+                canvas.set_fill_color(white);
+                canvas.paint();
+            }
+            first = false;
+
+            canvas.push_layer();
+            canvas.set_fill_color(white);
+            canvas.set_stroke_color(white);
+            if mask.inverted {
+                canvas.paint();
+                canvas.push_layer();
+            }
+
+            let path = masks.get_path(index, frame);
+            MiscUtils::trace_path(canvas, &path);
+            canvas.fill();
+            // the path grows or shrinks by the half of a stroke around it
+            let expansion = masks.get_expansion(index, frame);
+            if expansion != 0.0 {
+                if expansion < 0.0 {
+                    canvas.push_layer();
+                }
+                canvas.set_line_width(expansion.abs() * 2.0);
+                canvas.set_line_join(LineJoin::Round);
+                MiscUtils::trace_path(canvas, &path);
+                canvas.stroke();
+                if expansion < 0.0 {
+                    canvas.pop_layer(LayerComposite::DestinationOut, 1.0);
+                }
+            }
+
+            if mask.inverted {
+                canvas.pop_layer(LayerComposite::DestinationOut, 1.0);
+            }
+            canvas.pop_layer(composite, masks.get_opacity(index, frame));
+        }
+    }
+}
+
+/// A layer matches its name, the contents of a shape layer and the layers
+/// of a precomposition are matched after it.
+impl KeyPathElement for BaseLayer {
+    fn resolve_key_path(
+        &self,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+    ) {
+        let name = &self.layer.name;
+        if !key_path.matches(name, depth) {
+            return;
+        }
+        let current_partial_key_path = current_partial_key_path.add_key(name);
+        if key_path.fully_resolves_to(name, depth) {
+            accumulator.push(current_partial_key_path.clone());
+        }
+        if let Some(depth) = key_path.children_depth(name, depth) {
+            match &self.kind {
+                LayerKind::PreComp(layer) => {
+                    layer.resolve_key_path(key_path, depth, accumulator, &current_partial_key_path)
+                }
+                LayerKind::Shape(layer) => layer.get_content_group().resolve_contents_key_path(
+                    key_path,
+                    depth,
+                    accumulator,
+                    &current_partial_key_path,
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    /// Sets the callback on the transform of the layer and on the
+    /// properties of a text layer if the layer matches, and on the contents
+    /// matching after it.
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        let name = &self.layer.name;
+        if !key_path.matches(name, depth) {
+            return;
+        }
+        if key_path.fully_resolves_to(name, depth) && !self.transform.apply_value_callback(property)
+        {
+            if let LayerKind::Text(layer) = &mut self.kind {
+                layer.apply_value_callback(property);
+            }
+        }
+        if let Some(depth) = key_path.children_depth(name, depth) {
+            match &mut self.kind {
+                LayerKind::PreComp(layer) => layer.add_value_callback(key_path, depth, property),
+                LayerKind::Shape(layer) => layer
+                    .get_content_group_mut()
+                    .add_contents_value_callback(key_path, depth, property),
+                _ => {}
+            }
+        }
+    }
+}
@@ -1,59 +1,208 @@
-// use dx::Matrix; // not available in wasm
+use super::{BaseLayer, Layer, LayerKind, MatteType};
 use crate::{
     lottie::{
-        animation::keyframe::BaseKeyframeAnimation, model::KeyPath, value::LottieValueCallback,
+        model::{KeyPath, KeyPathElement},
+        LottieComposition, LottieDelegates, LottieProperty, PerformanceTracker,
     },
-    Canvas, Rect, Size,
+    CanvasContext, LayerComposite, OffscreenContext, Transform,
 };
+use std::{collections::HashSet, rc::Rc};
 
-use super::BaseLayer;
-
+/// The layers of a composition or of a precomposition.
 pub struct CompositionLayer {
-    time_remapping: BaseKeyframeAnimation,
     layers: Vec<BaseLayer>,
-    // layer_paint: Paint,
-    has_mate: bool,
-    has_masks: bool,
+    /// The indices of the parents of each layer, the closest first.
+    parents: Vec<Vec<usize>>,
+    /// The paths the draws of the layers are recorded with.
+    paths: Vec<String>,
+    performance_tracker: Rc<PerformanceTracker>,
 }
 
 impl CompositionLayer {
-    // override
-    // parent_alpha ???
-    // pub fn draw_layer(&self, canvas: Canvas, size: Size<f64>, parent_matrix: Matrix) {
-    //     unimplemented!()
-    // }
+    /// Creates the layers of `layers`, layers of unknown type are left out.
+    ///
+    /// `precomps` holds the ids of the precompositions the layers are in,
+    /// see [`BaseLayer::new`].
+    pub fn new(
+        layers: &[Layer],
+        composition: &LottieComposition,
+        enable_merge_paths: bool,
+        precomps: &mut HashSet<String>,
+    ) -> Self {
+        let mut layers: Vec<BaseLayer> = layers
+            .iter()
+            .filter_map(|layer| BaseLayer::new(layer, composition, enable_merge_paths, precomps))
+            .collect();
+
+        // a name shared by layers is told apart with the index of the layer
+        let paths: Vec<String> = layers
+            .iter()
+            .map(|layer| {
+                let layer = layer.get_layer();
+                let shared = layers
+                    .iter()
+                    .filter(|other| other.get_layer().name == layer.name)
+                    .count()
+                    > 1;
+                if shared {
+                    format!("{}#{}", layer.name, layer.id)
+                } else {
+                    layer.name.clone()
+                }
+            })
+            .collect();
+        for (layer, path) in layers.iter_mut().zip(paths.iter()) {
+            if let LayerKind::PreComp(precomp) = layer.get_kind_mut() {
+                precomp.prefix_paths(path);
+            }
+        }
 
-    // // override
-    // fn get_bounds(&self, parent_matrix: Matrix, apply_parents: bool) -> Rect<f64> {
-    //     unimplemented!()
-    // }
+        let index_of = |id: i64| layers.iter().position(|layer| layer.get_layer().id == id);
+        let parents = layers
+            .iter()
+            .map(|layer| {
+                let mut chain = Vec::new();
+                let mut parent_id = layer.get_layer().parent_id;
+                while let Some(index) = parent_id.and_then(index_of) {
+                    // a parent cycle is broken at the first repeated layer
+                    if chain.contains(&index) {
+                        break;
+                    }
+                    chain.push(index);
+                    parent_id = layers[index].get_layer().parent_id;
+                }
+                chain
+            })
+            .collect();
+
+        Self {
+            layers,
+            parents,
+            paths,
+            performance_tracker: composition.get_performance_tracker(),
+        }
+    }
 
-    //override
-    fn set_progress(progress: f64) {
-        unimplemented!()
+    pub fn get_layers(&self) -> &[BaseLayer] {
+        &self.layers
     }
 
-    fn has_masks(&self) -> bool {
-        unimplemented!()
+    /// Puts the path of the precomposition layer `path` in front of the
+    /// paths of the layers, and of the layers of their precompositions.
+    fn prefix_paths(&mut self, path: &str) {
+        for (layer, own) in self.layers.iter_mut().zip(self.paths.iter_mut()) {
+            *own = format!("{}/{}", path, own);
+            if let LayerKind::PreComp(precomp) = layer.get_kind_mut() {
+                precomp.prefix_paths(path);
+            }
+        }
     }
 
-    fn has_matte(&self) -> bool {
-        unimplemented!()
+    /// Returns the transform of the layer at `index` and of its parents at
+    /// `frame`.
+    pub fn get_matrix(&self, index: usize, frame: f64) -> Transform<f64> {
+        let mut matrix = self.layers[index].get_matrix(frame);
+        for &parent in self.parents[index].iter() {
+            matrix = matrix.then(&self.layers[parent].get_matrix(frame));
+        }
+        matrix
     }
 
-    //override
-    fn resolve_child_key_path(
+    /// Returns how the layer at `index` is composited with its track matte,
+    /// the layer before it.
+    fn matte_composite(&self, index: usize) -> Option<LayerComposite> {
+        if index == 0 {
+            return None;
+        }
+        match self.layers[index].get_layer().matte_type {
+            MatteType::Add => Some(LayerComposite::DestinationIn),
+            MatteType::Invert => Some(LayerComposite::DestinationOut),
+            MatteType::Luma => Some(LayerComposite::LumaIn),
+            MatteType::LumaInverted => Some(LayerComposite::LumaOut),
+            MatteType::None | MatteType::Unknown => None,
+        }
+    }
+
+    /// Draws the layers at `frame`, the first layer on top.
+    ///
+    /// The layers used as track mattes are only drawn offscreen, to mask
+    /// the layer after them.
+    ///
+    /// The draws of the layers are recorded by the performance tracker of
+    /// the composition when it is enabled.
+    pub fn draw<C: OffscreenContext>(
+        &self,
+        canvas: &C,
+        delegates: &LottieDelegates,
+        parent_alpha: f64,
+        frame: f64,
+    ) {
+        for (index, layer) in self.layers.iter().enumerate().rev() {
+            let is_matte =
+                index + 1 < self.layers.len() && self.matte_composite(index + 1).is_some();
+            if is_matte || !layer.is_visible(frame) {
+                continue;
+            }
+
+            let stopwatch = self.performance_tracker.start();
+            let matrix = self.get_matrix(index, frame);
+            let matte_composite = self.matte_composite(index);
+            match matte_composite {
+                Some(composite) => {
+                    canvas.push_layer();
+                    layer.draw(canvas, delegates, &matrix, parent_alpha, frame);
+                    canvas.push_layer();
+                    let matte_matrix = self.get_matrix(index - 1, frame);
+                    self.layers[index - 1].draw(canvas, delegates, &matte_matrix, 1.0, frame);
+                    canvas.pop_layer(composite, 1.0);
+                    canvas.pop_layer(LayerComposite::SourceOver, 1.0);
+                }
+                None => layer.draw(canvas, delegates, &matrix, parent_alpha, frame),
+            }
+
+            // the counts are only worked out when tracking, to keep them off
+            // the render path
+            if let Some(stopwatch) = stopwatch {
+                let elapsed = stopwatch.elapsed();
+                let mut masks = layer.get_mask_count();
+                let mut offscreen_passes = layer.get_offscreen_pass_count(frame);
+                let mut mattes = 0;
+                if matte_composite.is_some() {
+                    let matte = &self.layers[index - 1];
+                    masks += matte.get_mask_count();
+                    offscreen_passes += 2 + matte.get_offscreen_pass_count(frame);
+                    mattes = 1;
+                }
+                self.performance_tracker.record_draw(
+                    &self.paths[index],
+                    elapsed,
+                    masks,
+                    mattes,
+                    offscreen_passes,
+                );
+            }
+        }
+    }
+}
+
+/// The layers are matched at the depth of the composition, which is not a
+/// part of key paths.
+impl KeyPathElement for CompositionLayer {
+    fn resolve_key_path(
         &self,
-        key_path: KeyPath,
-        depth: i32,
-        accumulator: Vec<KeyPath>,
-        current_partial_key_path: KeyPath,
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
     ) {
-        unimplemented!()
+        for layer in self.layers.iter() {
+            layer.resolve_key_path(key_path, depth, accumulator, current_partial_key_path);
+        }
     }
 
-    // override
-    fn add_value_callback<T>(property: T, callback: LottieValueCallback<T>) {
-        unimplemented!()
+    fn add_value_callback(&mut self, key_path: &KeyPath, depth: usize, property: &LottieProperty) {
+        for layer in self.layers.iter_mut() {
+            layer.add_value_callback(key_path, depth, property);
+        }
     }
 }
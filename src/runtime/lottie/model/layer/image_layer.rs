@@ -1 +1,46 @@
-pub struct ImageLayer;
+use super::Layer;
+use crate::{lottie::LottieComposition, Bitmap, OffscreenContext};
+use std::rc::Rc;
+
+/// A layer showing the image of an image asset.
+pub struct ImageLayer {
+    /// The image loaded for the asset, the layer draws nothing without it.
+    image: Option<Rc<Bitmap>>,
+    /// The size of the asset.
+    width: f64,
+    height: f64,
+}
+
+impl ImageLayer {
+    pub fn new(layer: &Layer, composition: &LottieComposition) -> Self {
+        let image = layer
+            .ref_id
+            .as_ref()
+            .and_then(|id| composition.images().get(id))
+            .and_then(|asset| asset.get_loaded_image())
+            .cloned();
+        Self {
+            image,
+            width: layer.pre_comp_width,
+            height: layer.pre_comp_height,
+        }
+    }
+
+    /// Draws the image stretched to the size of its asset, an image loaded
+    /// at another resolution takes the same place.
+    pub fn draw<C: OffscreenContext>(&self, canvas: &C, parent_alpha: f64) {
+        let image = match &self.image {
+            Some(image) if image.width() > 0 && image.height() > 0 => image,
+            _ => return,
+        };
+        canvas.save();
+        if self.width > 0.0 && self.height > 0.0 {
+            canvas.scale(
+                self.width / image.width() as f64,
+                self.height / image.height() as f64,
+            );
+        }
+        canvas.draw_image(image, 0.0, 0.0, parent_alpha);
+        canvas.restore();
+    }
+}
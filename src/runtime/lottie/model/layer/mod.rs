@@ -19,4 +19,131 @@ pub use solid_layer::*;
 mod text_layer;
 pub use text_layer::*;
 
-pub struct Layer;
+use super::{
+    animatable::{
+        AnimatableDoubleValue, AnimatableTextFrame, AnimatableTextProperties, AnimatableTransform,
+    },
+    content::{ContentModel, Mask},
+};
+use crate::RgbaColor;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayerType {
+    PreComp,
+    Solid,
+    Image,
+    Null,
+    Shape,
+    Text,
+    Unknown,
+}
+
+impl LayerType {
+    pub fn for_id(id: i64) -> Self {
+        match id {
+            0 => LayerType::PreComp,
+            1 => LayerType::Solid,
+            2 => LayerType::Image,
+            3 => LayerType::Null,
+            4 => LayerType::Shape,
+            5 => LayerType::Text,
+            _ => LayerType::Unknown,
+        }
+    }
+}
+
+/// How a layer is masked by the layer above it, its track matte.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatteType {
+    None,
+    /// Alpha matte.
+    Add,
+    /// Inverted alpha matte.
+    Invert,
+    Luma,
+    LumaInverted,
+    Unknown,
+}
+
+impl MatteType {
+    pub fn for_id(id: i64) -> Self {
+        match id {
+            0 => MatteType::None,
+            1 => MatteType::Add,
+            2 => MatteType::Invert,
+            3 => MatteType::Luma,
+            4 => MatteType::LumaInverted,
+            _ => MatteType::Unknown,
+        }
+    }
+}
+
+/// A layer of a composition or of a precomposition, as parsed.
+///
+/// Frames are in the time of the composition holding the layer.
+#[derive(Clone, Debug)]
+pub struct Layer {
+    pub name: String,
+    pub id: i64,
+    pub layer_type: LayerType,
+    pub parent_id: Option<i64>,
+    /// The id of the precomposition or image asset of the layer.
+    pub ref_id: Option<String>,
+    pub shapes: Vec<ContentModel>,
+    pub masks: Vec<Mask>,
+    pub transform: AnimatableTransform,
+    pub solid_width: f64,
+    pub solid_height: f64,
+    pub solid_color: RgbaColor,
+    /// The speed factor of the time of the layer.
+    pub time_stretch: f64,
+    /// The frame the time of the layer starts at.
+    pub start_frame: f64,
+    /// The frame the layer appears at.
+    pub in_frame: f64,
+    /// The frame the layer disappears at.
+    pub out_frame: f64,
+    pub pre_comp_width: f64,
+    pub pre_comp_height: f64,
+    pub text: Option<AnimatableTextFrame>,
+    pub text_properties: Option<AnimatableTextProperties>,
+    /// Maps the time of a precomposition layer to the time of its contents,
+    /// in seconds.
+    pub time_remapping: Option<AnimatableDoubleValue>,
+    pub matte_type: MatteType,
+    pub hidden: bool,
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Layer {
+            name: String::new(),
+            id: -1,
+            layer_type: LayerType::Unknown,
+            parent_id: None,
+            ref_id: None,
+            shapes: Vec::new(),
+            masks: Vec::new(),
+            transform: AnimatableTransform::default(),
+            solid_width: 0.0,
+            solid_height: 0.0,
+            solid_color: RgbaColor {
+                red: 0,
+                green: 0,
+                blue: 0,
+                alpha: 0,
+            },
+            time_stretch: 1.0,
+            start_frame: 0.0,
+            in_frame: 0.0,
+            out_frame: 0.0,
+            pre_comp_width: 0.0,
+            pre_comp_height: 0.0,
+            text: None,
+            text_properties: None,
+            time_remapping: None,
+            matte_type: MatteType::None,
+            hidden: false,
+        }
+    }
+}
@@ -1 +1,2 @@
+/// A layer which draws nothing, only its transform is used by its children.
 pub struct NullLayer;
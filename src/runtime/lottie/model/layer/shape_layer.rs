@@ -1 +1,28 @@
-pub struct ShapeLayer;
+use super::Layer;
+use crate::{lottie::animation::content::ContentGroup, CanvasContext};
+
+/// A layer of shapes.
+pub struct ShapeLayer {
+    content_group: ContentGroup,
+}
+
+impl ShapeLayer {
+    pub fn new(layer: &Layer, enable_merge_paths: bool) -> Self {
+        let name = Some(layer.name.clone());
+        Self {
+            content_group: ContentGroup::new(name, &layer.shapes, enable_merge_paths),
+        }
+    }
+
+    pub fn get_content_group(&self) -> &ContentGroup {
+        &self.content_group
+    }
+
+    pub fn get_content_group_mut(&mut self) -> &mut ContentGroup {
+        &mut self.content_group
+    }
+
+    pub fn draw<C: CanvasContext>(&self, canvas: &C, parent_alpha: f64, frame: f64) {
+        self.content_group.draw(canvas, frame, parent_alpha);
+    }
+}
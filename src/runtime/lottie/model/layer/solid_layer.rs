@@ -1 +1,27 @@
-pub struct SolidLayer;
+use super::Layer;
+use crate::{lottie::utils::MiscUtils, CanvasContext, RgbaColor};
+
+/// A layer filled with a color.
+pub struct SolidLayer {
+    color: RgbaColor,
+    width: f64,
+    height: f64,
+}
+
+impl SolidLayer {
+    pub fn new(layer: &Layer) -> Self {
+        Self {
+            color: layer.solid_color,
+            width: layer.solid_width,
+            height: layer.solid_height,
+        }
+    }
+
+    pub fn draw<C: CanvasContext>(&self, canvas: &C, parent_alpha: f64) {
+        if self.color.alpha == 0 {
+            return;
+        }
+        canvas.set_fill_color(MiscUtils::with_alpha(self.color, parent_alpha));
+        canvas.fill_rect(0.0, 0.0, self.width, self.height);
+    }
+}
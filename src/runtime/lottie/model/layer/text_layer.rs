@@ -1 +1,348 @@
-pub struct TextLayer;
+use super::Layer;
+use crate::{
+    lottie::{
+        animation::{
+            content::{ContentGroup, PathContent},
+            keyframe::{
+                set_optional_value_callback, ColorKeyframeAnimation, DoubleKeyframeAnimation,
+                KeyframeAnimation, TextKeyframeAnimation,
+            },
+        },
+        model::{animatable::AnimatableValue, DocumentData, Font, FontCharacter, Justification},
+        utils::MiscUtils,
+        LottieComposition, LottieDelegates, LottieFontStyle, LottieFrameInfo, LottieProperty,
+        LottieValueCallback,
+    },
+    path::Path,
+    CanvasContext, Color, TextStyle, TextWeight, Transform,
+};
+use std::collections::HashMap;
+
+/// The shapes of a glyph for a font size of 100.
+struct Glyph {
+    width: f64,
+    groups: Vec<ContentGroup>,
+}
+
+/// The colors and the stroke of a document at a frame.
+struct TextPaint {
+    fill: Color,
+    stroke: Option<(Color, f64)>,
+    stroke_over_fill: bool,
+}
+
+impl TextPaint {
+    /// Fills with `fill` and strokes with `stroke`, the stroke over the fill
+    /// if the document says so.
+    fn paint<C, F, S>(&self, canvas: &C, fill: F, stroke: S)
+    where
+        C: CanvasContext,
+        F: Fn(),
+        S: Fn(),
+    {
+        let stroke = || {
+            if let Some((color, width)) = self.stroke {
+                canvas.set_stroke_color(color);
+                canvas.set_line_width(width);
+                stroke();
+            }
+        };
+        let fill = || {
+            canvas.set_fill_color(self.fill);
+            fill();
+        };
+        if self.stroke_over_fill {
+            fill();
+            stroke();
+        } else {
+            stroke();
+            fill();
+        }
+    }
+}
+
+/// A layer of text, drawn with the glyphs embedded in the composition or
+/// with fonts.
+pub struct TextLayer {
+    documents: Option<TextKeyframeAnimation>,
+    color: Option<ColorKeyframeAnimation>,
+    stroke_color: Option<ColorKeyframeAnimation>,
+    stroke_width: Option<DoubleKeyframeAnimation>,
+    /// The extra space between characters added to the one of the
+    /// document, in thousandths of an em.
+    tracking: Option<DoubleKeyframeAnimation>,
+    /// The fonts of the documents, by name.
+    fonts: HashMap<String, Font>,
+    /// The glyphs of the characters of the documents.
+    glyphs: HashMap<u64, Glyph>,
+    /// Whether the text is overridden by a value callback.
+    has_text_callback: bool,
+}
+
+impl TextLayer {
+    pub fn new(layer: &Layer, composition: &LottieComposition, enable_merge_paths: bool) -> Self {
+        let mut fonts = HashMap::new();
+        let mut glyphs = HashMap::new();
+        let documents = layer
+            .text
+            .iter()
+            .flat_map(|text| text.keyframes())
+            .flat_map(|keyframe| keyframe.start_value.iter().chain(keyframe.end_value.iter()));
+        for document in documents {
+            let font: &Font = match composition.get_fonts().get(&document.font_name) {
+                Some(font) => font,
+                None => continue,
+            };
+            fonts.insert(document.font_name.clone(), font.clone());
+            for character in document.text.chars() {
+                let hash =
+                    FontCharacter::hash_for(&character.to_string(), &font.family, &font.style);
+                if let Some(character) = composition.get_characters().get(&hash) {
+                    glyphs.entry(hash).or_insert_with(|| Glyph {
+                        width: character.width,
+                        groups: character
+                            .shapes
+                            .iter()
+                            .map(|group| ContentGroup::from_group(group, enable_merge_paths))
+                            .collect(),
+                    });
+                }
+            }
+        }
+
+        let properties = layer.text_properties.as_ref();
+        Self {
+            documents: layer.text.as_ref().map(|text| text.create_animation()),
+            color: properties
+                .and_then(|properties| properties.color.as_ref())
+                .map(|color| color.create_animation()),
+            stroke_color: properties
+                .and_then(|properties| properties.stroke.as_ref())
+                .map(|color| color.create_animation()),
+            stroke_width: properties
+                .and_then(|properties| properties.stroke_width.as_ref())
+                .map(|width| width.create_animation()),
+            tracking: properties
+                .and_then(|properties| properties.tracking.as_ref())
+                .map(|tracking| tracking.create_animation()),
+            fonts,
+            glyphs,
+            has_text_callback: false,
+        }
+    }
+
+    /// Overrides the colors, the stroke width, the tracking or the text
+    /// with the callback of `property`.
+    ///
+    /// The text callback is given the text of the document and returns the
+    /// text drawn instead, with fonts.
+    pub fn apply_value_callback(&mut self, property: &LottieProperty) {
+        match property {
+            LottieProperty::Color(callback) => {
+                set_optional_value_callback(&mut self.color, callback)
+            }
+            LottieProperty::StrokeColor(callback) => {
+                set_optional_value_callback(&mut self.stroke_color, callback)
+            }
+            LottieProperty::StrokeWidth(callback) => {
+                set_optional_value_callback(&mut self.stroke_width, callback)
+            }
+            LottieProperty::TextTracking(callback) => {
+                set_optional_value_callback(&mut self.tracking, callback)
+            }
+            LottieProperty::Text(callback) => {
+                let documents = match &mut self.documents {
+                    Some(documents) => documents,
+                    None => return,
+                };
+                let callback = callback.clone();
+                documents.set_value_callback(Some(LottieValueCallback::from_fn(
+                    move |frame_info: &LottieFrameInfo<DocumentData>| {
+                        let mut document = frame_info.value.clone()?;
+                        let text_info = frame_info.map(|document| document.text.clone());
+                        document.text = callback.get_value(&text_info)?;
+                        Some(document)
+                    },
+                )));
+                self.has_text_callback = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the document shown at `frame`.
+    pub fn get_document(&self, frame: f64) -> Option<DocumentData> {
+        self.documents.as_ref()?.value_at(frame)
+    }
+
+    /// Returns `true` if the layer is drawn with the glyphs of the
+    /// composition, rather than with fonts.
+    ///
+    /// Glyphs are left out when the text is replaced, the new text may use
+    /// other characters.
+    pub fn uses_glyphs(&self, delegates: &LottieDelegates) -> bool {
+        delegates.text.is_none() && !self.has_text_callback && !self.glyphs.is_empty()
+    }
+
+    /// Draws the text of the document at `frame`, each line below the one
+    /// before it and justified around the origin of the layer.
+    pub fn draw<C: CanvasContext>(
+        &self,
+        canvas: &C,
+        delegates: &LottieDelegates,
+        parent_alpha: f64,
+        frame: f64,
+    ) {
+        let document = match self.get_document(frame) {
+            Some(document) => document,
+            None => return,
+        };
+        let font = match self.fonts.get(&document.font_name) {
+            Some(font) => font,
+            None => return,
+        };
+
+        let value = |animation: &Option<DoubleKeyframeAnimation>| {
+            animation
+                .as_ref()
+                .and_then(|animation| animation.value_at(frame))
+        };
+        let color = |animation: &Option<ColorKeyframeAnimation>| {
+            animation
+                .as_ref()
+                .and_then(|animation| animation.value_at(frame))
+        };
+        let stroke_width = value(&self.stroke_width).unwrap_or(document.stroke_width);
+        let paint = TextPaint {
+            fill: MiscUtils::with_alpha(color(&self.color).unwrap_or(document.color), parent_alpha),
+            stroke: Some(stroke_width)
+                .filter(|&width| width > 0.0)
+                .map(|width| {
+                    let stroke = color(&self.stroke_color).unwrap_or(document.stroke_color);
+                    (MiscUtils::with_alpha(stroke, parent_alpha), width)
+                }),
+            stroke_over_fill: document.stroke_over_fill,
+        };
+        let tracking = (document.tracking as f64 + value(&self.tracking).unwrap_or(0.0)) / 1000.0
+            * document.size;
+
+        canvas.save();
+        canvas.translate(0.0, -document.baseline_shift);
+        if self.uses_glyphs(delegates) {
+            let scale = document.size / 100.0;
+            self.draw_lines(&document, &document.text, tracking, |character, x, y| {
+                let hash =
+                    FontCharacter::hash_for(&character.to_string(), &font.family, &font.style);
+                let glyph = match self.glyphs.get(&hash) {
+                    Some(glyph) => glyph,
+                    None => return 0.0,
+                };
+                if let Some(y) = y {
+                    let mut path = Path::new();
+                    for group in glyph.groups.iter() {
+                        path.extend(group.get_path(frame).0);
+                    }
+                    path.transform(&Transform::translation(x, y).pre_scale(scale, scale));
+                    paint.paint(
+                        canvas,
+                        || {
+                            MiscUtils::trace_path(canvas, &path);
+                            canvas.fill();
+                        },
+                        || {
+                            MiscUtils::trace_path(canvas, &path);
+                            canvas.stroke();
+                        },
+                    );
+                }
+                glyph.width * scale
+            });
+        } else {
+            let style = LottieFontStyle::new(&font.family, &font.style);
+            let family = match &delegates.fonts {
+                Some(fonts) => fonts.get_family(&style),
+                None => font.family.clone(),
+            };
+            let (text_style, weight) = Self::parse_style(&font.style);
+            canvas.set_font(&family, text_style, weight, document.size);
+
+            let text = delegates.get_text(&document.text);
+            self.draw_lines(&document, &text, tracking, |character, x, y| {
+                let text = character.to_string();
+                if let Some(y) = y.filter(|_| !character.is_whitespace()) {
+                    paint.paint(
+                        canvas,
+                        || canvas.fill_text(&text, x, y),
+                        || canvas.stroke_text(&text, x, y),
+                    );
+                }
+                delegates
+                    .fonts
+                    .as_ref()
+                    .and_then(|fonts| fonts.get_advance(&style, character, document.size))
+                    .unwrap_or_else(|| canvas.measure_text(&text).width)
+            });
+        }
+        canvas.restore();
+    }
+
+    /// Lays out the lines of `text`, `draw` draws a character at a position
+    /// and returns its advance width.
+    ///
+    /// Each line is measured first: `draw` is given no vertical position
+    /// then, and only returns the advance.
+    fn draw_lines<F>(&self, document: &DocumentData, text: &str, tracking: f64, mut draw: F)
+    where
+        F: FnMut(char, f64, Option<f64>) -> f64,
+    {
+        let line_height = if document.line_height > 0.0 {
+            document.line_height
+        } else {
+            document.size * 1.2
+        };
+        let lines = text
+            .split("\r\n")
+            .flat_map(|line| line.split(&['\r', '\n', '\u{3}'][..]));
+        for (index, line) in lines.enumerate() {
+            let advances: Vec<f64> = line
+                .chars()
+                .map(|character| draw(character, 0.0, None) + tracking)
+                .collect();
+            // the tracking after the last character is not part of the line
+            let width =
+                advances.iter().sum::<f64>() - if advances.is_empty() { 0.0 } else { tracking };
+            let mut x = match document.justification {
+                Justification::LeftAlign => 0.0,
+                Justification::RightAlign => -width,
+                Justification::Center => -width / 2.0,
+            };
+            let y = index as f64 * line_height;
+            for (character, advance) in line.chars().zip(advances) {
+                draw(character, x, Some(y));
+                x += advance;
+            }
+        }
+    }
+
+    /// Returns the style and the weight of a font style of the composition,
+    /// such as `"Bold Italic"`.
+    fn parse_style(style: &str) -> (TextStyle, TextWeight) {
+        let style = style.to_lowercase();
+        let text_style = if style.contains("italic") {
+            TextStyle::Italic
+        } else if style.contains("oblique") {
+            TextStyle::Oblique
+        } else {
+            TextStyle::Normal
+        };
+        let weight = if ["bold", "black", "heavy"]
+            .iter()
+            .any(|weight| style.contains(weight))
+        {
+            TextWeight::Bold
+        } else {
+            TextWeight::Normal
+        };
+        (text_style, weight)
+    }
+}
@@ -1,36 +1,35 @@
 use crate::runtime::lottie::LottieComposition;
 
+/// A named frame, or segment of frames, of a composition.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Marker {
-    composition: LottieComposition,
-    name: String,
-    start_frame: f64,
-    duration_frames: f64,
+    pub name: String,
+    pub start_frame: f64,
+    pub duration_frames: f64,
 }
 
 impl Marker {
-    pub fn new(
-        composition: LottieComposition,
-        name: String,
-        start_frame: f64,
-        duration_frames: f64,
-    ) -> Self {
+    pub fn new(name: String, start_frame: f64, duration_frames: f64) -> Self {
         Self {
-            composition,
             name,
             start_frame,
             duration_frames,
         }
     }
 
-    fn matches_name(&self, name: String) -> bool {
+    /// Returns `true` if the marker has `name`, ignoring case.
+    pub fn matches_name(&self, name: &str) -> bool {
         self.name.to_lowercase() == name.to_lowercase()
     }
 
-    fn get_start(&self) -> f64 {
-        unimplemented!()
+    /// Returns the progress of the start of the marker in `composition`.
+    pub fn get_start(&self, composition: &LottieComposition) -> f64 {
+        (self.start_frame - composition.get_start_frame()) / composition.get_duration_frames()
     }
 
-    fn get_end(&self) -> f64 {
-        unimplemented!()
+    /// Returns the progress of the end of the marker in `composition`.
+    pub fn get_end(&self, composition: &LottieComposition) -> f64 {
+        (self.start_frame + self.duration_frames - composition.get_start_frame())
+            / composition.get_duration_frames()
     }
 }
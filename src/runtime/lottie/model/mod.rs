@@ -1,6 +1,6 @@
-pub(crate) mod animatable;
-pub(crate) mod content;
-pub(crate) mod layer;
+pub mod animatable;
+pub mod content;
+pub mod layer;
 
 mod cubic_curve_data;
 pub use cubic_curve_data::*;
@@ -8,6 +8,9 @@ pub use cubic_curve_data::*;
 mod document_data;
 pub use document_data::*;
 
+mod dot_lottie_manifest;
+pub use dot_lottie_manifest::*;
+
 mod font_character;
 pub use font_character::*;
 
@@ -1,2 +1,8 @@
+/// Enables or disables some features of a `Lottie` widget.
 #[derive(Default, Clone)]
-pub struct LottieOptions;
+pub struct LottieOptions {
+    /// Enables merge paths, they are disabled by default.
+    ///
+    /// See [`LottieDrawable::set_enable_merge_paths`](super::LottieDrawable::set_enable_merge_paths).
+    pub enable_merge_paths: bool,
+}
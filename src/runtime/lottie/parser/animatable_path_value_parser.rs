@@ -1 +1,45 @@
+use super::{AnimatableValueParser, JsonUtils, KeyframesParser, PathKeyframeParser, PathParser};
+use crate::{
+    lottie::{
+        model::animatable::{
+            AnimatablePathValue, AnimatablePosition, AnimatableSplitDimensionPathValue,
+        },
+        value::Keyframe,
+        LottieComposition,
+    },
+    Point,
+};
+use serde_json::Value;
+
+/// Parses positions, which may move along spatial curves.
 pub struct AnimatablePathValueParser;
+
+impl AnimatablePathValueParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> AnimatablePathValue {
+        let value = json.get("k").unwrap_or(&Value::Null);
+        let keyframes = if KeyframesParser::is_animated(value) {
+            PathKeyframeParser::parse(json, composition)
+        } else {
+            let point = PathParser::parse(value).unwrap_or_else(|| Point::new(0.0, 0.0));
+            vec![Keyframe::new_static(point)]
+        };
+        AnimatablePathValue::new(keyframes)
+            .with_expression(KeyframesParser::parse_expression(json, composition))
+    }
+
+    /// Parses a position which may have separate X and Y keyframes, when
+    /// `s` is set.
+    pub fn parse_split_path(json: &Value, composition: &LottieComposition) -> AnimatablePosition {
+        if JsonUtils::get_bool(json, "s") {
+            let axis = |key| {
+                let value = json.get(key).unwrap_or(&Value::Null);
+                AnimatableValueParser::parse_float(value, composition)
+            };
+            return AnimatablePosition::Split(AnimatableSplitDimensionPathValue::new(
+                axis("x"),
+                axis("y"),
+            ));
+        }
+        AnimatablePosition::Path(Self::parse(json, composition))
+    }
+}
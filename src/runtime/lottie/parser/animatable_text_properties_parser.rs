@@ -1 +1,30 @@
+use super::AnimatableValueParser;
+use crate::lottie::{model::animatable::AnimatableTextProperties, LottieComposition};
+use serde_json::Value;
+
+/// Parses the properties of the first text animator of a text layer.
 pub struct AnimatableTextPropertiesParser;
+
+impl AnimatableTextPropertiesParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> AnimatableTextProperties {
+        let properties = match json.get("a") {
+            Some(properties) => properties,
+            None => return AnimatableTextProperties::default(),
+        };
+
+        AnimatableTextProperties {
+            color: properties
+                .get("fc")
+                .map(|value| AnimatableValueParser::parse_color(value, composition)),
+            stroke: properties
+                .get("sc")
+                .map(|value| AnimatableValueParser::parse_color(value, composition)),
+            stroke_width: properties
+                .get("sw")
+                .map(|value| AnimatableValueParser::parse_float(value, composition)),
+            tracking: properties
+                .get("t")
+                .map(|value| AnimatableValueParser::parse_float(value, composition)),
+        }
+    }
+}
@@ -1 +1,64 @@
+use super::{AnimatablePathValueParser, AnimatableValueParser};
+use crate::lottie::{model::animatable::AnimatableTransform, LottieComposition};
+use serde_json::Value;
+
+/// Parses the transform `ks` of layers, the `tr` items of shape groups and
+/// the transform of repeaters.
 pub struct AnimatableTransformParser;
+
+impl AnimatableTransformParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> AnimatableTransform {
+        let mut transform = AnimatableTransform::default();
+        let object = match json.as_object() {
+            Some(object) => object,
+            None => return transform,
+        };
+
+        for (key, value) in object {
+            match key.as_str() {
+                "a" => {
+                    transform.anchor_point =
+                        Some(AnimatablePathValueParser::parse(value, composition))
+                }
+                "p" => {
+                    transform.position = Some(AnimatablePathValueParser::parse_split_path(
+                        value,
+                        composition,
+                    ))
+                }
+                "s" => {
+                    transform.scale = Some(AnimatableValueParser::parse_scale(value, composition))
+                }
+                "r" | "rz" => {
+                    transform.rotation =
+                        Some(AnimatableValueParser::parse_float(value, composition))
+                }
+                "o" => {
+                    transform.opacity =
+                        Some(AnimatableValueParser::parse_integer(value, composition))
+                }
+                "so" => {
+                    transform.start_opacity =
+                        Some(AnimatableValueParser::parse_float(value, composition))
+                }
+                "eo" => {
+                    transform.end_opacity =
+                        Some(AnimatableValueParser::parse_float(value, composition))
+                }
+                "sk" => {
+                    transform.skew = Some(AnimatableValueParser::parse_float(value, composition))
+                }
+                "sa" => {
+                    transform.skew_angle =
+                        Some(AnimatableValueParser::parse_float(value, composition))
+                }
+                "rx" | "ry" | "or" => {
+                    composition.add_warning("Lottie doesn't support 3D rotations.")
+                }
+                _ => {}
+            }
+        }
+
+        transform
+    }
+}
@@ -1 +1,122 @@
+use super::{
+    ColorParser, DocumentDataParser, FloatParser, GradientColorParser, IntegerParser, JsonUtils,
+    KeyframesParser, OffsetParser, ScaleXYParser, ShapeDataParser,
+};
+use crate::lottie::{
+    model::animatable::{
+        AnimatableColorValue, AnimatableDoubleValue, AnimatableGradientColorValue,
+        AnimatableIntegerValue, AnimatablePointValue, AnimatableScaleValue, AnimatableShapeValue,
+        AnimatableTextFrame,
+    },
+    LottieComposition,
+};
+use serde_json::Value;
+
+/// Parses the animatable properties of the composition model, from the
+/// objects holding their keyframes under `k`.
 pub struct AnimatableValueParser;
+
+impl AnimatableValueParser {
+    pub fn parse_float(json: &Value, composition: &LottieComposition) -> AnimatableDoubleValue {
+        AnimatableDoubleValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            FloatParser::parse,
+            false,
+            false,
+        ))
+        .with_expression(KeyframesParser::parse_expression(json, composition))
+    }
+
+    pub fn parse_integer(json: &Value, composition: &LottieComposition) -> AnimatableIntegerValue {
+        AnimatableIntegerValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            IntegerParser::parse,
+            false,
+            false,
+        ))
+        .with_expression(KeyframesParser::parse_expression(json, composition))
+    }
+
+    pub fn parse_point(json: &Value, composition: &LottieComposition) -> AnimatablePointValue {
+        AnimatablePointValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            OffsetParser::parse,
+            true,
+            false,
+        ))
+        .with_expression(KeyframesParser::parse_expression(json, composition))
+    }
+
+    pub fn parse_scale(json: &Value, composition: &LottieComposition) -> AnimatableScaleValue {
+        AnimatableScaleValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            ScaleXYParser::parse,
+            true,
+            false,
+        ))
+        // scales are factors, expressions use percents
+        .with_expression(
+            KeyframesParser::parse_expression(json, composition)
+                .map(|expression| expression.with_unit(100.0)),
+        )
+    }
+
+    pub fn parse_shape_data(json: &Value, composition: &LottieComposition) -> AnimatableShapeValue {
+        KeyframesParser::skip_expression(json, composition);
+        AnimatableShapeValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            ShapeDataParser::parse,
+            false,
+            false,
+        ))
+    }
+
+    pub fn parse_document_data(
+        json: &Value,
+        composition: &LottieComposition,
+    ) -> AnimatableTextFrame {
+        KeyframesParser::skip_expression(json, composition);
+        AnimatableTextFrame::new(KeyframesParser::parse(
+            json,
+            composition,
+            DocumentDataParser::parse,
+            false,
+            false,
+        ))
+    }
+
+    pub fn parse_color(json: &Value, composition: &LottieComposition) -> AnimatableColorValue {
+        AnimatableColorValue::new(KeyframesParser::parse(
+            json,
+            composition,
+            ColorParser::parse,
+            false,
+            false,
+        ))
+        .with_expression(KeyframesParser::parse_expression(json, composition))
+    }
+
+    /// Parses gradient stops, `json` holds the number of color stops `p`
+    /// and the keyframes under `k`.
+    pub fn parse_gradient_color(
+        json: &Value,
+        composition: &LottieComposition,
+    ) -> AnimatableGradientColorValue {
+        let points = JsonUtils::get_i64(json, "p").unwrap_or(0).max(0) as usize;
+        let parser = GradientColorParser::new(points);
+        let stops = json.get("k").unwrap_or(&Value::Null);
+        KeyframesParser::skip_expression(stops, composition);
+        AnimatableGradientColorValue::new(KeyframesParser::parse(
+            stops,
+            composition,
+            |value: &Value| parser.parse(value),
+            false,
+            false,
+        ))
+    }
+}
@@ -1 +1,23 @@
+use super::{AnimatablePathValueParser, AnimatableValueParser, JsonUtils};
+use crate::lottie::{model::content::CircleShape, LottieComposition};
+use serde_json::Value;
+
 pub struct CircleShapeParser;
+
+impl CircleShapeParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> CircleShape {
+        CircleShape {
+            name: JsonUtils::get_string(json, "nm"),
+            position: AnimatablePathValueParser::parse_split_path(
+                json.get("p").unwrap_or(&Value::Null),
+                composition,
+            ),
+            size: AnimatableValueParser::parse_point(
+                json.get("s").unwrap_or(&Value::Null),
+                composition,
+            ),
+            is_reversed: JsonUtils::get_i64(json, "d") == Some(3),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
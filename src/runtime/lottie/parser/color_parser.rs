@@ -1 +1,56 @@
+use super::JsonUtils;
+use crate::RgbaColor;
+use serde_json::Value;
 
+/// Parses `[r, g, b, a]` colors, from `0..=1` channels or, for old
+/// compositions, `0..=255` channels.
+pub struct ColorParser;
+
+impl ColorParser {
+    pub fn parse(json: &Value) -> Option<RgbaColor> {
+        let channels: Vec<f64> = json
+            .as_array()?
+            .iter()
+            .filter_map(JsonUtils::value_from_object)
+            .collect();
+        if channels.len() < 3 {
+            return None;
+        }
+        let alpha = channels.get(3).copied().unwrap_or(1.0);
+        Some(Self::from_channels(
+            channels[0],
+            channels[1],
+            channels[2],
+            alpha,
+        ))
+    }
+
+    /// Reads a `#rrggbb` color, as the color of solid layers.
+    pub fn parse_hex(text: &str) -> Option<RgbaColor> {
+        let hex = text.trim_start_matches('#');
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |at: usize| u8::from_str_radix(hex.get(at..at + 2)?, 16).ok();
+        Some(RgbaColor {
+            red: channel(0)?,
+            green: channel(2)?,
+            blue: channel(4)?,
+            alpha: 255,
+        })
+    }
+
+    pub(crate) fn from_channels(red: f64, green: f64, blue: f64, alpha: f64) -> RgbaColor {
+        let unit = red <= 1.0 && green <= 1.0 && blue <= 1.0;
+        let channel = |value: f64, unit: bool| {
+            let scale = if unit { 255.0 } else { 1.0 };
+            (value * scale).round().clamp(0.0, 255.0) as u8
+        };
+        RgbaColor {
+            red: channel(red, unit),
+            green: channel(green, unit),
+            blue: channel(blue, unit),
+            alpha: channel(alpha, alpha <= 1.0),
+        }
+    }
+}
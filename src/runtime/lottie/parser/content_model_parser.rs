@@ -1 +1,48 @@
+use super::{
+    AnimatableTransformParser, CircleShapeParser, GradientFillParser, GradientStrokeParser,
+    JsonUtils, MergePathsParser, PolystarShapeParser, RectangleShapeParser, RepeaterParser,
+    ShapeFillParser, ShapeGroupParser, ShapePathParser, ShapeStrokeParser, ShapeTrimPathParser,
+};
+use crate::lottie::{model::content::ContentModel, LottieComposition};
+use serde_json::Value;
+
+/// Parses an item of a shape layer or a shape group, from its type `ty`.
 pub struct ContentModelParser;
+
+impl ContentModelParser {
+    /// Returns `None` for unknown or unsupported items, after adding a
+    /// warning to the composition.
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Option<ContentModel> {
+        let kind = JsonUtils::get_string(json, "ty").unwrap_or_default();
+        let model = match kind.as_str() {
+            "gr" => ContentModel::Group(ShapeGroupParser::parse(json, composition)),
+            "st" => ContentModel::Stroke(ShapeStrokeParser::parse(json, composition)),
+            "gs" => ContentModel::GradientStroke(GradientStrokeParser::parse(json, composition)),
+            "fl" => ContentModel::Fill(ShapeFillParser::parse(json, composition)),
+            "gf" => ContentModel::GradientFill(GradientFillParser::parse(json, composition)),
+            "tr" => ContentModel::Transform(AnimatableTransformParser::parse(json, composition)),
+            "sh" => ContentModel::Path(ShapePathParser::parse(json, composition)),
+            "el" => ContentModel::Ellipse(CircleShapeParser::parse(json, composition)),
+            "rc" => ContentModel::Rectangle(RectangleShapeParser::parse(json, composition)),
+            "tm" => ContentModel::TrimPath(ShapeTrimPathParser::parse(json, composition)?),
+            "sr" => ContentModel::Polystar(PolystarShapeParser::parse(json, composition)?),
+            "mm" => {
+                composition.add_warning(
+                    "Animation contains merge paths, they are drawn only when enabled with \
+                     `LottieDrawable::set_enable_merge_paths`.",
+                );
+                ContentModel::MergePaths(MergePathsParser::parse(json, composition))
+            }
+            "rp" => ContentModel::Repeater(RepeaterParser::parse(json, composition)),
+            "rd" => {
+                composition.add_warning("Lottie doesn't support rounded corners.");
+                return None;
+            }
+            _ => {
+                composition.add_warning(&format!("Unknown shape type {}", kind));
+                return None;
+            }
+        };
+        Some(model)
+    }
+}
@@ -1 +1,43 @@
+use super::{ColorParser, JsonUtils};
+use crate::{
+    lottie::model::{DocumentData, Justification},
+    RgbaColor,
+};
+use serde_json::Value;
 
+pub struct DocumentDataParser;
+
+impl DocumentDataParser {
+    pub fn parse(json: &Value) -> Option<DocumentData> {
+        if !json.is_object() {
+            return None;
+        }
+
+        let justification = match JsonUtils::get_i64(json, "j") {
+            Some(1) => Justification::RightAlign,
+            Some(2) => Justification::Center,
+            _ => Justification::LeftAlign,
+        };
+        let color = |key| json.get(key).and_then(ColorParser::parse);
+        let black = RgbaColor {
+            red: 0,
+            green: 0,
+            blue: 0,
+            alpha: 255,
+        };
+
+        Some(DocumentData::new(
+            JsonUtils::get_string(json, "t").unwrap_or_default(),
+            JsonUtils::get_string(json, "f").unwrap_or_default(),
+            JsonUtils::get_f64(json, "s").unwrap_or(0.0),
+            justification,
+            JsonUtils::get_f64(json, "tr").unwrap_or(0.0).round() as i32,
+            JsonUtils::get_f64(json, "lh").unwrap_or(0.0),
+            JsonUtils::get_f64(json, "ls").unwrap_or(0.0),
+            color("fc").unwrap_or(black),
+            color("sc").unwrap_or(black),
+            JsonUtils::get_f64(json, "sw").unwrap_or(0.0),
+            JsonUtils::get_bool(json, "of"),
+        ))
+    }
+}
@@ -0,0 +1,58 @@
+use super::JsonUtils;
+use crate::lottie::{
+    model::{DotLottieAnimation, DotLottieManifest},
+    LottieError,
+};
+use serde_json::Value;
+
+/// Parses the `manifest.json` of a dotLottie file.
+pub struct DotLottieManifestParser;
+
+impl DotLottieManifestParser {
+    /// Fails when the manifest has no animations, or an animation has no id.
+    pub fn parse(json: &Value) -> Result<DotLottieManifest, LottieError> {
+        let animations = JsonUtils::get_array(json, "animations")
+            .iter()
+            .map(Self::parse_animation)
+            .collect::<Result<Vec<_>, _>>()?;
+        if animations.is_empty() {
+            return Err(LottieError::InvalidDotLottie(
+                "the manifest has no animations".into(),
+            ));
+        }
+
+        Ok(DotLottieManifest {
+            version: Self::get_text(json, "version"),
+            generator: JsonUtils::get_string(json, "generator"),
+            author: JsonUtils::get_string(json, "author"),
+            active_animation_id: JsonUtils::get_string(json, "activeAnimationId"),
+            animations,
+        })
+    }
+
+    fn parse_animation(json: &Value) -> Result<DotLottieAnimation, LottieError> {
+        let id = JsonUtils::get_string(json, "id").ok_or_else(|| {
+            LottieError::InvalidDotLottie("an animation of the manifest has no id".into())
+        })?;
+        let direction = match JsonUtils::get_f64(json, "direction") {
+            Some(direction) if direction < 0.0 => -1,
+            _ => 1,
+        };
+        Ok(DotLottieAnimation {
+            id,
+            speed: JsonUtils::get_f64(json, "speed").unwrap_or(1.0),
+            looping: JsonUtils::get_bool(json, "loop"),
+            autoplay: JsonUtils::get_bool(json, "autoplay"),
+            direction,
+        })
+    }
+
+    /// Reads a string or a number, versions are written either way.
+    fn get_text(json: &Value, key: &str) -> Option<String> {
+        match json.get(key)? {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        }
+    }
+}
@@ -1 +1,10 @@
+use super::JsonUtils;
+use serde_json::Value;
 
+pub struct FloatParser;
+
+impl FloatParser {
+    pub fn parse(json: &Value) -> Option<f64> {
+        JsonUtils::value_from_object(json)
+    }
+}
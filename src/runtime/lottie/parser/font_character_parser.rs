@@ -1 +1,26 @@
-pub struct FontCharacterParser;
\ No newline at end of file
+use super::{JsonUtils, ShapeGroupParser};
+use crate::lottie::{model::FontCharacter, LottieComposition};
+use serde_json::Value;
+
+pub struct FontCharacterParser;
+
+impl FontCharacterParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> FontCharacter {
+        let string = |key| JsonUtils::get_string(json, key).unwrap_or_default();
+        let shapes = json
+            .get("data")
+            .map(|data| JsonUtils::get_array(data, "shapes"))
+            .unwrap_or(&[])
+            .iter()
+            .map(|shape| ShapeGroupParser::parse(shape, composition))
+            .collect();
+        FontCharacter::new(
+            shapes,
+            string("ch"),
+            JsonUtils::get_f64(json, "size").unwrap_or(0.0),
+            JsonUtils::get_f64(json, "w").unwrap_or(0.0),
+            string("style"),
+            string("fFamily"),
+        )
+    }
+}
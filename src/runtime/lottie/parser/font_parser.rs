@@ -1 +1,17 @@
-pub struct FontParser;
\ No newline at end of file
+use super::JsonUtils;
+use crate::lottie::model::Font;
+use serde_json::Value;
+
+pub struct FontParser;
+
+impl FontParser {
+    pub fn parse(json: &Value) -> Font {
+        let string = |key| JsonUtils::get_string(json, key).unwrap_or_default();
+        Font::new(
+            string("fFamily"),
+            string("fName"),
+            string("fStyle"),
+            JsonUtils::get_f64(json, "ascent").unwrap_or(0.0),
+        )
+    }
+}
@@ -1 +1,95 @@
-pub struct GradientColorParser;
+use super::{ColorParser, JsonUtils};
+use crate::{lottie::model::content::GradientColor, RgbaColor};
+use serde_json::Value;
+
+/// Parses gradient stops: `color_points` groups of `[position, r, g, b]`,
+/// then optional `[position, opacity]` pairs.
+///
+/// Opacity stops are merged with color stops: the gradient gets a stop at
+/// every position of both lists, colors and opacities are interpolated at
+/// the positions they lack, colors in linear light.
+pub struct GradientColorParser {
+    color_points: usize,
+}
+
+impl GradientColorParser {
+    pub fn new(color_points: usize) -> Self {
+        Self { color_points }
+    }
+
+    pub fn parse(&self, json: &Value) -> Option<GradientColor> {
+        let values: Vec<f64> = json
+            .as_array()?
+            .iter()
+            .filter_map(JsonUtils::value_from_object)
+            .collect();
+
+        // some exports lie about the number of color points
+        let color_points = if values.len() >= self.color_points * 4 {
+            self.color_points
+        } else {
+            values.len() / 4
+        };
+
+        let (positions, colors) = values[..color_points * 4]
+            .chunks(4)
+            .map(|stop| {
+                let color = ColorParser::from_channels(stop[1], stop[2], stop[3], 1.0);
+                (stop[0], color)
+            })
+            .unzip();
+        let color_stops = GradientColor::new(positions, colors);
+        let opacity_stops: Vec<(f64, f64)> = values[color_points * 4..]
+            .chunks(2)
+            .filter(|stop| stop.len() == 2)
+            .map(|stop| (stop[0], stop[1]))
+            .collect();
+
+        if opacity_stops.is_empty() {
+            return Some(color_stops);
+        }
+
+        let mut positions: Vec<f64> = color_stops
+            .positions
+            .iter()
+            .copied()
+            .chain(opacity_stops.iter().map(|stop| stop.0))
+            .collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        positions.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+
+        let colors: Vec<RgbaColor> = positions
+            .iter()
+            .map(|&position| {
+                let color = color_stops.color_at(position);
+                let alpha = opacity_at(&opacity_stops, position).clamp(0.0, 1.0);
+                RgbaColor::new(
+                    color.red,
+                    color.green,
+                    color.blue,
+                    (alpha * 255.0).round() as u8,
+                )
+            })
+            .collect();
+
+        Some(GradientColor::new(positions, colors))
+    }
+}
+
+/// Returns the opacity at `position`, interpolated between the stops around
+/// it, stops are sorted by position.
+fn opacity_at(stops: &[(f64, f64)], position: f64) -> f64 {
+    match stops.iter().position(|stop| stop.0 >= position) {
+        None => stops[stops.len() - 1].1,
+        Some(0) => stops[0].1,
+        Some(index) => {
+            let ((from, from_opacity), (to, to_opacity)) = (stops[index - 1], stops[index]);
+            let f = if to > from {
+                (position - from) / (to - from)
+            } else {
+                1.0
+            };
+            from_opacity + (to_opacity - from_opacity) * f
+        }
+    }
+}
@@ -1 +1,38 @@
+use super::{AnimatableValueParser, JsonUtils, ShapeFillParser};
+use crate::lottie::{
+    model::content::{GradientFill, GradientType},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct GradientFillParser;
+
+impl GradientFillParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> GradientFill {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        let optional = |key| {
+            json.get(key)
+                .map(|value| AnimatableValueParser::parse_float(value, composition))
+        };
+        GradientFill {
+            name: JsonUtils::get_string(json, "nm"),
+            gradient_type: Self::gradient_type(json),
+            fill_type: ShapeFillParser::fill_type(json),
+            gradient_color: AnimatableValueParser::parse_gradient_color(property("g"), composition),
+            opacity: AnimatableValueParser::parse_integer(property("o"), composition),
+            start_point: AnimatableValueParser::parse_point(property("s"), composition),
+            end_point: AnimatableValueParser::parse_point(property("e"), composition),
+            highlight_length: optional("h"),
+            highlight_angle: optional("a"),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+
+    /// Reads the gradient type `t`.
+    pub fn gradient_type(json: &Value) -> GradientType {
+        match JsonUtils::get_i64(json, "t") {
+            Some(2) => GradientType::Radial,
+            _ => GradientType::Linear,
+        }
+    }
+}
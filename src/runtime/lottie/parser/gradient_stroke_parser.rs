@@ -1 +1,36 @@
+use super::{AnimatableValueParser, GradientFillParser, JsonUtils, ShapeStrokeParser};
+use crate::lottie::{
+    model::content::{GradientStroke, LineCapType, LineJoinType},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct GradientStrokeParser;
+
+impl GradientStrokeParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> GradientStroke {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        let optional = |key| {
+            json.get(key)
+                .map(|value| AnimatableValueParser::parse_float(value, composition))
+        };
+        let (line_dash_pattern, dash_offset) = ShapeStrokeParser::parse_dashes(json, composition);
+        GradientStroke {
+            name: JsonUtils::get_string(json, "nm"),
+            gradient_type: GradientFillParser::gradient_type(json),
+            gradient_color: AnimatableValueParser::parse_gradient_color(property("g"), composition),
+            opacity: AnimatableValueParser::parse_integer(property("o"), composition),
+            start_point: AnimatableValueParser::parse_point(property("s"), composition),
+            end_point: AnimatableValueParser::parse_point(property("e"), composition),
+            width: AnimatableValueParser::parse_float(property("w"), composition),
+            cap_type: LineCapType::for_id(JsonUtils::get_i64(json, "lc").unwrap_or(1)),
+            join_type: LineJoinType::for_id(JsonUtils::get_i64(json, "lj").unwrap_or(1)),
+            miter_limit: JsonUtils::get_f64(json, "ml").unwrap_or(4.0),
+            line_dash_pattern,
+            dash_offset,
+            highlight_length: optional("h"),
+            highlight_angle: optional("a"),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
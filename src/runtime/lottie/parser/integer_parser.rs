@@ -1 +1,10 @@
+use super::JsonUtils;
+use serde_json::Value;
 
+pub struct IntegerParser;
+
+impl IntegerParser {
+    pub fn parse(json: &Value) -> Option<i32> {
+        JsonUtils::value_from_object(json).map(|value| value.round() as i32)
+    }
+}
@@ -1 +1,74 @@
+use crate::Point;
+use serde_json::Value;
+
+/// Helpers reading the loosely typed values of Bodymovin JSON.
 pub struct JsonUtils;
+
+impl JsonUtils {
+    /// Reads a number, the first element of an array or a boolean as `0.0`
+    /// or `1.0`.
+    pub fn value_from_object(json: &Value) -> Option<f64> {
+        match json {
+            Value::Number(number) => number.as_f64(),
+            Value::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+            Value::Array(array) => array.first().and_then(Self::value_from_object),
+            _ => None,
+        }
+    }
+
+    /// Reads a point from an array of numbers, or from an object with `x`
+    /// and `y` properties.
+    pub fn json_to_point(json: &Value) -> Option<Point<f64>> {
+        match json {
+            Value::Array(array) => {
+                let x = array.first().and_then(Self::value_from_object)?;
+                let y = array
+                    .get(1)
+                    .and_then(Self::value_from_object)
+                    .unwrap_or(0.0);
+                Some(Point::new(x, y))
+            }
+            Value::Object(object) => {
+                let x = object.get("x").and_then(Self::value_from_object)?;
+                let y = object.get("y").and_then(Self::value_from_object)?;
+                Some(Point::new(x, y))
+            }
+            Value::Number(_) => Some(Point::new(Self::value_from_object(json)?, 0.0)),
+            _ => None,
+        }
+    }
+
+    /// Reads an array of points.
+    pub fn json_to_points(json: &Value) -> Vec<Point<f64>> {
+        json.as_array()
+            .map(|array| array.iter().filter_map(Self::json_to_point).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get_f64(json: &Value, key: &str) -> Option<f64> {
+        json.get(key).and_then(Self::value_from_object)
+    }
+
+    pub fn get_i64(json: &Value, key: &str) -> Option<i64> {
+        Self::get_f64(json, key).map(|value| value as i64)
+    }
+
+    pub fn get_bool(json: &Value, key: &str) -> bool {
+        json.get(key)
+            .and_then(Self::value_from_object)
+            .map(|value| value != 0.0)
+            .unwrap_or(false)
+    }
+
+    pub fn get_string(json: &Value, key: &str) -> Option<String> {
+        json.get(key).and_then(Value::as_str).map(String::from)
+    }
+
+    /// Returns the array under `key`, an empty slice when it is missing.
+    pub fn get_array<'a>(json: &'a Value, key: &str) -> &'a [Value] {
+        json.get(key)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
@@ -1 +1,94 @@
+use super::{JsonUtils, ValueParser};
+use crate::{easing::EasingFunction, lottie::value::Keyframe, Point};
+use serde_json::Value;
+
+/// The largest control point value of an easing, some exports have
+/// extremely large values which break the easing curve.
+const MAX_CP_VALUE: f64 = 100.0;
+
+/// Parses one keyframe of an animated property.
+///
+/// Keyframes hold their start value at `t`, move to their end value `e`
+/// along the easing between the out tangent `o` and the in tangent `i`, or
+/// keep their start value until the next keyframe when `h` is set. End
+/// frames and values missing from newer exports are filled in by
+/// [`KeyframesParser`](super::KeyframesParser).
 pub struct KeyframeParser;
+
+impl KeyframeParser {
+    /// Parses a keyframe, `multi_dimensional` values may have an easing per
+    /// axis and `path` keyframes have spatial control points.
+    pub fn parse<T, P>(json: &Value, parser: &P, multi_dimensional: bool, path: bool) -> Keyframe<T>
+    where
+        T: Clone,
+        P: ValueParser<T>,
+    {
+        let start_value = json.get("s").and_then(|value| parser.parse_value(value));
+        let end_value = json.get("e").and_then(|value| parser.parse_value(value));
+
+        let hold = JsonUtils::get_bool(json, "h");
+        let (cp1, cp2) = (json.get("o"), json.get("i"));
+        let mut interpolator = None;
+        let mut x_interpolator = None;
+        let mut y_interpolator = None;
+        if !hold {
+            if let (Some(cp1), Some(cp2)) = (cp1, cp2) {
+                let split = |cp: &Value| match (cp.get("x"), cp.get("y")) {
+                    (Some(Value::Array(x)), Some(Value::Array(y))) => x.len() > 1 && y.len() > 1,
+                    _ => false,
+                };
+                if multi_dimensional && split(cp1) && split(cp2) {
+                    x_interpolator = Some(Self::interpolator(cp1, cp2, 0));
+                    y_interpolator = Some(Self::interpolator(cp1, cp2, 1));
+                } else {
+                    interpolator = Some(Self::interpolator(cp1, cp2, 0));
+                }
+            }
+        }
+
+        let (path_cp1, path_cp2) = if path {
+            (
+                json.get("to").and_then(JsonUtils::json_to_point),
+                json.get("ti").and_then(JsonUtils::json_to_point),
+            )
+        } else {
+            (None, None)
+        };
+
+        Keyframe {
+            end_value: if hold { start_value.clone() } else { end_value },
+            start_value,
+            interpolator,
+            x_interpolator,
+            y_interpolator,
+            path_cp1,
+            path_cp2,
+            hold,
+            start_frame: JsonUtils::get_f64(json, "t").unwrap_or(0.0),
+            end_frame: None,
+        }
+    }
+
+    /// Returns the easing between the tangents `cp1` and `cp2` on `axis`.
+    fn interpolator(cp1: &Value, cp2: &Value, axis: usize) -> EasingFunction {
+        let point = |cp: &Value| {
+            let coordinate = |key| {
+                cp.get(key)
+                    .and_then(|value| match value {
+                        Value::Array(array) => array
+                            .get(axis)
+                            .or_else(|| array.first())
+                            .and_then(JsonUtils::value_from_object),
+                        value => JsonUtils::value_from_object(value),
+                    })
+                    .unwrap_or(0.0)
+            };
+            Point::new(
+                coordinate("x").clamp(0.0, 1.0),
+                coordinate("y").clamp(-MAX_CP_VALUE, MAX_CP_VALUE),
+            )
+        };
+        let (cp1, cp2) = (point(cp1), point(cp2));
+        EasingFunction::cubic_bezier(cp1.x, cp1.y, cp2.x, cp2.y)
+    }
+}
@@ -1 +1,115 @@
+use super::{JsonUtils, KeyframeParser, ValueParser};
+use crate::lottie::{
+    animation::expression::{Expression, ExpressionType, PropertyExpression},
+    value::Keyframe,
+    LottieComposition,
+};
+use serde_json::Value;
+use std::rc::Rc;
+
+/// Parses the keyframes under `k` of a property, or its static value.
 pub struct KeyframesParser;
+
+impl KeyframesParser {
+    pub fn parse<T, P>(
+        json: &Value,
+        composition: &LottieComposition,
+        parser: P,
+        multi_dimensional: bool,
+        path: bool,
+    ) -> Vec<Keyframe<T>>
+    where
+        T: Clone,
+        P: ValueParser<T>,
+    {
+        let value = match json.get("k") {
+            Some(value) => value,
+            None => return Vec::new(),
+        };
+
+        if !Self::is_animated(value) {
+            return parser
+                .parse_value(value)
+                .map(|value| vec![Keyframe::new_static(value)])
+                .unwrap_or_default();
+        }
+
+        let mut keyframes: Vec<Keyframe<T>> = JsonUtils::get_array(json, "k")
+            .iter()
+            .map(|keyframe| KeyframeParser::parse(keyframe, &parser, multi_dimensional, path))
+            .collect();
+        Self::set_end_frames(&mut keyframes);
+        keyframes
+    }
+
+    /// Parses the expression `x` of a property.
+    ///
+    /// An expression which is not supported is left out with a warning, the
+    /// property keeps the values of its keyframes.
+    pub fn parse_expression<T: ExpressionType>(
+        json: &Value,
+        composition: &LottieComposition,
+    ) -> Option<PropertyExpression<T>> {
+        let source = json.get("x")?;
+        let expression = source
+            .as_str()
+            .ok_or_else(|| "not a string".to_string())
+            .and_then(|source| Expression::parse(source).map_err(|error| error.to_string()));
+        match expression {
+            Ok(expression) => Some(PropertyExpression::new(
+                Rc::new(expression),
+                composition.get_frame_rate(),
+            )),
+            Err(reason) => {
+                composition.add_warning(&format!(
+                    "Lottie doesn't support the expression {}, {}.",
+                    source, reason
+                ));
+                None
+            }
+        }
+    }
+
+    /// Adds a warning for the expression of a property which expressions
+    /// can't compute, such as a shape.
+    pub fn skip_expression(json: &Value, composition: &LottieComposition) {
+        if json.get("x").is_some() {
+            composition
+                .add_warning("Lottie doesn't support expressions of shapes, texts and gradients.");
+        }
+    }
+
+    /// Returns `true` if `value` is a list of keyframes rather than a static
+    /// value.
+    pub fn is_animated(value: &Value) -> bool {
+        value
+            .as_array()
+            .and_then(|array| array.first())
+            .map(|first| first.is_object() && first.get("t").is_some())
+            .unwrap_or(false)
+    }
+
+    /// Ends every keyframe at the start of the next one, newer exports only
+    /// give the start values.
+    ///
+    /// The last keyframe only marks the end of the one before it, it is
+    /// removed unless it is the only keyframe.
+    fn set_end_frames<T: Clone>(keyframes: &mut Vec<Keyframe<T>>) {
+        for i in 1..keyframes.len() {
+            let (start_frame, start_value) =
+                (keyframes[i].start_frame, keyframes[i].start_value.clone());
+            let keyframe = &mut keyframes[i - 1];
+            keyframe.end_frame = Some(start_frame);
+            if keyframe.end_value.is_none() {
+                keyframe.end_value = start_value;
+            }
+        }
+
+        if keyframes.len() > 1 {
+            let last = &keyframes[keyframes.len() - 1];
+            if last.start_value.is_none() || last.end_value.is_none() {
+                keyframes.pop();
+            }
+        }
+    }
+}
@@ -1 +1,115 @@
+use super::{
+    AnimatableTextPropertiesParser, AnimatableTransformParser, AnimatableValueParser, ColorParser,
+    ContentModelParser, JsonUtils, MaskParser,
+};
+use crate::lottie::{
+    model::layer::{Layer, LayerType, MatteType},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct LayerParser;
+
+impl LayerParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Layer {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+
+        let mut layer = Layer {
+            name: JsonUtils::get_string(json, "nm").unwrap_or_else(|| "UNSET".into()),
+            id: JsonUtils::get_i64(json, "ind").unwrap_or(-1),
+            layer_type: LayerType::for_id(JsonUtils::get_i64(json, "ty").unwrap_or(-1)),
+            parent_id: JsonUtils::get_i64(json, "parent"),
+            ref_id: JsonUtils::get_string(json, "refId"),
+            transform: AnimatableTransformParser::parse(property("ks"), composition),
+            solid_width: JsonUtils::get_f64(json, "sw").unwrap_or(0.0),
+            solid_height: JsonUtils::get_f64(json, "sh").unwrap_or(0.0),
+            time_stretch: JsonUtils::get_f64(json, "sr").unwrap_or(1.0),
+            start_frame: JsonUtils::get_f64(json, "st").unwrap_or(0.0),
+            in_frame: JsonUtils::get_f64(json, "ip").unwrap_or(0.0),
+            out_frame: JsonUtils::get_f64(json, "op").unwrap_or(0.0),
+            pre_comp_width: JsonUtils::get_f64(json, "w").unwrap_or(0.0),
+            pre_comp_height: JsonUtils::get_f64(json, "h").unwrap_or(0.0),
+            hidden: JsonUtils::get_bool(json, "hd"),
+            ..Layer::default()
+        };
+
+        if let Some(color) = json
+            .get("sc")
+            .and_then(Value::as_str)
+            .and_then(ColorParser::parse_hex)
+        {
+            layer.solid_color = color;
+        }
+
+        if let Some(matte) = JsonUtils::get_i64(json, "tt") {
+            layer.matte_type = MatteType::for_id(matte);
+            if layer.matte_type == MatteType::Unknown {
+                composition.add_warning(&format!("Unsupported matte type: {}", matte));
+            }
+            composition.increment_matte_or_mask_count(1);
+        }
+
+        layer.masks = JsonUtils::get_array(json, "masksProperties")
+            .iter()
+            .map(|mask| MaskParser::parse(mask, composition))
+            .collect();
+        composition.increment_matte_or_mask_count(layer.masks.len() as u32);
+
+        layer.shapes = JsonUtils::get_array(json, "shapes")
+            .iter()
+            .filter_map(|shape| ContentModelParser::parse(shape, composition))
+            .collect();
+
+        if let Some(text) = json.get("t") {
+            layer.text = text
+                .get("d")
+                .map(|document| AnimatableValueParser::parse_document_data(document, composition));
+            layer.text_properties = JsonUtils::get_array(text, "a")
+                .first()
+                .map(|animator| AnimatableTextPropertiesParser::parse(animator, composition));
+        }
+
+        if layer.time_stretch <= 0.0 {
+            composition
+                .add_warning("Layers with a negative or zero time stretch are not supported.");
+            layer.time_stretch = 1.0;
+        }
+
+        if layer.layer_type != LayerType::PreComp {
+            // only precompositions remap their time
+            layer.time_remapping = None;
+        } else if let Some(remapping) = json.get("tm") {
+            layer.time_remapping = Some(AnimatableValueParser::parse_float(remapping, composition));
+        }
+
+        if layer.layer_type == LayerType::Unknown {
+            composition.add_warning(&format!(
+                "Unknown layer type {}",
+                JsonUtils::get_i64(json, "ty").unwrap_or(-1)
+            ));
+        }
+
+        let effects: Vec<_> = JsonUtils::get_array(json, "ef")
+            .iter()
+            .filter_map(|effect| JsonUtils::get_string(effect, "nm"))
+            .collect();
+        if json.get("ef").is_some() {
+            composition.add_warning(&format!(
+                "Lottie doesn't support layer effects. If you are using them for fills, \
+                 strokes, trim paths etc. then try adding them directly as contents in your \
+                 shape. Found: {:?}",
+                effects
+            ));
+        }
+
+        if JsonUtils::get_i64(json, "bm").unwrap_or(0) != 0 {
+            composition.add_warning("Lottie doesn't support blend modes.");
+        }
+
+        if JsonUtils::get_bool(json, "ddd") {
+            composition.add_warning("Lottie doesn't support 3D layers.");
+        }
+
+        layer
+    }
+}
@@ -1 +1,142 @@
+use super::{FontCharacterParser, FontParser, JsonUtils, LayerParser};
+use crate::lottie::{
+    model::{layer::LayerType, Marker},
+    CompositionParameters, LottieComposition, LottieError, LottieImageAsset,
+};
+use crate::{Point, Rect, Size};
+use serde_json::Value;
+
+/// Parses a Bodymovin JSON document into a [`LottieComposition`].
 pub struct LottieCompositionParser;
+
+impl LottieCompositionParser {
+    /// Fails only when `json` is not a composition, unsupported features are
+    /// added to the warnings of the composition instead.
+    pub fn parse(json: &Value) -> Result<LottieComposition, LottieError> {
+        if !json.is_object() {
+            return Err(LottieError::InvalidComposition(
+                "the root of a composition must be an object".into(),
+            ));
+        }
+
+        let mut composition = LottieComposition::default();
+        let mut parameters = CompositionParameters::default();
+
+        let width = JsonUtils::get_i64(json, "w").unwrap_or(0) as i32;
+        let height = JsonUtils::get_i64(json, "h").unwrap_or(0) as i32;
+        parameters.bounds = Rect {
+            origin: Point::new(0, 0),
+            size: Size::new(width, height),
+        };
+        parameters.start_frame = JsonUtils::get_f64(json, "ip").unwrap_or(0.0);
+        parameters.end_frame = JsonUtils::get_f64(json, "op").unwrap_or(0.0);
+        parameters.frame_rate = JsonUtils::get_f64(json, "fr").unwrap_or(0.0);
+        if parameters.frame_rate <= 0.0 {
+            return Err(LottieError::InvalidComposition(
+                "the frame rate must be positive".into(),
+            ));
+        }
+        // expressions are parsed with the frame rate, to tell the time
+        composition.set_parameters(
+            None,
+            CompositionParameters {
+                frame_rate: parameters.frame_rate,
+                ..CompositionParameters::default()
+            },
+        );
+
+        if let Some(version) = JsonUtils::get_string(json, "v") {
+            if !Self::is_at_least_version(&version, 4, 4, 0) {
+                composition.add_warning("Lottie only supports bodymovin >= 4.4.0");
+            }
+        }
+
+        for asset in JsonUtils::get_array(json, "assets") {
+            let id = JsonUtils::get_string(asset, "id").unwrap_or_default();
+            if let Some(layers) = asset.get("layers").and_then(Value::as_array) {
+                let layers = layers
+                    .iter()
+                    .map(|layer| LayerParser::parse(layer, &composition))
+                    .collect();
+                parameters.precomps.insert(id, layers);
+            } else if asset.get("p").is_some() {
+                let image = LottieImageAsset::new(
+                    JsonUtils::get_i64(asset, "w").unwrap_or(0) as i32,
+                    JsonUtils::get_i64(asset, "h").unwrap_or(0) as i32,
+                    &id,
+                    &JsonUtils::get_string(asset, "p").unwrap_or_default(),
+                    &JsonUtils::get_string(asset, "u").unwrap_or_default(),
+                );
+                parameters.images.insert(id, image);
+            }
+        }
+
+        parameters.layers = JsonUtils::get_array(json, "layers")
+            .iter()
+            .map(|layer| LayerParser::parse(layer, &composition))
+            .collect();
+        parameters.layer_map = parameters
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| (layer.id, index))
+            .collect();
+
+        // image layers are as large as their image
+        let images = &parameters.images;
+        let image_layers = parameters
+            .layers
+            .iter_mut()
+            .chain(parameters.precomps.values_mut().flatten())
+            .filter(|layer| layer.layer_type == LayerType::Image);
+        for layer in image_layers {
+            if let Some(image) = layer.ref_id.as_ref().and_then(|id| images.get(id)) {
+                layer.pre_comp_width = image.get_width() as f64;
+                layer.pre_comp_height = image.get_height() as f64;
+            }
+        }
+
+        if let Some(fonts) = json.get("fonts") {
+            for font in JsonUtils::get_array(fonts, "list") {
+                let font = FontParser::parse(font);
+                parameters.fonts.insert(font.name.clone(), font);
+            }
+        }
+
+        for character in JsonUtils::get_array(json, "chars") {
+            let character = FontCharacterParser::parse(character, &composition);
+            parameters
+                .characters
+                .insert(character.get_hash_code(), character);
+        }
+
+        parameters.markers = JsonUtils::get_array(json, "markers")
+            .iter()
+            .map(|marker| {
+                let name = JsonUtils::get_string(marker, "cm").unwrap_or_default();
+                Marker::new(
+                    name.trim_end_matches(['\r', '\n']).into(),
+                    JsonUtils::get_f64(marker, "tm").unwrap_or(0.0),
+                    JsonUtils::get_f64(marker, "dr").unwrap_or(0.0),
+                )
+            })
+            .collect();
+
+        composition.set_parameters(JsonUtils::get_string(json, "nm"), parameters);
+        Ok(composition)
+    }
+
+    /// Returns `true` if the version `major.minor.patch` is at least the
+    /// given one.
+    fn is_at_least_version(version: &str, major: u32, minor: u32, patch: u32) -> bool {
+        let mut parts = version
+            .split('.')
+            .map(|part| part.trim().parse::<u32>().unwrap_or(0));
+        let version = (
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+        );
+        version >= (major, minor, patch)
+    }
+}
@@ -1 +1,40 @@
+use super::{AnimatableValueParser, JsonUtils};
+use crate::lottie::{
+    model::content::{Mask, MaskMode},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct MaskParser;
+
+impl MaskParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Mask {
+        let mode = match json.get("mode").and_then(Value::as_str) {
+            Some("a") => MaskMode::Add,
+            Some("s") => MaskMode::Subtract,
+            Some("i") => MaskMode::Intersect,
+            Some("l") => MaskMode::Lighten,
+            Some("d") => MaskMode::Darken,
+            Some("f") => MaskMode::Difference,
+            Some("n") => MaskMode::None,
+            mode => {
+                composition.add_warning(&format!(
+                    "Unknown mask mode {}. Defaulting to Add.",
+                    mode.unwrap_or_default()
+                ));
+                MaskMode::Add
+            }
+        };
+
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        Mask {
+            mode,
+            path: AnimatableValueParser::parse_shape_data(property("pt"), composition),
+            opacity: AnimatableValueParser::parse_integer(property("o"), composition),
+            expansion: json
+                .get("x")
+                .map(|value| AnimatableValueParser::parse_float(value, composition)),
+            inverted: JsonUtils::get_bool(json, "inv"),
+        }
+    }
+}
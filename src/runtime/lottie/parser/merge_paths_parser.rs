@@ -1 +1,18 @@
+use super::JsonUtils;
+use crate::lottie::{
+    model::content::{MergePaths, MergePathsMode},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct MergePathsParser;
+
+impl MergePathsParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> MergePaths {
+        MergePaths {
+            name: JsonUtils::get_string(json, "nm"),
+            mode: MergePathsMode::for_id(JsonUtils::get_i64(json, "mm").unwrap_or(1)),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
@@ -22,9 +22,18 @@ use content_model_parser::*;
 mod document_data_parser;
 use document_data_parser::*;
 
+mod dot_lottie_manifest_parser;
+pub(crate) use dot_lottie_manifest_parser::*;
+
 mod float_parser;
 use float_parser::*;
 
+mod font_character_parser;
+use font_character_parser::*;
+
+mod font_parser;
+use font_parser::*;
+
 mod gradient_color_parser;
 use gradient_color_parser::*;
 
@@ -50,7 +59,7 @@ mod layer_parser;
 use layer_parser::*;
 
 mod lottie_composition_parser;
-use lottie_composition_parser::*;
+pub(crate) use lottie_composition_parser::*;
 
 mod mask_parser;
 use mask_parser::*;
@@ -99,3 +108,6 @@ use shape_trim_path_parser::*;
 
 mod value_parser;
 use value_parser::*;
+
+#[cfg(test)]
+mod tests;
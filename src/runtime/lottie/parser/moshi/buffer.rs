@@ -1 +0,0 @@
-pub struct Buffer;
\ No newline at end of file
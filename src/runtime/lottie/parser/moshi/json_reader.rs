@@ -1 +0,0 @@
-pub struct JsonReader;
\ No newline at end of file
@@ -1 +0,0 @@
-pub struct JsonScope;
\ No newline at end of file
@@ -1 +0,0 @@
-pub struct JsonUtf8Reader;
\ No newline at end of file
@@ -1,11 +0,0 @@
-mod buffer;
-use buffer::*;
-
-mod json_reader;
-use json_reader::*;
-
-mod json_scope;
-use json_scope::*;
-
-mod json_utf8_reader;
-use json_utf8_reader::*;
\ No newline at end of file
@@ -1 +1,12 @@
+use super::JsonUtils;
+use crate::Point;
+use serde_json::Value;
 
+/// Parses points, like sizes and gradient ends.
+pub struct OffsetParser;
+
+impl OffsetParser {
+    pub fn parse(json: &Value) -> Option<Point<f64>> {
+        JsonUtils::json_to_point(json)
+    }
+}
@@ -1 +1,16 @@
+use super::{KeyframesParser, PathParser};
+use crate::{
+    lottie::{value::Keyframe, LottieComposition},
+    Point,
+};
+use serde_json::Value;
+
+/// Parses the keyframes of a position along with their spatial control
+/// points `to` and `ti`.
 pub struct PathKeyframeParser;
+
+impl PathKeyframeParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Vec<Keyframe<Point<f64>>> {
+        KeyframesParser::parse(json, composition, PathParser::parse, true, true)
+    }
+}
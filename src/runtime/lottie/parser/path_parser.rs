@@ -1 +1,12 @@
+use super::JsonUtils;
+use crate::Point;
+use serde_json::Value;
 
+/// Parses positions moving along spatial curves.
+pub struct PathParser;
+
+impl PathParser {
+    pub fn parse(json: &Value) -> Option<Point<f64>> {
+        JsonUtils::json_to_point(json)
+    }
+}
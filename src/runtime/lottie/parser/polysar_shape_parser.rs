@@ -1 +1,42 @@
+use super::{AnimatablePathValueParser, AnimatableValueParser, JsonUtils};
+use crate::lottie::{
+    model::content::{PolystarShape, PolystarShapeType},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct PolystarShapeParser;
+
+impl PolystarShapeParser {
+    /// Returns `None` when the type `sy` is neither a star nor a polygon.
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Option<PolystarShape> {
+        let kind = JsonUtils::get_i64(json, "sy").and_then(PolystarShapeType::for_value);
+        let kind = match kind {
+            Some(kind) => kind,
+            None => {
+                composition.add_warning("Unknown polystar type.");
+                return None;
+            }
+        };
+
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        let optional = |key| {
+            json.get(key)
+                .filter(|_| kind == PolystarShapeType::Star)
+                .map(|value| AnimatableValueParser::parse_float(value, composition))
+        };
+        Some(PolystarShape {
+            name: JsonUtils::get_string(json, "nm"),
+            kind,
+            points: AnimatableValueParser::parse_float(property("pt"), composition),
+            position: AnimatablePathValueParser::parse_split_path(property("p"), composition),
+            rotation: AnimatableValueParser::parse_float(property("r"), composition),
+            inner_radius: optional("ir"),
+            outer_radius: AnimatableValueParser::parse_float(property("or"), composition),
+            inner_roundness: optional("is"),
+            outer_roundness: AnimatableValueParser::parse_float(property("os"), composition),
+            is_reversed: JsonUtils::get_i64(json, "d") == Some(3),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        })
+    }
+}
@@ -1 +1,19 @@
+use super::{AnimatablePathValueParser, AnimatableValueParser, JsonUtils};
+use crate::lottie::{model::content::RectangleShape, LottieComposition};
+use serde_json::Value;
+
 pub struct RectangleShapeParser;
+
+impl RectangleShapeParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> RectangleShape {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        RectangleShape {
+            name: JsonUtils::get_string(json, "nm"),
+            position: AnimatablePathValueParser::parse_split_path(property("p"), composition),
+            size: AnimatableValueParser::parse_point(property("s"), composition),
+            corner_radius: AnimatableValueParser::parse_float(property("r"), composition),
+            is_reversed: JsonUtils::get_i64(json, "d") == Some(3),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
@@ -1 +1,18 @@
+use super::{AnimatableTransformParser, AnimatableValueParser, JsonUtils};
+use crate::lottie::{model::content::Repeater, LottieComposition};
+use serde_json::Value;
+
 pub struct RepeaterParser;
+
+impl RepeaterParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Repeater {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        Repeater {
+            name: JsonUtils::get_string(json, "nm"),
+            copies: AnimatableValueParser::parse_float(property("c"), composition),
+            offset: AnimatableValueParser::parse_float(property("o"), composition),
+            transform: AnimatableTransformParser::parse(property("tr"), composition),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
@@ -1 +1,26 @@
+use super::JsonUtils;
+use crate::Point;
+use serde_json::Value;
 
+/// Parses scales in percents into factors.
+pub struct ScaleXYParser;
+
+impl ScaleXYParser {
+    pub fn parse(json: &Value) -> Option<Point<f64>> {
+        let scale = match json {
+            Value::Array(array) => {
+                let x = array.first().and_then(JsonUtils::value_from_object)?;
+                let y = array
+                    .get(1)
+                    .and_then(JsonUtils::value_from_object)
+                    .unwrap_or(x);
+                Point::new(x, y)
+            }
+            _ => {
+                let value = JsonUtils::value_from_object(json)?;
+                Point::new(value, value)
+            }
+        };
+        Some(Point::new(scale.x / 100.0, scale.y / 100.0))
+    }
+}
@@ -1 +1,63 @@
+use super::JsonUtils;
+use crate::{
+    lottie::model::{content::ShapeData, CubicCurveData},
+    Point,
+};
+use serde_json::Value;
 
+/// Parses Bezier shapes: the vertices `v` with their in and out tangents
+/// `i` and `o`, relative to the vertices, closed when `c` is set.
+pub struct ShapeDataParser;
+
+impl ShapeDataParser {
+    pub fn parse(json: &Value) -> Option<ShapeData> {
+        // the shapes of keyframes are wrapped in an array
+        let json = match json {
+            Value::Array(array) => array.first()?,
+            json => json,
+        };
+
+        let closed = JsonUtils::get_bool(json, "c");
+        let vertices = JsonUtils::json_to_points(json.get("v")?);
+        let in_tangents = json
+            .get("i")
+            .map(JsonUtils::json_to_points)
+            .unwrap_or_default();
+        let out_tangents = json
+            .get("o")
+            .map(JsonUtils::json_to_points)
+            .unwrap_or_default();
+
+        let initial_point = match vertices.first() {
+            Some(point) => *point,
+            None => return Some(ShapeData::new(Point::new(0.0, 0.0), false, Vec::new())),
+        };
+
+        let tangent = |tangents: &[Point<f64>], index: usize| {
+            tangents
+                .get(index)
+                .copied()
+                .unwrap_or_else(|| Point::new(0.0, 0.0))
+        };
+        let curve = |from: usize, to: usize| {
+            let previous = vertices[from];
+            let vertex = vertices[to];
+            let out = tangent(&out_tangents, from);
+            let into = tangent(&in_tangents, to);
+            CubicCurveData::new(
+                Point::new(previous.x + out.x, previous.y + out.y),
+                Point::new(vertex.x + into.x, vertex.y + into.y),
+                vertex,
+            )
+        };
+
+        let mut curves: Vec<CubicCurveData> = (1..vertices.len())
+            .map(|index| curve(index - 1, index))
+            .collect();
+        if closed {
+            curves.push(curve(vertices.len() - 1, 0));
+        }
+
+        Some(ShapeData::new(initial_point, closed, curves))
+    }
+}
@@ -1 +1,36 @@
+use super::{AnimatableValueParser, JsonUtils};
+use crate::lottie::{
+    model::content::{FillType, ShapeFill},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct ShapeFillParser;
+
+impl ShapeFillParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> ShapeFill {
+        ShapeFill {
+            name: JsonUtils::get_string(json, "nm"),
+            fill_enabled: json
+                .get("fillEnabled")
+                .map(|_| JsonUtils::get_bool(json, "fillEnabled"))
+                .unwrap_or(true),
+            fill_type: Self::fill_type(json),
+            color: json
+                .get("c")
+                .map(|value| AnimatableValueParser::parse_color(value, composition)),
+            opacity: json
+                .get("o")
+                .map(|value| AnimatableValueParser::parse_integer(value, composition)),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+
+    /// Reads the fill rule `r`.
+    pub fn fill_type(json: &Value) -> FillType {
+        match JsonUtils::get_i64(json, "r") {
+            Some(2) => FillType::EvenOdd,
+            _ => FillType::NonZero,
+        }
+    }
+}
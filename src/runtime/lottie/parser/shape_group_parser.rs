@@ -1 +1,18 @@
+use super::{ContentModelParser, JsonUtils};
+use crate::lottie::{model::content::ShapeGroup, LottieComposition};
+use serde_json::Value;
+
 pub struct ShapeGroupParser;
+
+impl ShapeGroupParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> ShapeGroup {
+        ShapeGroup {
+            name: JsonUtils::get_string(json, "nm"),
+            items: JsonUtils::get_array(json, "it")
+                .iter()
+                .filter_map(|item| ContentModelParser::parse(item, composition))
+                .collect(),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
@@ -1 +1,19 @@
+use super::{AnimatableValueParser, JsonUtils};
+use crate::lottie::{model::content::ShapePath, LottieComposition};
+use serde_json::Value;
+
 pub struct ShapePathParser;
+
+impl ShapePathParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> ShapePath {
+        ShapePath {
+            name: JsonUtils::get_string(json, "nm"),
+            index: JsonUtils::get_i64(json, "ind").unwrap_or(0),
+            shape: AnimatableValueParser::parse_shape_data(
+                json.get("ks").unwrap_or(&Value::Null),
+                composition,
+            ),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+}
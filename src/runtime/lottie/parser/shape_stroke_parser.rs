@@ -1 +1,60 @@
+use super::{AnimatableValueParser, JsonUtils};
+use crate::lottie::{
+    model::{
+        animatable::AnimatableDoubleValue,
+        content::{LineCapType, LineJoinType, ShapeStroke},
+    },
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct ShapeStrokeParser;
+
+impl ShapeStrokeParser {
+    pub fn parse(json: &Value, composition: &LottieComposition) -> ShapeStroke {
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        let (line_dash_pattern, dash_offset) = Self::parse_dashes(json, composition);
+        ShapeStroke {
+            name: JsonUtils::get_string(json, "nm"),
+            dash_offset,
+            line_dash_pattern,
+            color: AnimatableValueParser::parse_color(property("c"), composition),
+            opacity: AnimatableValueParser::parse_integer(property("o"), composition),
+            width: AnimatableValueParser::parse_float(property("w"), composition),
+            cap_type: LineCapType::for_id(JsonUtils::get_i64(json, "lc").unwrap_or(1)),
+            join_type: LineJoinType::for_id(JsonUtils::get_i64(json, "lj").unwrap_or(1)),
+            miter_limit: JsonUtils::get_f64(json, "ml").unwrap_or(4.0),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        }
+    }
+
+    /// Reads the dash pattern `d` of a stroke: its dashes and gaps, and its
+    /// offset.
+    pub fn parse_dashes(
+        json: &Value,
+        composition: &LottieComposition,
+    ) -> (Vec<AnimatableDoubleValue>, Option<AnimatableDoubleValue>) {
+        let mut pattern = Vec::new();
+        let mut offset = None;
+        for dash in JsonUtils::get_array(json, "d") {
+            let value = AnimatableValueParser::parse_float(
+                dash.get("v").unwrap_or(&Value::Null),
+                composition,
+            );
+            match dash.get("n").and_then(Value::as_str) {
+                Some("o") => offset = Some(value),
+                Some("d") | Some("g") => {
+                    composition.set_has_dash_pattern(true);
+                    pattern.push(value);
+                }
+                _ => {}
+            }
+        }
+
+        // a single value stands for a dash and a gap of the same length
+        if pattern.len() == 1 {
+            pattern.push(pattern[0].clone());
+        }
+        (pattern, offset)
+    }
+}
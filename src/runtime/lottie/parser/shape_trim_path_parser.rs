@@ -1 +1,31 @@
+use super::{AnimatableValueParser, JsonUtils};
+use crate::lottie::{
+    model::content::{ShapeTrimPath, TrimPathType},
+    LottieComposition,
+};
+use serde_json::Value;
+
 pub struct ShapeTrimPathParser;
+
+impl ShapeTrimPathParser {
+    /// Returns `None` when the trim mode `m` is unknown.
+    pub fn parse(json: &Value, composition: &LottieComposition) -> Option<ShapeTrimPath> {
+        let kind = match TrimPathType::for_id(JsonUtils::get_i64(json, "m").unwrap_or(1)) {
+            Some(kind) => kind,
+            None => {
+                composition.add_warning("Unknown trim path type.");
+                return None;
+            }
+        };
+
+        let property = |key| json.get(key).unwrap_or(&Value::Null);
+        Some(ShapeTrimPath {
+            name: JsonUtils::get_string(json, "nm"),
+            kind,
+            start: AnimatableValueParser::parse_float(property("s"), composition),
+            end: AnimatableValueParser::parse_float(property("e"), composition),
+            offset: AnimatableValueParser::parse_float(property("o"), composition),
+            hidden: JsonUtils::get_bool(json, "hd"),
+        })
+    }
+}
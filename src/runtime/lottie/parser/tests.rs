@@ -0,0 +1,246 @@
+#![allow(clippy::float_cmp)]
+
+use super::*;
+use crate::lottie::{
+    model::{
+        animatable::{AnimatablePosition, AnimatableValue},
+        content::{ContentModel, MaskMode},
+        layer::{LayerType, MatteType},
+        FontCharacter,
+    },
+    LottieComposition, LottieError,
+};
+use crate::{Point, RgbaColor};
+use serde_json::json;
+
+const COMPOSITION: &str = r##"{
+    "v": "5.7.4",
+    "nm": "scene",
+    "fr": 30,
+    "ip": 0,
+    "op": 60,
+    "w": 200,
+    "h": 100,
+    "assets": [
+        { "id": "image_0", "w": 64, "h": 32, "u": "images/", "p": "img_0.png" },
+        { "id": "comp_0", "layers": [
+            { "ty": 3, "ind": 1, "nm": "null", "ks": {} }
+        ] }
+    ],
+    "fonts": { "list": [
+        { "fFamily": "Sans", "fName": "Sans-Bold", "fStyle": "Bold", "ascent": 72.5 }
+    ] },
+    "chars": [
+        { "ch": "A", "size": 12, "w": 60, "style": "Bold", "fFamily": "Sans",
+          "data": { "shapes": [ { "ty": "gr", "nm": "A", "it": [] } ] } }
+    ],
+    "markers": [ { "cm": "intro\r\n", "tm": 0, "dr": 20 } ],
+    "layers": [
+        { "ty": 4, "ind": 1, "nm": "shapes", "parent": 2, "ip": 0, "op": 60, "st": 0,
+          "tt": 1,
+          "ks": {
+              "p": { "a": 1, "k": [
+                  { "t": 0, "s": [0, 0], "e": [100, 50],
+                    "o": { "x": [0.4], "y": [0] }, "i": { "x": [0.6], "y": [1] } },
+                  { "t": 30 }
+              ] },
+              "s": { "a": 0, "k": [50, 200, 100] },
+              "o": { "a": 0, "k": 80 }
+          },
+          "masksProperties": [
+              { "mode": "s", "inv": true, "o": { "a": 0, "k": 50 },
+                "pt": { "a": 0, "k": { "c": true, "v": [[0, 0], [10, 0], [10, 10]],
+                                       "i": [[0, 0], [0, 0], [0, 0]],
+                                       "o": [[0, 0], [0, 0], [0, 0]] } } }
+          ],
+          "shapes": [
+              { "ty": "gr", "nm": "group", "it": [
+                  { "ty": "rc", "nm": "box", "p": { "a": 0, "k": [0, 0] },
+                    "s": { "a": 0, "k": [20, 10] }, "r": { "a": 0, "k": 2 } },
+                  { "ty": "st", "c": { "a": 0, "k": [1, 0, 0, 1] },
+                    "o": { "a": 0, "k": 100 }, "w": { "a": 0, "k": 3 },
+                    "lc": 2, "lj": 3,
+                    "d": [ { "n": "d", "v": { "a": 0, "k": 4 } },
+                           { "n": "o", "v": { "a": 0, "k": 1 } } ] },
+                  { "ty": "tr", "o": { "a": 0, "k": 100 } }
+              ] },
+              { "ty": "rd", "nm": "rounded" }
+          ] },
+        { "ty": 2, "ind": 2, "nm": "image", "refId": "image_0", "ks": {}, "bm": 3 },
+        { "ty": 0, "ind": 3, "nm": "precomp", "refId": "comp_0", "w": 10, "h": 10,
+          "tm": { "a": 0, "k": 0.5 }, "ks": {} },
+        { "ty": 1, "ind": 4, "nm": "solid", "sc": "#ff8000", "sw": 20, "sh": 30,
+          "ks": {} },
+        { "ty": 5, "ind": 5, "nm": "text", "ks": {},
+          "t": { "d": { "k": [ { "t": 0, "s": { "t": "Hi", "f": "Sans-Bold", "s": 24,
+                                                "j": 2, "fc": [0, 0, 1] } } ] },
+                 "a": [ { "a": { "t": { "a": 0, "k": 10 } } } ] } }
+    ]
+}"##;
+
+fn composition() -> LottieComposition {
+    LottieComposition::from_bytes(COMPOSITION.as_bytes()).unwrap()
+}
+
+#[test]
+fn composition_parameters() {
+    let composition = composition();
+    assert_eq!(composition.get_name(), Some("scene"));
+    assert_eq!(composition.get_bounds().size.width, 200);
+    assert_eq!(composition.get_bounds().size.height, 100);
+    assert_eq!(composition.get_duration_frames(), 60.0);
+    assert_eq!(composition.get_seconds(), 2.0);
+    assert_eq!(composition.get_layers().len(), 5);
+    assert_eq!(composition.layer_model_for_id(4).unwrap().name, "solid");
+    assert_eq!(composition.get_mask_and_matte_count(), 2);
+    assert!(composition.has_dash_pattern());
+}
+
+#[test]
+fn assets() {
+    let composition = composition();
+    let image = &composition.images()["image_0"];
+    assert_eq!((image.get_width(), image.get_height()), (64, 32));
+    assert_eq!(image.get_file_name(), "img_0.png");
+    assert_eq!(image.get_dir_name(), "images/");
+
+    let layer = composition.layer_model_for_id(2).unwrap();
+    assert_eq!(layer.layer_type, LayerType::Image);
+    assert_eq!((layer.pre_comp_width, layer.pre_comp_height), (64.0, 32.0));
+
+    let precomp = composition.get_precomps("comp_0").unwrap();
+    assert_eq!(precomp[0].layer_type, LayerType::Null);
+    let layer = composition.layer_model_for_id(3).unwrap();
+    assert_eq!(layer.ref_id.as_deref(), Some("comp_0"));
+    assert!(layer.time_remapping.is_some());
+}
+
+#[test]
+fn fonts_characters_and_markers() {
+    let composition = composition();
+    let font = &composition.get_fonts()["Sans-Bold"];
+    assert_eq!((font.family.as_str(), font.ascent), ("Sans", 72.5));
+
+    let character = &composition.get_characters()[&FontCharacter::hash_for("A", "Sans", "Bold")];
+    assert_eq!(character.width, 60.0);
+    assert_eq!(character.shapes[0].name.as_deref(), Some("A"));
+
+    let marker = composition.get_marker("Intro").unwrap();
+    assert_eq!(marker.name, "intro");
+    assert_eq!(
+        (marker.get_start(&composition), marker.get_end(&composition)),
+        (0.0, 1.0 / 3.0)
+    );
+}
+
+#[test]
+fn layer_transform_and_masks() {
+    let composition = composition();
+    let layer = &composition.get_layers()[0];
+    assert_eq!(layer.parent_id, Some(2));
+    assert_eq!(layer.matte_type, MatteType::Add);
+
+    let position = match layer.transform.position.as_ref().unwrap() {
+        AnimatablePosition::Path(position) => position,
+        AnimatablePosition::Split(_) => panic!("position is not split"),
+    };
+    let keyframe = &position.keyframes()[0];
+    assert_eq!(keyframe.start_value, Some(Point::new(0.0, 0.0)));
+    assert_eq!(keyframe.end_value, Some(Point::new(100.0, 50.0)));
+    assert_eq!(keyframe.end_frame, Some(30.0));
+    assert!(keyframe.interpolator.is_some());
+
+    let scale = &layer.transform.scale.as_ref().unwrap().keyframes()[0];
+    assert_eq!(scale.start_value, Some(Point::new(0.5, 2.0)));
+
+    let mask = &layer.masks[0];
+    assert_eq!(mask.mode, MaskMode::Subtract);
+    assert!(mask.inverted);
+    let shape = mask.path.keyframes()[0].start_value.as_ref().unwrap();
+    assert!(shape.closed);
+    assert_eq!(shape.curves.len(), 3);
+}
+
+#[test]
+fn shapes() {
+    let composition = composition();
+    let layer = &composition.get_layers()[0];
+    // the rounded corners are skipped
+    assert_eq!(layer.shapes.len(), 1);
+
+    let items = match &layer.shapes[0] {
+        ContentModel::Group(group) => &group.items,
+        _ => panic!("not a group"),
+    };
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].name(), Some("box"));
+    match &items[1] {
+        ContentModel::Stroke(stroke) => {
+            let color = stroke.color.keyframes()[0].start_value;
+            assert_eq!(
+                color,
+                Some(RgbaColor {
+                    red: 255,
+                    green: 0,
+                    blue: 0,
+                    alpha: 255
+                })
+            );
+            // a single dash length is a dash and a gap
+            assert_eq!(stroke.line_dash_pattern.len(), 2);
+            assert!(stroke.dash_offset.is_some());
+        }
+        _ => panic!("not a stroke"),
+    }
+    assert!(matches!(items[2], ContentModel::Transform(_)));
+}
+
+#[test]
+fn solid_and_text_layers() {
+    let composition = composition();
+    let solid = composition.layer_model_for_id(4).unwrap();
+    assert_eq!(
+        solid.solid_color,
+        RgbaColor {
+            red: 255,
+            green: 128,
+            blue: 0,
+            alpha: 255
+        }
+    );
+    assert_eq!((solid.solid_width, solid.solid_height), (20.0, 30.0));
+
+    let text = composition.layer_model_for_id(5).unwrap();
+    let document = text.text.as_ref().unwrap().keyframes()[0]
+        .start_value
+        .clone()
+        .unwrap();
+    assert_eq!(document.text, "Hi");
+    assert_eq!(document.size, 24.0);
+    assert!(text.text_properties.as_ref().unwrap().tracking.is_some());
+}
+
+#[test]
+fn warnings() {
+    let warnings = composition().get_warnings();
+    assert!(warnings.contains(&"Lottie doesn't support rounded corners.".to_string()));
+    assert!(warnings.contains(&"Lottie doesn't support blend modes.".to_string()));
+
+    let old = json!({ "v": "4.1.0", "fr": 25, "op": 10, "layers": [ { "ty": 9, "ks": {} } ] });
+    let warnings = LottieComposition::from_json(&old).unwrap().get_warnings();
+    assert!(warnings.contains(&"Lottie only supports bodymovin >= 4.4.0".to_string()));
+    assert!(warnings.contains(&"Unknown layer type 9".to_string()));
+}
+
+#[test]
+fn invalid() {
+    assert!(matches!(
+        LottieComposition::from_bytes(b"{ \"fr\": "),
+        Err(LottieError::Json(_))
+    ));
+    assert!(matches!(
+        LottieComposition::from_json(&json!([])),
+        Err(LottieError::InvalidComposition(_))
+    ));
+    assert!(LottieCompositionParser::parse(&json!({ "fr": 0 })).is_err());
+}
@@ -1 +1,15 @@
-pub struct ValueParser;
+use serde_json::Value;
+
+/// Parses the values of the keyframes of a property.
+pub trait ValueParser<T> {
+    fn parse_value(&self, json: &Value) -> Option<T>;
+}
+
+impl<T, F> ValueParser<T> for F
+where
+    F: Fn(&Value) -> Option<T>,
+{
+    fn parse_value(&self, json: &Value) -> Option<T> {
+        self(json)
+    }
+}
@@ -1 +1,270 @@
-pub struct PerformanceTracker;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    time::Duration,
+};
+
+use super::utils::MeanCalculator;
+
+/// The number of draws of a layer its mean render time is taken over.
+pub const DEFAULT_RENDER_TIME_WINDOW: usize = 30;
+
+/// What drawing a layer costs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerRenderStats {
+    /// The path of the layer, the names of the precomposition layers it is
+    /// in and its own name separated by `/`, like `"Scene/Logo"`.
+    ///
+    /// A name shared by layers of the same composition is followed by `#`
+    /// and the index of the layer, like `"Shape#2"`, so every layer is
+    /// tracked on its own.
+    pub name: String,
+    /// The mean render time over the last draws of the layer.
+    pub mean_time: Duration,
+    /// The longest render time over the last draws of the layer.
+    pub max_time: Duration,
+    /// The number of draws the times are taken over.
+    pub samples: usize,
+    /// The number of masks drawn with the layer in its latest draw.
+    pub masks: u32,
+    /// The number of track mattes drawn with the layer in its latest draw.
+    pub mattes: u32,
+    /// The number of layers drawn offscreen, then composited, in the latest
+    /// draw of the layer.
+    pub offscreen_passes: u32,
+}
+
+/// The slowest layers of a composition and what a frame costs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PerformanceReport {
+    /// The slowest layers, the slowest first.
+    pub layers: Vec<LayerRenderStats>,
+    /// The masks of all the layers.
+    pub mask_count: u32,
+    /// The track mattes of all the layers.
+    pub matte_count: u32,
+    /// The offscreen passes of all the layers.
+    pub offscreen_pass_count: u32,
+}
+
+impl fmt::Display for PerformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} masks, {} mattes, {} offscreen passes",
+            self.mask_count, self.matte_count, self.offscreen_pass_count
+        )?;
+        for layer in self.layers.iter() {
+            writeln!(
+                f,
+                "{}: {:.3} ms mean, {:.3} ms max, {} masks, {} mattes, {} offscreen passes",
+                layer.name,
+                layer.mean_time.as_secs_f64() * 1000.0,
+                layer.max_time.as_secs_f64() * 1000.0,
+                layer.masks,
+                layer.mattes,
+                layer.offscreen_passes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct LayerRecord {
+    render_times: MeanCalculator,
+    masks: u32,
+    mattes: u32,
+    offscreen_passes: u32,
+}
+
+/// Collects the render times of the layers of a composition, to find the
+/// layers which make an animation too expensive to play.
+///
+/// Tracking is disabled by default, it is shared by the clones of a
+/// composition and enabled with
+/// [`LottieComposition::set_performance_tracking_enabled`](super::LottieComposition::set_performance_tracking_enabled).
+///
+/// The time of a precomposition includes the time of its layers, which are
+/// also tracked on their own under its path, and the time of a layer with a
+/// track matte includes the time of the matte.
+pub struct PerformanceTracker {
+    enabled: Cell<bool>,
+    window: Cell<usize>,
+    layers: RefCell<HashMap<String, LayerRecord>>,
+}
+
+impl Default for PerformanceTracker {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(false),
+            window: Cell::new(DEFAULT_RENDER_TIME_WINDOW),
+            layers: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl PerformanceTracker {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    /// Returns the number of draws of a layer its mean render time is taken
+    /// over.
+    pub fn get_window(&self) -> usize {
+        self.window.get()
+    }
+
+    /// Sets the number of draws of a layer its mean render time is taken
+    /// over, at least one.
+    pub fn set_window(&self, window: usize) {
+        let window = window.max(1);
+        self.window.set(window);
+        for record in self.layers.borrow_mut().values_mut() {
+            record.render_times.set_window(window);
+        }
+    }
+
+    /// Starts timing a draw, `None` when tracking is disabled.
+    pub(crate) fn start(&self) -> Option<Stopwatch> {
+        if self.is_enabled() {
+            Some(Stopwatch::start())
+        } else {
+            None
+        }
+    }
+
+    /// Records a draw of the layer at `path`, does nothing when tracking is
+    /// disabled.
+    pub(crate) fn record_draw(
+        &self,
+        path: &str,
+        time: Duration,
+        masks: u32,
+        mattes: u32,
+        offscreen_passes: u32,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+        let window = self.get_window();
+        let mut layers = self.layers.borrow_mut();
+        let record = layers
+            .entry(path.to_string())
+            .or_insert_with(|| LayerRecord {
+                render_times: MeanCalculator::new(window),
+                masks: 0,
+                mattes: 0,
+                offscreen_passes: 0,
+            });
+        record.render_times.add(time.as_secs_f64());
+        record.masks = masks;
+        record.mattes = mattes;
+        record.offscreen_passes = offscreen_passes;
+    }
+
+    /// Returns the layers drawn since tracking was enabled or cleared, the
+    /// slowest first.
+    pub fn get_sorted_render_times(&self) -> Vec<LayerRenderStats> {
+        let mut layers: Vec<LayerRenderStats> = self
+            .layers
+            .borrow()
+            .iter()
+            .map(|(name, record)| LayerRenderStats {
+                name: name.clone(),
+                mean_time: Duration::from_secs_f64(record.render_times.get_mean()),
+                max_time: Duration::from_secs_f64(record.render_times.get_max()),
+                samples: record.render_times.get_count(),
+                masks: record.masks,
+                mattes: record.mattes,
+                offscreen_passes: record.offscreen_passes,
+            })
+            .collect();
+        layers.sort_by(|a, b| {
+            b.mean_time
+                .cmp(&a.mean_time)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        layers
+    }
+
+    /// Returns a report of the `count` slowest layers, with the masks,
+    /// mattes and offscreen passes of all the layers.
+    ///
+    /// A precomposition and its layers are all in the report, the time of
+    /// the precomposition counts the time of its layers again, while its
+    /// masks, mattes and offscreen passes are only its own.
+    pub fn get_report(&self, count: usize) -> PerformanceReport {
+        let mut layers = self.get_sorted_render_times();
+        let mut report = PerformanceReport::default();
+        for layer in layers.iter() {
+            report.mask_count += layer.masks;
+            report.matte_count += layer.mattes;
+            report.offscreen_pass_count += layer.offscreen_passes;
+        }
+        layers.truncate(count);
+        report.layers = layers;
+        report
+    }
+
+    /// Writes the report of all the layers to the log.
+    pub fn log_render_times(&self) {
+        info!(
+            "Render times:\n{}",
+            self.get_report(usize::MAX).to_string().trim_end()
+        );
+    }
+
+    /// Forgets the layers drawn so far.
+    pub fn clear_render_times(&self) {
+        self.layers.borrow_mut().clear();
+    }
+}
+
+/// Measures the time since it started, with the clock of the browser on the
+/// web.
+pub(crate) struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    started: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    started: f64,
+}
+
+impl Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start() -> Self {
+        Self {
+            started: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn start() -> Self {
+        Self {
+            started: Self::now(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(((Self::now() - self.started) / 1000.0).max(0.0))
+    }
+
+    /// Returns the time in milliseconds.
+    #[cfg(target_arch = "wasm32")]
+    fn now() -> f64 {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or_else(js_sys::Date::now)
+    }
+}
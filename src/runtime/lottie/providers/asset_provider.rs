@@ -1,22 +1,114 @@
-use crate::runtime::lottie::{
-    composition::LottieComposition, lottie_image_asset::LottieImageAsset,
+use super::{load_file, LottieImageProvider, LottieProvider};
+use crate::{
+    lottie::{LottieComposition, LottieError, LottieImageAsset, LottieImageProviderFactory},
+    Bitmap,
 };
-// use crate::Image; // not available in wasm
-use super::LottieProvider;
+use std::{path::PathBuf, rc::Rc};
 
+/// Loads the data of assets by name, such as the assets bundled with an
+/// application.
+pub trait LottieAssetBundle {
+    /// Returns the data of the asset `key`, `None` if there is none.
+    fn load(&self, key: &str) -> Option<Vec<u8>>;
+}
+
+/// The assets in the files of a directory, by their path in it.
 #[derive(Default, Clone)]
-pub struct AssetLottie {}
+pub struct DirectoryAssetBundle {
+    dir: PathBuf,
+}
+
+impl DirectoryAssetBundle {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl LottieAssetBundle for DirectoryAssetBundle {
+    fn load(&self, key: &str) -> Option<Vec<u8>> {
+        load_file(&self.dir.join(key))
+    }
+}
+
+/// Loads the images of a composition from the bundle of the composition,
+/// next to it.
+struct AssetImageProvider {
+    bundle: Rc<dyn LottieAssetBundle>,
+    /// The directory of the composition in the bundle, with a trailing
+    /// slash.
+    dir: String,
+}
+
+impl LottieImageProvider for AssetImageProvider {
+    fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap> {
+        // embedded images are decoded by the factory
+        if asset.get_file_name().starts_with("data:") {
+            return None;
+        }
+        let key = format!(
+            "{}{}{}",
+            self.dir,
+            asset.get_dir_name(),
+            asset.get_file_name()
+        );
+        Bitmap::from_png(&self.bundle.load(&key)?)
+    }
+}
+
+/// Loads a composition from an asset bundle, its images are loaded from the
+/// bundle too.
+#[derive(Clone)]
+pub struct AssetLottie {
+    name: String,
+    bundle: Rc<dyn LottieAssetBundle>,
+    /// The package of the asset, its key is `packages/<package>/<name>`.
+    pub package: Option<String>,
+    /// Loads the images of the composition, the images of the bundle are
+    /// only loaded if it has no image provider.
+    pub image_provider_factory: LottieImageProviderFactory,
+}
 
 impl AssetLottie {
-    pub fn new(asset_name: &str) -> Self {
-        unimplemented!()
+    pub fn new(name: &str, bundle: Rc<dyn LottieAssetBundle>) -> Self {
+        Self {
+            name: name.into(),
+            bundle,
+            package: None,
+            image_provider_factory: Default::default(),
+        }
     }
 
-    // fn load_image(composition: LottieComposition, lottie_image: LottieImageAsset) -> Image {
-    //     unimplemented!()
-    // }
+    /// Returns the key of the composition in the bundle.
+    pub fn get_key_name(&self) -> String {
+        match &self.package {
+            Some(package) => format!("packages/{}/{}", package, self.name),
+            None => self.name.clone(),
+        }
+    }
 }
 
 impl LottieProvider for AssetLottie {
-    fn load(&self) {}
+    fn get_key(&self) -> String {
+        format!("asset:{}", self.get_key_name())
+    }
+
+    fn load(&self) -> Result<LottieComposition, LottieError> {
+        let key = self.get_key_name();
+        let data = self
+            .bundle
+            .load(&key)
+            .ok_or_else(|| LottieError::Load(key.clone()))?;
+        let mut composition = LottieComposition::from_bytes(&data)?;
+
+        let mut factory = self.image_provider_factory.clone();
+        if factory.provider.is_none() {
+            let dir = key.rfind('/').map_or("", |index| &key[..=index]);
+            factory.provider = Some(Rc::new(AssetImageProvider {
+                bundle: self.bundle.clone(),
+                dir: dir.into(),
+            }));
+        }
+        composition.load_images(&factory);
+        Ok(composition)
+    }
 }
@@ -0,0 +1,161 @@
+use super::{hash_bytes, LottieAssetBundle, LottieImageProvider, LottieProvider};
+use crate::{
+    lottie::{
+        model::{DotLottieAnimation, DotLottieManifest},
+        parser::DotLottieManifestParser,
+        LottieComposition, LottieError, LottieImageAsset, LottieImageProviderFactory,
+    },
+    Bitmap,
+};
+use bytes::Bytes;
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+    rc::Rc,
+};
+use zip::{result::ZipError, ZipArchive};
+
+/// The contents of a dotLottie file: a ZIP archive of a `manifest.json`, of
+/// animations in `animations/` and of their images in `images/`.
+///
+/// Clones are cheap and share the same files.
+#[derive(Clone)]
+pub struct DotLottieArchive {
+    manifest: Rc<DotLottieManifest>,
+    /// The data of the files of the archive, by path.
+    files: Rc<HashMap<String, Vec<u8>>>,
+}
+
+impl DotLottieArchive {
+    /// Reads the files of a dotLottie file, fails if it is not a ZIP
+    /// archive or has no valid manifest.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LottieError> {
+        let invalid = |error: ZipError| LottieError::InvalidDotLottie(error.to_string());
+        let mut archive = ZipArchive::new(Cursor::new(data)).map_err(invalid)?;
+        let mut files = HashMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).map_err(invalid)?;
+            if file.is_dir() {
+                continue;
+            }
+            let mut data = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut data)
+                .map_err(|error| LottieError::InvalidDotLottie(error.to_string()))?;
+            files.insert(file.name().trim_start_matches('/').to_string(), data);
+        }
+
+        let manifest = files
+            .get("manifest.json")
+            .ok_or_else(|| LottieError::InvalidDotLottie("there is no manifest.json".into()))?;
+        let manifest = DotLottieManifestParser::parse(&serde_json::from_slice(manifest)?)?;
+        Ok(Self {
+            manifest: Rc::new(manifest),
+            files: Rc::new(files),
+        })
+    }
+
+    pub fn get_manifest(&self) -> &DotLottieManifest {
+        &self.manifest
+    }
+
+    /// Returns the animations listed in the manifest.
+    pub fn get_animations(&self) -> &[DotLottieAnimation] {
+        &self.manifest.animations
+    }
+
+    /// Returns the data of the file at `path` in the archive.
+    pub fn get_file(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// Loads the animation `id`, the active animation of the manifest if
+    /// `id` is `None`.
+    ///
+    /// Its images are loaded from the archive, unless `factory` has an
+    /// image provider.
+    pub fn load_animation(
+        &self,
+        id: Option<&str>,
+        factory: &LottieImageProviderFactory,
+    ) -> Result<LottieComposition, LottieError> {
+        let animation = match id {
+            Some(id) => self.manifest.get_animation(id),
+            None => self.manifest.get_active_animation(),
+        }
+        .ok_or_else(|| {
+            LottieError::InvalidDotLottie(format!("there is no animation {}", id.unwrap_or("")))
+        })?;
+        let path = format!("animations/{}.json", animation.id);
+        let data = self
+            .get_file(&path)
+            .ok_or_else(|| LottieError::InvalidDotLottie(format!("there is no {}", path)))?;
+        let mut composition = LottieComposition::from_bytes(data)?;
+
+        let mut factory = factory.clone();
+        if factory.provider.is_none() {
+            factory.provider = Some(Rc::new(self.clone()));
+        }
+        composition.load_images(&factory);
+        Ok(composition)
+    }
+}
+
+impl LottieAssetBundle for DotLottieArchive {
+    fn load(&self, key: &str) -> Option<Vec<u8>> {
+        self.get_file(key).map(<[u8]>::to_vec)
+    }
+}
+
+impl LottieImageProvider for DotLottieArchive {
+    /// Images are found by their path in the archive, or by their file name
+    /// in `images/`.
+    fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap> {
+        let file_name = asset.get_file_name();
+        // embedded images are decoded by the factory
+        if file_name.starts_with("data:") {
+            return None;
+        }
+        let path = format!("{}{}", asset.get_dir_name(), file_name);
+        let data = self
+            .get_file(path.trim_start_matches('/'))
+            .or_else(|| self.get_file(&format!("images/{}", file_name)))?;
+        Bitmap::from_png(data)
+    }
+}
+
+/// Loads an animation of a dotLottie file in memory.
+#[derive(Default, Clone)]
+pub struct DotLottie {
+    bytes: Bytes,
+    animation_id: Option<String>,
+    /// Loads the images of the animation, the images of the archive are
+    /// only loaded if it has no image provider.
+    pub image_provider_factory: LottieImageProviderFactory,
+}
+
+impl DotLottie {
+    /// Loads the animation `animation_id` of the dotLottie file in `bytes`,
+    /// the active animation of its manifest if it is `None`.
+    pub fn new(bytes: Bytes, animation_id: Option<&str>) -> Self {
+        Self {
+            bytes,
+            animation_id: animation_id.map(Into::into),
+            image_provider_factory: Default::default(),
+        }
+    }
+}
+
+impl LottieProvider for DotLottie {
+    fn get_key(&self) -> String {
+        format!(
+            "dotlottie:{}:{}",
+            hash_bytes(&self.bytes),
+            self.animation_id.as_deref().unwrap_or("")
+        )
+    }
+
+    fn load(&self) -> Result<LottieComposition, LottieError> {
+        DotLottieArchive::from_bytes(&self.bytes)?
+            .load_animation(self.animation_id.as_deref(), &self.image_provider_factory)
+    }
+}
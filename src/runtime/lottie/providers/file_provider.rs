@@ -1,22 +1,44 @@
-use crate::runtime::lottie::{
-    composition::LottieComposition, lottie_image_asset::LottieImageAsset,
-};
-// use crate::Image; // not available in wasm
-use super::LottieProvider;
+use super::{load_file, LottieProvider};
+use crate::lottie::{LottieComposition, LottieError, LottieImageProviderFactory};
+use std::path::{Path, PathBuf};
 
+/// Loads a composition from a JSON file.
 #[derive(Default, Clone)]
-pub struct FileLottie {}
+pub struct FileLottie {
+    path: PathBuf,
+    /// Loads the images of the composition, their files are relative to the
+    /// directory of the JSON file unless the factory has a directory.
+    pub image_provider_factory: LottieImageProviderFactory,
+}
 
 impl FileLottie {
-    pub fn new(asset_name: &str) -> Self {
-        unimplemented!()
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().into(),
+            image_provider_factory: Default::default(),
+        }
     }
 
-    // fn load_image(composition: LottieComposition, lottie_image: LottieImageAsset) -> Image {
-    //     unimplemented!()
-    // }
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
 }
 
 impl LottieProvider for FileLottie {
-    fn load(&self) {}
+    fn get_key(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    fn load(&self) -> Result<LottieComposition, LottieError> {
+        let data = load_file(&self.path)
+            .ok_or_else(|| LottieError::Load(self.path.display().to_string()))?;
+        let mut composition = LottieComposition::from_bytes(&data)?;
+
+        let mut factory = self.image_provider_factory.clone();
+        if factory.dir.is_none() {
+            factory.dir = self.path.parent().map(Path::to_path_buf);
+        }
+        composition.load_images(&factory);
+        Ok(composition)
+    }
 }
@@ -0,0 +1,60 @@
+use crate::lottie::{LottieError, LottieFontStyle};
+use fontdue::{Font, FontSettings};
+
+/// Resolves the fonts of the text layers drawn without glyphs.
+pub trait LottieFontProvider {
+    /// Returns the family the canvas draws the text of `style` with, the
+    /// family of the composition by default.
+    fn get_family(&self, style: &LottieFontStyle) -> String {
+        style.get_font_family().into()
+    }
+
+    /// Returns the advance width of `character` drawn at `size` in the font
+    /// of `style`, `None` to measure it with the canvas.
+    fn get_advance(&self, style: &LottieFontStyle, character: char, size: f64) -> Option<f64>;
+}
+
+/// Fonts loaded with fontdue, to lay out text the same way on every canvas.
+#[derive(Default)]
+pub struct LottieFonts {
+    /// The fonts by family and style, in the order they were added.
+    fonts: Vec<((String, String), Font)>,
+}
+
+impl LottieFonts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the TrueType or OpenType font in `data` for the text of
+    /// `font_family` in `style`, it replaces the font added before for
+    /// them.
+    pub fn add(&mut self, font_family: &str, style: &str, data: &[u8]) -> Result<(), LottieError> {
+        let font = Font::from_bytes(data, FontSettings::default())
+            .map_err(|reason| LottieError::InvalidFont(reason.into()))?;
+        let key = (font_family.into(), style.into());
+        match self.fonts.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, other)) => *other = font,
+            None => self.fonts.push((key, font)),
+        }
+        Ok(())
+    }
+
+    /// Returns the font of `style`, or the first font added for its family
+    /// in another style.
+    fn get_font(&self, style: &LottieFontStyle) -> Option<&Font> {
+        let family = style.get_font_family();
+        self.fonts
+            .iter()
+            .find(|((other, other_style), _)| other == family && other_style == style.get_style())
+            .or_else(|| self.fonts.iter().find(|((other, _), _)| other == family))
+            .map(|(_, font)| font)
+    }
+}
+
+impl LottieFontProvider for LottieFonts {
+    fn get_advance(&self, style: &LottieFontStyle, character: char, size: f64) -> Option<f64> {
+        let font = self.get_font(style)?;
+        Some(font.metrics(character, size as f32).advance_width as f64)
+    }
+}
@@ -1,2 +1,86 @@
+use super::load_file;
+use crate::{lottie::LottieImageAsset, Bitmap};
+use std::{path::PathBuf, rc::Rc};
+
+/// Loads the images of the image assets of a composition, to draw them
+/// from other sources than the composition.
+pub trait LottieImageProvider {
+    /// Returns the image of `asset`, `None` to load it from the composition.
+    fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap>;
+}
+
+/// Loads the images of a composition: with the image provider first, then
+/// from the data URIs embedded in the composition or from PNG files.
 #[derive(Default, Clone)]
-pub struct LottieImageProviderFactory;
+pub struct LottieImageProviderFactory {
+    pub provider: Option<Rc<dyn LottieImageProvider>>,
+    /// The directory of the composition, the files of the images are
+    /// relative to it. Without it they are relative to the working
+    /// directory.
+    ///
+    /// Files are only read on native targets, images are given by the
+    /// image provider on the web.
+    pub dir: Option<PathBuf>,
+}
+
+impl LottieImageProviderFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the image of `asset`, `None` if it can't be loaded or
+    /// decoded.
+    pub fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap> {
+        if let Some(image) = self
+            .provider
+            .as_ref()
+            .and_then(|provider| provider.load_image(asset))
+        {
+            return Some(image);
+        }
+
+        let data = match from_data_uri(asset.get_file_name()) {
+            Some(data) => data,
+            None => {
+                let dir = self.dir.clone().unwrap_or_default();
+                load_file(&dir.join(asset.get_dir_name()).join(asset.get_file_name()))?
+            }
+        };
+        Bitmap::from_png(&data)
+    }
+}
+
+/// Returns the data of a base64 data URI, `None` if `uri` is not one.
+pub(crate) fn from_data_uri(uri: &str) -> Option<Vec<u8>> {
+    if !uri.starts_with("data:") {
+        return None;
+    }
+    let start = uri.find("base64,")? + "base64,".len();
+    decode_base64(&uri[start..])
+}
+
+/// Decodes base64 of the standard or the URL-safe alphabet, whitespace
+/// and padding are skipped.
+pub(crate) fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut data = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' | b' ' | b'\t' | b'\r' | b'\n' => continue,
+            _ => return None,
+        };
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            data.push((buffer >> bits) as u8);
+        }
+    }
+    Some(data)
+}
@@ -1,14 +1,38 @@
-use std::collections::HashMap;
-
-use crate::runtime::lottie::composition::LottieComposition;
+use crate::lottie::{LottieComposition, LottieError};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+};
 
+/// Loads and parses a composition, with its images.
 pub trait LottieProvider {
-    fn load(&self);
+    /// Returns the key of the composition in a `LottieCache`, providers of
+    /// the same key load the same composition.
+    fn get_key(&self) -> String;
+
+    /// Loads the composition.
+    fn load(&self) -> Result<LottieComposition, LottieError>;
+
+    /// Returns the composition of the key in `cache`, it is loaded and put
+    /// in the cache the first time.
+    fn load_cached(&self, cache: &mut LottieCache) -> Result<LottieComposition, LottieError> {
+        cache.put_if_absent(&self.get_key(), || self.load())
+    }
+}
+
+thread_local! {
+    static SHARED_CACHE: RefCell<LottieCache> = RefCell::new(LottieCache::new(None));
 }
 
+/// The compositions loaded by providers, by key.
+///
+/// The oldest compositions are dropped when there are more of them than
+/// the maximum size.
 pub struct LottieCache {
     maximum_size: usize,
     cache: HashMap<String, LottieComposition>,
+    /// The keys of the compositions, from the oldest to the newest.
+    keys: VecDeque<String>,
 }
 
 impl LottieCache {
@@ -18,14 +42,71 @@ impl LottieCache {
         Self {
             maximum_size,
             cache: Default::default(),
+            keys: Default::default(),
         }
     }
 
-    pub fn put_if_absent(&self, key: &str, load: LottieComposition) -> LottieComposition {
-        unimplemented!()
+    /// Calls `f` with the cache shared by the `Lottie` widgets of the
+    /// thread.
+    pub fn with_shared<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut LottieCache) -> R,
+    {
+        SHARED_CACHE.with(|cache| f(&mut cache.borrow_mut()))
     }
 
-    fn check_cache_size(&self) {}
+    pub fn get(&self, key: &str) -> Option<&LottieComposition> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
 
-    fn clear(&self) {}
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the composition of `key`, loaded with `load` if it is not in
+    /// the cache yet.
+    ///
+    /// Compositions failing to load are not cached, they are loaded again
+    /// the next time.
+    pub fn put_if_absent<F>(&mut self, key: &str, load: F) -> Result<LottieComposition, LottieError>
+    where
+        F: FnOnce() -> Result<LottieComposition, LottieError>,
+    {
+        if let Some(composition) = self.cache.get(key) {
+            return Ok(composition.clone());
+        }
+
+        let composition = load()?;
+        self.cache.insert(key.into(), composition.clone());
+        self.keys.push_back(key.into());
+        self.check_cache_size();
+        Ok(composition)
+    }
+
+    pub fn get_maximum_size(&self) -> usize {
+        self.maximum_size
+    }
+
+    /// Sets the number of compositions kept, the oldest ones are dropped.
+    pub fn set_maximum_size(&mut self, maximum_size: usize) {
+        self.maximum_size = maximum_size;
+        self.check_cache_size();
+    }
+
+    fn check_cache_size(&mut self) {
+        while self.keys.len() > self.maximum_size {
+            if let Some(key) = self.keys.pop_front() {
+                self.cache.remove(&key);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.keys.clear();
+    }
 }
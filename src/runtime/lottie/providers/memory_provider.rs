@@ -1,22 +1,46 @@
-use crate::runtime::lottie::{
-    composition::LottieComposition, lottie_image_asset::LottieImageAsset,
-};
-// use crate::Image; // not available in wasm
 use super::LottieProvider;
+use crate::lottie::{LottieComposition, LottieError, LottieImageProviderFactory};
+use bytes::Bytes;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
+/// Loads a composition from the bytes of a JSON file in memory.
 #[derive(Default, Clone)]
-pub struct MemoryLottie {}
+pub struct MemoryLottie {
+    bytes: Bytes,
+    /// Loads the images of the composition.
+    pub image_provider_factory: LottieImageProviderFactory,
+}
 
 impl MemoryLottie {
-    pub fn new(asset_name: &str) -> Self {
-        unimplemented!()
+    pub fn new(bytes: Bytes) -> Self {
+        Self {
+            bytes,
+            image_provider_factory: Default::default(),
+        }
     }
-
-    // fn load_image(composition: LottieComposition, lottie_image: LottieImageAsset) -> Image {
-    //     unimplemented!()
-    // }
 }
 
 impl LottieProvider for MemoryLottie {
-    fn load(&self) {}
+    /// The key is made of a hash of the bytes, the same bytes in memory
+    /// twice share a composition.
+    fn get_key(&self) -> String {
+        format!("memory:{}", hash_bytes(&self.bytes))
+    }
+
+    fn load(&self) -> Result<LottieComposition, LottieError> {
+        let mut composition = LottieComposition::from_byte_data(&self.bytes)?;
+        composition.load_images(&self.image_provider_factory);
+        Ok(composition)
+    }
+}
+
+/// Returns a hash of `bytes` and their length, to key compositions loaded
+/// from memory.
+pub(crate) fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:x}:{}", hasher.finish(), bytes.len())
 }
@@ -1,9 +1,15 @@
 mod asset_provider;
 pub use asset_provider::*;
 
+mod dot_lottie_provider;
+pub use dot_lottie_provider::*;
+
 mod file_provider;
 pub use file_provider::*;
 
+mod font_provider;
+pub use font_provider::*;
+
 mod load_image;
 pub use load_image::*;
 
@@ -16,8 +22,12 @@ pub use memory_provider::*;
 mod network_provider;
 pub use network_provider::*;
 
+#[cfg(not(target_arch = "wasm32"))]
 mod provider_io;
+#[cfg(not(target_arch = "wasm32"))]
 pub use provider_io::*;
 
+#[cfg(target_arch = "wasm32")]
 mod provider_web;
+#[cfg(target_arch = "wasm32")]
 pub use provider_web::*;
@@ -1,22 +1,90 @@
-use crate::runtime::lottie::{
-    composition::LottieComposition, lottie_image_asset::LottieImageAsset,
+use super::{LottieImageProvider, LottieProvider};
+use crate::{
+    lottie::{LottieComposition, LottieError, LottieImageAsset, LottieImageProviderFactory},
+    Bitmap,
 };
-// use crate::Image; // not available in wasm
-use super::LottieProvider;
+use std::rc::Rc;
 
-#[derive(Default, Clone)]
-pub struct NetworkLottie {}
+/// Fetches the data at a URL, returns the reason of the failure otherwise.
+///
+/// The runtime has no HTTP client of its own, so it works with the client
+/// of the application on any platform.
+pub type LottieFetch = Rc<dyn Fn(&str) -> Result<Vec<u8>, String>>;
+
+/// Fetches the images of a composition, their URLs are relative to the URL
+/// of the composition.
+struct NetworkImageProvider {
+    fetch: LottieFetch,
+    /// The URL of the composition, up to its last slash.
+    base: String,
+}
+
+impl LottieImageProvider for NetworkImageProvider {
+    fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap> {
+        let file_name = asset.get_file_name();
+        // embedded images are decoded by the factory
+        if file_name.starts_with("data:") {
+            return None;
+        }
+        let url = if file_name.contains("://") {
+            file_name.into()
+        } else {
+            format!("{}{}{}", self.base, asset.get_dir_name(), file_name)
+        };
+        match (self.fetch)(&url) {
+            Ok(data) => Bitmap::from_png(&data),
+            Err(reason) => {
+                warn!("Unable to fetch {}: {}", url, reason);
+                None
+            }
+        }
+    }
+}
+
+/// Loads a composition from a URL, with the fetch function of the
+/// application.
+#[derive(Clone)]
+pub struct NetworkLottie {
+    url: String,
+    fetch: LottieFetch,
+    /// Loads the images of the composition, the images are fetched next to
+    /// the composition if it has no image provider.
+    pub image_provider_factory: LottieImageProviderFactory,
+}
 
 impl NetworkLottie {
-    pub fn new(asset_name: &str) -> Self {
-        unimplemented!()
+    pub fn new(url: &str, fetch: LottieFetch) -> Self {
+        Self {
+            url: url.into(),
+            fetch,
+            image_provider_factory: Default::default(),
+        }
     }
 
-    // fn load_image(composition: LottieComposition, lottie_image: LottieImageAsset) -> Image {
-    //     unimplemented!()
-    // }
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
 }
 
 impl LottieProvider for NetworkLottie {
-    fn load(&self) {}
+    fn get_key(&self) -> String {
+        format!("network:{}", self.url)
+    }
+
+    fn load(&self) -> Result<LottieComposition, LottieError> {
+        let data = (self.fetch)(&self.url)
+            .map_err(|reason| LottieError::Load(format!("{}: {}", self.url, reason)))?;
+        let mut composition = LottieComposition::from_bytes(&data)?;
+
+        let mut factory = self.image_provider_factory.clone();
+        if factory.provider.is_none() {
+            let base = self.url.rfind('/').map_or("", |index| &self.url[..=index]);
+            factory.provider = Some(Rc::new(NetworkImageProvider {
+                fetch: self.fetch.clone(),
+                base: base.into(),
+            }));
+        }
+        composition.load_images(&factory);
+        Ok(composition)
+    }
 }
@@ -1 +1,12 @@
+use std::{fs, path::Path};
 
+/// Reads the file at `path`, `None` if it can't be read.
+pub(crate) fn load_file(path: &Path) -> Option<Vec<u8>> {
+    match fs::read(path) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            warn!("Unable to read {}: {}", path.display(), err);
+            None
+        }
+    }
+}
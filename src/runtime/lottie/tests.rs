@@ -0,0 +1,2959 @@
+#![allow(clippy::float_cmp)]
+
+use super::{
+    animation::{
+        content::{Content, ContentGroup},
+        expression::{Expression, ExpressionContext, ExpressionValue},
+        keyframe::{KeyframeAnimation, TransformKeyframeAnimation},
+    },
+    model::{
+        animatable::AnimatableTransform,
+        content::{ContentModel, GradientColor},
+        KeyPath,
+    },
+    providers::{decode_base64, from_data_uri},
+    utils::{DashPath, GammaEvaluator, MeanCalculator},
+    AssetLottie, DotLottie, DotLottieArchive, FileLottie, FrameRate, Lottie, LottieAssetBundle,
+    LottieBuilderExt, LottieCache, LottieComposition, LottieDelegates, LottieDrawable, LottieError,
+    LottieFetch, LottieFontProvider, LottieFontStyle, LottieFonts, LottieFrameInfo,
+    LottieImageAsset, LottieImageProvider, LottieImageProviderFactory, LottieOptions, LottiePlayer,
+    LottieProperty, LottieProvider, LottieRasterizer, LottieRelativeDoubleValueCallback,
+    LottieValueCallback, MemoryLottie, NetworkLottie, PerformanceTracker, ValueDelegate,
+};
+use crate::{
+    interpolate::Interpolate, path::Path, tween::Group, BaseLine, Bitmap, CanvasContext, Color,
+    Direction, Gradient, GradientType, LayerComposite, LineCap, LineJoin, LinearGradient,
+    OffscreenContext, Point, RadialGradient, Rect, RgbaColor, Size, TextAlign, TextMetrics,
+    TextStyle, TextWeight, Transform,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    io::{Cursor, Write},
+    rc::Rc,
+    str::FromStr,
+    time::Duration,
+};
+
+/// A canvas which records the calls it receives.
+#[derive(Default)]
+struct RecordingCanvas {
+    ops: RefCell<Vec<String>>,
+}
+
+impl RecordingCanvas {
+    fn record(&self, op: &str, args: &[f64]) {
+        let mut line = op.to_string();
+        for arg in args {
+            line.push_str(&format!(" {:.2}", arg));
+        }
+        self.ops.borrow_mut().push(line);
+    }
+
+    fn record_color(&self, op: &str, color: Color) {
+        let color: RgbaColor = color.into();
+        self.ops.borrow_mut().push(format!(
+            "{} {} {} {} {}",
+            op, color.red, color.green, color.blue, color.alpha
+        ));
+    }
+
+    /// Records the geometry of `gradient`, then each of its stops.
+    fn record_gradient(&self, op: &str, gradient: &Gradient) {
+        match gradient.kind {
+            GradientType::Linear(LinearGradient { x0, y0, x1, y1 }) => {
+                self.record(&format!("{} linear", op), &[x0, y0, x1, y1])
+            }
+            GradientType::Radial(RadialGradient {
+                x0,
+                y0,
+                r0,
+                x1,
+                y1,
+                r1,
+            }) => self.record(&format!("{} radial", op), &[x0, y0, r0, x1, y1, r1]),
+        }
+        for stop in gradient.stops.borrow().iter() {
+            self.record_color(&format!("color_stop {:.3}", stop.offset), stop.color);
+        }
+    }
+
+    fn ops(&self) -> Vec<String> {
+        self.ops.borrow().clone()
+    }
+
+    fn count(&self, op: &str) -> usize {
+        self.ops
+            .borrow()
+            .iter()
+            .filter(|line| line.starts_with(op))
+            .count()
+    }
+
+    fn count_exact(&self, line: &str) -> usize {
+        self.ops.borrow().iter().filter(|op| *op == line).count()
+    }
+
+    fn contains(&self, line: &str) -> bool {
+        self.ops.borrow().iter().any(|op| op == line)
+    }
+}
+
+impl CanvasContext for RecordingCanvas {
+    type Pattern = ();
+
+    fn get_direction(&self) -> Direction {
+        Direction::Inherit
+    }
+
+    fn set_direction(&self, value: Direction) -> String {
+        String::new()
+    }
+
+    fn set_fill_color(&self, value: Color) {
+        self.record_color("set_fill_color", value);
+    }
+
+    fn set_fill_gradient(&self, value: &Gradient) {
+        self.record_gradient("set_fill_gradient", value);
+    }
+
+    fn set_fill_pattern(&self, value: &Self::Pattern) {}
+
+    fn get_filter(&self) -> String {
+        String::new()
+    }
+
+    fn set_filter(&self, value: &str) {}
+
+    fn get_font(&self) -> String {
+        String::new()
+    }
+
+    fn set_font(&self, family: &str, style: TextStyle, weight: TextWeight, size: f64) {
+        self.record(&format!("set_font {}", family), &[size]);
+    }
+
+    fn get_global_alpha(&self) -> f64 {
+        1.0
+    }
+
+    fn set_global_alpha(&self, value: f64) {
+        self.record("set_global_alpha", &[value]);
+    }
+
+    fn get_global_composite_operation(&self) -> String {
+        String::new()
+    }
+
+    fn set_global_composite_operation(&self, value: &str) {}
+
+    fn is_image_smoothing_enabled(&self) -> bool {
+        true
+    }
+
+    fn set_image_smoothing(&self, value: bool) {}
+
+    fn get_line_cap(&self) -> LineCap {
+        LineCap::Butt
+    }
+
+    fn set_line_cap(&self, value: LineCap) {}
+
+    fn get_line_dash_offset(&self) -> f64 {
+        0.0
+    }
+
+    fn set_line_dash_offset(&self, value: f64) {
+        self.record("set_line_dash_offset", &[value]);
+    }
+
+    fn get_line_join(&self) -> LineJoin {
+        LineJoin::Miter
+    }
+
+    fn set_line_join(&self, value: LineJoin) {}
+
+    fn get_line_width(&self) -> f64 {
+        1.0
+    }
+
+    fn set_line_width(&self, value: f64) {
+        self.record("set_line_width", &[value]);
+    }
+
+    fn get_miter_limit(&self) -> f64 {
+        10.0
+    }
+
+    fn set_miter_limit(&self, value: f64) {}
+
+    fn get_shadow_blur(&self) -> f64 {
+        0.0
+    }
+
+    fn set_shadow_blur(&self, value: f64) {}
+
+    fn get_shadow_color(&self) -> Color {
+        RgbaColor::new(0, 0, 0, 0).into()
+    }
+
+    fn set_shadow_color(&self, value: Color) {}
+
+    fn get_shadow_offset_x(&self) -> f64 {
+        0.0
+    }
+
+    fn set_shadow_offset_x(&self, value: f64) {}
+
+    fn get_shadow_offset_y(&self) -> f64 {
+        0.0
+    }
+
+    fn set_shadow_offset_y(&self, value: f64) {}
+
+    fn set_stroke_color(&self, value: Color) {
+        self.record_color("set_stroke_color", value);
+    }
+
+    fn set_stroke_gradient(&self, value: &Gradient) {
+        self.record_gradient("set_stroke_gradient", value);
+    }
+
+    fn set_stroke_pattern(&self, value: &Self::Pattern) {}
+
+    fn get_text_align(&self) -> TextAlign {
+        TextAlign::Left
+    }
+
+    fn set_text_align(&self, value: TextAlign) {}
+
+    fn get_text_baseline(&self) -> BaseLine {
+        BaseLine::Alphabetic
+    }
+
+    fn set_text_baseline(&self, value: BaseLine) {}
+
+    fn arc(
+        &self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        anticlockwise: bool,
+    ) {
+        self.record("arc", &[x, y, radius, start_angle, end_angle]);
+    }
+
+    fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
+        self.record("arc_to", &[x1, y1, x2, y2, radius]);
+    }
+
+    fn begin_path(&self) {
+        self.record("begin_path", &[]);
+    }
+
+    fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        self.record("bezier_curve_to", &[cp1x, cp1y, cp2x, cp2y, x, y]);
+    }
+
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("clear_rect", &[x, y, width, height]);
+    }
+
+    fn close_path(&self) {
+        self.record("close_path", &[]);
+    }
+
+    fn ellipse(
+        &self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64,
+        start_angle: f64,
+        end_angle: f64,
+        anticlockwise: bool,
+    ) {
+        self.record(
+            "ellipse",
+            &[x, y, radius_x, radius_y, rotation, start_angle, end_angle],
+        );
+    }
+
+    fn fill(&self) {
+        self.record("fill", &[]);
+    }
+
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("fill_rect", &[x, y, width, height]);
+    }
+
+    fn fill_text(&self, text: &str, x: f64, y: f64) {
+        self.record(&format!("fill_text {}", text), &[x, y]);
+    }
+
+    fn get_line_dash(&self) -> Vec<f64> {
+        Vec::new()
+    }
+
+    fn line_to(&self, x: f64, y: f64) {
+        self.record("line_to", &[x, y]);
+    }
+
+    /// Measures 10 units per character.
+    fn measure_text(&self, text: &str) -> TextMetrics {
+        TextMetrics {
+            width: text.chars().count() as f64 * 10.0,
+            height: 10.0,
+        }
+    }
+
+    fn move_to(&self, x: f64, y: f64) {
+        self.record("move_to", &[x, y]);
+    }
+
+    fn quadratic_curve_to(&self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        self.record("quadratic_curve_to", &[cpx, cpy, x, y]);
+    }
+
+    fn rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("rect", &[x, y, width, height]);
+    }
+
+    fn reset_transform(&self) {
+        self.record("reset_transform", &[]);
+    }
+
+    fn restore(&self) {
+        self.record("restore", &[]);
+    }
+
+    fn rotate(&self, angle: f64) {
+        self.record("rotate", &[angle]);
+    }
+
+    fn save(&self) {
+        self.record("save", &[]);
+    }
+
+    fn scale(&self, x: f64, y: f64) {
+        self.record("scale", &[x, y]);
+    }
+
+    fn set_line_dash(&self, dash: &[f64]) {
+        self.record("set_line_dash", dash);
+    }
+
+    fn set_transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        self.record("set_transform", &[a, b, c, d, e, f]);
+    }
+
+    fn stroke(&self) {
+        self.record("stroke", &[]);
+    }
+
+    fn stroke_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+        self.record("stroke_rect", &[x, y, width, height]);
+    }
+
+    fn stroke_text(&self, text: &str, x: f64, y: f64) {
+        self.record(&format!("stroke_text {}", text), &[x, y]);
+    }
+
+    fn transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        self.record("transform", &[a, b, c, d, e, f]);
+    }
+
+    fn translate(&self, x: f64, y: f64) {
+        self.record("translate", &[x, y]);
+    }
+}
+
+impl OffscreenContext for RecordingCanvas {
+    fn push_layer(&self) {
+        self.record("push_layer", &[]);
+    }
+
+    fn pop_layer(&self, composite: LayerComposite, alpha: f64) {
+        self.record(&format!("pop_layer {:?}", composite), &[alpha]);
+    }
+
+    fn paint(&self) {
+        self.record("paint", &[]);
+    }
+
+    fn clip(&self) {
+        self.record("clip", &[]);
+    }
+
+    fn draw_image(&self, image: &Bitmap, x: f64, y: f64, alpha: f64) {
+        let op = format!("draw_image {} {}", image.width(), image.height());
+        self.record(&op, &[x, y, alpha]);
+    }
+}
+
+/// Returns a 100x100 composition of 10 frames with `layers`.
+fn composition(layers: &str) -> LottieComposition {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100, "layers": [{}] }}"#,
+        layers
+    );
+    LottieComposition::from_bytes(json.as_bytes()).unwrap()
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect<f64> {
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
+/// Draws `layers` at `frame` and returns the calls received by the canvas.
+fn draw(layers: &str, frame: f64) -> RecordingCanvas {
+    let mut drawable = LottieDrawable::new(composition(layers));
+    drawable.set_frame(frame);
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    canvas
+}
+
+/// Returns the content of a shape item given as JSON.
+fn content(item: &str) -> Content {
+    let layer = format!(
+        r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}}, "shapes": [{}] }}"#,
+        item
+    );
+    let composition = composition(&layer);
+    Content::from_model(&composition.get_layers()[0].shapes[0], true).unwrap()
+}
+
+const RED_BOX: &str = r#"{ "ty": "gr", "it": [
+    { "ty": "rc", "p": { "a": 0, "k": [50, 50] }, "s": { "a": 0, "k": [20, 10] },
+      "r": { "a": 0, "k": 0 } },
+    { "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] }, "o": { "a": 0, "k": 50 } },
+    { "ty": "tr", "o": { "a": 0, "k": 100 } }
+] }"#;
+
+fn shape_layer(index: i64, transform: &str, extra: &str) -> String {
+    format!(
+        r#"{{ "ty": 4, "ind": {}, "ip": 0, "op": 10, "ks": {} {}, "shapes": [{}] }}"#,
+        index, transform, extra, RED_BOX
+    )
+}
+
+#[test]
+fn progress_and_frame() {
+    let mut drawable = LottieDrawable::new(composition(""));
+    assert!(drawable.set_frame(5.0));
+    assert_eq!(drawable.get_progress(), 0.5);
+    assert!(!drawable.set_progress(0.5));
+    assert!(drawable.set_progress(2.0));
+    assert_eq!(drawable.get_progress(), 1.0);
+    assert_eq!(drawable.get_frame(), 10.0);
+}
+
+#[test]
+fn fills_a_rectangle() {
+    let canvas = draw(&shape_layer(1, "{}", ""), 0.0);
+    let ops = canvas.ops();
+    let start = ops.iter().position(|op| op == "begin_path").unwrap();
+    assert_eq!(
+        ops[start - 1..start + 7],
+        [
+            "set_fill_color 255 0 0 128",
+            "begin_path",
+            "move_to 60.00 45.00",
+            "line_to 60.00 55.00",
+            "line_to 40.00 55.00",
+            "line_to 40.00 45.00",
+            "line_to 60.00 45.00",
+            "close_path",
+        ]
+    );
+    assert_eq!(canvas.count("fill"), 1);
+    assert_eq!(canvas.count("save"), canvas.count("restore"));
+}
+
+#[test]
+fn stretches_to_the_rect() {
+    let drawable = LottieDrawable::new(composition(&shape_layer(1, "{}", "")));
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(10.0, 0.0, 200.0, 50.0));
+    assert_eq!(
+        canvas.ops()[1..3],
+        ["translate 10.00 0.00", "scale 2.00 0.50"]
+    );
+}
+
+#[test]
+fn layer_transform_and_opacity() {
+    let transform = r#"{
+        "p": { "a": 1, "k": [
+            { "t": 0, "s": [0, 0], "e": [100, 0],
+              "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+            { "t": 10 }
+        ] },
+        "o": { "a": 0, "k": 50 }
+    }"#;
+    let canvas = draw(&shape_layer(1, transform, ""), 5.0);
+    assert!(canvas.contains("transform 1.00 0.00 0.00 1.00 50.00 0.00"));
+    // the layer opacity is multiplied into the fill
+    assert!(canvas.contains("set_fill_color 255 0 0 64"));
+}
+
+#[test]
+fn in_and_out_frames() {
+    let layer = r##"{ "ty": 1, "ind": 1, "ip": 2, "op": 5, "sc": "#00ff00", "sw": 10, "sh": 20,
+                     "ks": {} }"##;
+    assert_eq!(draw(layer, 1.0).count("fill_rect"), 0);
+    let canvas = draw(layer, 3.0);
+    assert!(canvas.contains("set_fill_color 0 255 0 255"));
+    assert!(canvas.contains("fill_rect 0.00 0.00 10.00 20.00"));
+    assert_eq!(draw(layer, 5.0).count("fill_rect"), 0);
+}
+
+#[test]
+fn parents() {
+    let parent = r#"{ "ty": 3, "ind": 1, "ip": 0, "op": 10,
+                      "ks": { "p": { "a": 0, "k": [10, 20] } } }"#;
+    let child = shape_layer(2, r#"{ "p": { "a": 0, "k": [1, 2] } }"#, r#", "parent": 1"#);
+    let canvas = draw(&format!("{}, {}", parent, child), 0.0);
+    assert!(canvas.contains("transform 1.00 0.00 0.00 1.00 11.00 22.00"));
+}
+
+#[test]
+fn track_mattes() {
+    let matte = shape_layer(1, "{}", r#", "td": 1"#);
+    for (tt, composite) in [
+        (1, "DestinationIn"),
+        (2, "DestinationOut"),
+        (3, "LumaIn"),
+        (4, "LumaOut"),
+    ]
+    .iter()
+    {
+        let matted = format!(
+            r##"{{ "ty": 1, "ind": 2, "ip": 0, "op": 10, "tt": {}, "sc": "#0000ff",
+                   "sw": 10, "sh": 10, "ks": {{}} }}"##,
+            tt
+        );
+        let canvas = draw(&format!("{}, {}", matte, matted), 0.0);
+        // the matte is only drawn onto the layer of the matted layer
+        let ops = canvas.ops();
+        let start = ops.iter().position(|op| op == "push_layer").unwrap();
+        assert!(ops[start + 1..].contains(&"push_layer".to_string()));
+        assert_eq!(canvas.count("fill_rect"), 1);
+        assert_eq!(canvas.count_exact("fill"), 1);
+        assert!(canvas.contains(&format!("pop_layer {} 1.00", composite)));
+        assert!(canvas.contains("pop_layer SourceOver 1.00"));
+        let fill_rect = ops
+            .iter()
+            .position(|op| op.starts_with("fill_rect"))
+            .unwrap();
+        let fill = ops.iter().position(|op| op == "fill").unwrap();
+        assert!(start < fill_rect && fill_rect < fill);
+    }
+}
+
+#[test]
+fn draws_precompositions_in_their_time() {
+    let json = r##"{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+        "assets": [ { "id": "comp", "layers": [
+            { "ty": 1, "ind": 1, "ip": 0, "op": 2, "sc": "#ffffff", "sw": 5, "sh": 5, "ks": {} }
+        ] } ],
+        "layers": [ { "ty": 0, "ind": 1, "refId": "comp", "ip": 0, "op": 10, "st": 4,
+                      "w": 5, "h": 5, "ks": {} } ] }"##;
+    let composition = LottieComposition::from_bytes(json.as_bytes()).unwrap();
+    let mut drawable = LottieDrawable::new(composition);
+    for (frame, count) in [(1.0, 0), (5.0, 1), (7.0, 0)].iter() {
+        drawable.set_frame(*frame);
+        let canvas = RecordingCanvas::default();
+        drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(canvas.count("fill_rect"), *count, "frame {}", frame);
+    }
+}
+
+/// Draws a precomposition layer of `layers` at `frame`, the layer given by
+/// `precomp` without its closing brace.
+fn draw_precomp(layers: &str, precomp: &str, frame: f64) -> RecordingCanvas {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+             "assets": [ {{ "id": "comp", "layers": [{}] }} ],
+             "layers": [ {{ "ty": 0, "ind": 1, "refId": "comp", "ip": 0, "op": 10 {} }} ] }}"#,
+        layers, precomp
+    );
+    let mut drawable = LottieDrawable::new(LottieComposition::from_bytes(json.as_bytes()).unwrap());
+    drawable.set_frame(frame);
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    canvas
+}
+
+#[test]
+fn stretches_and_clips_precompositions() {
+    let solid = r##"{ "ty": 1, "ind": 1, "ip": 0, "op": 2, "sc": "#ffffff", "sw": 10, "sh": 10,
+                     "ks": {} }"##;
+    let precomp = r#", "st": 1, "sr": 2, "w": 5, "h": 4, "ks": {}"#;
+    // the contents run at half the speed from the start frame
+    for (frame, count) in [(0.0, 0), (1.0, 1), (4.0, 1), (5.0, 0)].iter() {
+        let canvas = draw_precomp(solid, precomp, *frame);
+        assert_eq!(canvas.count("fill_rect"), *count, "frame {}", frame);
+    }
+
+    let canvas = draw_precomp(solid, precomp, 2.0);
+    let ops = canvas.ops();
+    let rect = ops
+        .iter()
+        .position(|op| op == "rect 0.00 0.00 5.00 4.00")
+        .unwrap();
+    let clip = ops.iter().position(|op| op == "clip").unwrap();
+    let fill_rect = ops
+        .iter()
+        .position(|op| op.starts_with("fill_rect"))
+        .unwrap();
+    assert!(rect < clip && clip < fill_rect);
+    assert_eq!(canvas.count("save"), canvas.count("restore"));
+
+    // without a size there is nothing to clip to
+    let canvas = draw_precomp(solid, r#", "ks": {}"#, 0.0);
+    assert!(!canvas.contains("clip"));
+}
+
+#[test]
+fn remaps_precomposition_time() {
+    let solid = r##"{ "ty": 1, "ind": 1, "ip": 0, "op": 1, "sc": "#ffffff", "sw": 10, "sh": 10,
+                     "ks": {} }"##;
+    // from 0.2 seconds back to the start, the start frame is left out
+    let precomp = r#", "st": 4, "ks": {}, "tm": { "a": 1, "k": [
+        { "t": 0, "s": [0.2], "e": [0], "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+        { "t": 10 }
+    ] }"#;
+    for (frame, count) in [(0.0, 0), (4.0, 0), (6.0, 1), (9.0, 1)].iter() {
+        let canvas = draw_precomp(solid, precomp, *frame);
+        assert_eq!(canvas.count("fill_rect"), *count, "frame {}", frame);
+    }
+}
+
+#[test]
+fn precompositions_inside_themselves_are_empty() {
+    let json = r##"{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+        "assets": [
+            { "id": "comp_0", "layers": [
+                { "ty": 0, "ind": 1, "refId": "comp_0", "ip": 0, "op": 10, "ks": {} },
+                { "ty": 0, "ind": 2, "refId": "comp_1", "ip": 0, "op": 10, "ks": {} },
+                { "ty": 1, "ind": 3, "ip": 0, "op": 10, "sc": "#ffffff", "sw": 10, "sh": 10,
+                  "ks": {} }
+            ] },
+            { "id": "comp_1", "layers": [
+                { "ty": 0, "ind": 1, "refId": "comp_0", "ip": 0, "op": 10, "ks": {} }
+            ] }
+        ],
+        "layers": [ { "ty": 0, "ind": 1, "refId": "comp_0", "ip": 0, "op": 10, "ks": {} },
+                    { "ty": 0, "ind": 2, "refId": "comp_1", "ip": 0, "op": 10, "ks": {} } ] }"##;
+    let composition = LottieComposition::from_bytes(json.as_bytes()).unwrap();
+    let drawable = LottieDrawable::new(composition.clone());
+    assert_eq!(
+        composition.get_warnings(),
+        [
+            "Precomposition comp_0 contains itself.",
+            "Precomposition comp_1 contains itself."
+        ]
+    );
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    // the solid of comp_0 is drawn once in each layer of the composition
+    assert_eq!(canvas.count("fill_rect"), 2);
+}
+
+#[test]
+fn parents_through_null_layers() {
+    // a hidden and transparent null still moves its children
+    let grandparent = r#"{ "ty": 3, "ind": 1, "ip": 0, "op": 1,
+                           "ks": { "p": { "a": 0, "k": [10, 20] }, "o": { "a": 0, "k": 0 } } }"#;
+    let parent = r#"{ "ty": 3, "ind": 2, "ip": 0, "op": 10, "parent": 1,
+                      "ks": { "p": { "a": 0, "k": [1, 1] }, "s": { "a": 0, "k": [200, 200] } } }"#;
+    let child = shape_layer(3, r#"{ "p": { "a": 0, "k": [1, 2] } }"#, r#", "parent": 2"#);
+    let canvas = draw(&format!("{}, {}, {}", grandparent, parent, child), 5.0);
+    assert!(canvas.contains("transform 2.00 0.00 0.00 2.00 13.00 25.00"));
+    assert_eq!(canvas.count_exact("fill"), 1);
+}
+
+#[test]
+fn parents_inside_precompositions() {
+    let layers = r##"{ "ty": 3, "ind": 1, "ip": 0, "op": 10,
+                       "ks": { "p": { "a": 0, "k": [3, 4] } } },
+                     { "ty": 1, "ind": 2, "ip": 0, "op": 10, "parent": 1, "sc": "#ffffff",
+                       "sw": 10, "sh": 10, "ks": {} }"##;
+    // the parent index points into the precomposition, not to its layer
+    let precomp = r#", "ks": { "p": { "a": 0, "k": [10, 10] } }"#;
+    let canvas = draw_precomp(layers, precomp, 0.0);
+    let ops = canvas.ops();
+    let outer = ops
+        .iter()
+        .position(|op| op == "transform 1.00 0.00 0.00 1.00 10.00 10.00")
+        .unwrap();
+    let inner = ops
+        .iter()
+        .position(|op| op == "transform 1.00 0.00 0.00 1.00 3.00 4.00")
+        .unwrap();
+    assert!(outer < inner);
+    assert_eq!(canvas.count("fill_rect"), 1);
+}
+
+#[test]
+fn strokes() {
+    let group = r#"{ "ty": "gr", "it": [
+        { "ty": "el", "p": { "a": 0, "k": [0, 0] }, "s": { "a": 0, "k": [10, 10] } },
+        { "ty": "st", "c": { "a": 0, "k": [0, 0, 1, 1] }, "o": { "a": 0, "k": 100 },
+          "w": { "a": 0, "k": 4 }, "lc": 2, "lj": 2,
+          "d": [ { "n": "d", "v": { "a": 0, "k": 0.5 } }, { "n": "g", "v": { "a": 0, "k": 0 } },
+                 { "n": "o", "v": { "a": 0, "k": 3 } } ] }
+    ] }"#;
+    let layer = format!(
+        r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}}, "shapes": [{}] }}"#,
+        group
+    );
+    let canvas = draw(&layer, 0.0);
+    assert!(canvas.contains("set_line_width 4.00"));
+    // dashes are at least 1 long and gaps at least 0.1
+    assert!(canvas.contains("set_line_dash 1.00 0.10"));
+    assert!(canvas.contains("set_line_dash_offset 3.00"));
+    assert!(canvas.contains("set_stroke_color 0 0 255 255"));
+    assert_eq!(canvas.count("bezier_curve_to"), 4);
+    assert_eq!(canvas.count("stroke"), 1);
+}
+
+#[test]
+fn gradient_fill() {
+    let group = r#"{ "ty": "gr", "it": [
+        { "ty": "rc", "p": { "a": 0, "k": [0, 0] }, "s": { "a": 0, "k": [10, 10] },
+          "r": { "a": 0, "k": 0 } },
+        { "ty": "gf", "t": 2, "o": { "a": 0, "k": 100 },
+          "s": { "a": 0, "k": [0, 0] }, "e": { "a": 0, "k": [5, 0] },
+          "g": { "p": 2, "k": { "a": 0, "k": [0, 1, 0, 0, 1, 0, 0, 1] } } }
+    ] }"#;
+    let layer = format!(
+        r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}}, "shapes": [{}] }}"#,
+        group
+    );
+    let canvas = draw(&layer, 0.0);
+    assert_eq!(canvas.count("set_fill_gradient"), 1);
+    assert_eq!(canvas.count("fill"), 1);
+}
+
+/// Returns a shape layer of a 10x10 square with the gradient fill `fill`.
+fn gradient_layer(fill: &str) -> String {
+    format!(
+        r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}}, "shapes": [ {{ "ty": "gr", "it": [
+            {{ "ty": "rc", "p": {{ "a": 0, "k": [0, 0] }}, "s": {{ "a": 0, "k": [10, 10] }},
+               "r": {{ "a": 0, "k": 0 }} }},
+            {{ "ty": "gf", "o": {{ "a": 0, "k": 100 }}, {} }}
+        ] }} ] }}"#,
+        fill
+    )
+}
+
+#[test]
+fn gamma_evaluator() {
+    let black = RgbaColor::new(0, 0, 0, 0);
+    let white = RgbaColor::new(255, 255, 255, 255);
+    // the middle of black and white in linear light is lighter than 128
+    assert_eq!(
+        GammaEvaluator::evaluate(0.5, black, white),
+        RgbaColor::new(188, 188, 188, 128)
+    );
+    assert_eq!(GammaEvaluator::evaluate(0.0, black, white), black);
+    assert_eq!(GammaEvaluator::evaluate(1.0, black, white), white);
+}
+
+#[test]
+fn interpolates_gradient_colors() {
+    let red = RgbaColor::new(255, 0, 0, 255);
+    let blue = RgbaColor::new(0, 0, 255, 255);
+    let start = GradientColor::new(vec![0.0, 1.0], vec![red, blue]);
+    let end = GradientColor::new(vec![0.0, 0.5], vec![blue, red]);
+    let middle = start.interpolate(&end, 0.5);
+    assert_eq!(middle.positions, vec![0.0, 0.75]);
+    assert_eq!(middle.colors, vec![RgbaColor::new(188, 0, 188, 255); 2]);
+
+    // gradients of different stops are sampled at all the stops
+    let end = GradientColor::new(vec![0.0, 0.5, 1.0], vec![red, red, red]);
+    let middle = start.interpolate(&end, 1.0);
+    assert_eq!(middle.positions, vec![0.0, 0.5, 1.0]);
+    assert_eq!(middle.colors, vec![red; 3]);
+    let middle = start.interpolate(&end, 0.0);
+    assert_eq!(middle.colors[1], RgbaColor::new(188, 0, 188, 255));
+}
+
+#[test]
+fn linear_gradients_with_opacity_stops() {
+    let fill = r#""t": 1, "s": { "a": 0, "k": [0, 0] }, "e": { "a": 0, "k": [10, 0] },
+        "g": { "p": 2, "k": { "a": 0, "k": [0, 1, 0, 0, 1, 0, 0, 1, 0, 1, 0.5, 0.5, 1, 0] } }"#;
+    let canvas = draw(&gradient_layer(fill), 0.0);
+    assert!(canvas.contains("set_fill_gradient linear 0.00 0.00 10.00 0.00"));
+    // the opacity stops add a stop between the colors, which are blended
+    // in linear light by more stops
+    assert_eq!(canvas.count("color_stop"), 3 + 2 * 7);
+    assert!(canvas.contains("color_stop 0.000 255 0 0 255"));
+    assert!(canvas.contains("color_stop 0.500 188 0 188 128"));
+    assert!(canvas.contains("color_stop 1.000 0 0 255 0"));
+    assert_eq!(canvas.count("fill"), 1);
+}
+
+#[test]
+fn radial_gradient_highlights() {
+    let fill = r#""t": 2, "s": { "a": 0, "k": [0, 0] }, "e": { "a": 0, "k": [10, 0] },
+        "h": { "a": 0, "k": 50 }, "a": { "a": 0, "k": 90 },
+        "g": { "p": 2, "k": { "a": 0, "k": [0, 1, 1, 1, 1, 1, 1, 1] } }"#;
+    let canvas = draw(&gradient_layer(fill), 0.0);
+    // the focal point is half of the radius away, a quarter turn from the
+    // end point
+    assert!(canvas.contains("set_fill_gradient radial 0.00 5.00 0.00 0.00 0.00 10.00"));
+    // stops of the same color need no stops between them
+    assert_eq!(canvas.count("color_stop"), 2);
+
+    // the focal point stays inside the circle
+    let fill = fill.replace(r#""k": 50"#, r#""k": 150"#);
+    let canvas = draw(&gradient_layer(&fill), 0.0);
+    assert!(canvas.contains("set_fill_gradient radial 0.00 9.90 0.00 0.00 0.00 10.00"));
+}
+
+#[test]
+fn animated_gradient_stops() {
+    let fill = r#""t": 1, "s": { "a": 0, "k": [0, 0] }, "e": { "a": 0, "k": [10, 0] },
+        "g": { "p": 2, "k": { "a": 1, "k": [
+            { "t": 0, "s": [0, 1, 0, 0, 1, 1, 0, 0], "e": [0, 0, 0, 1, 1, 0, 0, 1],
+              "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+            { "t": 10 }
+        ] } }"#;
+    let canvas = draw(&gradient_layer(fill), 5.0);
+    assert_eq!(canvas.count("color_stop"), 2);
+    assert!(canvas.contains("color_stop 0.000 188 0 188 255"));
+    assert!(canvas.contains("color_stop 1.000 188 0 188 255"));
+}
+
+#[test]
+fn group_paths() {
+    let group = content(
+        r#"{ "ty": "gr", "it": [
+            { "ty": "rc", "p": { "a": 0, "k": [0, 0] }, "s": { "a": 0, "k": [2, 2] },
+              "r": { "a": 0, "k": 0 } },
+            { "ty": "tr", "p": { "a": 0, "k": [10, 10] }, "s": { "a": 0, "k": [200, 200] } }
+        ] }"#,
+    );
+    let path = group.get_path(0.0).unwrap();
+    let bounds = path.bounding_box().unwrap();
+    assert_eq!((bounds.origin.x, bounds.origin.y), (8.0, 8.0));
+    assert_eq!((bounds.size.width, bounds.size.height), (4.0, 4.0));
+}
+
+#[test]
+fn rounded_rectangle() {
+    let rectangle = content(
+        r#"{ "ty": "rc", "p": { "a": 0, "k": [0, 0] }, "s": { "a": 0, "k": [20, 10] },
+             "r": { "a": 0, "k": 8 } }"#,
+    );
+    let path = rectangle.get_path(0.0).unwrap();
+    // the radius is at most half of the height
+    assert_eq!(
+        path.length().round(),
+        (20.0 + 2.0 * std::f64::consts::PI * 5.0).round()
+    );
+}
+
+#[test]
+fn polystars() {
+    let polygon = content(
+        r#"{ "ty": "sr", "sy": 2, "pt": { "a": 0, "k": 4 }, "p": { "a": 0, "k": [10, 10] },
+             "r": { "a": 0, "k": 0 }, "or": { "a": 0, "k": 10 }, "os": { "a": 0, "k": 0 } }"#,
+    );
+    let bounds = polygon.get_path(0.0).unwrap().bounding_box().unwrap();
+    assert!((bounds.origin.x - 0.0).abs() < 1e-9 && (bounds.origin.y - 0.0).abs() < 1e-9);
+    assert!((bounds.size.width - 20.0).abs() < 1e-9);
+
+    let star = content(
+        r#"{ "ty": "sr", "sy": 1, "pt": { "a": 0, "k": 5 }, "p": { "a": 0, "k": [0, 0] },
+             "r": { "a": 0, "k": 0 }, "ir": { "a": 0, "k": 4 }, "is": { "a": 0, "k": 0 },
+             "or": { "a": 0, "k": 10 }, "os": { "a": 0, "k": 0 } }"#,
+    );
+    let path = star.get_path(0.0).unwrap();
+    // a move, ten sides and a close
+    assert_eq!(path.len(), 12);
+    let bounds = path.bounding_box().unwrap();
+    assert!((bounds.origin.y + 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn transform_matrix() {
+    let layer = r#"{ "ty": 3, "ind": 1, "ks": {
+        "a": { "a": 0, "k": [5, 5] },
+        "p": { "a": 0, "k": [20, 10] },
+        "s": { "a": 0, "k": [200, 100] },
+        "r": { "a": 0, "k": 90 },
+        "o": { "a": 1, "k": [
+            { "t": 0, "s": [0], "e": [100], "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+            { "t": 10 }
+        ] }
+    } }"#;
+    let composition = composition(layer);
+    let transform = TransformKeyframeAnimation::new(&composition.get_layers()[0].transform);
+    let matrix = transform.get_matrix(0.0);
+    // the anchor point lands on the position
+    let point = matrix.transform_point(Point::new(5.0, 5.0));
+    assert!((point.x - 20.0).abs() < 1e-9 && (point.y - 10.0).abs() < 1e-9);
+    // scaled twice on X, then turned a quarter clockwise
+    let point = matrix.transform_point(Point::new(6.0, 5.0));
+    assert!((point.x - 20.0).abs() < 1e-9 && (point.y - 12.0).abs() < 1e-9);
+    assert_eq!(transform.get_opacity(2.5), 0.25);
+    assert_eq!(
+        TransformKeyframeAnimation::new(&AnimatableTransform::default()).get_matrix(3.0),
+        Transform::identity()
+    );
+}
+
+#[test]
+fn keyframes() {
+    let layer = r#"{ "ty": 3, "ind": 1, "ks": {
+        "r": { "a": 1, "k": [
+            { "t": 0, "s": [0], "e": [90], "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+            { "t": 4, "s": [90], "h": 1 },
+            { "t": 8, "s": [0] }
+        ] }
+    } }"#;
+    let composition = composition(layer);
+    let rotation = composition.get_layers()[0]
+        .transform
+        .rotation
+        .as_ref()
+        .unwrap()
+        .create_animation();
+    assert_eq!(rotation.value_at(-1.0), Some(0.0));
+    assert_eq!(rotation.value_at(2.0), Some(45.0));
+    // held until the last keyframe
+    assert_eq!(rotation.value_at(7.9), Some(90.0));
+    assert_eq!(rotation.value_at(4.0), Some(90.0));
+}
+
+const MASK_PATH: &str = r#"{ "a": 0, "k": { "c": true, "v": [[0, 0], [10, 0], [10, 10]],
+    "i": [[0, 0], [0, 0], [0, 0]], "o": [[0, 0], [0, 0], [0, 0]] } }"#;
+
+/// Draws a red box layer with `masks`.
+fn draw_masked(masks: &[&str]) -> RecordingCanvas {
+    let masks: Vec<String> = masks
+        .iter()
+        .map(|mask| format!(r#"{{ "pt": {}, {} }}"#, MASK_PATH, mask))
+        .collect();
+    let extra = format!(r#", "masksProperties": [{}]"#, masks.join(", "));
+    draw(&shape_layer(1, "{}", &extra), 0.0)
+}
+
+#[test]
+fn masks() {
+    let canvas = draw_masked(&[
+        r#""mode": "a", "o": { "a": 0, "k": 50 }"#,
+        r#""mode": "s""#,
+        r#""mode": "i""#,
+        r#""mode": "f""#,
+    ]);
+    let layers: Vec<String> = canvas
+        .ops()
+        .into_iter()
+        .filter(|op| op.starts_with("push_layer") || op.starts_with("pop_layer"))
+        .collect();
+    assert_eq!(
+        layers,
+        [
+            "push_layer",
+            "push_layer",
+            "push_layer",
+            "pop_layer SourceOver 0.50",
+            "push_layer",
+            "pop_layer DestinationOut 1.00",
+            "push_layer",
+            "pop_layer DestinationIn 1.00",
+            "push_layer",
+            "pop_layer Xor 1.00",
+            "pop_layer DestinationIn 1.00",
+            "pop_layer SourceOver 1.00",
+        ]
+    );
+    // an additive first mask starts from an empty layer
+    assert_eq!(canvas.count("paint"), 0);
+    // the box and the four masks
+    assert_eq!(canvas.count_exact("fill"), 5);
+}
+
+#[test]
+fn subtracted_and_inverted_masks() {
+    let canvas = draw_masked(&[r#""mode": "s""#]);
+    // a subtracted first mask removes from a full layer
+    assert_eq!(canvas.count("paint"), 1);
+    assert!(canvas.contains("pop_layer DestinationOut 1.00"));
+
+    let canvas = draw_masked(&[r#""mode": "a", "inv": true"#]);
+    let ops = canvas.ops();
+    let paint = ops.iter().position(|op| op == "paint").unwrap();
+    assert_eq!(ops[paint + 1], "push_layer");
+    assert_eq!(canvas.count("pop_layer DestinationOut"), 1);
+}
+
+#[test]
+fn disabled_masks() {
+    let canvas = draw_masked(&[r#""mode": "n""#]);
+    assert_eq!(canvas.count("push_layer"), 0);
+
+    let canvas = draw_masked(&[r#""mode": "n""#, r#""mode": "i""#]);
+    // the disabled mask does not count as the first one
+    assert_eq!(canvas.count("paint"), 1);
+    assert_eq!(canvas.count_exact("fill"), 2);
+}
+
+#[test]
+fn mask_expansion() {
+    let canvas = draw_masked(&[r#""mode": "a", "x": { "a": 0, "k": 3 }"#]);
+    assert!(canvas.contains("set_line_width 6.00"));
+    assert!(canvas.contains("stroke"));
+    assert_eq!(canvas.count("pop_layer DestinationOut"), 0);
+
+    let canvas = draw_masked(&[r#""mode": "a", "x": { "a": 0, "k": -2 }"#]);
+    assert!(canvas.contains("set_line_width 4.00"));
+    let ops = canvas.ops();
+    let stroke = ops.iter().position(|op| op == "stroke").unwrap();
+    assert_eq!(ops[stroke + 1], "pop_layer DestinationOut 1.00");
+}
+
+#[test]
+fn mean_calculator() {
+    let mut mean = MeanCalculator::new(3);
+    assert_eq!(mean.get_mean(), 0.0);
+    for value in [1.0, 2.0, 3.0, 6.0].iter() {
+        mean.add(*value);
+    }
+    // the first value left the window
+    assert_eq!(mean.get_count(), 3);
+    assert_eq!(mean.get_mean(), 11.0 / 3.0);
+    assert_eq!(mean.get_max(), 6.0);
+
+    mean.set_window(1);
+    assert_eq!(mean.get_mean(), 6.0);
+    mean.clear();
+    assert_eq!(mean.get_count(), 0);
+    assert_eq!(MeanCalculator::new(0).get_window(), 1);
+}
+
+#[test]
+fn performance_tracking() {
+    let masked = format!(
+        r#", "nm": "Masked", "masksProperties": [{{ "pt": {}, "mode": "a", "inv": true }},
+            {{ "pt": {}, "mode": "i", "x": {{ "a": 0, "k": -1 }} }}]"#,
+        MASK_PATH, MASK_PATH
+    );
+    let matte = shape_layer(2, "{}", r#", "nm": "Matte", "td": 1"#);
+    let matted = shape_layer(3, "{}", r#", "nm": "Matted", "tt": 1"#);
+    let layers = format!("{}, {}, {}", shape_layer(1, "{}", &masked), matte, matted);
+    let composition = composition(&layers);
+    let mut drawable = LottieDrawable::new(composition.clone());
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    // tracking is disabled by default
+    let tracker = composition.get_performance_tracker();
+    assert!(tracker.get_sorted_render_times().is_empty());
+
+    composition.set_performance_tracking_enabled(true);
+    assert!(drawable
+        .get_composition()
+        .get_performance_tracking_enabled());
+    tracker.set_window(4);
+    let canvas = RecordingCanvas::default();
+    for frame in 0..6 {
+        drawable.set_frame(frame as f64);
+        drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    }
+
+    let report = tracker.get_report(10);
+    let mut names: Vec<&str> = report
+        .layers
+        .iter()
+        .map(|layer| layer.name.as_str())
+        .collect();
+    names.sort_unstable();
+    // the matte is tracked with the layer it masks
+    assert_eq!(names, ["Masked", "Matted"]);
+    let layer = |name: &str| {
+        report
+            .layers
+            .iter()
+            .find(|layer| layer.name == name)
+            .unwrap()
+            .clone()
+    };
+    let masked = layer("Masked");
+    assert_eq!(masked.samples, 4);
+    assert!(masked.max_time >= masked.mean_time);
+    assert_eq!(
+        (masked.masks, masked.mattes, masked.offscreen_passes),
+        (2, 0, 6)
+    );
+    let matted = layer("Matted");
+    assert_eq!(
+        (matted.masks, matted.mattes, matted.offscreen_passes),
+        (0, 1, 2)
+    );
+    // the offscreen passes are the layers pushed onto the canvas
+    assert_eq!(
+        report.offscreen_pass_count as usize,
+        canvas.count("push_layer") / 6
+    );
+    assert_eq!((report.mask_count, report.matte_count), (2, 1));
+
+    tracker.clear_render_times();
+    assert!(tracker.get_sorted_render_times().is_empty());
+}
+
+#[test]
+fn performance_tracking_by_layer_path() {
+    let solid = |id: i32| {
+        format!(
+            r##"{{ "ty": 1, "nm": "Solid", "ind": {}, "ip": 0, "op": 10, "sc": "#ffffff",
+                "sw": 10, "sh": 10, "ks": {{}} }}"##,
+            id
+        )
+    };
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+             "assets": [ {{ "id": "comp", "layers": [{}, {}] }} ],
+             "layers": [ {{ "ty": 0, "nm": "Scene", "ind": 1, "refId": "comp", "ip": 0,
+                           "op": 10, "ks": {{}} }},
+                         {} ] }}"#,
+        solid(1),
+        solid(2),
+        solid(2)
+    );
+    let composition = LottieComposition::from_bytes(json.as_bytes()).unwrap();
+    composition.set_performance_tracking_enabled(true);
+    let drawable = LottieDrawable::new(composition.clone());
+    drawable.draw(&RecordingCanvas::default(), rect(0.0, 0.0, 100.0, 100.0));
+
+    let mut names: Vec<String> = composition
+        .get_performance_tracker()
+        .get_sorted_render_times()
+        .into_iter()
+        .map(|layer| layer.name)
+        .collect();
+    names.sort_unstable();
+    // the layers of the precomposition are told apart from each other and
+    // from the layer outside of it
+    assert_eq!(names, ["Scene", "Scene/Solid#1", "Scene/Solid#2", "Solid"]);
+}
+
+#[test]
+fn slowest_layers_report() {
+    let tracker = PerformanceTracker::default();
+    tracker.record_draw("Ignored", Duration::from_millis(1), 0, 0, 0);
+    assert!(tracker.get_sorted_render_times().is_empty());
+
+    tracker.set_enabled(true);
+    tracker.record_draw("Fast", Duration::from_millis(1), 0, 0, 0);
+    tracker.record_draw("Slow", Duration::from_millis(2), 1, 0, 3);
+    tracker.record_draw("Slow", Duration::from_millis(6), 1, 0, 3);
+    tracker.record_draw("Matted", Duration::from_millis(3), 0, 1, 2);
+
+    let report = tracker.get_report(2);
+    let names: Vec<&str> = report
+        .layers
+        .iter()
+        .map(|layer| layer.name.as_str())
+        .collect();
+    assert_eq!(names, ["Slow", "Matted"]);
+    assert_eq!(report.layers[0].mean_time, Duration::from_millis(4));
+    assert_eq!(report.layers[0].max_time, Duration::from_millis(6));
+    assert_eq!(report.layers[0].samples, 2);
+    // the counts are of all the layers, not only the slowest ones
+    assert_eq!(
+        (
+            report.mask_count,
+            report.matte_count,
+            report.offscreen_pass_count
+        ),
+        (1, 1, 5)
+    );
+    assert_eq!(
+        report.to_string(),
+        "1 masks, 1 mattes, 5 offscreen passes\n\
+         Slow: 4.000 ms mean, 6.000 ms max, 1 masks, 0 mattes, 3 offscreen passes\n\
+         Matted: 3.000 ms mean, 3.000 ms max, 0 masks, 1 mattes, 2 offscreen passes\n"
+    );
+}
+
+/// Draws a shape layer of `items` at frame 0.
+fn draw_shapes(items: &str) -> RecordingCanvas {
+    let layer = format!(
+        r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}}, "shapes": [{}] }}"#,
+        items
+    );
+    draw(&layer, 0.0)
+}
+
+/// Returns the JSON of an open path from `from` to `to`.
+fn line(from: (i32, i32), to: (i32, i32)) -> String {
+    format!(
+        r#"{{ "ty": "sh", "ks": {{ "a": 0, "k": {{ "c": false, "v": [[{}, {}], [{}, {}]],
+            "i": [[0, 0], [0, 0]], "o": [[0, 0], [0, 0]] }} }} }}"#,
+        from.0, from.1, to.0, to.1
+    )
+}
+
+/// Returns the JSON of a trim path.
+fn trim(start: f64, end: f64, offset: f64, kind: i32) -> String {
+    format!(
+        r#"{{ "ty": "tm", "s": {{ "a": 0, "k": {} }}, "e": {{ "a": 0, "k": {} }},
+            "o": {{ "a": 0, "k": {} }}, "m": {} }}"#,
+        start, end, offset, kind
+    )
+}
+
+const STROKE: &str = r#"{ "ty": "st", "c": { "a": 0, "k": [0, 0, 0, 1] }, "o": { "a": 0, "k": 100 },
+    "w": { "a": 0, "k": 1 }, "lc": 1, "lj": 1 }"#;
+
+/// Returns the moves and the lines drawn.
+fn outline(canvas: &RecordingCanvas) -> Vec<String> {
+    canvas
+        .ops()
+        .into_iter()
+        .filter(|op| op.starts_with("move_to") || op.starts_with("line_to"))
+        .collect()
+}
+
+#[test]
+fn trim_paths() {
+    let items = [
+        line((0, 0), (100, 0)),
+        trim(0.0, 50.0, 0.0, 1),
+        STROKE.into(),
+    ];
+    let canvas = draw_shapes(&items.join(", "));
+    assert_eq!(
+        outline(&canvas),
+        ["move_to 0.00 0.00", "line_to 50.00 0.00"]
+    );
+
+    // the offset is in degrees, a full turn is the whole path
+    let items = [
+        line((0, 0), (100, 0)),
+        trim(0.0, 50.0, 90.0, 1),
+        STROKE.into(),
+    ];
+    let canvas = draw_shapes(&items.join(", "));
+    assert_eq!(
+        outline(&canvas),
+        ["move_to 25.00 0.00", "line_to 75.00 0.00"]
+    );
+
+    // a trim listed after the stroke still cuts the path
+    let items = [
+        line((0, 0), (100, 0)),
+        STROKE.into(),
+        trim(50.0, 100.0, 0.0, 1),
+    ];
+    let canvas = draw_shapes(&items.join(", "));
+    assert_eq!(
+        outline(&canvas),
+        ["move_to 50.00 0.00", "line_to 100.00 0.00"]
+    );
+}
+
+#[test]
+fn simultaneous_and_individual_trims() {
+    let lines = [line((0, 0), (100, 0)), line((0, 10), (100, 10))].join(", ");
+    let canvas = draw_shapes(&format!(
+        "{}, {}, {}",
+        lines,
+        trim(0.0, 25.0, 0.0, 1),
+        STROKE
+    ));
+    assert_eq!(
+        outline(&canvas),
+        [
+            "move_to 0.00 0.00",
+            "line_to 25.00 0.00",
+            "move_to 0.00 10.00",
+            "line_to 25.00 10.00"
+        ]
+    );
+
+    let canvas = draw_shapes(&format!(
+        "{}, {}, {}",
+        lines,
+        trim(25.0, 75.0, 0.0, 2),
+        STROKE
+    ));
+    assert_eq!(
+        outline(&canvas),
+        [
+            "move_to 50.00 0.00",
+            "line_to 100.00 0.00",
+            "move_to 0.00 10.00",
+            "line_to 50.00 10.00"
+        ]
+    );
+}
+
+#[test]
+fn trims_of_outer_groups() {
+    let inner = format!(
+        r#"{{ "ty": "gr", "it": [{}, {}] }}"#,
+        line((0, 0), (100, 0)),
+        STROKE
+    );
+    let canvas = draw_shapes(&format!("{}, {}", inner, trim(0.0, 10.0, 0.0, 1)));
+    assert_eq!(
+        outline(&canvas),
+        ["move_to 0.00 0.00", "line_to 10.00 0.00"]
+    );
+
+    // the paths of the group are trimmed as one path
+    let inner = format!(
+        r#"{{ "ty": "gr", "it": [{}, {}, {}] }}"#,
+        line((0, 0), (100, 0)),
+        line((0, 10), (100, 10)),
+        STROKE
+    );
+    let canvas = draw_shapes(&format!("{}, {}", inner, trim(0.0, 25.0, 0.0, 2)));
+    assert_eq!(
+        outline(&canvas),
+        ["move_to 0.00 0.00", "line_to 50.00 0.00"]
+    );
+}
+
+#[test]
+fn repeaters() {
+    let repeater = r#"{ "ty": "rp", "c": { "a": 0, "k": 3 }, "o": { "a": 0, "k": 1 },
+        "tr": { "ty": "tr", "p": { "a": 0, "k": [10, 0] }, "s": { "a": 0, "k": [100, 100] },
+                "so": { "a": 0, "k": 100 }, "eo": { "a": 0, "k": 40 } } }"#;
+    let canvas = draw_shapes(&format!("{}, {}", RED_BOX, repeater));
+    assert_eq!(canvas.count_exact("fill"), 3);
+    // the last copy first, moved once more by the offset
+    let moves: Vec<String> = canvas
+        .ops()
+        .into_iter()
+        .filter(|op| op.starts_with("transform 1.00 0.00 0.00 1.00") && !op.ends_with(" 0.00 0.00"))
+        .collect();
+    assert_eq!(
+        moves,
+        [
+            "transform 1.00 0.00 0.00 1.00 30.00 0.00",
+            "transform 1.00 0.00 0.00 1.00 20.00 0.00",
+            "transform 1.00 0.00 0.00 1.00 10.00 0.00"
+        ]
+    );
+    // the opacity goes from the start opacity to the end opacity
+    assert!(canvas.contains("set_fill_color 255 0 0 128"));
+    assert!(canvas.contains("set_fill_color 255 0 0 77"));
+
+    let content = content(&format!(
+        r#"{{ "ty": "gr", "it": [{}, {}] }}"#,
+        line((0, 0), (10, 0)),
+        repeater
+    ));
+    let path = content.get_path(0.0).unwrap();
+    assert_eq!(
+        path.to_string(),
+        "M 30 0 L 40 0 M 20 0 L 30 0 M 10 0 L 20 0"
+    );
+}
+
+#[test]
+fn repeater_rotation() {
+    let repeater = r#"{ "ty": "rp", "c": { "a": 0, "k": 2 }, "o": { "a": 0, "k": 0 },
+        "tr": { "ty": "tr", "a": { "a": 0, "k": [10, 0] }, "r": { "a": 0, "k": 90 },
+                "s": { "a": 0, "k": [50, 50] } } }"#;
+    let content = content(&format!(
+        r#"{{ "ty": "gr", "it": [{}, {}] }}"#,
+        line((0, 0), (20, 0)),
+        repeater
+    ));
+    let path = content.get_path(0.0).unwrap();
+    let bounds = path.bounding_box().unwrap();
+    // the second copy is turned around the anchor point and scaled
+    assert!((bounds.origin.y - -5.0).abs() < 1e-9, "{}", path);
+    assert!((bounds.size.width - 20.0).abs() < 1e-9, "{}", path);
+}
+
+#[test]
+fn merge_paths() {
+    let square = |x: i32| {
+        format!(
+            r#"{{ "ty": "rc", "p": {{ "a": 0, "k": [{}, 5] }}, "s": {{ "a": 0, "k": [10, 10] }},
+                "r": {{ "a": 0, "k": 0 }} }}"#,
+            x
+        )
+    };
+    let fill = r#"{ "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] }, "o": { "a": 0, "k": 100 } }"#;
+    let draw_merged = |mode: i32, enable: bool| {
+        let layer = format!(
+            r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {{}},
+                "shapes": [{}, {}, {{ "ty": "mm", "mm": {} }}, {}] }}"#,
+            square(5),
+            square(10),
+            mode,
+            fill
+        );
+        let composition = composition(&layer);
+        assert_eq!(composition.get_warnings().len(), 1);
+        let mut drawable = LottieDrawable::new(composition);
+        assert!(!drawable.get_enable_merge_paths());
+        drawable.set_enable_merge_paths(enable);
+        let canvas = RecordingCanvas::default();
+        drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+        canvas
+    };
+    let merged = |mode: i32| draw_merged(mode, true);
+
+    let moves = |canvas: &RecordingCanvas| canvas.count("move_to");
+    // the paths are drawn unmerged unless merge paths are enabled
+    assert_eq!(moves(&draw_merged(2, false)), 2);
+    assert_eq!(moves(&merged(1)), 2);
+    assert_eq!(moves(&merged(2)), 1);
+    assert_eq!(moves(&merged(5)), 2);
+
+    let bounds = |canvas: &RecordingCanvas| {
+        let points: Vec<(f64, f64)> = outline(canvas)
+            .iter()
+            .map(|op| {
+                let values: Vec<f64> = op.split(' ').skip(1).map(|v| v.parse().unwrap()).collect();
+                (values[0], values[1])
+            })
+            .collect();
+        let xs = points.iter().map(|p| p.0);
+        (
+            xs.clone().fold(f64::INFINITY, f64::min),
+            xs.fold(f64::NEG_INFINITY, f64::max),
+        )
+    };
+    assert_eq!(bounds(&merged(2)), (0.0, 15.0));
+    assert_eq!(bounds(&merged(4)), (5.0, 10.0));
+    // the square listed last minus the squares before it
+    assert_eq!(bounds(&merged(3)), (10.0, 15.0));
+    assert_eq!(moves(&merged(4)), 1);
+}
+
+#[test]
+fn merge_paths_follow_animated_paths() {
+    let layer = r#"{ "ty": 4, "ind": 1, "ip": 0, "op": 20, "ks": {}, "shapes": [
+        { "ty": "rc", "s": { "a": 0, "k": [10, 10] }, "r": { "a": 0, "k": 0 },
+          "p": { "a": 1, "k": [
+              { "t": 0, "s": [10, 5], "e": [40, 5],
+                "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+              { "t": 10 }
+          ] } },
+        { "ty": "rc", "p": { "a": 0, "k": [10, 5] }, "s": { "a": 0, "k": [10, 10] },
+          "r": { "a": 0, "k": 0 } },
+        { "ty": "mm", "mm": 2 },
+        { "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] }, "o": { "a": 0, "k": 100 } }] }"#;
+    let mut drawable = LottieDrawable::new(composition(layer));
+    drawable.set_enable_merge_paths(true);
+    let draw = |drawable: &LottieDrawable| {
+        let canvas = RecordingCanvas::default();
+        drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+        canvas.count("move_to")
+    };
+    // the squares overlap into one path, then apart into two
+    assert_eq!(draw(&drawable), 1);
+    assert_eq!(draw(&drawable), 1);
+    drawable.set_frame(10.0);
+    assert_eq!(draw(&drawable), 2);
+}
+
+fn svg_path(text: &str) -> Path {
+    Path::from_str(text).unwrap()
+}
+
+#[test]
+fn trims() {
+    let line = svg_path("M 0 0 H 100");
+    assert_eq!(
+        DashPath::trim(&line, 0.25, 0.5, 0.0).to_string(),
+        "M 25 0 L 50 0"
+    );
+    // the start and the end may be swapped
+    assert_eq!(
+        DashPath::trim(&line, 0.5, 0.25, 0.0).to_string(),
+        "M 25 0 L 50 0"
+    );
+    assert_eq!(DashPath::trim(&line, 0.0, 1.0, 0.0), line);
+    assert!(DashPath::trim(&line, 0.3, 0.3, 0.0).is_empty());
+    // an offset past the end goes on from the start of the path
+    assert_eq!(
+        DashPath::trim(&line, 0.5, 0.75, 0.5).to_string(),
+        "M 0 0 L 25 0"
+    );
+    assert_eq!(
+        DashPath::trim(&line, 0.5, 0.75, 0.375).to_string(),
+        "M 87.5 0 L 100 0 M 0 0 L 12.5 0"
+    );
+}
+
+#[test]
+fn trims_closed_paths_in_one_piece() {
+    let square = svg_path("M 0 0 H 10 V 10 H 0 Z");
+    assert_eq!(
+        DashPath::trim(&square, 0.0, 0.5, -0.125).to_string(),
+        "M 0 5 L 0 0 L 10 0 L 10 5"
+    );
+}
+
+#[test]
+fn trims_together() {
+    let paths = [svg_path("M 0 0 H 10"), svg_path("M 0 10 H 30")];
+    let trimmed = DashPath::trim_together(&paths, 0.0, 0.5, 0.0);
+    assert_eq!(trimmed[0].to_string(), "M 0 0 L 10 0");
+    assert_eq!(trimmed[1].to_string(), "M 0 10 L 10 10");
+
+    let trimmed = DashPath::trim_together(&paths, 0.5, 1.0, 0.0);
+    assert!(trimmed[0].is_empty());
+    assert_eq!(trimmed[1].to_string(), "M 10 10 L 30 10");
+}
+
+#[test]
+fn dashes() {
+    let line = svg_path("M 0 0 H 25");
+    assert_eq!(
+        DashPath::dash(&line, &[5.0, 5.0], 0.0).to_string(),
+        "M 0 0 L 5 0 M 10 0 L 15 0 M 20 0 L 25 0"
+    );
+    assert_eq!(
+        DashPath::dash(&line, &[5.0], 2.0).to_string(),
+        "M 0 0 L 3 0 M 8 0 L 13 0 M 18 0 L 23 0"
+    );
+    assert_eq!(DashPath::dash(&line, &[0.0, 0.0], 0.0), line);
+}
+
+/// A 50x50 square glyph of "A", 60 wide, for a font size of 100.
+const GLYPHS: &str = r#""chars": [
+    { "ch": "A", "size": 100, "w": 60, "style": "Bold", "fFamily": "Sans",
+      "data": { "shapes": [ { "ty": "gr", "it": [
+          { "ty": "rc", "p": { "a": 0, "k": [25, -25] }, "s": { "a": 0, "k": [50, 50] },
+            "r": { "a": 0, "k": 0 } },
+          { "ty": "tr", "o": { "a": 0, "k": 100 } }
+      ] } ] } }
+],"#;
+
+/// Returns a composition with a text layer of `documents`, keyframes of
+/// documents with font "Sans-Bold", and `glyphs`.
+fn text_composition(glyphs: &str, documents: &str, animators: &str) -> LottieComposition {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+              "fonts": {{ "list": [ {{ "fFamily": "Sans", "fName": "Sans-Bold",
+                                      "fStyle": "Bold", "ascent": 72 }} ] }}, {}
+              "layers": [ {{ "ty": 5, "ind": 1, "ip": 0, "op": 10, "ks": {{}},
+                             "t": {{ "d": {{ "k": [{}] }}, "a": [{}] }} }} ] }}"#,
+        glyphs, documents, animators
+    );
+    LottieComposition::from_bytes(json.as_bytes()).unwrap()
+}
+
+/// Draws a text layer of `documents`, keyframes of documents with font
+/// "Sans-Bold", with `glyphs` and `delegates`.
+fn draw_text(
+    glyphs: &str,
+    documents: &str,
+    animators: &str,
+    delegates: LottieDelegates,
+    frame: f64,
+) -> RecordingCanvas {
+    let mut drawable = LottieDrawable::new(text_composition(glyphs, documents, animators));
+    drawable.set_delegates(delegates);
+    drawable.set_frame(frame);
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    canvas
+}
+
+fn document(text: &str, extra: &str) -> String {
+    format!(
+        r#"{{ "t": 0, "s": {{ "t": "{}", "f": "Sans-Bold", "s": 50, "fc": [1, 0, 0] {} }} }}"#,
+        text, extra
+    )
+}
+
+/// Returns the starts of the paths drawn and the texts filled.
+fn text_starts(canvas: &RecordingCanvas) -> Vec<String> {
+    canvas
+        .ops()
+        .into_iter()
+        .filter(|op| op.starts_with("move_to") || op.starts_with("fill_text"))
+        .collect()
+}
+
+#[test]
+fn text_glyphs() {
+    // 60 wide at half the size, with a tenth of an em between characters
+    let doc = document(r"AA\rA", r#", "tr": 100, "lh": 60"#);
+    let canvas = draw_text(GLYPHS, &doc, "", LottieDelegates::default(), 0.0);
+    assert_eq!(
+        text_starts(&canvas),
+        [
+            "move_to 25.00 -25.00",
+            "move_to 60.00 -25.00",
+            "move_to 25.00 35.00"
+        ]
+    );
+    assert_eq!(canvas.count("set_fill_color 255 0 0 255"), 3);
+    assert_eq!(canvas.count("fill"), 3);
+    assert_eq!(canvas.count("stroke"), 0);
+
+    // characters without glyphs are left out
+    let canvas = draw_text(
+        GLYPHS,
+        &document("BA", ""),
+        "",
+        LottieDelegates::default(),
+        0.0,
+    );
+    assert_eq!(text_starts(&canvas), ["move_to 25.00 -25.00"]);
+}
+
+#[test]
+fn text_justification() {
+    for (justification, start) in [(0, "25.00"), (1, "-5.00"), (2, "10.00")].iter() {
+        let doc = document("A", &format!(r#", "j": {}, "tr": 100"#, justification));
+        let canvas = draw_text(GLYPHS, &doc, "", LottieDelegates::default(), 0.0);
+        assert_eq!(text_starts(&canvas), [format!("move_to {} -25.00", start)]);
+    }
+}
+
+#[test]
+fn text_strokes_and_animators() {
+    let doc = document("A", r#", "sc": [0, 0, 1], "sw": 2, "of": true, "ls": 10"#);
+    let canvas = draw_text(GLYPHS, &doc, "", LottieDelegates::default(), 0.0);
+    let ops = canvas.ops();
+    let fill = ops.iter().position(|op| op == "fill").unwrap();
+    let stroke = ops.iter().position(|op| op == "stroke").unwrap();
+    assert!(fill < stroke);
+    assert_eq!(canvas.count("set_stroke_color 0 0 255 255"), 1);
+    assert_eq!(canvas.count("set_line_width 2.00"), 1);
+    // the baseline shift moves the text up
+    assert_eq!(canvas.count("translate 0.00 -10.00"), 1);
+
+    let doc = document("A", r#", "sc": [0, 0, 1], "sw": 2"#);
+    let animator = r#"{ "a": { "fc": { "a": 0, "k": [0, 1, 0] }, "sw": { "a": 0, "k": 4 },
+                            "t": { "a": 0, "k": 200 } } }"#;
+    let canvas = draw_text(GLYPHS, &doc, animator, LottieDelegates::default(), 0.0);
+    let ops = canvas.ops();
+    let fill = ops.iter().position(|op| op == "fill").unwrap();
+    let stroke = ops.iter().position(|op| op == "stroke").unwrap();
+    assert!(stroke < fill);
+    assert_eq!(canvas.count("set_fill_color 0 255 0 255"), 1);
+    assert_eq!(canvas.count("set_line_width 4.00"), 1);
+}
+
+#[test]
+fn animated_text_documents() {
+    let documents = format!(
+        r#"{}, {{ "t": 5, "s": {{ "t": "AA", "f": "Sans-Bold", "s": 100 }} }}"#,
+        document("A", "")
+    );
+    let canvas = draw_text(GLYPHS, &documents, "", LottieDelegates::default(), 4.0);
+    assert_eq!(text_starts(&canvas), ["move_to 25.00 -25.00"]);
+    let canvas = draw_text(GLYPHS, &documents, "", LottieDelegates::default(), 5.0);
+    assert_eq!(
+        text_starts(&canvas),
+        ["move_to 50.00 -50.00", "move_to 110.00 -50.00"]
+    );
+}
+
+#[test]
+fn text_with_fonts() {
+    // the canvas measures 10 units per character
+    let doc = document(r"Hi\nyou", r#", "j": 2, "lh": 60"#);
+    let canvas = draw_text("", &doc, "", LottieDelegates::default(), 0.0);
+    assert_eq!(canvas.count("set_font Sans 50.00"), 1);
+    assert_eq!(
+        text_starts(&canvas),
+        [
+            "fill_text H -10.00 0.00",
+            "fill_text i 0.00 0.00",
+            "fill_text y -15.00 60.00",
+            "fill_text o -5.00 60.00",
+            "fill_text u 5.00 60.00"
+        ]
+    );
+    assert_eq!(canvas.count("stroke_text"), 0);
+}
+
+#[test]
+fn text_delegates() {
+    let delegates = LottieDelegates {
+        text: Some(Rc::new(|text: &str| format!("{}!", text))),
+        ..Default::default()
+    };
+    // replaced text is drawn with fonts, the glyphs may miss its characters
+    let canvas = draw_text(GLYPHS, &document("A", ""), "", delegates, 0.0);
+    assert_eq!(
+        text_starts(&canvas),
+        ["fill_text A 0.00 0.00", "fill_text ! 10.00 0.00"]
+    );
+
+    struct Fonts;
+    impl LottieFontProvider for Fonts {
+        fn get_family(&self, style: &LottieFontStyle) -> String {
+            format!("{} {}", style.get_font_family(), style.get_style())
+        }
+
+        fn get_advance(&self, style: &LottieFontStyle, character: char, size: f64) -> Option<f64> {
+            Some(if character == 'W' { size } else { size / 2.0 })
+        }
+    }
+    let delegates = LottieDelegates {
+        fonts: Some(Rc::new(Fonts)),
+        ..Default::default()
+    };
+    let canvas = draw_text("", &document("Wo w", ""), "", delegates, 0.0);
+    assert_eq!(canvas.count("set_font Sans Bold 50.00"), 1);
+    assert_eq!(
+        text_starts(&canvas),
+        [
+            "fill_text W 0.00 0.00",
+            "fill_text o 50.00 0.00",
+            "fill_text w 100.00 0.00"
+        ]
+    );
+}
+
+#[test]
+fn use_text_glyphs() {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100, {} "layers": [] }}"#,
+        GLYPHS
+    );
+    let mut drawable = LottieDrawable::new(LottieComposition::from_bytes(json.as_bytes()).unwrap());
+    assert!(drawable.get_use_text_glyphs());
+    drawable.set_delegates(LottieDelegates {
+        text: Some(Rc::new(|text: &str| text.to_uppercase())),
+        ..Default::default()
+    });
+    assert!(!drawable.get_use_text_glyphs());
+    assert!(!LottieDrawable::new(composition("")).get_use_text_glyphs());
+
+    assert!(LottieFonts::new()
+        .add("Sans", "Bold", b"not a font")
+        .is_err());
+}
+
+/// A layer "Box" with a filled rectangle and a layer "Line" with a stroked
+/// ellipse, each in a group.
+const NAMED_LAYERS: &str = r#"
+    { "ty": 4, "nm": "Box", "ind": 1, "ip": 0, "op": 10, "ks": {}, "shapes": [
+        { "ty": "gr", "nm": "Group", "it": [
+            { "ty": "rc", "nm": "Rect", "p": { "a": 0, "k": [50, 50] },
+              "s": { "a": 0, "k": [20, 10] }, "r": { "a": 0, "k": 0 } },
+            { "ty": "fl", "nm": "Fill", "c": { "a": 0, "k": [1, 0, 0, 1] },
+              "o": { "a": 0, "k": 50 } },
+            { "ty": "tr", "o": { "a": 0, "k": 100 } }
+        ] }
+    ] },
+    { "ty": 4, "nm": "Line", "ind": 2, "ip": 0, "op": 10, "ks": {}, "shapes": [
+        { "ty": "gr", "nm": "Group", "it": [
+            { "ty": "el", "nm": "Circle", "p": { "a": 0, "k": [0, 0] },
+              "s": { "a": 0, "k": [10, 10] } },
+            { "ty": "st", "nm": "Stroke", "c": { "a": 0, "k": [0, 0, 1, 1] },
+              "o": { "a": 0, "k": 100 }, "w": { "a": 0, "k": 4 } },
+            { "ty": "tr", "o": { "a": 0, "k": 100 } }
+        ] }
+    ] }"#;
+
+/// Returns the key paths `keys` resolves to in `NAMED_LAYERS`, as strings.
+fn resolve(keys: &[&str]) -> Vec<String> {
+    let drawable = LottieDrawable::new(composition(NAMED_LAYERS));
+    drawable
+        .resolve_key_path(&KeyPath::from_keys(keys))
+        .iter()
+        .map(|key_path| key_path.get_keys().join("/"))
+        .collect()
+}
+
+/// Draws `NAMED_LAYERS` at `frame` with `values`.
+fn draw_with_values(values: Vec<ValueDelegate>, frame: f64) -> RecordingCanvas {
+    let mut drawable = LottieDrawable::new(composition(NAMED_LAYERS));
+    drawable.set_delegates(LottieDelegates {
+        values,
+        ..Default::default()
+    });
+    drawable.set_frame(frame);
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    canvas
+}
+
+#[test]
+fn key_path_matching() {
+    let key_path = KeyPath::from_keys(&["*", "Group", "**"]);
+    assert!(key_path.matches("Box", 0));
+    assert!(!key_path.matches("Box", 1));
+    assert!(key_path.matches("Group", 1));
+    assert!(key_path.fully_resolves_to("Group", 1));
+    assert!(key_path.fully_resolves_to("Fill", 2));
+    assert!(key_path.propagate_to_children("Group", 1));
+    assert!(!KeyPath::from_keys(&["Box"]).propagate_to_children("Box", 0));
+    assert_eq!(key_path.to_string(), "KeyPath[*, Group, **]");
+
+    // a globstar followed by the key skips to the key after it
+    let key_path = KeyPath::from_keys(&["**", "Group", "Fill"]);
+    assert_eq!(key_path.increment_depth_by("Group", 0), 2);
+    assert_eq!(key_path.increment_depth_by("Box", 0), 0);
+}
+
+#[test]
+fn resolves_key_paths() {
+    assert_eq!(resolve(&["Box"]), ["Box"]);
+    assert_eq!(resolve(&["*"]), ["Box", "Line"]);
+    assert_eq!(resolve(&["*", "Group", "Fill"]), ["Box/Group/Fill"]);
+    assert_eq!(resolve(&["**", "Stroke"]), ["Line/Group/Stroke"]);
+    assert_eq!(resolve(&["**", "Group"]), ["Box/Group", "Line/Group"]);
+    assert_eq!(
+        resolve(&["Box", "**"]),
+        ["Box", "Box/Group", "Box/Group/Rect", "Box/Group/Fill"]
+    );
+    assert_eq!(resolve(&["**"]).len(), 8);
+    assert!(resolve(&["Box", "Fill"]).is_empty());
+    assert!(resolve(&["Missing", "**"]).is_empty());
+}
+
+#[test]
+fn constant_value_callbacks() {
+    let blue = RgbaColor::new(0, 0, 255, 255);
+    let values = vec![
+        ValueDelegate::new(
+            KeyPath::from_keys(&["**", "Fill"]),
+            LottieProperty::Color(LottieValueCallback::new(blue)),
+        ),
+        ValueDelegate::new(
+            KeyPath::from_keys(&["Box", "Group", "Fill"]),
+            LottieProperty::Opacity(LottieValueCallback::new(100)),
+        ),
+        ValueDelegate::new(
+            KeyPath::from_keys(&["Line", "**"]),
+            LottieProperty::StrokeColor(LottieValueCallback::new(blue)),
+        ),
+        ValueDelegate::new(
+            KeyPath::from_keys(&["*", "Group"]),
+            LottieProperty::TransformRotation(LottieValueCallback::new(90.0)),
+        ),
+    ];
+    let canvas = draw_with_values(values, 0.0);
+    assert!(canvas.contains("set_fill_color 0 0 255 255"));
+    assert!(!canvas.contains("set_fill_color 255 0 0 128"));
+    assert!(canvas.contains("set_stroke_color 0 0 255 255"));
+    assert_eq!(canvas.count("transform 0.00 1.00 -1.00 0.00 0.00 0.00"), 2);
+}
+
+#[test]
+fn per_frame_value_callbacks() {
+    let values = vec![
+        ValueDelegate::new(
+            KeyPath::from_keys(&["Box"]),
+            LottieProperty::TransformPosition(LottieValueCallback::from_fn(|info| {
+                Some(Point::new(info.frame * 2.0, 0.0))
+            })),
+        ),
+        // the position is missing from the transform of the layer
+        ValueDelegate::new(
+            KeyPath::from_keys(&["**", "Stroke"]),
+            LottieProperty::StrokeWidth(LottieRelativeDoubleValueCallback::new(2.0).into()),
+        ),
+        ValueDelegate::new(
+            KeyPath::from_keys(&["Box", "Group", "Rect"]),
+            LottieProperty::RectangleSize(LottieValueCallback::from_fn(
+                |info: &LottieFrameInfo<Point<f64>>| {
+                    info.value.map(|size| Point::new(size.x * 2.0, size.y))
+                },
+            )),
+        ),
+    ];
+    let canvas = draw_with_values(values, 5.0);
+    assert!(canvas.contains("transform 1.00 0.00 0.00 1.00 10.00 0.00"));
+    assert!(canvas.contains("set_line_width 6.00"));
+    assert!(canvas.contains("move_to 70.00 45.00"));
+}
+
+#[test]
+fn lottie_draws_with_delegates() {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100, "layers": [{}] }}"#,
+        NAMED_LAYERS
+    );
+    let fill = |color| LottieDelegates {
+        values: vec![ValueDelegate::new(
+            KeyPath::from_keys(&["**", "Fill"]),
+            LottieProperty::Color(LottieValueCallback::new(color)),
+        )],
+        ..Default::default()
+    };
+    let draw = |lottie: &Lottie| {
+        let canvas = RecordingCanvas::default();
+        lottie.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0)).unwrap();
+        canvas
+    };
+
+    let lottie = Lottie::new();
+    assert!(lottie
+        .draw(&RecordingCanvas::default(), rect(0.0, 0.0, 100.0, 100.0))
+        .is_err());
+    lottie
+        .memory(json.into())
+        .delegates(fill(RgbaColor::new(0, 0, 255, 255)));
+    assert!(draw(&lottie).contains("set_fill_color 0 0 255 128"));
+
+    // the delegates replace the ones of the drawn composition
+    lottie.delegates(fill(RgbaColor::new(0, 255, 0, 255)));
+    let canvas = draw(&lottie);
+    assert!(canvas.contains("set_fill_color 0 255 0 128"));
+    assert!(!canvas.contains("set_fill_color 0 0 255 128"));
+}
+
+#[test]
+fn lottie_draws_with_options() {
+    let json = r#"{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100, "layers": [
+        { "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {}, "shapes": [
+            { "ty": "rc", "p": { "a": 0, "k": [10, 5] }, "s": { "a": 0, "k": [10, 10] },
+              "r": { "a": 0, "k": 0 } },
+            { "ty": "rc", "p": { "a": 0, "k": [15, 5] }, "s": { "a": 0, "k": [10, 10] },
+              "r": { "a": 0, "k": 0 } },
+            { "ty": "mm", "mm": 2 },
+            { "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] }, "o": { "a": 0, "k": 100 } }] }] }"#;
+    let moves = |lottie: &Lottie| {
+        let canvas = RecordingCanvas::default();
+        lottie.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0)).unwrap();
+        canvas.count("move_to")
+    };
+
+    let lottie = Lottie::new();
+    lottie.memory(json.into()).options(LottieOptions {
+        enable_merge_paths: true,
+    });
+    assert_eq!(moves(&lottie), 1);
+
+    // the options apply to the drawn composition
+    lottie.options(LottieOptions::default());
+    assert_eq!(moves(&lottie), 2);
+}
+
+#[test]
+fn value_delegates_are_replaced() {
+    let mut drawable = LottieDrawable::new(composition(NAMED_LAYERS));
+    drawable.set_delegates(LottieDelegates {
+        values: vec![ValueDelegate::new(
+            KeyPath::from_keys(&["**"]),
+            LottieProperty::Opacity(LottieValueCallback::new(100)),
+        )],
+        ..Default::default()
+    });
+    drawable.set_delegates(LottieDelegates::default());
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    assert!(canvas.contains("set_fill_color 255 0 0 128"));
+}
+
+#[test]
+fn text_value_callbacks() {
+    let delegates = LottieDelegates {
+        values: vec![
+            ValueDelegate::new(
+                KeyPath::from_keys(&["**"]),
+                LottieProperty::Text(LottieValueCallback::from_fn(
+                    |info: &LottieFrameInfo<String>| {
+                        info.value.as_ref().map(|text| text.to_lowercase())
+                    },
+                )),
+            ),
+            ValueDelegate::new(
+                KeyPath::from_keys(&["**"]),
+                LottieProperty::Color(LottieValueCallback::new(RgbaColor::new(0, 255, 0, 255))),
+            ),
+        ],
+        ..Default::default()
+    };
+    // the new text is drawn with fonts
+    let canvas = draw_text(GLYPHS, &document("AB", ""), "", delegates, 0.0);
+    assert_eq!(
+        text_starts(&canvas),
+        ["fill_text a 0.00 0.00", "fill_text b 10.00 0.00"]
+    );
+    assert!(canvas.contains("set_fill_color 0 255 0 255"));
+}
+
+/// A 2x1 PNG of an opaque red pixel and a half transparent blue pixel.
+const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAIAAAABCAYAAAD0In+KAAAAFElEQVR4AQEJAPb/AP8AAP8AAP+AD3oDfkpucAwAAAAASUVORK5CYII=";
+
+/// Returns the JSON of a composition with an image layer at half opacity,
+/// showing the 4x2 image asset `file_name` in `dir_name`.
+fn image_json(dir_name: &str, file_name: &str) -> String {
+    format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 100,
+              "assets": [ {{ "id": "image_0", "w": 4, "h": 2, "u": "{}", "p": "{}" }} ],
+              "layers": [ {{ "ty": 2, "ind": 1, "refId": "image_0", "ip": 0, "op": 10,
+                             "ks": {{ "o": {{ "a": 0, "k": 50 }} }} }} ] }}"#,
+        dir_name, file_name
+    )
+}
+
+fn image_composition(dir_name: &str, file_name: &str) -> LottieComposition {
+    LottieComposition::from_bytes(image_json(dir_name, file_name).as_bytes()).unwrap()
+}
+
+fn draw_composition(composition: LottieComposition) -> RecordingCanvas {
+    let drawable = LottieDrawable::new(composition);
+    let canvas = RecordingCanvas::default();
+    drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+    canvas
+}
+
+#[test]
+fn base64_data_uris() {
+    assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+    assert_eq!(decode_base64("aGVs\nbG8").unwrap(), b"hello");
+    assert_eq!(decode_base64("-_8=").unwrap(), [0xfb, 0xff]);
+    assert!(decode_base64("a*b").is_none());
+    assert_eq!(from_data_uri("data:text/plain;base64,aGk=").unwrap(), b"hi");
+    assert!(from_data_uri("images/img_0.png").is_none());
+    assert!(from_data_uri("data:text/plain,hi").is_none());
+}
+
+#[test]
+fn embedded_images() {
+    let mut composition = image_composition("", &format!("data:image/png;base64,{}", PNG));
+    // images are only drawn once loaded
+    assert_eq!(draw_composition(composition.clone()).count("draw_image"), 0);
+
+    composition.load_images(&LottieImageProviderFactory::new());
+    assert!(composition.get_warnings().is_empty());
+    let image = composition.images()["image_0"].get_loaded_image().unwrap();
+    assert_eq!((image.width(), image.height()), (2, 1));
+    assert_eq!(image.data(), [255, 0, 0, 255, 0, 0, 128, 128]);
+
+    // the image is stretched to the size of the asset
+    let canvas = draw_composition(composition);
+    let ops = canvas.ops();
+    let draw = ops
+        .iter()
+        .position(|op| op.starts_with("draw_image"))
+        .unwrap();
+    assert_eq!(
+        ops[draw - 1..=draw],
+        ["scale 2.00 2.00", "draw_image 2 1 0.00 0.00 0.50"]
+    );
+}
+
+#[test]
+fn image_providers() {
+    struct Provider;
+    impl LottieImageProvider for Provider {
+        fn load_image(&self, asset: &LottieImageAsset) -> Option<Bitmap> {
+            if asset.get_id() == "image_0" {
+                Bitmap::new(1, 1, vec![0, 255, 0, 255])
+            } else {
+                None
+            }
+        }
+    }
+
+    let mut composition = image_composition("images/", "missing.png");
+    composition.load_images(&LottieImageProviderFactory {
+        provider: Some(Rc::new(Provider)),
+        ..Default::default()
+    });
+    let canvas = draw_composition(composition);
+    assert!(canvas.contains("scale 4.00 2.00"));
+    assert!(canvas.contains("draw_image 1 1 0.00 0.00 0.50"));
+
+    assert!(Bitmap::new(2, 2, vec![0; 4]).is_none());
+    assert!(Bitmap::from_png(b"not a png").is_none());
+}
+
+#[test]
+fn lottie_image_provider_factory() {
+    struct Provider;
+    impl LottieImageProvider for Provider {
+        fn load_image(&self, _asset: &LottieImageAsset) -> Option<Bitmap> {
+            Bitmap::new(1, 1, vec![0, 0, 255, 255])
+        }
+    }
+
+    // the source is set before the factory, the factory is still used
+    let lottie = Lottie::new();
+    lottie
+        .memory(image_json("images/", "lottie_factory.png").into())
+        .image_provider_factory(LottieImageProviderFactory {
+            provider: Some(Rc::new(Provider)),
+            ..Default::default()
+        });
+    let composition = lottie.load().unwrap();
+    assert!(composition.get_warnings().is_empty());
+    assert!(draw_composition(composition).contains("draw_image 1 1 0.00 0.00 0.50"));
+}
+
+#[test]
+fn image_files() {
+    let dir = std::env::temp_dir().join("animate-lottie-image-files");
+    std::fs::create_dir_all(dir.join("images")).unwrap();
+    std::fs::write(dir.join("images/img_0.png"), decode_base64(PNG).unwrap()).unwrap();
+
+    // files are relative to the directory of the composition
+    let factory = LottieImageProviderFactory {
+        dir: Some(dir.clone()),
+        ..Default::default()
+    };
+    let mut composition = image_composition("images/", "img_0.png");
+    composition.load_images(&factory);
+    assert!(composition.get_warnings().is_empty());
+    assert!(draw_composition(composition).contains("draw_image 2 1 0.00 0.00 0.50"));
+
+    let mut composition = image_composition("images/", "img_1.png");
+    composition.load_images(&factory);
+    assert_eq!(composition.get_warnings(), ["Unable to load image image_0"]);
+    assert_eq!(draw_composition(composition).count("draw_image"), 0);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+/// Returns whether the image of the composition is loaded.
+fn has_image(composition: &LottieComposition) -> bool {
+    composition.images()["image_0"].get_loaded_image().is_some()
+}
+
+#[test]
+fn lottie_cache() {
+    let mut cache = LottieCache::new(Some(2));
+    let loads = Cell::new(0);
+    let mut load = |key: &str| {
+        cache.put_if_absent(key, || {
+            loads.set(loads.get() + 1);
+            Ok(image_composition("", "a.png"))
+        })
+    };
+    load("a").unwrap();
+    load("a").unwrap();
+    load("b").unwrap();
+    assert_eq!(loads.get(), 2);
+
+    // the oldest composition is dropped
+    load("c").unwrap();
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a").is_none() && cache.get("c").is_some());
+
+    // failures are not cached
+    let failure = cache.put_if_absent("d", || Err(LottieError::Load("d".into())));
+    assert_eq!(failure.err().unwrap().to_string(), "unable to load: d");
+    assert!(cache.get("d").is_none());
+
+    cache.set_maximum_size(1);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get("c").is_some());
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn memory_and_file_providers() {
+    let json = image_json("images/", "img_0.png");
+    let memory = MemoryLottie::new(json.clone().into());
+    assert_eq!(
+        memory.get_key(),
+        MemoryLottie::new(json.clone().into()).get_key()
+    );
+    assert_ne!(memory.get_key(), MemoryLottie::new("{}".into()).get_key());
+    let composition = memory.load().unwrap();
+    assert_eq!(composition.get_layers().len(), 1);
+    // images are relative to the working directory
+    assert!(!has_image(&composition));
+
+    // images are relative to the JSON file
+    let dir = std::env::temp_dir().join("animate-lottie-file-providers");
+    std::fs::create_dir_all(dir.join("images")).unwrap();
+    std::fs::write(dir.join("images/img_0.png"), decode_base64(PNG).unwrap()).unwrap();
+    std::fs::write(dir.join("data.json"), &json).unwrap();
+    let file = FileLottie::new(dir.join("data.json"));
+    assert_eq!(
+        file.get_key(),
+        format!("file:{}", dir.join("data.json").display())
+    );
+    assert!(has_image(&file.load().unwrap()));
+
+    let missing = FileLottie::new(dir.join("missing.json")).load();
+    assert!(matches!(missing, Err(LottieError::Load(_))));
+    std::fs::remove_dir_all(dir).unwrap();
+
+    assert!(matches!(
+        MemoryLottie::new("{".into()).load(),
+        Err(LottieError::Json(_))
+    ));
+}
+
+#[test]
+fn asset_providers() {
+    #[derive(Default)]
+    struct Bundle(HashMap<String, Vec<u8>>);
+    impl LottieAssetBundle for Bundle {
+        fn load(&self, key: &str) -> Option<Vec<u8>> {
+            self.0.get(key).cloned()
+        }
+    }
+
+    let mut bundle = Bundle::default();
+    let json = image_json("images/", "img_0.png").into_bytes();
+    bundle.0.insert("anim/data.json".into(), json.clone());
+    bundle.0.insert("packages/icons/data.json".into(), json);
+    bundle
+        .0
+        .insert("anim/images/img_0.png".into(), decode_base64(PNG).unwrap());
+    let bundle: Rc<dyn LottieAssetBundle> = Rc::new(bundle);
+
+    // images are next to the composition in the bundle
+    let asset = AssetLottie::new("anim/data.json", bundle.clone());
+    assert_eq!(asset.get_key(), "asset:anim/data.json");
+    assert!(has_image(&asset.load().unwrap()));
+
+    let mut asset = AssetLottie::new("data.json", bundle.clone());
+    asset.package = Some("icons".into());
+    assert_eq!(asset.get_key_name(), "packages/icons/data.json");
+    let composition = asset.load().unwrap();
+    assert!(!has_image(&composition));
+    assert_eq!(composition.get_warnings(), ["Unable to load image image_0"]);
+
+    let missing = AssetLottie::new("missing.json", bundle).load();
+    assert_eq!(
+        missing.err().unwrap().to_string(),
+        "unable to load: missing.json"
+    );
+}
+
+#[test]
+fn network_providers() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let fetch: LottieFetch = {
+        let requests = requests.clone();
+        Rc::new(move |url: &str| {
+            requests.borrow_mut().push(url.to_string());
+            match url {
+                "https://example.com/anim/data.json" => {
+                    Ok(image_json("images/", "img_0.png").into_bytes())
+                }
+                "https://example.com/anim/images/img_0.png" => Ok(decode_base64(PNG).unwrap()),
+                _ => Err("not found".into()),
+            }
+        })
+    };
+
+    // images are fetched next to the composition
+    let network = NetworkLottie::new("https://example.com/anim/data.json", fetch.clone());
+    assert!(has_image(&network.load().unwrap()));
+    assert_eq!(
+        *requests.borrow(),
+        [
+            "https://example.com/anim/data.json",
+            "https://example.com/anim/images/img_0.png"
+        ]
+    );
+
+    let missing = NetworkLottie::new("https://example.com/missing.json", fetch.clone()).load();
+    assert_eq!(
+        missing.err().unwrap().to_string(),
+        "unable to load: https://example.com/missing.json: not found"
+    );
+
+    // the builder loads a source once
+    requests.borrow_mut().clear();
+    let lottie = Lottie::new();
+    assert!(lottie.load().is_err());
+    lottie.network("https://example.com/anim/data.json", fetch);
+    let composition = lottie.load().unwrap();
+    assert!(has_image(&composition));
+    lottie.load().unwrap();
+    assert_eq!(requests.borrow().len(), 2);
+    LottieCache::with_shared(|cache| {
+        assert!(cache
+            .get("network:https://example.com/anim/data.json")
+            .is_some());
+        cache.clear();
+    });
+
+    let json = image_json("", "a.png");
+    let composition = Lottie::new().memory(json.into()).load().unwrap();
+    assert_eq!(composition.get_layers().len(), 1);
+}
+
+/// Returns a ZIP archive of `files`.
+fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in files {
+        writer
+            .start_file(*name, zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// Returns a dotLottie file of two animations, the first one showing an
+/// image of the archive.
+fn dot_lottie() -> Vec<u8> {
+    let manifest = r#"{ "version": 1, "generator": "test", "activeAnimationId": "second",
+        "animations": [ { "id": "first", "speed": 2, "loop": true },
+                        { "id": "second", "direction": -1, "autoplay": true } ] }"#;
+    let first = image_json("/images/", "img_0.png");
+    let second = r#"{ "v": "5.7.4", "fr": 30, "ip": 0, "op": 60, "w": 10, "h": 10, "layers": [] }"#;
+    zip(&[
+        ("manifest.json", manifest.as_bytes()),
+        ("animations/first.json", first.as_bytes()),
+        ("animations/second.json", second.as_bytes()),
+        ("images/img_0.png", &decode_base64(PNG).unwrap()),
+    ])
+}
+
+#[test]
+fn dot_lottie_archives() {
+    let archive = DotLottieArchive::from_bytes(&dot_lottie()).unwrap();
+    let manifest = archive.get_manifest();
+    assert_eq!(manifest.version.as_deref(), Some("1"));
+    assert_eq!(manifest.generator.as_deref(), Some("test"));
+    let animations = archive.get_animations();
+    assert_eq!(animations.len(), 2);
+    assert_eq!(
+        (
+            animations[0].id.as_str(),
+            animations[0].speed,
+            animations[0].looping
+        ),
+        ("first", 2.0, true)
+    );
+    assert_eq!(
+        (
+            animations[1].direction,
+            animations[1].autoplay,
+            animations[1].speed
+        ),
+        (-1, true, 1.0)
+    );
+
+    let factory = LottieImageProviderFactory::new();
+    let first = archive.load_animation(Some("first"), &factory).unwrap();
+    assert!(has_image(&first));
+    assert!(first.get_warnings().is_empty());
+    // the active animation is loaded by default
+    let second = archive.load_animation(None, &factory).unwrap();
+    assert_eq!(second.get_frame_rate(), 30.0);
+
+    let missing = archive.load_animation(Some("third"), &factory);
+    assert_eq!(
+        missing.err().unwrap().to_string(),
+        "invalid dotLottie: there is no animation third"
+    );
+}
+
+#[test]
+fn invalid_dot_lotties() {
+    let error = |data: &[u8]| {
+        DotLottieArchive::from_bytes(data)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+    assert!(error(b"{}").starts_with("invalid dotLottie"));
+    assert_eq!(
+        error(&zip(&[("animations/a.json", b"{}")])),
+        "invalid dotLottie: there is no manifest.json"
+    );
+    assert_eq!(
+        error(&zip(&[("manifest.json", br#"{ "animations": [] }"#)])),
+        "invalid dotLottie: the manifest has no animations"
+    );
+    assert_eq!(
+        error(&zip(&[("manifest.json", br#"{ "animations": [ {} ] }"#)])),
+        "invalid dotLottie: an animation of the manifest has no id"
+    );
+    let archive = zip(&[("manifest.json", br#"{ "animations": [ { "id": "a" } ] }"#)]);
+    let missing = DotLottieArchive::from_bytes(&archive)
+        .unwrap()
+        .load_animation(None, &LottieImageProviderFactory::new());
+    assert_eq!(
+        missing.err().unwrap().to_string(),
+        "invalid dotLottie: there is no animations/a.json"
+    );
+}
+
+#[test]
+fn dot_lottie_providers() {
+    let bytes = dot_lottie();
+    let first = DotLottie::new(bytes.clone().into(), Some("first"));
+    assert_ne!(
+        first.get_key(),
+        DotLottie::new(bytes.clone().into(), None).get_key()
+    );
+
+    let composition = Lottie::new()
+        .dot_lottie(bytes.into(), Some("first"))
+        .load()
+        .unwrap();
+    assert!(has_image(&composition));
+    assert!(draw_composition(composition).contains("draw_image 2 1 0.00 0.00 0.50"));
+}
+
+/// Three seconds at 10 frames per second, in three marked states.
+const MARKERS: &str = r#"{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 30, "w": 10, "h": 10,
+    "layers": [],
+    "markers": [ { "cm": "idle", "tm": 0, "dr": 10 }, { "cm": "hover", "tm": 10, "dr": 10 },
+                 { "cm": "pressed\r\n", "tm": 20, "dr": 10 } ] }"#;
+
+/// Returns a player of the `MARKERS` composition, which records the names of
+/// the markers reached.
+fn marker_player() -> (LottiePlayer, Rc<RefCell<Vec<String>>>) {
+    let player = LottiePlayer::new(LottieComposition::from_bytes(MARKERS.as_bytes()).unwrap());
+    let reached = Rc::new(RefCell::new(Vec::new()));
+    {
+        let reached = reached.clone();
+        player.on_marker(move |marker| reached.borrow_mut().push(marker.name.clone()));
+    }
+    (player, reached)
+}
+
+#[test]
+fn round_progress() {
+    let composition = LottieComposition::from_bytes(MARKERS.as_bytes()).unwrap();
+    let round = |progress, frame_rate| composition.round_progress(progress, frame_rate);
+    assert_eq!(round(0.55, FrameRate::Composition), 16.0 / 30.0);
+    assert_eq!(round(0.55, FrameRate::Max), 0.55);
+    assert_eq!(round(0.55, FrameRate::Fps(5.0)), 8.0 / 15.0);
+    assert_eq!(round(0.55, FrameRate::Fps(20.0)), 33.0 / 60.0);
+    // progresses of frames are kept
+    assert_eq!(round(0.1 * 3.0, FrameRate::Composition), 9.0 / 30.0);
+    assert_eq!(round(1.0, FrameRate::Composition), 1.0);
+    assert_eq!(FrameRate::default(), FrameRate::Composition);
+}
+
+#[test]
+fn marker_segments() {
+    let (player, reached) = marker_player();
+    let completed = Rc::new(Cell::new(false));
+    {
+        let completed = completed.clone();
+        player.on_complete(move || completed.set(true));
+    }
+    player.marker("hover").unwrap().start(0.0);
+    assert_eq!(player.get_segment(), (10.0, 20.0));
+    assert_eq!(player.get_frame(), 10.0);
+
+    // the progress moves from a frame of the composition to the next
+    assert!(player.update(250.0));
+    assert_eq!(player.get_frame(), 12.0);
+    assert_eq!(*reached.borrow(), ["hover"]);
+    player.frame_rate(FrameRate::Max);
+    assert_eq!(player.get_progress(), 12.5 / 30.0);
+
+    // the marker at the end of the segment belongs to the next one
+    assert!(!player.update(1250.0));
+    assert!(!player.is_playing() && completed.get());
+    assert_eq!(player.get_frame(), 20.0);
+    assert_eq!(*reached.borrow(), ["hover"]);
+
+    let missing = player.marker("missing").err().unwrap();
+    assert_eq!(missing.to_string(), "unknown marker: missing");
+    // marker names are matched ignoring case
+    assert!(player.marker("PRESSED").is_ok());
+}
+
+#[test]
+fn looping_between_markers() {
+    let (player, reached) = marker_player();
+    player
+        .between_markers("idle", "pressed", true)
+        .unwrap()
+        .repeat(f64::INFINITY);
+    assert_eq!(player.get_segment(), (0.0, 21.0));
+    player.between_markers("idle", "pressed", false).unwrap();
+    assert_eq!(player.get_segment(), (0.0, 20.0));
+
+    let group = Group::new();
+    player.group(&group).start(0.0);
+    assert_eq!(group.get_all().len(), 1);
+    assert!(group.update(2500.0));
+    assert_eq!(player.get_frame(), 5.0);
+    assert_eq!(*reached.borrow(), ["idle", "hover", "idle"]);
+
+    // the time spent paused is skipped
+    player.pause(2500.0);
+    assert!(group.update(5000.0));
+    assert_eq!(player.get_frame(), 5.0);
+    player.resume(5000.0);
+    group.update(5100.0);
+    assert_eq!(player.get_frame(), 6.0);
+
+    player.stop();
+    assert!(!group.update(6000.0));
+    assert!(group.is_empty());
+}
+
+#[test]
+fn yoyo_and_backward_segments() {
+    let (player, reached) = marker_player();
+    player.segment(15.0, 5.0).yoyo(true).repeat(1.0).start(0.0);
+    assert_eq!(player.get_segment(), (5.0, 15.0));
+
+    // there and back again, the marker is reached on both ways
+    assert!(player.update(1500.0));
+    assert_eq!(player.get_frame(), 10.0);
+    assert_eq!(*reached.borrow(), ["hover"]);
+    assert!(!player.update(2500.0));
+    assert_eq!(player.get_frame(), 5.0);
+    assert_eq!(*reached.borrow(), ["hover", "hover"]);
+
+    // a negative speed plays backward from the end
+    reached.borrow_mut().clear();
+    player
+        .segment(0.0, 40.0)
+        .yoyo(false)
+        .repeat(0.0)
+        .speed(-2.0);
+    assert_eq!(player.get_segment(), (0.0, 30.0));
+    player.start(0.0);
+    assert_eq!(player.get_frame(), 30.0);
+    player.update(1000.0);
+    assert_eq!(player.get_frame(), 10.0);
+    // a marker is reached when the playhead moves on from it
+    assert_eq!(*reached.borrow(), ["pressed"]);
+    player.update(1100.0);
+    assert_eq!(player.get_frame(), 8.0);
+    assert_eq!(*reached.borrow(), ["pressed", "hover"]);
+
+    player.end();
+    assert_eq!(player.get_frame(), 0.0);
+    player.set_frame(50.0);
+    assert_eq!(player.get_frame(), 30.0);
+}
+
+#[test]
+fn lottie_playback() {
+    let lottie = Lottie::new();
+    assert!(lottie.marker("hover").is_err());
+    lottie.memory(MARKERS.into());
+    let reached = Rc::new(RefCell::new(Vec::new()));
+    {
+        let reached = reached.clone();
+        lottie
+            .on_marker(move |marker| reached.borrow_mut().push(marker.name.clone()))
+            .unwrap();
+    }
+    lottie.marker("hover").unwrap().framerate(FrameRate::Max);
+
+    // it plays automatically from the first update, in a loop
+    assert!(lottie.update(0.0).unwrap());
+    assert!(lottie.update(1250.0).unwrap());
+    let player = lottie.get_player().unwrap();
+    assert_eq!(player.get_progress(), 12.5 / 30.0);
+    assert_eq!(*reached.borrow(), ["hover", "hover"]);
+
+    // back and forth, then once more to the start of the segment
+    lottie.reverse(true);
+    assert!(lottie.update(2250.0).unwrap());
+    assert_eq!(player.get_progress(), 17.5 / 30.0);
+    lottie.repeat(false);
+    assert!(!lottie.update(4250.0).unwrap());
+    assert_eq!(player.get_frame(), 10.0);
+
+    // a new source gets a new player
+    lottie.memory(MARKERS.into()).animate(false);
+    assert!(!lottie.update(5000.0).unwrap());
+    assert_eq!(lottie.get_player().unwrap().get_segment(), (0.0, 30.0));
+    lottie.animate(true);
+    assert!(lottie.update(5000.0).unwrap());
+}
+
+/// Evaluates `source` at `frame` of a property going from 0 at frame 0 to
+/// 100 at frame 10, at 10 frames a second.
+fn evaluate(source: &str, frame: f64) -> Option<ExpressionValue> {
+    let expression = Expression::parse(source).unwrap();
+    let value_at = |frame: f64| Some(ExpressionValue::Number(frame.clamp(0.0, 10.0) * 10.0));
+    let context = ExpressionContext {
+        frame,
+        frame_rate: 10.0,
+        key_frames: &[0.0, 10.0],
+        value_at: &value_at,
+    };
+    expression.evaluate(&context)
+}
+
+fn evaluate_number(source: &str, frame: f64) -> f64 {
+    evaluate(source, frame)
+        .and_then(|value| value.as_number())
+        .unwrap()
+}
+
+#[test]
+fn expressions() {
+    assert_eq!(evaluate_number("time * 360", 5.0), 180.0);
+    assert_eq!(evaluate_number("value + 1", 5.0), 51.0);
+    assert_eq!(evaluate_number("var a = 2; a * value / 10", 5.0), 10.0);
+    assert_eq!(evaluate_number("time > 0.4 ? 1 : -1", 5.0), 1.0);
+    assert_eq!(
+        evaluate_number("Math.max(1, Math.abs(-3), 2) % 2", 0.0),
+        1.0
+    );
+    assert_eq!(evaluate_number("[1, 2, 3][2] + [4, 5].length", 0.0), 5.0);
+    assert_eq!(
+        evaluate_number("valueAtTime(0.2) + thisComp.frameDuration", 0.0),
+        20.1
+    );
+    assert_eq!(evaluate_number("linear(time, 0, 1, 0, 100)", 5.0), 50.0);
+    assert_eq!(evaluate_number("linear(time, 1, 0, 0, 100)", 5.0), 50.0);
+    assert_eq!(evaluate_number("linear(time * 2, 100, 200)", 10.0), 200.0);
+    assert_eq!(evaluate_number("ease(time, 0, 1, 0, 100)", 0.0), 0.0);
+    assert!(evaluate_number("ease(time, 0, 1, 0, 100)", 2.0) < 20.0);
+    // exported expressions assign their result to $bm_rt
+    assert_eq!(
+        evaluate(
+            "var $bm_rt;\n$bm_rt = $bm_sum([value, 1], $bm_mul([10, 0], 2)); // moved",
+            5.0
+        ),
+        Some(ExpressionValue::Vector(vec![70.0, 1.0]))
+    );
+    assert_eq!(
+        evaluate("normalize([3, 4]) - [0.6, 0]", 0.0),
+        Some(ExpressionValue::Vector(vec![0.0, 0.8]))
+    );
+    assert_eq!(evaluate_number("length([1, 1], [4, 5])", 0.0), 5.0);
+    assert_eq!(
+        evaluate("clamp([-5, 5, 15], 0, 10)", 0.0),
+        Some(ExpressionValue::Vector(vec![0.0, 5.0, 10.0]))
+    );
+    // vectors can't be multiplied
+    assert_eq!(evaluate("[1, 2] * [3, 4]", 0.0), None);
+
+    for unsupported in [
+        "thisLayer.transform.position",
+        "for (var i = 0; i < 10; i++) {}",
+        "foo(1)",
+        "undefinedVariable * 2",
+        "Math.random()",
+        "value +",
+        "",
+    ]
+    .iter()
+    {
+        assert!(Expression::parse(unsupported).is_err(), "{}", unsupported);
+    }
+    let nested = format!("{}1{}", "(".repeat(100), ")".repeat(100));
+    assert!(Expression::parse(&nested).is_err());
+}
+
+#[test]
+fn looping_expressions() {
+    for (source, frame, value) in [
+        ("loopOut()", 12.0, 20.0),
+        ("loopOut('cycle')", 5.0, 50.0),
+        ("loopOut('pingpong')", 12.0, 80.0),
+        ("loopOut('pingpong')", 22.0, 20.0),
+        ("loopOut('offset')", 12.0, 120.0),
+        ("loopOut('continue')", 12.0, 120.0),
+        ("loopIn('cycle')", -2.0, 80.0),
+        ("loopIn('pingpong')", -2.0, 20.0),
+        ("loopIn('offset')", -2.0, -20.0),
+        ("loopIn('continue')", -2.0, -20.0),
+    ]
+    .iter()
+    {
+        let result = evaluate_number(source, *frame);
+        assert!(
+            (result - value).abs() < 1e-6,
+            "{} at {}: {}",
+            source,
+            frame,
+            result
+        );
+    }
+
+    // the loop only covers the last keyframes
+    let expression = Expression::parse("loopOut('cycle', 1)").unwrap();
+    let value_at = |frame: f64| Some(ExpressionValue::Number(frame * 10.0));
+    let context = ExpressionContext {
+        frame: 12.0,
+        frame_rate: 10.0,
+        key_frames: &[0.0, 5.0, 10.0],
+        value_at: &value_at,
+    };
+    assert_eq!(
+        expression.evaluate(&context),
+        Some(ExpressionValue::Number(70.0))
+    );
+}
+
+#[test]
+fn wiggle_expressions() {
+    let wiggle = |frame| match evaluate("wiggle(2, 30, 2) - [value, value]", frame) {
+        Some(ExpressionValue::Number(offset)) => vec![offset],
+        Some(ExpressionValue::Vector(offsets)) => offsets,
+        _ => panic!("no wiggle at {}", frame),
+    };
+    let value = |frame| evaluate_number("wiggle(2, 30) - value", frame);
+    // the same time wiggles the same way
+    assert_eq!(value(3.0), value(3.0));
+    let offsets: Vec<f64> = (0..40).map(|frame| value(frame as f64 / 2.0)).collect();
+    assert!(offsets.iter().all(|offset| offset.abs() <= 30.0));
+    assert!(offsets.iter().any(|offset| offset.abs() > 1.0));
+    // the motion is smooth
+    assert!(offsets
+        .windows(2)
+        .all(|pair| (pair[1] - pair[0]).abs() < 15.0));
+    // each component moves on its own, within the amplitude of the octaves
+    let offsets = wiggle(7.0);
+    assert_eq!(offsets.len(), 2);
+    assert!(offsets.iter().all(|offset| offset.abs() <= 45.0));
+}
+
+#[test]
+fn expressions_of_properties() {
+    let layer = |transform: &str| {
+        composition(&format!(
+            r#"{{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {}, "shapes": [{}] }}"#,
+            transform, RED_BOX
+        ))
+    };
+    let draw_at = |composition: LottieComposition, frame: f64| {
+        let mut drawable = LottieDrawable::new(composition);
+        drawable.set_frame(frame);
+        let canvas = RecordingCanvas::default();
+        drawable.draw(&canvas, rect(0.0, 0.0, 100.0, 100.0));
+        canvas
+    };
+
+    let transform = r#"{
+        "p": { "a": 1, "k": [
+            { "t": 0, "s": [0, 0], "e": [10, 0],
+              "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+            { "t": 4 }
+        ], "x": "loopOut(\"cycle\")" },
+        "s": { "a": 0, "k": [100, 100], "x": "value * 2" },
+        "o": { "a": 0, "k": 100, "x": "value / 4" }
+    }"#;
+    let composition = layer(transform);
+    assert!(composition.get_warnings().is_empty());
+    let canvas = draw_at(composition, 6.0);
+    assert!(canvas.contains("transform 2.00 0.00 0.00 2.00 5.00 0.00"));
+    assert!(canvas.contains("set_fill_color 255 0 0 32"));
+
+    // unsupported expressions keep the value of the keyframes
+    let transform = r#"{ "o": { "a": 0, "k": 100, "x": "thisLayer.opacity / 2" } }"#;
+    let composition = layer(transform);
+    assert_eq!(composition.get_warnings().len(), 1);
+    assert!(composition.get_warnings()[0].contains("thisLayer"));
+    let canvas = draw_at(composition, 0.0);
+    assert!(canvas.contains("set_fill_color 255 0 0 128"));
+}
+
+/// Returns the premultiplied RGBA pixel of `bitmap` at `x`, `y`.
+fn pixel(bitmap: &Bitmap, x: u32, y: u32) -> [u8; 4] {
+    let index = (y * bitmap.width() + x) as usize * 4;
+    let mut pixel = [0; 4];
+    pixel.copy_from_slice(&bitmap.data()[index..index + 4]);
+    pixel
+}
+
+#[test]
+fn rasterizes_frames() {
+    let moving = r#"{ "p": { "a": 1, "k": [
+        { "t": 0, "s": [0, 0], "e": [20, 0],
+          "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+        { "t": 10 }
+    ] } }"#;
+    let composition = composition(&shape_layer(1, moving, ""));
+    let mut rasterizer = LottieRasterizer::new(composition, 50, 50);
+    assert_eq!(rasterizer.get_frames(), 0..10);
+
+    // the half opaque red box is drawn at half the size of the composition
+    let bitmap = rasterizer.rasterize(0.0).unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (50, 50));
+    assert_eq!(pixel(&bitmap, 25, 25), [128, 0, 0, 128]);
+    assert_eq!(pixel(&bitmap, 32, 25), [0, 0, 0, 0]);
+    assert_eq!(pixel(&bitmap, 5, 5), [0, 0, 0, 0]);
+
+    // the layer is out at the end frame
+    let bitmap = rasterizer.rasterize(9.0).unwrap();
+    assert_eq!(pixel(&bitmap, 22, 25), [0, 0, 0, 0]);
+    assert_eq!(pixel(&bitmap, 34, 25), [128, 0, 0, 128]);
+
+    match LottieRasterizer::new(composition_of_size(0), 0, 10).rasterize(0.0) {
+        Err(LottieError::Render(_)) => {}
+        _ => panic!("rasterized an empty image"),
+    }
+}
+
+#[test]
+fn rasterizes_text_only_with_glyphs() {
+    // the glyph is moved from above the baseline into the image
+    let doc = document("A", r#", "ls": -30"#);
+    let composition = text_composition(GLYPHS, &doc, "");
+    let mut rasterizer = LottieRasterizer::new(composition.clone(), 100, 100);
+    let bitmap = rasterizer.rasterize(0.0).unwrap();
+    assert_eq!(pixel(&bitmap, 10, 10), [255, 0, 0, 255]);
+    assert!(composition.get_warnings().is_empty());
+
+    // the canvas has no fonts to draw the text with
+    let composition = text_composition("", &doc, "");
+    let mut rasterizer = LottieRasterizer::new(composition.clone(), 100, 100);
+    rasterizer.rasterize(0.0).unwrap();
+    assert_eq!(
+        composition.get_warnings(),
+        ["Text drawn with fonts is not rasterized."]
+    );
+}
+
+fn composition_of_size(size: u32) -> LottieComposition {
+    let json = format!(
+        r#"{{ "v": "5.7.4", "fr": 10, "ip": 0, "op": 10, "w": {0}, "h": {0}, "layers": [] }}"#,
+        size
+    );
+    LottieComposition::from_bytes(json.as_bytes()).unwrap()
+}
+
+#[test]
+fn rasterizes_pngs() {
+    let mut rasterizer = LottieRasterizer::new(composition(&shape_layer(1, "{}", "")), 20, 20);
+    let png = rasterizer.rasterize_png(0.0).unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+    // the colors are divided by their alpha in PNG images
+    let decoded = Bitmap::from_png(&png).unwrap();
+    assert_eq!(decoded, rasterizer.rasterize(0.0).unwrap());
+    assert_eq!(
+        decoded.unpremultiplied_data()[(10 * 20 + 10) * 4..][..4],
+        [255, 0, 0, 128]
+    );
+}
+
+#[test]
+fn exports_frame_ranges() {
+    let directory = std::env::temp_dir().join(format!("lottie-frames-{}", std::process::id()));
+    let mut rasterizer = LottieRasterizer::new(composition(&shape_layer(1, "{}", "")), 10, 10);
+    let paths = rasterizer.export_frames(&directory, 2..5).unwrap();
+    let names: Vec<_> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["frame_0002.png", "frame_0003.png", "frame_0004.png"]
+    );
+    for path in &paths {
+        let bitmap = Bitmap::from_png(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!((bitmap.width(), bitmap.height()), (10, 10));
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+/// Compares `bitmap` with the golden image `name` next to the tests, or
+/// writes the golden image when `LOTTIE_UPDATE_GOLDEN` is set.
+///
+/// Colors may differ by 2 levels, for anti-aliasing to change slightly.
+fn assert_golden(name: &str, bitmap: &Bitmap) {
+    let path = std::path::Path::new(file!())
+        .with_file_name("golden")
+        .join(format!("{}.png", name));
+    if std::env::var_os("LOTTIE_UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, bitmap.to_png().unwrap()).unwrap();
+        return;
+    }
+    let golden = std::fs::read(&path)
+        .ok()
+        .and_then(|png| Bitmap::from_png(&png))
+        .unwrap_or_else(|| panic!("no golden image {}", path.display()));
+    assert_eq!(
+        (golden.width(), golden.height()),
+        (bitmap.width(), bitmap.height())
+    );
+    let image = Bitmap::from_png(&bitmap.to_png().unwrap()).unwrap();
+    for (index, (a, b)) in golden.data().iter().zip(image.data()).enumerate() {
+        let (x, y) = (
+            index / 4 % bitmap.width() as usize,
+            index / 4 / bitmap.width() as usize,
+        );
+        assert!(
+            (*a as i32 - *b as i32).abs() <= 2,
+            "{} differs at {}, {}: {:?} instead of {:?}",
+            name,
+            x,
+            y,
+            pixel(&image, x as u32, y as u32),
+            pixel(&golden, x as u32, y as u32),
+        );
+    }
+}
+
+fn rasterize_golden(layers: &str) -> Bitmap {
+    LottieRasterizer::new(composition(layers), 48, 48)
+        .rasterize(0.0)
+        .unwrap()
+}
+
+/// Returns the transform scaling a layer around the center of the
+/// composition.
+fn centered_scale(x: i32, y: i32) -> String {
+    format!(
+        r#"{{ "a": {{ "a": 0, "k": [50, 50] }}, "p": {{ "a": 0, "k": [50, 50] }},
+              "s": {{ "a": 0, "k": [{}, {}] }} }}"#,
+        x, y
+    )
+}
+
+#[test]
+fn golden_shapes() {
+    let shapes = r#"{ "ty": 4, "ind": 2, "ip": 0, "op": 10, "ks": {}, "shapes": [
+        { "ty": "gr", "it": [
+            { "ty": "el", "p": { "a": 0, "k": [50, 50] }, "s": { "a": 0, "k": [80, 60] } },
+            { "ty": "st", "c": { "a": 0, "k": [0, 0, 1, 1] }, "o": { "a": 0, "k": 100 },
+              "w": { "a": 0, "k": 6 }, "lc": 2, "lj": 2 }
+        ] },
+        { "ty": "gr", "it": [
+            { "ty": "sr", "sy": 1, "pt": { "a": 0, "k": 5 }, "p": { "a": 0, "k": [50, 50] },
+              "r": { "a": 0, "k": 0 }, "or": { "a": 0, "k": 24 }, "os": { "a": 0, "k": 0 },
+              "ir": { "a": 0, "k": 10 }, "is": { "a": 0, "k": 0 } },
+            { "ty": "fl", "c": { "a": 0, "k": [0, 0.6, 0, 1] }, "o": { "a": 0, "k": 100 } }
+        ] }
+    ] }"#;
+    let box_layer = shape_layer(1, &centered_scale(200, 100), "");
+    assert_golden(
+        "shapes",
+        &rasterize_golden(&format!("{}, {}", box_layer, shapes)),
+    );
+}
+
+#[test]
+fn golden_gradients() {
+    let layer = r#"{ "ty": 4, "ind": 1, "ip": 0, "op": 10, "ks": {}, "shapes": [
+        { "ty": "gr", "it": [
+            { "ty": "rc", "p": { "a": 0, "k": [50, 25] }, "s": { "a": 0, "k": [90, 40] },
+              "r": { "a": 0, "k": 10 } },
+            { "ty": "gf", "t": 1, "o": { "a": 0, "k": 100 },
+              "s": { "a": 0, "k": [5, 0] }, "e": { "a": 0, "k": [95, 0] },
+              "g": { "p": 2, "k": { "a": 0, "k": [0, 1, 0, 0, 1, 0, 0, 1] } } }
+        ] },
+        { "ty": "gr", "it": [
+            { "ty": "el", "p": { "a": 0, "k": [50, 72] }, "s": { "a": 0, "k": [44, 44] } },
+            { "ty": "gf", "t": 2, "o": { "a": 0, "k": 100 },
+              "s": { "a": 0, "k": [50, 72] }, "e": { "a": 0, "k": [72, 72] },
+              "h": { "a": 0, "k": 50 }, "a": { "a": 0, "k": 45 },
+              "g": { "p": 2, "k": { "a": 0, "k": [0, 1, 1, 0, 1, 0, 0.5, 0, 0, 1, 1, 0] } } }
+        ] }
+    ] }"#;
+    assert_golden("gradients", &rasterize_golden(layer));
+}
+
+#[test]
+fn golden_mattes_and_masks() {
+    let matte = shape_layer(1, &centered_scale(300, 600), r#", "td": 1"#);
+    let matted = r##"{ "ty": 1, "ind": 2, "ip": 0, "op": 10, "tt": 2, "sc": "#0000ff",
+        "sw": 100, "sh": 100, "ks": {} }"##;
+    let masked = r##"{ "ty": 1, "ind": 3, "ip": 0, "op": 10, "sc": "#00a000",
+        "sw": 100, "sh": 100, "ks": { "o": { "a": 0, "k": 50 } }, "masksProperties": [
+            { "mode": "a", "o": { "a": 0, "k": 100 }, "pt": { "a": 0, "k": { "c": true,
+                "v": [[50, 5], [95, 95], [5, 95]],
+                "i": [[0, 0], [0, 0], [0, 0]], "o": [[0, 0], [0, 0], [0, 0]] } } }
+        ] }"##;
+    let layers = format!("{}, {}, {}", masked, matte, matted);
+    assert_golden("mattes_and_masks", &rasterize_golden(&layers));
+}
@@ -1 +1,116 @@
+use crate::path::{Path, PathMeasure};
 
+/// Cuts paths into the parts between distances along them, for trim paths
+/// and dashes.
+pub struct DashPath;
+
+impl DashPath {
+    /// Returns the distances kept by a trim from `start` to `end` moved by
+    /// `offset` along a path of `length`, all three in fractions of the
+    /// length.
+    ///
+    /// Returns `None` if the whole path is kept. A trim going past the end
+    /// of the path continues from its start, so it is kept in two parts.
+    fn trim_intervals(length: f64, start: f64, end: f64, offset: f64) -> Option<Vec<(f64, f64)>> {
+        let (low, high) = (start.min(end), start.max(end));
+        if length <= 0.0 || high - low >= 0.99 {
+            return None;
+        }
+
+        let mut from = (low + offset) * length;
+        let mut to = (high + offset) * length;
+        // turns around the path are taken back
+        let turns = (from / length).floor() * length;
+        from -= turns;
+        to -= turns;
+
+        if from == to {
+            Some(Vec::new())
+        } else if to <= length {
+            Some(vec![(from, to)])
+        } else {
+            Some(vec![(from, length), (0.0, to - length)])
+        }
+    }
+
+    /// Returns the part of `path` from `start` to `end` moved by `offset`,
+    /// all three in fractions of the length of the path.
+    ///
+    /// The part of a closed path going over its start is kept in one piece.
+    pub fn trim(path: &Path, start: f64, end: f64, offset: f64) -> Path {
+        Self::trim_together(std::slice::from_ref(path), start, end, offset)
+            .pop()
+            .unwrap_or_default()
+    }
+
+    /// Trims `paths` as if they were one path following another, returns
+    /// the part kept of each path.
+    pub fn trim_together(paths: &[Path], start: f64, end: f64, offset: f64) -> Vec<Path> {
+        let measures: Vec<PathMeasure> = paths.iter().map(PathMeasure::new).collect();
+        let length: f64 = measures.iter().map(PathMeasure::length).sum();
+        let intervals = match Self::trim_intervals(length, start, end, offset) {
+            Some(intervals) => intervals,
+            None => return paths.to_vec(),
+        };
+
+        let mut distance = 0.0;
+        let mut trimmed = Vec::with_capacity(paths.len());
+        for measure in measures.iter() {
+            let path_length = measure.length();
+            let parts: Vec<Path> = intervals
+                .iter()
+                .map(|&(from, to)| {
+                    let from = (from - distance).max(0.0);
+                    let to = (to - distance).min(path_length);
+                    measure.segment(from, to)
+                })
+                .filter(|part| !part.is_empty())
+                .collect();
+            distance += path_length;
+
+            let mut path = Path::new();
+            for part in parts {
+                if measure.is_closed() && !path.is_empty() {
+                    // the second part goes on from the start of the path
+                    path.extend(part.0.into_iter().skip(1));
+                } else {
+                    path.extend(part.0);
+                }
+            }
+            trimmed.push(path);
+        }
+        trimmed
+    }
+
+    /// Returns the dashes of `path` along `pattern`, the lengths of the
+    /// dashes and of the gaps between them, starting `offset` into the
+    /// pattern.
+    ///
+    /// A pattern of an odd number of lengths is repeated, the path is kept
+    /// whole if the pattern has no length.
+    pub fn dash(path: &Path, pattern: &[f64], offset: f64) -> Path {
+        let mut pattern = pattern.to_vec();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&pattern.clone());
+        }
+        let period: f64 = pattern.iter().map(|length| length.max(0.0)).sum();
+        if period <= 0.0 {
+            return path.clone();
+        }
+
+        let measure = PathMeasure::new(path);
+        let length = measure.length();
+        let mut dashes = Path::new();
+        let mut distance = -offset.rem_euclid(period);
+        let mut index = 0;
+        while distance < length {
+            let dash = pattern[index % pattern.len()].max(0.0);
+            if index % 2 == 0 {
+                dashes.extend(measure.segment(distance, distance + dash).0);
+            }
+            distance += dash;
+            index += 1;
+        }
+        dashes
+    }
+}
@@ -1 +1,16 @@
+use crate::{
+    interpolate::{lerp_color, ColorSpace},
+    RgbaColor,
+};
+
+/// Interpolates colors in linear light, as After Effects blends them, rather
+/// than between their gamma encoded channels.
 pub struct GammaEvaluator;
+
+impl GammaEvaluator {
+    /// Returns the color at `fraction` between `start` and `end`, alpha is
+    /// interpolated linearly.
+    pub fn evaluate(fraction: f64, start: RgbaColor, end: RgbaColor) -> RgbaColor {
+        lerp_color(&start, &end, fraction, ColorSpace::LinearRgb)
+    }
+}
@@ -1 +1,63 @@
-pub struct MeanCalculator;
+use std::collections::VecDeque;
+
+/// Computes the mean of the last values added to it, the oldest value is
+/// dropped once the window is full.
+#[derive(Clone, Debug)]
+pub struct MeanCalculator {
+    values: VecDeque<f64>,
+    window: usize,
+}
+
+impl MeanCalculator {
+    /// Returns a calculator of the mean of the last `window` values, at
+    /// least one.
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            values: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if self.values.len() == self.window {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    /// Returns the mean of the values in the window, `0.0` without values.
+    pub fn get_mean(&self) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        self.values.iter().sum::<f64>() / self.values.len() as f64
+    }
+
+    /// Returns the largest value in the window, `0.0` without values.
+    pub fn get_max(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// Returns the number of values in the window.
+    pub fn get_count(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get_window(&self) -> usize {
+        self.window
+    }
+
+    /// Sets the size of the window, at least one, dropping the oldest values
+    /// which no longer fit.
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window.max(1);
+        while self.values.len() > self.window {
+            self.values.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
@@ -1 +1,137 @@
+use crate::{
+    lottie::model::{content::ShapeData, KeyPath},
+    path::{Path, PathSegment},
+    CanvasContext, Color, RgbaColor,
+};
+
 pub struct MiscUtils;
+
+impl MiscUtils {
+    /// Returns the path of a shape, curves without tangents become lines.
+    pub fn get_path_from_data(shape: &ShapeData) -> Path {
+        let mut path = Path::with_capacity(shape.curves.len() + 2);
+        let mut current = shape.initial_point;
+        path.push_move_to(current.x, current.y);
+        for curve in shape.curves.iter() {
+            let vertex = curve.vertex;
+            if curve.control_point1 == current && curve.control_point2 == vertex {
+                path.push_line_to(vertex.x, vertex.y);
+            } else {
+                path.push_curve_to(
+                    curve.control_point1.x,
+                    curve.control_point1.y,
+                    curve.control_point2.x,
+                    curve.control_point2.y,
+                    vertex.x,
+                    vertex.y,
+                );
+            }
+            current = vertex;
+        }
+        if shape.closed {
+            path.push_close_path();
+        }
+        path
+    }
+
+    /// Adds the key path of the leaf content named `name` to `accumulator`
+    /// if it fully matches `key_path` at `depth`.
+    pub fn resolve_key_path(
+        key_path: &KeyPath,
+        depth: usize,
+        accumulator: &mut Vec<KeyPath>,
+        current_partial_key_path: &KeyPath,
+        name: &str,
+    ) {
+        if key_path.fully_resolves_to(name, depth) {
+            accumulator.push(current_partial_key_path.add_key(name));
+        }
+    }
+
+    /// Starts a new path on `canvas` and traces `path` onto it.
+    pub fn trace_path<C: CanvasContext>(canvas: &C, path: &Path) {
+        let mut path = path.clone();
+        path.conv_to_absolute();
+        path.conv_arcs_to_curves();
+
+        canvas.begin_path();
+        // the last point and the last control point, to mirror smooth curves
+        let mut current = (0.0, 0.0);
+        let mut control = (0.0, 0.0);
+        let mut start = (0.0, 0.0);
+        for segment in path.iter() {
+            let mut next_control = None;
+            match *segment {
+                PathSegment::MoveTo { x, y, .. } => {
+                    canvas.move_to(x, y);
+                    current = (x, y);
+                    start = current;
+                }
+                PathSegment::LineTo { x, y, .. } => {
+                    canvas.line_to(x, y);
+                    current = (x, y);
+                }
+                PathSegment::HorizontalLineTo { x, .. } => {
+                    canvas.line_to(x, current.1);
+                    current.0 = x;
+                }
+                PathSegment::VerticalLineTo { y, .. } => {
+                    canvas.line_to(current.0, y);
+                    current.1 = y;
+                }
+                PathSegment::CurveTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                    ..
+                } => {
+                    canvas.bezier_curve_to(x1, y1, x2, y2, x, y);
+                    next_control = Some((x2, y2));
+                    current = (x, y);
+                }
+                PathSegment::SmoothCurveTo { x2, y2, x, y, .. } => {
+                    let (x1, y1) = (2.0 * current.0 - control.0, 2.0 * current.1 - control.1);
+                    canvas.bezier_curve_to(x1, y1, x2, y2, x, y);
+                    next_control = Some((x2, y2));
+                    current = (x, y);
+                }
+                PathSegment::Quadratic { x1, y1, x, y, .. } => {
+                    canvas.quadratic_curve_to(x1, y1, x, y);
+                    next_control = Some((x1, y1));
+                    current = (x, y);
+                }
+                PathSegment::SmoothQuadratic { x, y, .. } => {
+                    let (x1, y1) = (2.0 * current.0 - control.0, 2.0 * current.1 - control.1);
+                    canvas.quadratic_curve_to(x1, y1, x, y);
+                    next_control = Some((x1, y1));
+                    current = (x, y);
+                }
+                PathSegment::EllipticalArc { x, y, .. } => {
+                    // arcs were rewritten as curves
+                    canvas.line_to(x, y);
+                    current = (x, y);
+                }
+                PathSegment::ClosePath { .. } => {
+                    canvas.close_path();
+                    current = start;
+                }
+            }
+            // smooth segments mirror the control point of a curve of their
+            // kind, and the current point otherwise
+            control = next_control.unwrap_or(current);
+        }
+    }
+
+    /// Returns `color` with its alpha multiplied by `alpha`, from `0.0` to
+    /// `1.0`.
+    pub fn with_alpha(color: RgbaColor, alpha: f64) -> Color {
+        RgbaColor {
+            alpha: (color.alpha as f64 * alpha.clamp(0.0, 1.0)).round() as u8,
+            ..color
+        }
+        .into()
+    }
+}
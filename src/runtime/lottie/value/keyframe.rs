@@ -1 +1,74 @@
-pub struct Keyframe;
+use crate::{easing::EasingFunction, Point};
+
+/// A value of an animated property at a frame, and how it moves on to the
+/// value of the next keyframe.
+///
+/// A static property is a single keyframe without end frame, see
+/// [`Keyframe::new_static`].
+#[derive(Clone, Debug)]
+pub struct Keyframe<T> {
+    pub start_value: Option<T>,
+    pub end_value: Option<T>,
+    /// The easing from the start value to the end value, `None` when the
+    /// keyframe is static or holds its value.
+    pub interpolator: Option<EasingFunction>,
+    /// The easing of the X axis, when each axis has its own.
+    pub x_interpolator: Option<EasingFunction>,
+    /// The easing of the Y axis, when each axis has its own.
+    pub y_interpolator: Option<EasingFunction>,
+    pub start_frame: f64,
+    pub end_frame: Option<f64>,
+    /// The first control point of the spatial curve between two positions.
+    pub path_cp1: Option<Point<f64>>,
+    /// The second control point of the spatial curve between two positions.
+    pub path_cp2: Option<Point<f64>>,
+    /// Holds the start value until the next keyframe.
+    pub hold: bool,
+}
+
+impl<T> Keyframe<T> {
+    /// Returns a keyframe which has `value` at every frame.
+    pub fn new_static(value: T) -> Self {
+        Keyframe {
+            start_value: Some(value),
+            end_value: None,
+            interpolator: None,
+            x_interpolator: None,
+            y_interpolator: None,
+            start_frame: f64::MIN,
+            end_frame: None,
+            path_cp1: None,
+            path_cp2: None,
+            hold: false,
+        }
+    }
+
+    /// Returns `true` if the keyframe does not animate its value.
+    pub fn is_static(&self) -> bool {
+        self.end_frame.is_none() && self.interpolator.is_none() && self.x_interpolator.is_none()
+    }
+
+    /// Returns `true` if `frame` is between the start and the end frame.
+    pub fn contains_frame(&self, frame: f64) -> bool {
+        frame >= self.start_frame && self.end_frame.map(|end| frame < end).unwrap_or(true)
+    }
+
+    /// Returns the linear progress from the start to the end frame at
+    /// `frame`, clamped to `0.0..=1.0`.
+    pub fn progress_at(&self, frame: f64) -> f64 {
+        match self.end_frame {
+            Some(end) if end > self.start_frame => {
+                ((frame - self.start_frame) / (end - self.start_frame)).clamp(0.0, 1.0)
+            }
+            Some(end) if frame >= end => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the eased progress at `frame` along `interpolator`, or the
+    /// linear progress without easing.
+    pub fn interpolated_progress(&self, interpolator: Option<&EasingFunction>, frame: f64) -> f64 {
+        let progress = self.progress_at(frame);
+        interpolator.map_or(progress, |easing| easing.ease(progress))
+    }
+}
@@ -1 +1,71 @@
-pub struct LottieFrameInfo;
+use super::Keyframe;
+
+/// The state of an animation at a frame, given to value callbacks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LottieFrameInfo<T> {
+    /// The frame the value is asked for.
+    pub frame: f64,
+    /// The start frame of the keyframe active at the frame.
+    pub start_frame: f64,
+    /// The end frame of the keyframe, `None` for the last keyframe.
+    pub end_frame: Option<f64>,
+    pub start_value: Option<T>,
+    pub end_value: Option<T>,
+    /// The progress through the keyframe, from `0.0` to `1.0`.
+    pub linear_keyframe_progress: f64,
+    /// The progress through the keyframe along its easing.
+    pub interpolated_keyframe_progress: f64,
+    /// The value of the animation at the frame, `None` for a property
+    /// missing from the composition.
+    pub value: Option<T>,
+}
+
+impl<T: Clone> LottieFrameInfo<T> {
+    /// Returns the state at `frame` within `keyframe`, the keyframe active
+    /// at `frame`, for an animation which has `value`.
+    pub fn new(keyframe: Option<&Keyframe<T>>, frame: f64, value: Option<T>) -> Self {
+        match keyframe {
+            Some(keyframe) => {
+                let interpolator = keyframe
+                    .interpolator
+                    .as_ref()
+                    .or(keyframe.x_interpolator.as_ref());
+                Self {
+                    frame,
+                    start_frame: keyframe.start_frame,
+                    end_frame: keyframe.end_frame,
+                    start_value: keyframe.start_value.clone(),
+                    end_value: keyframe.end_value.clone(),
+                    linear_keyframe_progress: keyframe.progress_at(frame),
+                    interpolated_keyframe_progress: keyframe
+                        .interpolated_progress(interpolator, frame),
+                    value,
+                }
+            }
+            None => Self {
+                frame,
+                start_frame: frame,
+                end_frame: None,
+                start_value: None,
+                end_value: None,
+                linear_keyframe_progress: 0.0,
+                interpolated_keyframe_progress: 0.0,
+                value,
+            },
+        }
+    }
+
+    /// Returns the state with the values mapped by `f`.
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> LottieFrameInfo<U> {
+        LottieFrameInfo {
+            frame: self.frame,
+            start_frame: self.start_frame,
+            end_frame: self.end_frame,
+            start_value: self.start_value.as_ref().map(&f),
+            end_value: self.end_value.as_ref().map(&f),
+            linear_keyframe_progress: self.linear_keyframe_progress,
+            interpolated_keyframe_progress: self.interpolated_keyframe_progress,
+            value: self.value.as_ref().map(&f),
+        }
+    }
+}
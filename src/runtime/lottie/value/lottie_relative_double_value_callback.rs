@@ -1 +1,19 @@
+use super::LottieValueCallback;
 
+/// Adds an offset to the value of an animated number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LottieRelativeDoubleValueCallback {
+    offset: f64,
+}
+
+impl LottieRelativeDoubleValueCallback {
+    pub fn new(offset: f64) -> Self {
+        Self { offset }
+    }
+}
+
+impl From<LottieRelativeDoubleValueCallback> for LottieValueCallback<f64> {
+    fn from(relative: LottieRelativeDoubleValueCallback) -> Self {
+        LottieValueCallback::from_fn(move |info| info.value.map(|value| value + relative.offset))
+    }
+}
@@ -1 +1,19 @@
+use super::LottieValueCallback;
 
+/// Adds an offset to the value of an animated integer, such as an opacity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LottieRelativeIntegerValueCallback {
+    offset: i32,
+}
+
+impl LottieRelativeIntegerValueCallback {
+    pub fn new(offset: i32) -> Self {
+        Self { offset }
+    }
+}
+
+impl From<LottieRelativeIntegerValueCallback> for LottieValueCallback<i32> {
+    fn from(relative: LottieRelativeIntegerValueCallback) -> Self {
+        LottieValueCallback::from_fn(move |info| info.value.map(|value| value + relative.offset))
+    }
+}
@@ -1 +1,24 @@
+use super::{LottieFrameInfo, LottieValueCallback};
+use crate::Point;
 
+/// Moves the value of an animated point, such as a position, by an offset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LottieRelativePointValueCallback {
+    offset: Point<f64>,
+}
+
+impl LottieRelativePointValueCallback {
+    pub fn new(offset: Point<f64>) -> Self {
+        Self { offset }
+    }
+}
+
+impl From<LottieRelativePointValueCallback> for LottieValueCallback<Point<f64>> {
+    fn from(relative: LottieRelativePointValueCallback) -> Self {
+        let offset = relative.offset;
+        LottieValueCallback::from_fn(move |info: &LottieFrameInfo<Point<f64>>| {
+            info.value
+                .map(|value| Point::new(value.x + offset.x, value.y + offset.y))
+        })
+    }
+}
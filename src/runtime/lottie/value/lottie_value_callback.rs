@@ -1,8 +1,49 @@
-/// Allows you to set a callback on a resolved {@link com.airbnb.lottie.model.KeyPath} to modify
-/// its animation values at runtime.
-pub struct LottieValueCallback<T> {
-    // animation: BaseKeyframeAnimation,
-    value: T,
+use super::LottieFrameInfo;
+use std::{fmt, rc::Rc};
+
+/// The function of a value callback, `None` keeps the value of the
+/// animation.
+pub type ValueCallbackFn<T> = Rc<dyn Fn(&LottieFrameInfo<T>) -> Option<T>>;
+
+/// Overrides the values of an animated property at runtime, set on the
+/// contents matching a [`KeyPath`](crate::lottie::model::KeyPath).
+#[derive(Clone)]
+pub enum LottieValueCallback<T> {
+    /// The same value at every frame.
+    Value(T),
+    /// A value computed at each frame.
+    Callback(ValueCallbackFn<T>),
 }
 
-impl<T> LottieValueCallback<T> {}
+impl<T: Clone> LottieValueCallback<T> {
+    /// Returns a callback giving `value` at every frame.
+    pub fn new(value: T) -> Self {
+        LottieValueCallback::Value(value)
+    }
+
+    /// Returns a callback computing the value with `callback` at each frame.
+    pub fn from_fn<F>(callback: F) -> Self
+    where
+        F: Fn(&LottieFrameInfo<T>) -> Option<T> + 'static,
+    {
+        LottieValueCallback::Callback(Rc::new(callback))
+    }
+
+    /// Returns the value at the frame of `frame_info`, `None` to keep the
+    /// value of the animation.
+    pub fn get_value(&self, frame_info: &LottieFrameInfo<T>) -> Option<T> {
+        match self {
+            LottieValueCallback::Value(value) => Some(value.clone()),
+            LottieValueCallback::Callback(callback) => callback(frame_info),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LottieValueCallback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LottieValueCallback::Value(value) => f.debug_tuple("Value").field(value).finish(),
+            LottieValueCallback::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
@@ -1 +1,23 @@
-pub struct ValueDelegate;
+use super::{model::KeyPath, LottieProperty};
+
+/// Overrides a property of the contents matching a key path, see
+/// [`LottieDelegates::values`](super::LottieDelegates::values).
+#[derive(Clone, Debug)]
+pub struct ValueDelegate {
+    key_path: KeyPath,
+    property: LottieProperty,
+}
+
+impl ValueDelegate {
+    pub fn new(key_path: KeyPath, property: LottieProperty) -> Self {
+        Self { key_path, property }
+    }
+
+    pub fn get_key_path(&self) -> &KeyPath {
+        &self.key_path
+    }
+
+    pub fn get_property(&self) -> &LottieProperty {
+        &self.property
+    }
+}
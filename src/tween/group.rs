@@ -1,25 +1,135 @@
-use super::Tween;
+use super::Playable;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    rc::{Rc, Weak},
+};
 
-pub struct Group {}
+#[derive(Default)]
+pub(crate) struct GroupState {
+    tweens: BTreeMap<usize, Rc<dyn Playable>>,
+    tweens_added_during_update: BTreeMap<usize, Rc<dyn Playable>>,
+}
+
+/// A registry of tweens updated together.
+///
+/// Tweens are kept by their id and updated in the order of their ids, so an
+/// update loop is deterministic. Finished tweens are dropped by
+/// [`Group::update`], which also tells whether another frame is needed.
+///
+/// A tween joins a group with `Tween::group` when it is started, or directly
+/// with [`Group::add`]. `Group` is a cheap handle, clones refer to the same
+/// group.
+///
+/// # Example
+///
+/// ```
+/// use animate::tween::{Group, Tween};
+///
+/// let group = Group::new();
+/// let tween = Tween::new(0.0);
+/// tween.to(10.0, 100.0).group(&group).start(0.0);
+///
+/// assert!(group.update(50.0));
+/// assert!(!group.update(100.0));
+/// assert_eq!(tween.get_value(), 10.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct Group {
+    inner: Rc<RefCell<GroupState>>,
+}
 
 impl Group {
-    pub fn get_all() -> Vec<Tween> {
-        unimplemented!()
+    /// Constructs a new empty group.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns all the tweens of the group in the update order.
+    pub fn get_all(&self) -> Vec<Rc<dyn Playable>> {
+        self.inner.borrow().tweens.values().cloned().collect()
+    }
+
+    /// Removes all the tweens from the group.
+    pub fn remove_all(&self) {
+        let mut state = self.inner.borrow_mut();
+        state.tweens.clear();
+        state.tweens_added_during_update.clear();
+    }
+
+    /// Adds a tween to the group, replacing a tween with the same id.
+    pub fn add<P>(&self, tween: &P)
+    where
+        P: Playable + Clone + 'static,
+    {
+        self.add_rc(Rc::new(tween.clone()));
+    }
+
+    /// Removes a tween from the group.
+    pub fn remove(&self, tween: &dyn Playable) {
+        self.remove_id(tween.get_id());
+    }
+
+    /// Returns `true` if the group has no tweens.
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().tweens.is_empty()
+    }
+
+    /// Updates all the tweens to `time` in milliseconds.
+    ///
+    /// Finished tweens are removed from the group. Tweens started during the
+    /// update, for example chained ones, are updated in the same call.
+    ///
+    /// Returns `true` if any tween is still in the group.
+    pub fn update(&self, time: f64) -> bool {
+        let mut tweens: Vec<(usize, Rc<dyn Playable>)> = self
+            .inner
+            .borrow()
+            .tweens
+            .iter()
+            .map(|(id, tween)| (*id, tween.clone()))
+            .collect();
+
+        while !tweens.is_empty() {
+            self.inner.borrow_mut().tweens_added_during_update.clear();
+
+            for (id, tween) in tweens.iter() {
+                if !tween.update(time) {
+                    let mut state = self.inner.borrow_mut();
+                    // the tween may have been restarted by a callback
+                    if !tween.is_playing() {
+                        state.tweens.remove(id);
+                    }
+                }
+            }
+
+            let mut state = self.inner.borrow_mut();
+            tweens = std::mem::take(&mut state.tweens_added_during_update)
+                .into_iter()
+                .collect();
+        }
+
+        !self.is_empty()
     }
 
-    pub fn remove_all() {
-        unimplemented!()
+    pub(crate) fn add_rc(&self, tween: Rc<dyn Playable>) {
+        let id = tween.get_id();
+        let mut state = self.inner.borrow_mut();
+        state.tweens.insert(id, tween.clone());
+        state.tweens_added_during_update.insert(id, tween);
     }
 
-    pub fn add() {
-        unimplemented!()
+    pub(crate) fn remove_id(&self, id: usize) {
+        let mut state = self.inner.borrow_mut();
+        state.tweens.remove(&id);
+        state.tweens_added_during_update.remove(&id);
     }
 
-    pub fn remove() {
-        unimplemented!()
+    pub(crate) fn downgrade(&self) -> Weak<RefCell<GroupState>> {
+        Rc::downgrade(&self.inner)
     }
 
-    pub fn update() {
-        unimplemented!()
+    pub(crate) fn upgrade(weak: &Weak<RefCell<GroupState>>) -> Option<Self> {
+        weak.upgrade().map(|inner| Self { inner })
     }
 }
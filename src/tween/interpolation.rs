@@ -1,30 +1,286 @@
-pub fn linear(v: Vec<f64>, k: f64) -> f64 {
-    unimplemented!();
+//! Interpolation through a list of waypoints.
+//!
+//! Every function takes the waypoints `v` and the progress `k` in `0..=1` and
+//! returns the value at `k`. Values before the first and after the last
+//! waypoint are extrapolated.
+use crate::{interpolate::Interpolate, Point, RgbaColor};
+use std::ops::{Add, Mul, Sub};
+
+/// The interpolation function type, see [`linear`], [`bezier`] and [`catmull_rom`].
+pub type InterpolationFunction<T> = fn(v: &[T], k: f64) -> T;
+
+/// Piecewise linear interpolation passing through every waypoint.
+///
+/// It only needs [`Interpolate`], so it works for any animatable value.
+///
+/// # Panics
+///
+/// Panics if `v` is empty.
+pub fn linear<T>(v: &[T], k: f64) -> T
+where
+    T: Clone + Interpolate,
+{
+    assert!(!v.is_empty(), "no waypoints");
+    let m = v.len() - 1;
+    if m == 0 {
+        return v[0].clone();
+    }
+
+    let f = m as f64 * k;
+
+    if k < 0.0 {
+        return v[0].interpolate(&v[1], f);
+    }
+
+    if k > 1.0 {
+        return v[m].interpolate(&v[m - 1], m as f64 - f);
+    }
+
+    let i = (f.floor() as usize).min(m);
+    v[i].interpolate(&v[(i + 1).min(m)], f - i as f64)
+}
+
+/// Bezier curve interpolation using the waypoints as control points.
+///
+/// The curve passes only through the first and the last waypoint.
+///
+/// # Panics
+///
+/// Panics if `v` is empty.
+pub fn bezier<T>(v: &[T], k: f64) -> T
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>,
+{
+    assert!(!v.is_empty(), "no waypoints");
+    let n = v.len() - 1;
+
+    let mut b = v[0].clone() * (f64::powi(1.0 - k, n as i32) * utils::bernstein(n, 0));
+    for (i, value) in v.iter().enumerate().skip(1) {
+        let weight = f64::powi(1.0 - k, (n - i) as i32) * f64::powi(k, i as i32);
+        b = b + value.clone() * (weight * utils::bernstein(n, i));
+    }
+    b
 }
 
-pub fn bezier(v: Vec<f64>, k: f64) -> f64 {
-    unimplemented!();
+/// Catmull-Rom spline interpolation passing through every waypoint.
+///
+/// When the first and the last waypoints are equal the spline is closed.
+///
+/// # Panics
+///
+/// Panics if `v` is empty.
+pub fn catmull_rom<T>(v: &[T], k: f64) -> T
+where
+    T: Clone + PartialEq + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>,
+{
+    assert!(!v.is_empty(), "no waypoints");
+    let m = v.len() - 1;
+    if m == 0 {
+        return v[0].clone();
+    }
+
+    let mut f = m as f64 * k;
+
+    if v[0] == v[m] {
+        if k < 0.0 {
+            f = m as f64 * (1.0 + k);
+        }
+
+        let i = f.floor();
+        let idx = (i.rem_euclid(m as f64)) as usize;
+        return utils::catmull_rom(
+            &v[(idx + m - 1) % m],
+            &v[idx],
+            &v[(idx + 1) % m],
+            &v[(idx + 2) % m],
+            f - i,
+        );
+    }
+
+    if k < 0.0 {
+        let value = utils::catmull_rom(&v[0], &v[0], &v[1], &v[1], -f);
+        return v[0].clone() - (value - v[0].clone());
+    }
+
+    if k > 1.0 {
+        let value = utils::catmull_rom(&v[m], &v[m], &v[m - 1], &v[m - 1], f - m as f64);
+        return v[m].clone() - (value - v[m].clone());
+    }
+
+    let i = (f.floor() as usize).min(m);
+    utils::catmull_rom(
+        &v[if i > 0 { i - 1 } else { 0 }],
+        &v[i],
+        &v[(i + 1).min(m)],
+        &v[(i + 2).min(m)],
+        f - i as f64,
+    )
 }
 
-pub fn catmull_rom(v: Vec<f64>, k: f64) -> f64 {
-    unimplemented!();
+/// Interpolates points as one unit, each axis with the function `f`.
+///
+/// ```
+/// use animate::{tween::interpolation, Point};
+///
+/// let path = [Point::new(0.0, 0.0), Point::new(10.0, 0.0), Point::new(10.0, 10.0)];
+/// let point = interpolation::points(&path, 0.75, interpolation::linear);
+/// assert_eq!((point.x, point.y), (10.0, 5.0));
+/// ```
+pub fn points(v: &[Point<f64>], k: f64, f: InterpolationFunction<f64>) -> Point<f64> {
+    let xs: Vec<f64> = v.iter().map(|point| point.x).collect();
+    let ys: Vec<f64> = v.iter().map(|point| point.y).collect();
+    Point::new(f(&xs, k), f(&ys, k))
+}
+
+/// Interpolates colors as one unit, each channel with the function `f`.
+///
+/// Channels are clamped, as curves like [`bezier`] may overshoot.
+pub fn colors(v: &[RgbaColor], k: f64, f: InterpolationFunction<f64>) -> RgbaColor {
+    let channel = |get: fn(&RgbaColor) -> u8| -> u8 {
+        let values: Vec<f64> = v.iter().map(|color| get(color) as f64).collect();
+        f(&values, k).round().clamp(0.0, 255.0) as u8
+    };
+
+    RgbaColor {
+        red: channel(|color| color.red),
+        green: channel(|color| color.green),
+        blue: channel(|color| color.blue),
+        alpha: channel(|color| color.alpha),
+    }
 }
 
 mod utils {
-    pub fn linear(p0: f64, p1: f64, t: f64) -> f64 {
-        (p1 - p0) * t + p0
+    use std::ops::{Add, Mul, Sub};
+
+    pub fn bernstein(n: usize, i: usize) -> f64 {
+        factorial(n) / factorial(i) / factorial(n - i)
+    }
+
+    pub fn factorial(n: usize) -> f64 {
+        (2..=n).fold(1.0, |acc, i| acc * i as f64)
+    }
+
+    pub fn catmull_rom<T>(p0: &T, p1: &T, p2: &T, p3: &T, t: f64) -> T
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T> + Mul<f64, Output = T>,
+    {
+        let v0 = (p2.clone() - p0.clone()) * 0.5;
+        let v1 = (p3.clone() - p1.clone()) * 0.5;
+        let t2 = t * t;
+        let t3 = t * t2;
+
+        (p1.clone() * 2.0 - p2.clone() * 2.0 + v0.clone() + v1.clone()) * t3
+            + (p2.clone() * 3.0 - p1.clone() * 3.0 - v0.clone() * 2.0 - v1) * t2
+            + v0 * t
+            + p1.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn linear_waypoints() {
+        let v = [0.0, 10.0, 0.0];
+        assert_near(linear(&v, 0.0), 0.0);
+        assert_near(linear(&v, 0.25), 5.0);
+        assert_near(linear(&v, 0.5), 10.0);
+        assert_near(linear(&v, 0.75), 5.0);
+        assert_near(linear(&v, 1.0), 0.0);
     }
 
-    pub fn bernstein(n: f64, i: f64) -> f64 {
-        unimplemented!()
+    #[test]
+    fn linear_extrapolates() {
+        let v = [0.0, 10.0];
+        assert_near(linear(&v, -0.5), -5.0);
+        assert_near(linear(&v, 1.5), 15.0);
     }
 
-    // FIXME:
-    pub fn factorial() -> f64 {
-        unimplemented!()
+    #[test]
+    fn linear_single() {
+        assert_near(linear(&[3.0], 0.5), 3.0);
     }
 
-    pub fn catmull_rom(p0: f64, p1: f64, p3: f64, p4: f64, t: f64) -> f64 {
-        unimplemented!()
+    #[test]
+    #[should_panic(expected = "no waypoints")]
+    fn empty_waypoints() {
+        let v: [f64; 0] = [];
+        catmull_rom(&v, 0.5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bezier_endpoints() {
+        let v = [0.0, 10.0, 20.0, 5.0];
+        assert_near(bezier(&v, 0.0), 0.0);
+        assert_near(bezier(&v, 1.0), 5.0);
+        // quadratic curve with a symmetric control point
+        assert_near(bezier(&[0.0, 10.0, 0.0], 0.5), 5.0);
+    }
+
+    #[test]
+    fn catmull_rom_passes_waypoints() {
+        let v = [0.0, 10.0, 20.0, 5.0];
+        assert_near(catmull_rom(&v, 0.0), 0.0);
+        assert_near(catmull_rom(&v, 1.0 / 3.0), 10.0);
+        assert_near(catmull_rom(&v, 2.0 / 3.0), 20.0);
+        assert_near(catmull_rom(&v, 1.0), 5.0);
+        // a straight line stays straight
+        assert_near(catmull_rom(&[0.0, 1.0, 2.0, 3.0], 0.5), 1.5);
+    }
+
+    #[test]
+    fn catmull_rom_closed() {
+        let v = [0.0, 10.0, 0.0, -10.0, 0.0];
+        assert_near(catmull_rom(&v, 0.25), 10.0);
+        assert_near(catmull_rom(&v, 0.75), -10.0);
+        // closed splines wrap around
+        assert_near(catmull_rom(&v, -0.25), -10.0);
+    }
+
+    #[test]
+    fn factorial_values() {
+        assert_near(utils::factorial(0), 1.0);
+        assert_near(utils::factorial(5), 120.0);
+        assert_near(utils::bernstein(4, 2), 6.0);
+    }
+
+    #[test]
+    fn points_as_unit() {
+        let v = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ];
+        let point = points(&v, 0.25, linear);
+        assert_near(point.x, 5.0);
+        assert_near(point.y, 0.0);
+    }
+
+    #[test]
+    fn colors_as_unit() {
+        let v = [
+            RgbaColor {
+                red: 0,
+                green: 0,
+                blue: 0,
+                alpha: 255,
+            },
+            RgbaColor {
+                red: 200,
+                green: 100,
+                blue: 50,
+                alpha: 255,
+            },
+        ];
+        let color = colors(&v, 0.5, linear);
+        assert_eq!(
+            (color.red, color.green, color.blue, color.alpha),
+            (100, 50, 25, 255)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use super::{Group, GroupState, Sequence};
+use std::{cell::RefCell, rc::Weak};
+
+/// The state shared by everything played with timestamps: the id, the
+/// group, whether it plays and when it was paused.
+///
+/// The owners keep it inside their own state and join or leave the group
+/// once that state is no longer borrowed.
+pub(crate) struct Lifecycle {
+    id: usize,
+    group: Option<Weak<RefCell<GroupState>>>,
+    is_playing: bool,
+    is_paused: bool,
+    pause_start: f64,
+}
+
+impl Lifecycle {
+    pub fn new() -> Self {
+        Self {
+            id: Sequence::next(),
+            group: None,
+            is_playing: false,
+            is_paused: false,
+            pause_start: 0.0,
+        }
+    }
+
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.is_playing
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Sets the group joined on start, without keeping it alive.
+    pub fn set_group(&mut self, group: &Group) {
+        self.group = Some(group.downgrade());
+    }
+
+    /// Returns the group, `None` without a group or when it was dropped.
+    pub fn get_group(&self) -> Option<Group> {
+        self.group.as_ref().and_then(Group::upgrade)
+    }
+
+    /// Starts playing, returns `false` if it was already playing.
+    pub fn start(&mut self) -> bool {
+        if self.is_playing {
+            return false;
+        }
+        self.is_playing = true;
+        self.is_paused = false;
+        true
+    }
+
+    /// Stops playing and leaves the group, returns `false` if it was not
+    /// playing.
+    pub fn stop(&mut self) -> bool {
+        if !self.is_playing {
+            return false;
+        }
+        self.is_playing = false;
+        self.is_paused = false;
+        if let Some(group) = self.get_group() {
+            group.remove_id(self.id);
+        }
+        true
+    }
+
+    /// Stops playing at the end, the group drops it once its update returns
+    /// `false`.
+    pub fn finish(&mut self) {
+        self.is_playing = false;
+        self.is_paused = false;
+    }
+
+    /// Pauses at `time` in milliseconds, if playing.
+    pub fn pause(&mut self, time: f64) {
+        if self.is_paused || !self.is_playing {
+            return;
+        }
+        self.is_paused = true;
+        self.pause_start = time;
+    }
+
+    /// Resumes at `time` in milliseconds and returns the time spent in
+    /// pause, `None` if it was not paused.
+    pub fn resume(&mut self, time: f64) -> Option<f64> {
+        if !self.is_paused || !self.is_playing {
+            return None;
+        }
+        self.is_paused = false;
+        let paused = time - self.pause_start;
+        self.pause_start = 0.0;
+        Some(paused)
+    }
+}
+
+/// Calls the callback in the `slot` of `state` with `call`.
+///
+/// The callback is taken out of the state during the call, so it can use the
+/// handle of its owner freely. It is put back unless it was replaced.
+pub(crate) fn fire_callback<S, C, F, G>(state: &RefCell<S>, slot: F, call: G)
+where
+    C: ?Sized,
+    F: Fn(&mut S) -> &mut Option<Box<C>>,
+    G: FnOnce(&mut C),
+{
+    let callback = slot(&mut state.borrow_mut()).take();
+    if let Some(mut callback) = callback {
+        call(&mut callback);
+
+        let mut state = state.borrow_mut();
+        let slot = slot(&mut state);
+        if slot.is_none() {
+            *slot = Some(callback);
+        }
+    }
+}
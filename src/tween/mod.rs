@@ -2,7 +2,12 @@
 
 pub const VERSION: &str = "18.6.4";
 
-// pub const MAIN_GROUP: Group = Group::new();
+thread_local! {
+    /// The default group of the current thread.
+    ///
+    /// A tween joins it only when it is passed to `Tween::group`.
+    pub static MAIN_GROUP: Group = Group::new();
+}
 
 // Convert [seconds, nanoseconds] to milliseconds.
 // let now: () => number
@@ -19,11 +24,16 @@ pub use group::*;
 mod index;
 pub use index::*;
 
+pub mod interpolation;
+
+mod lifecycle;
+pub(crate) use lifecycle::*;
+
 mod sequence;
 pub use sequence::*;
 
+#[cfg(test)]
 mod tests;
-pub use tests::*;
 
 mod tween;
 pub use tween::*;
@@ -1,9 +1,26 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static SEQUENCE: RefCell<Sequence> = const { RefCell::new(Sequence::new()) };
+}
+
 // Utils
+#[derive(Debug, Default)]
 pub struct Sequence(usize);
 
 impl Sequence {
+    /// Constructs a new sequence starting at zero.
+    pub const fn new() -> Self {
+        Sequence(0)
+    }
+
     pub fn next_id(&mut self) -> usize {
         self.0 += 1;
         self.0
     }
+
+    /// Returns the next id of the thread-wide sequence shared by all tweens.
+    pub fn next() -> usize {
+        SEQUENCE.with(|seq| seq.borrow_mut().next_id())
+    }
 }
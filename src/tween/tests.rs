@@ -1,3 +1,339 @@
-#![allow(unused_imports)]
-#![allow(unused_variables)]
-#![allow(dead_code)]
+#![allow(clippy::float_cmp)]
+
+use super::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn tween_updates_target() {
+    let target = Rc::new(RefCell::new(0.0));
+    let tween = Tween::with_target(target.clone());
+    tween.to(100.0, 1000.0).start(0.0);
+
+    assert!(tween.update(250.0));
+    assert_eq!(*target.borrow(), 25.0);
+
+    assert!(!tween.update(1000.0));
+    assert_eq!(*target.borrow(), 100.0);
+    assert!(!tween.is_playing());
+}
+
+#[test]
+fn tween_from_value() {
+    let tween = Tween::new(0.0);
+    tween.from(50.0).to(100.0, 100.0).start(0.0);
+
+    tween.update(50.0);
+    assert_eq!(tween.get_value(), 75.0);
+}
+
+#[test]
+fn tween_not_started() {
+    let tween = Tween::new(10.0);
+    tween.to(20.0, 100.0);
+
+    assert!(!tween.update(50.0));
+    assert_eq!(tween.get_value(), 10.0);
+}
+
+#[test]
+fn tween_delay() {
+    let tween = Tween::new(0.0);
+    tween.to(10.0, 100.0).delay(100.0).start(0.0);
+
+    assert!(tween.update(50.0));
+    assert_eq!(tween.get_value(), 0.0);
+
+    tween.update(150.0);
+    assert_eq!(tween.get_value(), 5.0);
+}
+
+#[test]
+fn tween_easing() {
+    let tween = Tween::new(0.0);
+    tween
+        .to(100.0, 100.0)
+        .easing(quadratic::in_easing)
+        .start(0.0);
+
+    tween.update(50.0);
+    assert_eq!(tween.get_value(), 25.0);
+}
+
+#[test]
+fn tween_pause_resume() {
+    let tween = Tween::new(0.0);
+    tween.to(100.0, 100.0).start(0.0);
+
+    tween.update(20.0);
+    tween.pause(20.0);
+    assert!(tween.is_paused());
+    assert!(tween.update(80.0));
+    assert_eq!(tween.get_value(), 20.0);
+
+    tween.resume(80.0);
+    tween.update(90.0);
+    assert_eq!(tween.get_value(), 30.0);
+}
+
+#[test]
+fn tween_repeat() {
+    let repeats = Rc::new(RefCell::new(0));
+    let counter = repeats.clone();
+
+    let tween = Tween::new(0.0);
+    tween
+        .to(10.0, 100.0)
+        .repeat(2.0)
+        .on_repeat(move |_| *counter.borrow_mut() += 1)
+        .start(0.0);
+
+    assert!(tween.update(100.0));
+    assert!(tween.update(150.0));
+    assert_eq!(tween.get_value(), 5.0);
+    assert!(tween.update(200.0));
+    assert!(!tween.update(300.0));
+    assert_eq!(*repeats.borrow(), 2);
+}
+
+#[test]
+fn tween_yoyo() {
+    let tween = Tween::new(0.0);
+    tween.to(10.0, 100.0).repeat(1.0).yoyo(true).start(0.0);
+
+    tween.update(100.0);
+    assert_eq!(tween.get_value(), 10.0);
+    tween.update(125.0);
+    assert_eq!(tween.get_value(), 7.5);
+    assert!(!tween.update(200.0));
+    assert_eq!(tween.get_value(), 0.0);
+}
+
+#[test]
+fn tween_waypoints() {
+    let tween = Tween::new(0.0);
+    tween.to_waypoints(vec![10.0, 0.0], 100.0).start(0.0);
+
+    tween.update(25.0);
+    assert_eq!(tween.get_value(), 5.0);
+    tween.update(50.0);
+    assert_eq!(tween.get_value(), 10.0);
+    assert!(!tween.update(100.0));
+    assert_eq!(tween.get_value(), 0.0);
+}
+
+#[test]
+fn tween_waypoints_yoyo() {
+    let tween = Tween::new(0.0);
+    tween
+        .to_waypoints(vec![10.0, 20.0], 100.0)
+        .interpolation(interpolation::catmull_rom)
+        .repeat(1.0)
+        .yoyo(true)
+        .start(0.0);
+
+    tween.update(100.0);
+    assert_eq!(tween.get_value(), 20.0);
+    tween.update(150.0);
+    assert_eq!(tween.get_value(), 10.0);
+    tween.update(200.0);
+    assert_eq!(tween.get_value(), 0.0);
+}
+
+#[test]
+fn tween_repeat_delay() {
+    let tween = Tween::new(0.0);
+    tween
+        .to(10.0, 100.0)
+        .repeat(1.0)
+        .repeat_delay(50.0)
+        .start(0.0);
+
+    tween.update(100.0);
+    tween.update(140.0);
+    assert_eq!(tween.get_value(), 10.0);
+    tween.update(200.0);
+    assert_eq!(tween.get_value(), 5.0);
+}
+
+#[test]
+fn tween_callbacks() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let tween = Tween::new(0.0);
+    let (start, update, complete) = (log.clone(), log.clone(), log.clone());
+    tween
+        .to(10.0, 100.0)
+        .on_start(move |_| start.borrow_mut().push("start"))
+        .on_update(move |_, _| update.borrow_mut().push("update"))
+        .on_complete(move |_| complete.borrow_mut().push("complete"))
+        .start(0.0);
+
+    tween.update(50.0);
+    tween.update(100.0);
+    assert_eq!(*log.borrow(), vec!["start", "update", "update", "complete"]);
+}
+
+#[test]
+fn tween_stop() {
+    let stopped = Rc::new(RefCell::new(false));
+    let flag = stopped.clone();
+
+    let tween = Tween::new(0.0);
+    tween
+        .to(10.0, 100.0)
+        .on_stop(move |_| *flag.borrow_mut() = true)
+        .start(0.0);
+
+    tween.update(50.0);
+    tween.stop();
+    assert!(*stopped.borrow());
+    assert!(!tween.update(60.0));
+    assert_eq!(tween.get_value(), 5.0);
+}
+
+#[test]
+fn tween_end() {
+    let tween = Tween::new(0.0);
+    tween.to(10.0, 100.0).start(0.0);
+
+    tween.end();
+    assert_eq!(tween.get_value(), 10.0);
+    assert!(!tween.is_playing());
+}
+
+#[test]
+fn tween_chain() {
+    let first = Tween::new(0.0);
+    let second = Tween::new(0.0);
+    first.to(10.0, 100.0).chain(&second).start(0.0);
+    second.to(20.0, 100.0);
+
+    first.update(100.0);
+    assert!(second.is_playing());
+
+    second.update(150.0);
+    assert_eq!(second.get_value(), 10.0);
+}
+
+#[test]
+fn tween_stop_chained() {
+    let first = Tween::new(0.0);
+    let second = Tween::new(0.0);
+    first.to(10.0, 100.0).chain(&second).start(0.0);
+    second.to(20.0, 100.0).start(0.0);
+
+    first.stop();
+    assert!(!second.is_playing());
+}
+
+#[test]
+fn tween_callback_can_stop() {
+    let tween = Tween::new(0.0);
+    let handle = tween.clone();
+    tween
+        .to(10.0, 100.0)
+        .on_update(move |value, _| {
+            if *value >= 5.0 {
+                handle.stop();
+            }
+        })
+        .start(0.0);
+
+    assert!(tween.update(50.0));
+    assert!(!tween.is_playing());
+}
+
+#[test]
+fn group_updates_tweens() {
+    let group = Group::new();
+    let short = Tween::new(0.0);
+    let long = Tween::new(0.0);
+    short.to(10.0, 100.0).group(&group).start(0.0);
+    long.to(10.0, 200.0).group(&group).start(0.0);
+
+    assert_eq!(group.get_all().len(), 2);
+    assert!(group.update(100.0));
+    assert_eq!(short.get_value(), 10.0);
+    assert_eq!(long.get_value(), 5.0);
+    assert_eq!(group.get_all().len(), 1);
+
+    assert!(!group.update(200.0));
+    assert!(group.is_empty());
+}
+
+#[test]
+fn group_update_order() {
+    let group = Group::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let tweens: Vec<Tween<f64>> = (0..3).map(|_| Tween::new(0.0)).collect();
+    for tween in tweens.iter().rev() {
+        let log = log.clone();
+        let id = tween.get_id();
+        tween
+            .to(1.0, 100.0)
+            .on_update(move |_, _| log.borrow_mut().push(id))
+            .group(&group)
+            .start(0.0);
+    }
+
+    group.update(10.0);
+    let ids: Vec<usize> = tweens.iter().map(|tween| tween.get_id()).collect();
+    assert_eq!(*log.borrow(), ids);
+}
+
+#[test]
+fn group_remove() {
+    let group = Group::new();
+    let tween = Tween::new(0.0);
+    tween.to(10.0, 100.0).group(&group).start(0.0);
+
+    group.remove(&tween);
+    assert!(!group.update(50.0));
+    assert_eq!(tween.get_value(), 0.0);
+
+    group.add(&tween);
+    group.remove_all();
+    assert!(group.get_all().is_empty());
+}
+
+#[test]
+fn group_stop_removes() {
+    let group = Group::new();
+    let tween = Tween::new(0.0);
+    tween.to(10.0, 100.0).group(&group).start(0.0);
+
+    tween.stop();
+    assert!(group.is_empty());
+}
+
+#[test]
+fn group_chained_tweens() {
+    let group = Group::new();
+    let first = Tween::new(0.0);
+    let second = Tween::new(0.0);
+    second.to(10.0, 100.0).group(&group);
+    first
+        .to(10.0, 100.0)
+        .chain(&second)
+        .group(&group)
+        .start(0.0);
+
+    assert!(group.update(100.0));
+    assert!(second.is_playing());
+    assert_eq!(second.get_value(), 0.0);
+
+    assert!(group.update(150.0));
+    assert_eq!(second.get_value(), 5.0);
+    assert!(!group.update(200.0));
+}
+
+#[test]
+fn main_group() {
+    let tween = Tween::new(0.0);
+    MAIN_GROUP.with(|group| {
+        tween.to(10.0, 100.0).group(group).start(0.0);
+        assert!(!group.update(100.0));
+    });
+    assert_eq!(tween.get_value(), 10.0);
+}
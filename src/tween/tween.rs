@@ -1,103 +1,633 @@
-pub struct Tween {}
+use super::{
+    fire_callback,
+    interpolation::{self, InterpolationFunction},
+    Group, Lifecycle,
+};
+use crate::{easing::EasingFunction, interpolate::Interpolate};
+use std::{cell::RefCell, fmt, rc::Rc};
 
-impl Tween {
-    pub fn get_id() -> usize {
-        unimplemented!()
+type Callback<T> = Box<dyn FnMut(&T)>;
+
+type UpdateCallback<T> = Box<dyn FnMut(&T, f64)>;
+
+/// Type-erased control over anything which can be played by a tween loop.
+///
+/// It is implemented by [`Tween`] and allows tweens animating different value
+/// types to be chained one after another.
+pub trait Playable {
+    /// Returns the unique id of the playable.
+    fn get_id(&self) -> usize;
+
+    /// Returns `true` if the playable was started and is not finished yet.
+    fn is_playing(&self) -> bool;
+
+    /// Returns `true` if the playable is paused.
+    fn is_paused(&self) -> bool;
+
+    /// Starts playing at the given `time` in milliseconds.
+    fn start(&self, time: f64);
+
+    /// Stops playing, the current value is kept.
+    fn stop(&self);
+
+    /// Jumps to the end value and completes.
+    fn end(&self);
+
+    /// Pauses playing at the given `time` in milliseconds.
+    fn pause(&self, time: f64);
+
+    /// Resumes playing at the given `time` in milliseconds.
+    fn resume(&self, time: f64);
+
+    /// Advances to the given `time` in milliseconds.
+    ///
+    /// Returns `false` when the playable has finished.
+    fn update(&self, time: f64) -> bool;
+}
+
+struct TweenState<T> {
+    lifecycle: Lifecycle,
+    object: Rc<RefCell<T>>,
+    values_from: Option<T>,
+    values_start: Option<T>,
+    values_end: Option<T>,
+    values_start_repeat: Option<T>,
+    waypoints: Option<Vec<T>>,
+    path: Vec<T>,
+    interpolation: InterpolationFunction<T>,
+    duration: f64,
+    initial_repeat: f64,
+    repeat: f64,
+    repeat_delay_time: Option<f64>,
+    yoyo: bool,
+    reversed: bool,
+    delay_time: f64,
+    start_time: f64,
+    easing: EasingFunction,
+    chained_tweens: Vec<Box<dyn Playable>>,
+    on_start: Option<Callback<T>>,
+    on_start_fired: bool,
+    on_update: Option<UpdateCallback<T>>,
+    on_repeat: Option<Callback<T>>,
+    on_complete: Option<Callback<T>>,
+    on_stop: Option<Callback<T>>,
+    is_chain_stopped: bool,
+    go_to_end: bool,
+}
+
+/// What the update loop has to do once the state borrow is released.
+enum Step {
+    Idle(bool),
+    Running,
+    Repeated,
+    Completed(f64),
+}
+
+/// A tween.js-style tween of a single value.
+///
+/// The tween animates the value stored in a shared target from its start value
+/// to the end value set with [`Tween::to`]. Time is never read by the tween
+/// itself, it is passed explicitly in milliseconds to [`Tween::start`] and
+/// [`Tween::update`], so the same code runs natively and in wasm. Tweens are
+/// usually updated through a [`Group`].
+///
+/// Any value implementing [`Interpolate`] can be tweened, numbers, points,
+/// colors, transforms or paths.
+///
+/// `Tween` is a cheap handle, clones refer to the same tween.
+///
+/// # Example
+///
+/// ```
+/// use animate::tween::{quadratic, Tween};
+///
+/// let tween = Tween::new(0.0);
+/// tween
+///     .to(100.0, 1000.0)
+///     .easing(quadratic::in_out_easing)
+///     .start(0.0);
+///
+/// tween.update(500.0);
+/// assert_eq!(tween.get_value(), 50.0);
+///
+/// assert!(!tween.update(1000.0));
+/// assert_eq!(tween.get_value(), 100.0);
+/// ```
+pub struct Tween<T> {
+    inner: Rc<RefCell<TweenState<T>>>,
+}
+
+impl<T> Clone for Tween<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Tween<T>
+where
+    T: Clone + Interpolate + 'static,
+{
+    /// Constructs a new tween which owns its target `value`.
+    pub fn new(value: T) -> Self {
+        Self::with_target(Rc::new(RefCell::new(value)))
+    }
+
+    /// Constructs a new tween which animates a shared `target`.
+    pub fn with_target(target: Rc<RefCell<T>>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(TweenState {
+                lifecycle: Lifecycle::new(),
+                object: target,
+                values_from: None,
+                values_start: None,
+                values_end: None,
+                values_start_repeat: None,
+                waypoints: None,
+                path: Vec::new(),
+                interpolation: interpolation::linear,
+                duration: 1000.0,
+                initial_repeat: 0.0,
+                repeat: 0.0,
+                repeat_delay_time: None,
+                yoyo: false,
+                reversed: false,
+                delay_time: 0.0,
+                start_time: 0.0,
+                easing: EasingFunction::default(),
+                chained_tweens: Vec::new(),
+                on_start: None,
+                on_start_fired: false,
+                on_update: None,
+                on_repeat: None,
+                on_complete: None,
+                on_stop: None,
+                is_chain_stopped: false,
+                go_to_end: false,
+            })),
+        }
     }
 
-    pub fn is_playing() -> bool {
-        unimplemented!()
+    /// Returns the unique id of the tween.
+    pub fn get_id(&self) -> usize {
+        self.inner.borrow().lifecycle.get_id()
     }
 
-    pub fn is_paused() -> bool {
-        unimplemented!()
+    /// Returns the shared target animated by the tween.
+    pub fn get_target(&self) -> Rc<RefCell<T>> {
+        self.inner.borrow().object.clone()
     }
 
-    pub fn to() -> bool {
-        unimplemented!()
+    /// Returns a copy of the current value of the target.
+    pub fn get_value(&self) -> T {
+        let target = self.get_target();
+        let value = target.borrow().clone();
+        value
     }
 
-    pub fn duration() -> bool {
-        unimplemented!()
+    pub fn is_playing(&self) -> bool {
+        self.inner.borrow().lifecycle.is_playing()
     }
 
-    pub fn start() -> bool {
-        unimplemented!()
+    pub fn is_paused(&self) -> bool {
+        self.inner.borrow().lifecycle.is_paused()
     }
 
-    pub fn stop() -> bool {
-        unimplemented!()
+    /// Sets the start value.
+    ///
+    /// Without it the tween starts from the value the target has when
+    /// [`Tween::start`] is called.
+    pub fn from(&self, value: T) -> &Self {
+        self.inner.borrow_mut().values_from = Some(value);
+        self
     }
 
-    pub fn end() -> bool {
-        unimplemented!()
+    /// Sets the end value and the duration in milliseconds.
+    pub fn to(&self, value: T, duration: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        state.values_end = Some(value);
+        state.waypoints = None;
+        state.duration = duration;
+        drop(state);
+        self
     }
 
-    pub fn pause() -> bool {
-        unimplemented!()
+    /// Sets the waypoints the tween passes through and the duration in
+    /// milliseconds.
+    ///
+    /// The last waypoint is the end value. The curve through the start value
+    /// and the waypoints is set with [`Tween::interpolation`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is empty.
+    pub fn to_waypoints(&self, values: Vec<T>, duration: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        state.values_end = Some(values.last().expect("no waypoints").clone());
+        state.waypoints = Some(values);
+        state.duration = duration;
+        drop(state);
+        self
     }
 
-    pub fn resume() -> bool {
-        unimplemented!()
+    /// Sets the duration in milliseconds.
+    pub fn duration(&self, duration: f64) -> &Self {
+        self.inner.borrow_mut().duration = duration;
+        self
     }
 
-    pub fn stop_chained_tweens() -> bool {
-        unimplemented!()
+    /// Starts the tween at `time` in milliseconds.
+    ///
+    /// The actual animation begins after the delay set with [`Tween::delay`].
+    pub fn start(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if !state.lifecycle.start() {
+            return self;
+        }
+
+        state.repeat = state.initial_repeat;
+
+        if state.reversed {
+            // yoyo has left the values swapped, restore the original direction
+            state.reversed = false;
+            state.swap_end_start_repeat_values();
+            state.values_start = state.values_start_repeat.clone();
+        }
+
+        state.on_start_fired = false;
+        state.is_chain_stopped = false;
+
+        state.start_time = time + state.delay_time;
+
+        let start = match state.values_from {
+            Some(ref value) => value.clone(),
+            None => state.object.borrow().clone(),
+        };
+
+        if state.values_end.is_none() {
+            state.values_end = Some(start.clone());
+        }
+        state.path = match state.waypoints {
+            Some(ref waypoints) => std::iter::once(start.clone())
+                .chain(waypoints.iter().cloned())
+                .collect(),
+            None => Vec::new(),
+        };
+        state.values_start_repeat = Some(start.clone());
+        state.values_start = Some(start);
+
+        let group = state.lifecycle.get_group();
+        drop(state);
+
+        if let Some(group) = group {
+            group.add(self);
+        }
+        self
+    }
+
+    /// Stops the tween and all the tweens chained to it.
+    ///
+    /// The `on_stop` callback is fired if the tween was playing.
+    pub fn stop(&self) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if !state.is_chain_stopped {
+            state.is_chain_stopped = true;
+            drop(state);
+            self.stop_chained_tweens();
+            state = self.inner.borrow_mut();
+        }
+
+        let stopped = state.lifecycle.stop();
+        drop(state);
+
+        if stopped {
+            self.fire(|state| &mut state.on_stop);
+        }
+        self
     }
 
-    pub fn group() -> bool {
-        unimplemented!()
+    /// Jumps to the end value and completes the tween.
+    pub fn end(&self) -> &Self {
+        self.inner.borrow_mut().go_to_end = true;
+        self.update(f64::INFINITY);
+        self
     }
 
-    pub fn delay() -> bool {
-        unimplemented!()
+    /// Pauses the tween at `time` in milliseconds.
+    pub fn pause(&self, time: f64) -> &Self {
+        self.inner.borrow_mut().lifecycle.pause(time);
+        self
     }
 
-    pub fn repeat() -> bool {
-        unimplemented!()
+    /// Resumes the tween at `time` in milliseconds.
+    ///
+    /// The time spent in pause is not counted as playing time.
+    pub fn resume(&self, time: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        if let Some(paused) = state.lifecycle.resume(time) {
+            state.start_time += paused;
+        }
+        drop(state);
+        self
     }
 
-    pub fn repeat_delay() -> bool {
-        unimplemented!()
+    /// Stops all the tweens chained to this one.
+    pub fn stop_chained_tweens(&self) -> &Self {
+        // take the chain out, so a chained tween can safely refer back to us
+        let chained = std::mem::take(&mut self.inner.borrow_mut().chained_tweens);
+        for tween in chained.iter() {
+            tween.stop();
+        }
+        self.restore_chain(chained);
+        self
     }
 
-    pub fn yoyo() -> bool {
-        unimplemented!()
+    /// Sets the group the tween joins when it is started.
+    ///
+    /// The group does not keep the tween after it has finished, and the tween
+    /// does not keep the group alive.
+    pub fn group(&self, group: &Group) -> &Self {
+        self.inner.borrow_mut().lifecycle.set_group(group);
+        self
     }
 
-    pub fn easing() -> bool {
-        unimplemented!()
+    /// Sets the delay in milliseconds between the start and the first update.
+    pub fn delay(&self, amount: f64) -> &Self {
+        self.inner.borrow_mut().delay_time = amount;
+        self
     }
 
-    pub fn interpolation() -> bool {
-        unimplemented!()
+    /// Sets how many times the tween is repeated after the first run.
+    ///
+    /// Use `f64::INFINITY` to repeat forever.
+    pub fn repeat(&self, times: f64) -> &Self {
+        let mut state = self.inner.borrow_mut();
+        state.initial_repeat = times;
+        state.repeat = times;
+        drop(state);
+        self
     }
 
-    pub fn chain() -> bool {
-        unimplemented!()
+    /// Sets the delay in milliseconds before each repetition.
+    ///
+    /// By default repetitions wait for the delay set with [`Tween::delay`].
+    pub fn repeat_delay(&self, amount: f64) -> &Self {
+        self.inner.borrow_mut().repeat_delay_time = Some(amount);
+        self
     }
 
-    pub fn on_start() -> bool {
-        unimplemented!()
+    /// Makes every other repetition play backwards.
+    pub fn yoyo(&self, yoyo: bool) -> &Self {
+        self.inner.borrow_mut().yoyo = yoyo;
+        self
     }
 
-    pub fn on_update() -> bool {
-        unimplemented!()
+    /// Sets the interpolation function used with waypoints, see the
+    /// `tween::interpolation` module.
+    ///
+    /// The default is [`interpolation::linear`].
+    pub fn interpolation(&self, interpolation: InterpolationFunction<T>) -> &Self {
+        self.inner.borrow_mut().interpolation = interpolation;
+        self
     }
 
-    pub fn on_repeat() -> bool {
-        unimplemented!()
+    /// Sets the easing function, see the `tween::easing` modules.
+    ///
+    /// Anything which converts into an [`EasingFunction`] is accepted, the
+    /// parametric curves as well as plain functions and closures.
+    pub fn easing<E: Into<EasingFunction>>(&self, easing: E) -> &Self {
+        self.inner.borrow_mut().easing = easing.into();
+        self
     }
 
-    pub fn on_complete() -> bool {
-        unimplemented!()
+    /// Appends `tween` to the tweens started when this one completes.
+    ///
+    /// Chained tweens are started with the completion time of this tween. A
+    /// chained tween with a group joins it and is updated with the group.
+    pub fn chain<P>(&self, tween: &P) -> &Self
+    where
+        P: Playable + Clone + 'static,
+    {
+        self.inner
+            .borrow_mut()
+            .chained_tweens
+            .push(Box::new(tween.clone()));
+        self
     }
 
-    pub fn on_stop() -> bool {
-        unimplemented!()
+    /// Sets the callback fired on the first update after the start delay.
+    pub fn on_start<F: FnMut(&T) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_start = Some(Box::new(callback));
+        self
     }
 
-    pub fn update() -> bool {
-        unimplemented!()
+    /// Sets the callback fired on every update with the new value and the
+    /// elapsed portion of the tween in `0..=1`.
+    pub fn on_update<F: FnMut(&T, f64) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_update = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired each time the tween repeats.
+    pub fn on_repeat<F: FnMut(&T) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_repeat = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired when the tween completes.
+    pub fn on_complete<F: FnMut(&T) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets the callback fired when a playing tween is stopped.
+    pub fn on_stop<F: FnMut(&T) + 'static>(&self, callback: F) -> &Self {
+        self.inner.borrow_mut().on_stop = Some(Box::new(callback));
+        self
+    }
+
+    /// Advances the tween to `time` in milliseconds.
+    ///
+    /// Returns `true` while the tween is playing (or paused) and `false` once
+    /// it has completed.
+    pub fn update(&self, time: f64) -> bool {
+        let step = self.advance(time);
+
+        match step {
+            Step::Idle(playing) => playing,
+            Step::Running => true,
+            Step::Repeated => {
+                self.fire(|state| &mut state.on_repeat);
+                true
+            }
+            Step::Completed(end_time) => {
+                self.fire(|state| &mut state.on_complete);
+
+                let chained = std::mem::take(&mut self.inner.borrow_mut().chained_tweens);
+                for tween in chained.iter() {
+                    tween.start(end_time);
+                }
+                self.restore_chain(chained);
+                false
+            }
+        }
+    }
+
+    fn advance(&self, time: f64) -> Step {
+        let mut state = self.inner.borrow_mut();
+        if state.lifecycle.is_paused() {
+            return Step::Idle(true);
+        }
+
+        if !state.go_to_end && !state.lifecycle.is_playing() {
+            return Step::Idle(false);
+        }
+
+        state.go_to_end = false;
+
+        if time < state.start_time {
+            return Step::Idle(true);
+        }
+
+        if !state.on_start_fired {
+            state.on_start_fired = true;
+            drop(state);
+            self.fire(|state| &mut state.on_start);
+            state = self.inner.borrow_mut();
+        }
+
+        let mut elapsed = (time - state.start_time) / state.duration;
+        if state.duration == 0.0 || elapsed > 1.0 {
+            elapsed = 1.0;
+        }
+
+        let value = state.easing.ease(elapsed);
+        state.update_properties(value);
+        drop(state);
+
+        let target = self.get_target();
+        fire_callback(
+            &self.inner,
+            |state| &mut state.on_update,
+            |callback| callback(&target.borrow(), elapsed),
+        );
+
+        let mut state = self.inner.borrow_mut();
+        if elapsed < 1.0 {
+            return Step::Running;
+        }
+
+        if state.repeat > 0.0 {
+            if state.repeat.is_finite() {
+                state.repeat -= 1.0;
+            }
+
+            if state.yoyo {
+                state.swap_end_start_repeat_values();
+                state.path.reverse();
+                state.reversed = !state.reversed;
+            }
+            state.values_start = state.values_start_repeat.clone();
+
+            state.start_time = match state.repeat_delay_time {
+                Some(delay) => time + delay,
+                None => time + state.delay_time,
+            };
+
+            return Step::Repeated;
+        }
+
+        state.lifecycle.finish();
+        Step::Completed(state.start_time + state.duration)
+    }
+
+    /// Calls the callback in `slot` with the current value.
+    ///
+    /// The callback is taken out of the state during the call, so it can use
+    /// the tween handle freely.
+    fn fire<F>(&self, slot: F)
+    where
+        F: Fn(&mut TweenState<T>) -> &mut Option<Callback<T>>,
+    {
+        let target = self.get_target();
+        fire_callback(&self.inner, slot, |callback| callback(&target.borrow()));
+    }
+
+    fn restore_chain(&self, mut chained: Vec<Box<dyn Playable>>) {
+        let mut state = self.inner.borrow_mut();
+        // keep the tweens chained while the chain was taken out
+        chained.append(&mut state.chained_tweens);
+        state.chained_tweens = chained;
+    }
+}
+
+impl<T> TweenState<T>
+where
+    T: Clone + Interpolate,
+{
+    fn update_properties(&mut self, value: f64) {
+        if !self.path.is_empty() {
+            *self.object.borrow_mut() = (self.interpolation)(&self.path, value);
+        } else if let (Some(start), Some(end)) = (&self.values_start, &self.values_end) {
+            *self.object.borrow_mut() = start.interpolate(end, value);
+        }
+    }
+
+    fn swap_end_start_repeat_values(&mut self) {
+        std::mem::swap(&mut self.values_start_repeat, &mut self.values_end);
+    }
+}
+
+impl<T> Playable for Tween<T>
+where
+    T: Clone + Interpolate + 'static,
+{
+    fn get_id(&self) -> usize {
+        Tween::get_id(self)
+    }
+
+    fn is_playing(&self) -> bool {
+        Tween::is_playing(self)
+    }
+
+    fn is_paused(&self) -> bool {
+        Tween::is_paused(self)
+    }
+
+    fn start(&self, time: f64) {
+        Tween::start(self, time);
+    }
+
+    fn stop(&self) {
+        Tween::stop(self);
+    }
+
+    fn end(&self) {
+        Tween::end(self);
+    }
+
+    fn pause(&self, time: f64) {
+        Tween::pause(self, time);
+    }
+
+    fn resume(&self, time: f64) {
+        Tween::resume(self, time);
+    }
+
+    fn update(&self, time: f64) -> bool {
+        Tween::update(self, time)
+    }
+}
+
+impl<T> fmt::Debug for Tween<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.borrow();
+        f.debug_struct("Tween")
+            .field("id", &state.lifecycle.get_id())
+            .field("is_playing", &state.lifecycle.is_playing())
+            .field("is_paused", &state.lifecycle.is_paused())
+            .field("duration", &state.duration)
+            .finish()
     }
 }